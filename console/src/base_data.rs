@@ -2,18 +2,23 @@ pub(crate) mod event;
 pub(crate) mod in_the_moment_priority;
 pub(crate) mod item;
 pub(crate) mod mode;
+pub(crate) mod search_index;
 pub(crate) mod time_spent;
 
 use ahash::HashMap;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use ouroboros::self_referencing;
 use surrealdb::RecordId;
 
 use crate::{
     base_data::in_the_moment_priority::InTheMomentPriority,
     data_storage::surrealdb_layer::{
-        surreal_current_mode::SurrealCurrentMode, surreal_tables::SurrealTables,
-        surreal_working_on::SurrealWorkingOn,
+        surreal_break::SurrealBreak, surreal_current_mode::SurrealCurrentMode,
+        surreal_daily_plan::SurrealDailyPlan, surreal_interruption::SurrealInterruption,
+        surreal_item_history::SurrealItemHistory,
+        surreal_priority_comparison::SurrealPriorityComparison,
+        surreal_resume_note::SurrealResumeNote, surreal_smart_list::SurrealSmartList,
+        surreal_tables::SurrealTables, surreal_working_on::SurrealWorkingOn,
     },
 };
 
@@ -21,6 +26,7 @@ use self::{
     event::Event,
     item::{Item, ItemVecExtensions},
     mode::Mode,
+    search_index::SearchIndex,
     time_spent::TimeSpent,
 };
 
@@ -37,6 +43,10 @@ pub(crate) struct BaseData {
     #[covariant]
     active_items: Vec<&'this Item<'this>>,
 
+    #[borrows(items)]
+    #[covariant]
+    search_index: SearchIndex<'this>,
+
     #[borrows(surreal_tables)]
     #[covariant]
     events: HashMap<&'this RecordId, Event<'this>>,
@@ -63,6 +73,7 @@ impl BaseData {
             surreal_tables,
             items_builder: |surreal_tables, now| surreal_tables.make_items(now),
             active_items_builder: |items| items.filter_active_items(),
+            search_index_builder: |items| SearchIndex::new(items),
             events_builder: |surreal_tables| surreal_tables.make_events(),
             now,
             time_spent_log_builder: |surreal_tables| surreal_tables.make_time_spent_log().collect(),
@@ -90,6 +101,10 @@ impl BaseData {
         self.borrow_active_items()
     }
 
+    pub(crate) fn get_search_index(&self) -> &SearchIndex<'_> {
+        self.borrow_search_index()
+    }
+
     pub(crate) fn get_events(&self) -> &HashMap<&RecordId, Event<'_>> {
         self.borrow_events()
     }
@@ -110,6 +125,59 @@ impl BaseData {
         self.borrow_surreal_tables().get_surreal_working_on()
     }
 
+    pub(crate) fn get_surreal_priority_comparisons(&self) -> &[SurrealPriorityComparison] {
+        self.borrow_surreal_tables()
+            .get_surreal_priority_comparisons()
+    }
+
+    /// The changes made to one item, oldest first.
+    pub(crate) fn get_item_history(&self, item: &RecordId) -> Vec<&SurrealItemHistory> {
+        let mut history = self
+            .borrow_surreal_tables()
+            .get_surreal_item_history()
+            .iter()
+            .filter(|x| &x.item == item)
+            .collect::<Vec<_>>();
+        history.sort_by(|a, b| a.when.cmp(&b.when));
+        history
+    }
+
+    /// Where one item was left off, newest first so the first one is the one to resume from.
+    pub(crate) fn get_resume_notes(&self, item: &RecordId) -> Vec<&SurrealResumeNote> {
+        let mut resume_notes = self
+            .borrow_surreal_tables()
+            .get_surreal_resume_notes()
+            .iter()
+            .filter(|x| &x.item == item)
+            .collect::<Vec<_>>();
+        resume_notes.sort_by(|a, b| b.created.cmp(&a.created));
+        resume_notes
+    }
+
+    pub(crate) fn get_surreal_breaks(&self) -> &[SurrealBreak] {
+        self.borrow_surreal_tables().get_surreal_breaks()
+    }
+
+    pub(crate) fn get_surreal_interruptions(&self) -> &[SurrealInterruption] {
+        self.borrow_surreal_tables().get_surreal_interruptions()
+    }
+
+    pub(crate) fn get_surreal_resume_notes(&self) -> &[SurrealResumeNote] {
+        self.borrow_surreal_tables().get_surreal_resume_notes()
+    }
+
+    /// The commitments made for one local day, if it was planned
+    pub(crate) fn get_daily_plan(&self, day: &NaiveDate) -> Option<&SurrealDailyPlan> {
+        self.borrow_surreal_tables()
+            .get_surreal_daily_plans()
+            .iter()
+            .find(|x| x.is_for(day))
+    }
+
+    pub(crate) fn get_surreal_smart_lists(&self) -> &[SurrealSmartList] {
+        self.borrow_surreal_tables().get_surreal_smart_lists()
+    }
+
     pub(crate) fn get_modes(&self) -> &[Mode<'_>] {
         self.borrow_modes()
     }
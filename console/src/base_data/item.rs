@@ -7,8 +7,9 @@ use surrealdb::{RecordId, sql::Datetime};
 use crate::{
     calculated_data::parent_lookup::ParentLookup,
     data_storage::surrealdb_layer::surreal_item::{
-        Responsibility, SurrealDependency, SurrealFrequency, SurrealItem, SurrealItemType,
-        SurrealMotivationKind, SurrealOrderedSubItem, SurrealReviewGuidance, SurrealUrgencyPlan,
+        NotesLocation, Responsibility, SurrealDependency, SurrealFrequency, SurrealItem,
+        SurrealItemType, SurrealMotivationKind, SurrealOrderedSubItem, SurrealReviewGuidance,
+        SurrealUrgencyPlan, SurrealWaitingFor,
     },
 };
 
@@ -179,6 +180,53 @@ impl<'b> Item<'b> {
         &self.surreal_item.created
     }
 
+    pub(crate) fn get_last_reviewed(&self) -> Option<&DateTime<Utc>> {
+        self.surreal_item.last_reviewed.as_deref()
+    }
+
+    pub(crate) fn get_notes_location(&self) -> &'b NotesLocation {
+        &self.surreal_item.notes_location
+    }
+
+    pub(crate) fn has_notes(&self) -> bool {
+        self.get_notes_location() != &NotesLocation::None
+    }
+
+    /// Case insensitive search of the notes, for links the link itself is searched.
+    pub(crate) fn notes_contain(&self, search_for: &str) -> bool {
+        let search_for = search_for.to_lowercase();
+        match self.get_notes_location() {
+            NotesLocation::None => false,
+            NotesLocation::OneNoteLink(notes)
+            | NotesLocation::WebLink(notes)
+            | NotesLocation::Markdown(notes) => notes.to_lowercase().contains(&search_for),
+        }
+    }
+
+    pub(crate) fn get_tags(&self) -> &'b [String] {
+        self.surreal_item.tags.as_deref().unwrap_or_default()
+    }
+
+    pub(crate) fn has_tag(&self, tag: &str) -> bool {
+        self.get_tags().iter().any(|x| x.eq_ignore_ascii_case(tag))
+    }
+
+    /// Who this is waiting on and when to follow up, only while the responsibility is waiting for
+    pub(crate) fn get_waiting_for(&self) -> Option<&'b SurrealWaitingFor> {
+        match self.get_surreal_responsibility() {
+            Responsibility::WaitingFor => self.surreal_item.waiting_for.as_ref(),
+            Responsibility::ProactiveActionToTake
+            | Responsibility::ReactiveBeAvailableToAct
+            | Responsibility::TrackingToBeAwareOf => None,
+        }
+    }
+
+    /// The follow up date has passed and it is time to chase whoever this is waiting on
+    pub(crate) fn is_follow_up_due(&self) -> bool {
+        self.get_waiting_for()
+            .is_some_and(|x| &*x.follow_up <= self.now)
+    }
+
     pub(crate) fn get_surreal_urgency_plan(&self) -> &Option<SurrealUrgencyPlan> {
         &self.surreal_item.urgency_plan
     }
@@ -187,6 +235,20 @@ impl<'b> Item<'b> {
         &self.surreal_item.dependencies
     }
 
+    /// Returns when the item stops being snoozed, meaning it has an AfterDateTime dependency that is
+    /// still in the future.
+    pub(crate) fn get_snoozed_until(&self) -> Option<&DateTime<Utc>> {
+        self.surreal_item
+            .dependencies
+            .iter()
+            .filter_map(|x| match x {
+                SurrealDependency::AfterDateTime(after) => Some(&**after),
+                _ => None,
+            })
+            .filter(|after| *after > self.now)
+            .max()
+    }
+
     pub(crate) fn get_now(&self) -> &DateTime<Utc> {
         self.now
     }
@@ -436,4 +498,41 @@ mod tests {
 
         assert!(under_test_parent_item.has_active_children(&items));
     }
+
+    #[test]
+    fn notes_contain_is_case_insensitive_and_items_without_notes_never_match() {
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("surreal_item", "markdown").into()))
+                    .summary("Has markdown notes")
+                    .notes_location(NotesLocation::Markdown(
+                        "# Plan\nCall the Plumber about the leak".to_string(),
+                    ))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("surreal_item", "none").into()))
+                    .summary("No notes plumber")
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let now = Utc::now();
+        let items = surreal_tables.make_items(&now);
+
+        let with_notes = items
+            .get(&RecordId::from(("surreal_item", "markdown")))
+            .expect("In list");
+        assert!(with_notes.has_notes());
+        assert!(with_notes.notes_contain("plumber"));
+        assert!(!with_notes.notes_contain("electrician"));
+
+        let without_notes = items
+            .get(&RecordId::from(("surreal_item", "none")))
+            .expect("In list");
+        assert!(!without_notes.has_notes());
+        assert!(!without_notes.notes_contain("plumber"));
+    }
 }
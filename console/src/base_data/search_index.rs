@@ -0,0 +1,343 @@
+use ahash::{HashMap, HashMapExt};
+use chrono::{DateTime, Utc};
+use surrealdb::RecordId;
+
+use crate::data_storage::surrealdb_layer::surreal_item::NotesLocation;
+
+use super::item::Item;
+
+/// How well one word of the search matched, higher is better
+const EXACT_SCORE: u32 = 100;
+const PREFIX_SCORE: u32 = 90;
+const CONTAINS_SCORE: u32 = 70;
+const ONE_TYPO_SCORE: u32 = 60;
+const TWO_TYPOS_SCORE: u32 = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Summary,
+    Notes,
+}
+
+/// An inverted index from each distinct word to the items that use it. Searching compares the
+/// search words against the distinct words rather than against every item, so the cost grows with
+/// the size of the vocabulary rather than the number of items. Built once when `BaseData` is loaded.
+pub(crate) struct SearchIndex<'s> {
+    items: Vec<&'s Item<'s>>,
+    /// Each distinct word and the index into `items` of every item using it, and in which field
+    words: HashMap<String, Vec<(usize, Field)>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct SearchMatch<'s> {
+    item: &'s Item<'s>,
+    score: u32,
+}
+
+impl<'s> SearchMatch<'s> {
+    pub(crate) fn get_item(&self) -> &'s Item<'s> {
+        self.item
+    }
+
+    /// When the item was finished, or when it was created if it is still active
+    fn get_recency(&self) -> &DateTime<Utc> {
+        self.item
+            .get_finished_at()
+            .as_ref()
+            .unwrap_or(self.item.get_created())
+    }
+}
+
+fn split_into_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|x| !x.is_empty())
+        .map(|x| x.to_lowercase())
+}
+
+fn notes_text<'a>(notes_location: &'a NotesLocation) -> Option<&'a str> {
+    match notes_location {
+        NotesLocation::None => None,
+        NotesLocation::OneNoteLink(notes)
+        | NotesLocation::WebLink(notes)
+        | NotesLocation::Markdown(notes) => Some(notes),
+    }
+}
+
+/// Typos allowed before a word no longer matches, short words must be exact so "at" does not match
+/// "an" and "on"
+fn allowed_typos(search_word: &[char]) -> usize {
+    match search_word.len() {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+/// Optimal string alignment distance, the Levenshtein distance that also counts swapping two
+/// neighboring letters as one typo. Returns None as soon as the distance is known to be more than
+/// `max`.
+fn typo_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let width = b.len() + 1;
+    let mut previous_previous: Vec<usize> = vec![0; width];
+    let mut previous: Vec<usize> = (0..width).collect();
+    let mut current: Vec<usize> = vec![0; width];
+    for i in 1..=a.len() {
+        current[0] = i;
+        let mut row_minimum = current[0];
+        for j in 1..=b.len() {
+            let substitution = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(previous_previous[j - 2] + 1);
+            }
+            current[j] = distance;
+            row_minimum = row_minimum.min(distance);
+        }
+        if row_minimum > max {
+            return None;
+        }
+        std::mem::swap(&mut previous_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    let distance = previous[b.len()];
+    if distance <= max {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+/// How well a word typed in the search matches a word in an item, None if it does not match
+fn score_word(search_word: &str, search_chars: &[char], word: &str) -> Option<u32> {
+    if word == search_word {
+        return Some(EXACT_SCORE);
+    }
+    if search_chars.len() >= 2 && word.starts_with(search_word) {
+        return Some(PREFIX_SCORE);
+    }
+    if search_chars.len() >= 3 && word.contains(search_word) {
+        return Some(CONTAINS_SCORE);
+    }
+    let max = allowed_typos(search_chars);
+    if max == 0 {
+        return None;
+    }
+    let word_chars = word.chars().collect::<Vec<_>>();
+    match typo_distance(search_chars, &word_chars, max) {
+        Some(0) => Some(EXACT_SCORE),
+        Some(1) => Some(ONE_TYPO_SCORE),
+        Some(_) => Some(TWO_TYPOS_SCORE),
+        //Allow a typo in a word that is still being typed, "dentst" for "dentist's"
+        None => match typo_distance(
+            search_chars,
+            &word_chars[..word_chars.len().min(search_chars.len())],
+            max,
+        ) {
+            Some(_) if word_chars.len() > search_chars.len() => Some(TWO_TYPOS_SCORE),
+            _ => None,
+        },
+    }
+}
+
+impl<'s> SearchIndex<'s> {
+    pub(crate) fn new(items: &'s HashMap<&'s RecordId, Item<'s>>) -> Self {
+        let items = items.values().collect::<Vec<_>>();
+        let mut words: HashMap<String, Vec<(usize, Field)>> = HashMap::new();
+        for (index, item) in items.iter().enumerate() {
+            for word in split_into_words(item.get_summary()) {
+                words.entry(word).or_default().push((index, Field::Summary));
+            }
+            if let Some(notes) = notes_text(item.get_notes_location()) {
+                for word in split_into_words(notes) {
+                    words.entry(word).or_default().push((index, Field::Notes));
+                }
+            }
+        }
+        SearchIndex { items, words }
+    }
+
+    /// Finds the active and finished items where every word searched for is in the summary or the
+    /// notes, allowing for typos. Best matches come first, matches in the summary count for more than
+    /// matches in the notes, and ties go to the most recently created or finished item.
+    pub(crate) fn search(&self, search_for: &str) -> Vec<SearchMatch<'s>> {
+        let search_words = split_into_words(search_for).collect::<Vec<_>>();
+        if search_words.is_empty() {
+            return Vec::default();
+        }
+
+        //For each search word, the best score for each item
+        let mut scores_per_search_word: Vec<HashMap<usize, u32>> = Vec::default();
+        for search_word in &search_words {
+            let search_chars = search_word.chars().collect::<Vec<_>>();
+            let mut best_scores: HashMap<usize, u32> = HashMap::new();
+            for (word, uses) in &self.words {
+                let Some(score) = score_word(search_word, &search_chars, word) else {
+                    continue;
+                };
+                for (index, field) in uses {
+                    let score = match field {
+                        Field::Summary => score,
+                        Field::Notes => score / 2,
+                    };
+                    let best = best_scores.entry(*index).or_default();
+                    *best = (*best).max(score);
+                }
+            }
+            scores_per_search_word.push(best_scores);
+        }
+
+        let (first, rest) = scores_per_search_word
+            .split_first()
+            .expect("There is at least one search word");
+        let mut matches = first
+            .iter()
+            .filter_map(|(index, score)| {
+                let mut total = *score;
+                for scores in rest {
+                    total += scores.get(index)?;
+                }
+                Some(SearchMatch {
+                    item: self.items[*index],
+                    score: total,
+                })
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| b.get_recency().cmp(a.get_recency()))
+        });
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::HashMap;
+    use chrono::{TimeDelta, Utc};
+    use surrealdb::RecordId;
+
+    use crate::{
+        base_data::item::Item,
+        data_storage::surrealdb_layer::surreal_item::{
+            NotesLocation, SurrealItem, SurrealItemBuilder,
+        },
+    };
+
+    use super::{SearchIndex, typo_distance};
+
+    fn summaries(index: &SearchIndex<'_>, search_for: &str) -> Vec<String> {
+        index
+            .search(search_for)
+            .iter()
+            .map(|x| x.get_item().get_summary().to_string())
+            .collect()
+    }
+
+    fn distance(a: &str, b: &str, max: usize) -> Option<usize> {
+        let a = a.chars().collect::<Vec<_>>();
+        let b = b.chars().collect::<Vec<_>>();
+        typo_distance(&a, &b, max)
+    }
+
+    #[test]
+    fn typo_distance_counts_swapped_letters_as_one_typo() {
+        assert_eq!(distance("dentist", "dentist", 2), Some(0));
+        assert_eq!(distance("dentsit", "dentist", 2), Some(1));
+        assert_eq!(distance("dentst", "dentist", 2), Some(1));
+        assert_eq!(distance("dantsit", "dentist", 2), Some(2));
+        assert_eq!(distance("plumber", "dentist", 2), None);
+        assert_eq!(distance("dent", "dentist", 2), None);
+    }
+
+    #[test]
+    fn search_ranks_by_match_quality_then_recency_and_includes_finished_items() {
+        let now = Utc::now();
+        let surreal_items: Vec<SurrealItem> = vec![
+            SurrealItemBuilder::default()
+                .id(Some(("item", "old_dentist").into()))
+                .summary("Call the dentist")
+                .created((now - TimeDelta::days(300)).into())
+                .finished(Some((now - TimeDelta::days(200)).into()))
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("item", "new_dentist").into()))
+                .summary("Book a dentist appointment")
+                .created((now - TimeDelta::days(2)).into())
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("item", "dentures").into()))
+                .summary("Ask about dentures for grandpa")
+                .created((now - TimeDelta::days(1)).into())
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("item", "insurance").into()))
+                .summary("Renew insurance")
+                .notes_location(NotesLocation::Markdown(
+                    "Check the dentist coverage".to_string(),
+                ))
+                .created(now.into())
+                .build()
+                .unwrap(),
+            SurrealItemBuilder::default()
+                .id(Some(("item", "plumber").into()))
+                .summary("Call the plumber")
+                .created(now.into())
+                .build()
+                .unwrap(),
+        ];
+        let items: HashMap<&RecordId, Item<'_>> = surreal_items
+            .iter()
+            .map(|x| (x.id.as_ref().unwrap(), x.make_item(&now)))
+            .collect();
+        let index = SearchIndex::new(&items);
+
+        assert_eq!(
+            summaries(&index, "dentist"),
+            vec![
+                "Book a dentist appointment",
+                "Call the dentist",
+                "Renew insurance"
+            ],
+            "Exact summary matches first, most recent first, then notes"
+        );
+        assert_eq!(
+            summaries(&index, "dentsit"),
+            vec![
+                "Book a dentist appointment",
+                "Call the dentist",
+                "Renew insurance"
+            ],
+            "A swapped letter is still found"
+        );
+        assert_eq!(
+            summaries(&index, "dent"),
+            vec![
+                "Ask about dentures for grandpa",
+                "Book a dentist appointment",
+                "Call the dentist",
+                "Renew insurance"
+            ],
+            "Prefixes match"
+        );
+        assert_eq!(
+            summaries(&index, "call dentist"),
+            vec!["Call the dentist"],
+            "Every word must match"
+        );
+        assert!(
+            index.search("call dentist")[0].get_item().is_finished(),
+            "Finished items are included"
+        );
+        assert!(summaries(&index, "  ").is_empty());
+        assert!(summaries(&index, "orthodontist").is_empty());
+    }
+}
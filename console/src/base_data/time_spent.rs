@@ -91,6 +91,14 @@ impl<'s> TimeSpent<'s> {
         })
     }
 
+    /// The item that was worked on, the rest of `worked_towards` are its parents
+    pub(crate) fn get_worked_on(&self) -> Option<&RecordId> {
+        self.surreal_time_spent
+            .working_on
+            .first()
+            .map(|action| action.get_record_id())
+    }
+
     pub(crate) fn worked_towards(&self) -> &HashSet<RecordId> {
         &self.worked_towards
     }
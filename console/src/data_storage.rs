@@ -1 +1,3 @@
-pub(crate) mod surrealdb_layer;
+#[cfg(unix)]
+pub(crate) mod daemon;
+pub(crate) mod surrealdb_layer;
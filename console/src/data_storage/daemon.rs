@@ -0,0 +1,642 @@
+//! The embedded database can only be opened by one process, so a daemon owns it and every front end
+//! sends its `DataLayerCommands` to the daemon over a Unix domain socket instead.
+//!
+//! Protocol: each message is one line of JSON, a `DaemonRequest` from the front end or a
+//! `DaemonResponse` from the daemon. Both carry `version`, which is `PROTOCOL_VERSION`, and a request
+//! with any other version is refused. Every request is answered with one response, in the order the
+//! requests were written:
+//!
+//! - `{"version": 1, "request": "raw_data"}` with `{"version": 1, "response": {"raw_data": {...}}}`
+//! - `{"version": 1, "request": "time_spent_log"}` with
+//!   `{"version": 1, "response": {"time_spent_log": [...]}}`
+//! - `{"version": 1, "request": {"command": {...}}}` with `{"version": 1, "response": "done"}` once
+//!   the command has run, so whatever is asked next sees the change
+//!
+//! Anything that is refused, a line that is not understood or a command that refers to an item that
+//! does not exist, is answered with `{"version": 1, "response": {"error": "..."}}` and the connection
+//! stays open. A command is a `DataLayerCommands` the way serde writes it by default, so changing how
+//! those serialize, or `SurrealTables` and `SurrealTimeSpent` in the responses, means a new
+//! `PROTOCOL_VERSION`. The tests pin a command's JSON to catch that.
+//!
+//! The socket lives in a directory only the user can get into and both ends check that the other
+//! end is the same user, because whoever is on the other end can read and change everything.
+
+use std::{
+    env, io,
+    os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt},
+    path::{Path, PathBuf},
+    process,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream, unix::OwnedWriteHalf},
+    sync::{
+        mpsc::{Receiver, Sender},
+        oneshot,
+    },
+    task::JoinSet,
+};
+
+use super::surrealdb_layer::{
+    data_layer_commands::DataLayerCommands, surreal_tables::SurrealTables,
+    surreal_time_spent::SurrealTimeSpent,
+};
+
+/// Changes whenever a request or a response, including the commands and data inside them, is
+/// written differently, so a front end and a daemon from different builds refuse each other instead
+/// of misreading each other
+pub(crate) const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DaemonRequest {
+    pub(crate) version: u32,
+    pub(crate) request: DaemonRequestKind,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonRequestKind {
+    /// Answered with `DaemonResponseKind::RawData`
+    RawData,
+    /// Answered with `DaemonResponseKind::TimeSpentLog`
+    TimeSpentLog,
+    /// Answered with `DaemonResponseKind::Done` once it has run
+    Command(DataLayerCommands),
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DaemonResponse {
+    pub(crate) version: u32,
+    pub(crate) response: DaemonResponseKind,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DaemonResponseKind {
+    RawData(SurrealTables),
+    TimeSpentLog(Vec<SurrealTimeSpent>),
+    Done,
+    /// Why the request was refused
+    Error(String),
+}
+
+impl DaemonRequest {
+    fn new(request: DaemonRequestKind) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            request,
+        }
+    }
+}
+
+impl DaemonResponse {
+    fn new(response: DaemonResponseKind) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            response,
+        }
+    }
+}
+
+/// Read before the rest of a message so a message from another version is refused for that reason
+/// rather than for whatever part of it is not understood
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+fn read_message<'a, T: Deserialize<'a>>(line: &'a str) -> Result<T, String> {
+    let Version { version } =
+        serde_json::from_str(line).map_err(|err| format!("Not a message: {}", err))?;
+    if version != PROTOCOL_VERSION {
+        return Err(format!(
+            "The other end uses protocol version {} but this one uses version {}, run the same build of Task On Purpose on both ends",
+            version, PROTOCOL_VERSION
+        ));
+    }
+    serde_json::from_str(line).map_err(|err| format!("Not a message: {}", err))
+}
+
+fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail
+    unsafe { libc::getuid() }
+}
+
+/// One socket per database so daemons for different databases do not collide, the endpoint and
+/// namespace are folded into a hash to keep the path short enough for a socket. `XDG_RUNTIME_DIR` is
+/// already private to the user, without it the socket goes in a directory of its own in the
+/// temporary directory, which `bind_daemon_socket` makes private.
+pub(crate) fn default_socket_path(endpoint: &str, namespace: &str, database: &str) -> PathBuf {
+    let directory = env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::temp_dir().join(format!("taskonpurpose-{}", current_uid())));
+    directory.join(format!(
+        "taskonpurpose-{}-{:016x}.sock",
+        database,
+        stable_hash(&[endpoint, namespace])
+    ))
+}
+
+/// FNV-1a, which unlike the std and ahash hashers gives the same answer in every build so a front
+/// end finds the socket of a daemon from another build
+fn stable_hash(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in parts.iter().flat_map(|x| x.bytes().chain([0])) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn is_private_directory(directory: &Path) -> bool {
+    std::fs::symlink_metadata(directory).is_ok_and(|metadata| {
+        metadata.is_dir() && metadata.uid() == current_uid() && metadata.mode() & 0o077 == 0
+    })
+}
+
+/// Creates the directory if needed and makes sure it belongs to this user and nobody else can get
+/// into it, so another user cannot put their own socket there or connect to ours
+fn ensure_private_directory(directory: &Path) -> io::Result<()> {
+    match std::fs::DirBuilder::new().mode(0o700).create(directory) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(err) => return Err(err),
+    }
+    if !is_private_directory(directory) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{} must be a directory that belongs to this user and that only this user can use",
+                directory.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn socket_directory(socket_path: &Path) -> &Path {
+    socket_path
+        .parent()
+        .filter(|x| !x.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+}
+
+/// A socket given with `--socket` stays where it was asked for when only this user can use that
+/// directory. Otherwise, like `/tmp` or a home directory, it goes in a private directory next to it
+/// so `/tmp/tasks.sock` becomes `/tmp/taskonpurpose-<uid>/tasks.sock`. The daemon and the front ends
+/// are given the same path so they agree on where it is.
+pub(crate) fn socket_path_in_private_directory(socket_path: &Path) -> PathBuf {
+    let directory = socket_directory(socket_path);
+    match socket_path.file_name() {
+        Some(file_name) if !is_private_directory(directory) => directory
+            .join(format!("taskonpurpose-{}", current_uid()))
+            .join(file_name),
+        _ => socket_path.to_path_buf(),
+    }
+}
+
+/// True when the other end of the socket is running as this user
+fn is_same_user(stream: &UnixStream) -> bool {
+    stream
+        .peer_cred()
+        .is_ok_and(|credentials| credentials.uid() == current_uid())
+}
+
+async fn write_line<T: Serialize>(write_half: &mut OwnedWriteHalf, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_string(message).map_err(io::Error::other)?;
+    line.push('\n');
+    write_half.write_all(line.as_bytes()).await
+}
+
+/// None when no daemon is listening on the socket, or when what is listening is not this user
+pub(crate) async fn connect_to_daemon(socket_path: &Path) -> Option<UnixStream> {
+    let stream = UnixStream::connect(socket_path).await.ok()?;
+    if !is_same_user(&stream) {
+        eprintln!(
+            "Not using {} because it is not a daemon run by this user",
+            socket_path.display()
+        );
+        return None;
+    }
+    Some(stream)
+}
+
+/// A request that was sent to the daemon and is waiting on the answer
+enum WaitingOnAnswer {
+    RawData(oneshot::Sender<SurrealTables>),
+    TimeSpentLog(oneshot::Sender<Vec<SurrealTimeSpent>>),
+    Command,
+}
+
+/// Stands in for `data_storage_start_and_run` when a daemon is running, sending every command to the
+/// daemon until the last sender is dropped. Nothing can be saved once the connection to the daemon
+/// is lost so the program stops with a message rather than carrying on.
+pub(crate) async fn forward_to_daemon(
+    data_storage_layer_receive_rx: Receiver<DataLayerCommands>,
+    stream: UnixStream,
+) {
+    if let Err(err) = forward_requests(data_storage_layer_receive_rx, stream).await {
+        eprintln!(
+            "{}. Changes since the last one that was saved are lost, start Task On Purpose again to continue.",
+            err
+        );
+        process::exit(1);
+    }
+}
+
+async fn forward_requests(
+    mut data_storage_layer_receive_rx: Receiver<DataLayerCommands>,
+    stream: UnixStream,
+) -> Result<(), String> {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+    while let Some(command) = data_storage_layer_receive_rx.recv().await {
+        let (request, waiting_on_answer) = match command {
+            DataLayerCommands::SendRawData(oneshot) => (
+                DaemonRequestKind::RawData,
+                WaitingOnAnswer::RawData(oneshot),
+            ),
+            DataLayerCommands::SendTimeSpentLog(oneshot) => (
+                DaemonRequestKind::TimeSpentLog,
+                WaitingOnAnswer::TimeSpentLog(oneshot),
+            ),
+            command => (
+                DaemonRequestKind::Command(command),
+                WaitingOnAnswer::Command,
+            ),
+        };
+        write_line(&mut write_half, &DaemonRequest::new(request))
+            .await
+            .map_err(|err| format!("Lost the connection to the daemon: {}", err))?;
+
+        let line = lines
+            .next_line()
+            .await
+            .map_err(|err| format!("Lost the connection to the daemon: {}", err))?
+            .ok_or_else(|| "The daemon closed the connection".to_string())?;
+        let response: DaemonResponse = read_message(&line)
+            .map_err(|err| format!("The daemon sent a message that is not understood: {}", err))?;
+        //The one asking may have stopped waiting, which is fine
+        match (response.response, waiting_on_answer) {
+            (DaemonResponseKind::RawData(surreal_tables), WaitingOnAnswer::RawData(oneshot)) => {
+                oneshot.send(surreal_tables).ok();
+            }
+            (
+                DaemonResponseKind::TimeSpentLog(time_spent),
+                WaitingOnAnswer::TimeSpentLog(oneshot),
+            ) => {
+                oneshot.send(time_spent).ok();
+            }
+            (DaemonResponseKind::Done, WaitingOnAnswer::Command) => {}
+            //The menus carry on with the data as it is, the next time they load it they see that
+            //nothing changed
+            (DaemonResponseKind::Error(err), WaitingOnAnswer::Command) => {
+                eprintln!("The daemon refused a change: {}", err);
+            }
+            (DaemonResponseKind::Error(err), _) => {
+                return Err(format!("The daemon could not answer: {}", err));
+            }
+            _ => {
+                return Err(
+                    "The daemon answered a different request than the one asked".to_string()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn serve_connection(
+    stream: UnixStream,
+    send_to_data_storage_layer: Sender<DataLayerCommands>,
+) {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let response = match read_message::<DaemonRequest>(&line).map(|x| x.request) {
+            Ok(DaemonRequestKind::Command(command)) => {
+                match command.check_and_run(&send_to_data_storage_layer).await {
+                    Ok(Ok(())) => DaemonResponseKind::Done,
+                    Ok(Err(rejection)) => DaemonResponseKind::Error(rejection.to_string()),
+                    Err(_) => return,
+                }
+            }
+            Ok(DaemonRequestKind::RawData) => {
+                let Ok(surreal_tables) = SurrealTables::new(&send_to_data_storage_layer).await
+                else {
+                    return;
+                };
+                DaemonResponseKind::RawData(surreal_tables)
+            }
+            Ok(DaemonRequestKind::TimeSpentLog) => {
+                let (sender, receiver) = oneshot::channel();
+                if send_to_data_storage_layer
+                    .send(DataLayerCommands::SendTimeSpentLog(sender))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+                let Ok(time_spent) = receiver.await else {
+                    return;
+                };
+                DaemonResponseKind::TimeSpentLog(time_spent)
+            }
+            Err(err) => DaemonResponseKind::Error(err),
+        };
+        if write_line(&mut write_half, &DaemonResponse::new(response))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Takes over the socket, removing it first when it is left over from a daemon that is no longer
+/// running. The socket is made inside a directory that only the current user can use so it is never
+/// reachable by anyone else, not even before its own permissions are set.
+pub(crate) async fn bind_daemon_socket(socket_path: &Path) -> io::Result<UnixListener> {
+    ensure_private_directory(socket_directory(socket_path))?;
+    if socket_path.exists() {
+        if connect_to_daemon(socket_path).await.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("A daemon is already running at {}", socket_path.display()),
+            ));
+        }
+        std::fs::remove_file(socket_path)?;
+    }
+    let listener = UnixListener::bind(socket_path)?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Accepts front ends until `shutdown` completes, each connection is served on its own task and
+/// they all share the one data storage layer. Connections still open at shutdown are closed.
+pub(crate) async fn run_daemon(
+    listener: UnixListener,
+    send_to_data_storage_layer: Sender<DataLayerCommands>,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()> {
+    let mut connections = JoinSet::new();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                if !is_same_user(&stream) {
+                    eprintln!("Refused a connection from another user");
+                    continue;
+                }
+                connections.spawn(serve_connection(stream, send_to_data_storage_layer.clone()));
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use surrealdb::RecordId;
+    use tokio::sync::{mpsc, oneshot};
+
+    use crate::{
+        data_storage::surrealdb_layer::{
+            data_layer_commands::{
+                DataLayerCommands, SurrealDbConnectionConfig, data_storage_start_and_run,
+            },
+            surreal_tables::SurrealTables,
+        },
+        new_item::NewItem,
+    };
+
+    use super::{
+        DaemonRequest, DaemonRequestKind, DaemonResponse, DaemonResponseKind, PROTOCOL_VERSION,
+        bind_daemon_socket, connect_to_daemon, default_socket_path, ensure_private_directory,
+        forward_to_daemon, read_message, run_daemon, socket_path_in_private_directory,
+    };
+
+    #[test]
+    fn requests_are_one_line_of_json_with_the_version() {
+        let line = serde_json::to_string(&DaemonRequest::new(DaemonRequestKind::Command(
+            DataLayerCommands::UnsnoozeItem(("item", "a").into()),
+        )))
+        .unwrap();
+        assert!(!line.contains('\n'));
+        let expected: RecordId = ("item", "a").into();
+        assert!(matches!(
+            read_message(&line).unwrap(),
+            DaemonRequest {
+                version: PROTOCOL_VERSION,
+                request: DaemonRequestKind::Command(DataLayerCommands::UnsnoozeItem(id)),
+            } if id == expected
+        ));
+        assert_eq!(
+            serde_json::to_string(&DaemonRequest::new(DaemonRequestKind::RawData)).unwrap(),
+            format!(r#"{{"version":{},"request":"raw_data"}}"#, PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            serde_json::to_string(&DaemonResponse::new(DaemonResponseKind::Error(
+                "Why".to_string()
+            )))
+            .unwrap(),
+            format!(
+                r#"{{"version":{},"response":{{"error":"Why"}}}}"#,
+                PROTOCOL_VERSION
+            )
+        );
+    }
+
+    /// If this has to change then so does `PROTOCOL_VERSION`
+    #[test]
+    fn commands_are_written_the_same_way_as_in_this_protocol_version() {
+        let id: RecordId = ("item", "a").into();
+        let id_json = serde_json::to_value(&id).unwrap();
+        assert_eq!(
+            serde_json::to_value(DataLayerCommands::ClearWorkingOn).unwrap(),
+            serde_json::json!("ClearWorkingOn")
+        );
+        assert_eq!(
+            serde_json::to_value(DataLayerCommands::ReactivateItem { item: id.clone() }).unwrap(),
+            serde_json::json!({"ReactivateItem": {"item": id_json}})
+        );
+        assert_eq!(
+            serde_json::to_value(DataLayerCommands::UpdateSummary(
+                id,
+                "New summary".to_string()
+            ))
+            .unwrap(),
+            serde_json::json!({"UpdateSummary": [id_json, "New summary"]})
+        );
+        assert_eq!(PROTOCOL_VERSION, 1);
+    }
+
+    #[test]
+    fn a_message_from_another_version_is_refused_for_that_reason() {
+        let err = read_message::<DaemonRequest>(r#"{"version": 0, "request": "Renamed"}"#)
+            .err()
+            .unwrap();
+        assert!(err.contains("protocol version 0"));
+    }
+
+    #[tokio::test]
+    async fn a_second_front_end_shares_the_daemons_database() {
+        let (daemon_sender, daemon_receiver) = mpsc::channel(1);
+        let data_storage_join_handle = tokio::spawn(data_storage_start_and_run(
+            daemon_receiver,
+            SurrealDbConnectionConfig {
+                endpoint: "mem://".to_string(),
+                namespace: "TaskOnPurpose".to_string(),
+                database: "daemon_test".to_string(),
+                auth: None,
+            },
+        ));
+
+        let socket_directory =
+            std::env::temp_dir().join(format!("taskonpurpose-test-{}", std::process::id()));
+        let socket_path = socket_directory.join("daemon.sock");
+        let listener = bind_daemon_socket(&socket_path).await.unwrap();
+        let (stop_daemon, stopped) = oneshot::channel::<()>();
+        let daemon_join_handle = tokio::spawn(run_daemon(listener, daemon_sender, async {
+            stopped.await.ok();
+        }));
+
+        let (client_sender, client_receiver) = mpsc::channel(1);
+        let stream = connect_to_daemon(&socket_path).await.unwrap();
+        let client_join_handle = tokio::spawn(forward_to_daemon(client_receiver, stream));
+
+        client_sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Shared item".to_string(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&client_sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        assert_eq!(surreal_tables.surreal_items[0].summary, "Shared item");
+
+        drop(client_sender);
+        client_join_handle.await.unwrap();
+        stop_daemon.send(()).unwrap();
+        daemon_join_handle.await.unwrap().unwrap();
+        std::fs::remove_dir_all(&socket_directory).unwrap();
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn commands_for_items_that_do_not_exist_do_not_stop_the_daemon() {
+        let (daemon_sender, daemon_receiver) = mpsc::channel(1);
+        let data_storage_join_handle = tokio::spawn(data_storage_start_and_run(
+            daemon_receiver,
+            SurrealDbConnectionConfig {
+                endpoint: "mem://".to_string(),
+                namespace: "TaskOnPurpose".to_string(),
+                database: "daemon_refuse_test".to_string(),
+                auth: None,
+            },
+        ));
+
+        let socket_directory =
+            std::env::temp_dir().join(format!("taskonpurpose-refuse-test-{}", std::process::id()));
+        let socket_path = socket_directory.join("daemon.sock");
+        let listener = bind_daemon_socket(&socket_path).await.unwrap();
+        let (stop_daemon, stopped) = oneshot::channel::<()>();
+        let daemon_join_handle = tokio::spawn(run_daemon(listener, daemon_sender, async {
+            stopped.await.ok();
+        }));
+
+        let (client_sender, client_receiver) = mpsc::channel(1);
+        let stream = connect_to_daemon(&socket_path).await.unwrap();
+        let client_join_handle = tokio::spawn(forward_to_daemon(client_receiver, stream));
+
+        client_sender
+            .send(DataLayerCommands::FinishItem {
+                item: ("item", "nope").into(),
+                when_finished: Utc::now().into(),
+            })
+            .await
+            .unwrap();
+        client_sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Still running".to_string(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&client_sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+
+        drop(client_sender);
+        client_join_handle.await.unwrap();
+        stop_daemon.send(()).unwrap();
+        daemon_join_handle.await.unwrap().unwrap();
+        std::fs::remove_dir_all(&socket_directory).unwrap();
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[test]
+    fn each_database_has_its_own_socket() {
+        let default = default_socket_path("file://c:/.on_purpose.db", "TaskOnPurpose", "nash");
+        assert_eq!(
+            default,
+            default_socket_path("file://c:/.on_purpose.db", "TaskOnPurpose", "nash")
+        );
+        assert_ne!(
+            default,
+            default_socket_path("file://c:/other.db", "TaskOnPurpose", "nash")
+        );
+        assert_ne!(
+            default,
+            default_socket_path("file://c:/.on_purpose.db", "Other", "nash")
+        );
+        assert_ne!(
+            default,
+            default_socket_path("file://c:/.on_purpose.db", "TaskOnPurpose", "other")
+        );
+    }
+
+    #[test]
+    fn a_socket_asked_for_in_a_directory_others_can_use_goes_in_a_private_one_next_to_it() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let shared =
+            std::env::temp_dir().join(format!("taskonpurpose-shared-test-{}", std::process::id()));
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let private = shared.join("private");
+        ensure_private_directory(&private).unwrap();
+
+        let moved = socket_path_in_private_directory(&shared.join("tasks.sock"));
+        assert_eq!(
+            Some(shared.as_path()),
+            moved.parent().and_then(|x| x.parent())
+        );
+        assert_eq!(Some("tasks.sock".as_ref()), moved.file_name());
+        ensure_private_directory(moved.parent().unwrap()).unwrap();
+        assert_eq!(
+            private.join("tasks.sock"),
+            socket_path_in_private_directory(&private.join("tasks.sock"))
+        );
+
+        std::fs::remove_dir_all(&shared).unwrap();
+    }
+
+    #[test]
+    fn a_directory_others_can_use_is_not_trusted() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let directory =
+            std::env::temp_dir().join(format!("taskonpurpose-open-test-{}", std::process::id()));
+        ensure_private_directory(&directory).unwrap();
+        std::fs::set_permissions(&directory, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(ensure_private_directory(&directory).is_err());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
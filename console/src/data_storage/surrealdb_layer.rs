@@ -5,13 +5,22 @@ use surrealdb::{
 };
 
 pub(crate) mod data_layer_commands;
+pub(crate) mod surreal_break;
 pub(crate) mod surreal_current_mode;
+pub(crate) mod surreal_daily_plan;
+pub(crate) mod surreal_daily_summary;
 pub(crate) mod surreal_event;
 pub(crate) mod surreal_in_the_moment_priority;
+pub(crate) mod surreal_interruption;
 pub(crate) mod surreal_item;
+pub(crate) mod surreal_item_history;
 pub(crate) mod surreal_mode;
+pub(crate) mod surreal_priority_comparison;
+pub(crate) mod surreal_resume_note;
+pub(crate) mod surreal_smart_list;
 pub(crate) mod surreal_tables;
 pub(crate) mod surreal_time_spent;
+pub(crate) mod surreal_weekly_review;
 pub(crate) mod surreal_working_on;
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
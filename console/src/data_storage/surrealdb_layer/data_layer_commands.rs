@@ -1,5 +1,12 @@
-use chrono::Utc;
-use std::{future::Future, pin::Pin, time::Duration};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter},
+    future::Future,
+    pin::Pin,
+    time::Duration,
+};
 use surrealdb::{
     Error as SurrealError, RecordId, Surreal,
     engine::any::{Any, IntoEndpoint, connect},
@@ -21,7 +28,7 @@ use tokio::{
 use crate::{
     data_storage::surrealdb_layer::surreal_mode::SurrealMode,
     new_event::NewEvent,
-    new_item::{NewDependency, NewItem},
+    new_item::{NewDependency, NewItem, NewItemTree},
     new_mode::NewMode,
     new_time_spent::NewTimeSpent,
 };
@@ -30,24 +37,47 @@ use futures::stream::{self, StreamExt};
 
 use super::{
     SurrealTrigger,
+    surreal_break::SurrealBreak,
     surreal_current_mode::{NewCurrentMode, SurrealCurrentMode},
+    surreal_daily_plan::SurrealDailyPlan,
+    surreal_daily_summary::SurrealDailySummary,
     surreal_event::SurrealEvent,
     surreal_in_the_moment_priority::{
         SurrealAction, SurrealInTheMomentPriority, SurrealPriorityKind,
     },
+    surreal_interruption::SurrealInterruption,
     surreal_item::{
-        Responsibility, SurrealDependency, SurrealFrequency, SurrealItem, SurrealItemOldVersion,
-        SurrealItemType, SurrealOrderedSubItem, SurrealReviewGuidance, SurrealUrgencyPlan,
+        NotesLocation, Responsibility, SurrealDependency, SurrealFrequency, SurrealItem,
+        SurrealItemOldVersion, SurrealItemType, SurrealOrderedSubItem, SurrealReviewGuidance,
+        SurrealUrgencyPlan, SurrealWaitingFor,
     },
+    surreal_item_history::{SurrealItemChange, SurrealItemHistory},
     surreal_mode,
+    surreal_priority_comparison::SurrealPriorityComparison,
+    surreal_resume_note::SurrealResumeNote,
+    surreal_smart_list::{SurrealSmartList, SurrealSmartListFilter},
     surreal_tables::SurrealTables,
     surreal_time_spent::{SurrealTimeSpent, SurrealTimeSpentVersion0},
+    surreal_weekly_review::SurrealWeeklyReview,
     surreal_working_on::SurrealWorkingOn,
 };
 
+/// Serializable so the commands can be sent to a daemon that owns the database, the commands that
+/// answer back with a oneshot are sent as their own requests, see `daemon::DaemonRequestKind`
+#[derive(Serialize, Deserialize)]
 pub(crate) enum DataLayerCommands {
+    #[serde(skip)]
     SendRawData(oneshot::Sender<SurrealTables>),
+    #[serde(skip)]
     SendTimeSpentLog(oneshot::Sender<Vec<SurrealTimeSpent>>),
+    /// Runs the command only if `check_against` passes and answers with the result. The check and
+    /// the command are run one after the other by the data storage layer so nothing can change in
+    /// between.
+    #[serde(skip)]
+    CheckAndRun(
+        Box<DataLayerCommands>,
+        oneshot::Sender<Result<(), CommandRejection>>,
+    ),
     RecordTimeSpent(NewTimeSpent),
     SetWorkingOn {
         item: RecordId,
@@ -62,6 +92,8 @@ pub(crate) enum DataLayerCommands {
         item: RecordId,
     },
     NewItem(NewItem),
+    /// Removes the item and every reference to it, only meant for items that were never worked on
+    DeleteItem(RecordId),
     NewMode(NewMode),
     CoverItemWithANewItem {
         cover_this: RecordId,
@@ -86,6 +118,12 @@ pub(crate) enum DataLayerCommands {
         parent: RecordId,
         higher_importance_than_this: Option<RecordId>,
     },
+    /// Each tree is added the same way as `ParentItemWithANewChildItem`, in order so the first is
+    /// the most important, and then its smaller items are added under it the same way
+    ParentItemWithNewChildTrees {
+        children: Vec<NewItemTree>,
+        parent: RecordId,
+    },
     ParentNewItemWithAnExistingChildItem {
         child: RecordId,
         parent_new_item: NewItem,
@@ -98,7 +136,22 @@ pub(crate) enum DataLayerCommands {
     AddItemDependency(RecordId, SurrealDependency),
     RemoveItemDependency(RecordId, SurrealDependency),
     AddItemDependencyNewEvent(RecordId, NewEvent),
+    SnoozeItem {
+        item: RecordId,
+        until: Datetime,
+    },
+    UnsnoozeItem(RecordId),
     UpdateSummary(RecordId, String),
+    UpdateNotesLocation(RecordId, NotesLocation),
+    UpdateTags(RecordId, Vec<String>),
+    /// None means it is no longer waiting on anyone
+    UpdateWaitingFor(RecordId, Option<SurrealWaitingFor>),
+    WaitingForNewPerson {
+        item: RecordId,
+        person: NewItem,
+        requested: Datetime,
+        follow_up: Datetime,
+    },
     UpdateModeName(RecordId, String),
     UpdateUrgencyPlan(RecordId, Option<SurrealUrgencyPlan>),
     UpdateItemReviewFrequency(RecordId, SurrealFrequency, SurrealReviewGuidance),
@@ -110,6 +163,40 @@ pub(crate) enum DataLayerCommands {
         in_effect_until: Vec<SurrealTrigger>,
     },
     ClearInTheMomentPriority(RecordId),
+    RecordPriorityComparison {
+        winner: RecordId,
+        loser: RecordId,
+        for_mode: Option<RecordId>,
+    },
+    NewSmartList {
+        name: String,
+        filter: SurrealSmartListFilter,
+    },
+    DeleteSmartList(RecordId),
+    RecordBreak {
+        when_started: Datetime,
+        when_stopped: Datetime,
+    },
+    NewResumeNote {
+        item: RecordId,
+        doing: String,
+        next: String,
+        location: String,
+    },
+    RecordInterruption {
+        interrupted: Option<RecordId>,
+        source: String,
+        when_started: Datetime,
+        when_stopped: Datetime,
+    },
+    SetDailyPlan {
+        day: NaiveDate,
+        commitments: Vec<RecordId>,
+    },
+    /// Replaces any summary already written for the same day
+    WriteDailySummary(SurrealDailySummary),
+    /// Replaces the progress saved earlier for the same review
+    SaveWeeklyReview(SurrealWeeklyReview),
     SetCurrentMode(NewCurrentMode),
     TriggerEvent {
         event: RecordId,
@@ -197,6 +284,230 @@ impl DataLayerCommands {
             .unwrap();
         raw_data_receiver.await
     }
+
+    /// Sends the command as `CheckAndRun` and waits for it to be checked and run. A closed data
+    /// storage layer drops the command, and with it the answer, so that shows up as the `RecvError`.
+    pub(crate) async fn check_and_run(
+        self,
+        sender: &Sender<DataLayerCommands>,
+    ) -> Result<Result<(), CommandRejection>, RecvError> {
+        let (respond, result) = oneshot::channel();
+        sender
+            .send(DataLayerCommands::CheckAndRun(Box::new(self), respond))
+            .await
+            .ok();
+        result.await
+    }
+}
+
+/// Why a command that came from another program is not run, see `DataLayerCommands::check_against`
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CommandRejection {
+    /// Something the command refers to is not in the database
+    NotFound(String),
+    /// The command does not fit the data, for example ordering a child next to an item that is not
+    /// a child of the same parent
+    Invalid(String),
+    /// Running the command would leave other data pointing at nothing
+    Conflict(String),
+}
+
+impl Display for CommandRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandRejection::NotFound(message)
+            | CommandRejection::Invalid(message)
+            | CommandRejection::Conflict(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+fn is_child_of(surreal_item: &SurrealItem, child: &RecordId) -> bool {
+    surreal_item
+        .smaller_items_in_priority_order
+        .iter()
+        .any(|x| match x {
+            SurrealOrderedSubItem::SubItem { surreal_item_id } => surreal_item_id == child,
+        })
+}
+
+impl DataLayerCommands {
+    /// The items the command changes or points at, all of which have to exist already
+    fn get_existing_items_referenced(&self) -> Vec<&RecordId> {
+        match self {
+            DataLayerCommands::SendRawData(_)
+            | DataLayerCommands::SendTimeSpentLog(_)
+            | DataLayerCommands::CheckAndRun(..)
+            | DataLayerCommands::ClearWorkingOn
+            | DataLayerCommands::NewItem(_)
+            | DataLayerCommands::NewMode(_)
+            | DataLayerCommands::UpdateModeName(..)
+            | DataLayerCommands::ClearInTheMomentPriority(_)
+            | DataLayerCommands::NewSmartList { .. }
+            | DataLayerCommands::DeleteSmartList(_)
+            | DataLayerCommands::RecordBreak { .. }
+            | DataLayerCommands::WriteDailySummary(_)
+            | DataLayerCommands::SaveWeeklyReview(_)
+            | DataLayerCommands::SetCurrentMode(_)
+            | DataLayerCommands::TriggerEvent { .. }
+            | DataLayerCommands::UntriggerEvent { .. } => vec![],
+            DataLayerCommands::RecordTimeSpent(new_time_spent) => new_time_spent
+                .working_on
+                .iter()
+                .map(|x| x.get_record_id())
+                .collect(),
+            DataLayerCommands::SetWorkingOn { item, .. }
+            | DataLayerCommands::FinishItem { item, .. }
+            | DataLayerCommands::ReactivateItem { item }
+            | DataLayerCommands::DeleteItem(item)
+            | DataLayerCommands::CoverItemWithANewItem {
+                cover_this: item, ..
+            }
+            | DataLayerCommands::ParentItemWithNewChildTrees { parent: item, .. }
+            | DataLayerCommands::ParentNewItemWithAnExistingChildItem { child: item, .. }
+            | DataLayerCommands::UpdateResponsibilityAndItemType(item, ..)
+            | DataLayerCommands::RemoveItemDependency(item, _)
+            | DataLayerCommands::AddItemDependencyNewEvent(item, _)
+            | DataLayerCommands::SnoozeItem { item, .. }
+            | DataLayerCommands::UnsnoozeItem(item)
+            | DataLayerCommands::UpdateSummary(item, _)
+            | DataLayerCommands::UpdateNotesLocation(item, _)
+            | DataLayerCommands::UpdateTags(item, _)
+            | DataLayerCommands::UpdateWaitingFor(item, None)
+            | DataLayerCommands::WaitingForNewPerson { item, .. }
+            | DataLayerCommands::UpdateUrgencyPlan(item, _)
+            | DataLayerCommands::UpdateItemReviewFrequency(item, ..)
+            | DataLayerCommands::UpdateItemLastReviewedDate(item, _)
+            | DataLayerCommands::NewResumeNote { item, .. } => vec![item],
+            DataLayerCommands::CoverItemWithAnExistingItem {
+                item_to_be_covered: first,
+                item_that_should_do_the_covering: second,
+            }
+            | DataLayerCommands::ParentItemRemoveParent {
+                child: first,
+                parent_to_remove: second,
+            }
+            | DataLayerCommands::UpdateWaitingFor(
+                first,
+                Some(SurrealWaitingFor { person: second, .. }),
+            )
+            | DataLayerCommands::RecordPriorityComparison {
+                winner: first,
+                loser: second,
+                ..
+            } => vec![first, second],
+            DataLayerCommands::UpdateRelativeImportance {
+                parent,
+                update_this_child: child,
+                higher_importance_than_this_child: higher_importance_than_this,
+            }
+            | DataLayerCommands::ParentItemWithExistingItem {
+                child,
+                parent,
+                higher_importance_than_this,
+            } => [child, parent]
+                .into_iter()
+                .chain(higher_importance_than_this)
+                .collect(),
+            DataLayerCommands::ParentItemWithANewChildItem {
+                parent,
+                higher_importance_than_this,
+                ..
+            } => [parent]
+                .into_iter()
+                .chain(higher_importance_than_this)
+                .collect(),
+            DataLayerCommands::AddItemDependency(item, dependency) => match dependency {
+                SurrealDependency::AfterItem(waiting_on)
+                | SurrealDependency::DuringItem(waiting_on) => {
+                    vec![item, waiting_on]
+                }
+                SurrealDependency::AfterDateTime(_) | SurrealDependency::AfterEvent(_) => {
+                    vec![item]
+                }
+            },
+            DataLayerCommands::DeclareInTheMomentPriority { choice, kind, .. } => {
+                let not_chosen = match kind {
+                    SurrealPriorityKind::HighestPriority { not_chosen }
+                    | SurrealPriorityKind::LowestPriority { not_chosen } => not_chosen.as_slice(),
+                    SurrealPriorityKind::NotInMode => &[],
+                };
+                [choice]
+                    .into_iter()
+                    .chain(not_chosen)
+                    .map(|x| x.get_record_id())
+                    .collect()
+            }
+            DataLayerCommands::RecordInterruption { interrupted, .. } => {
+                interrupted.iter().collect()
+            }
+            DataLayerCommands::SetDailyPlan { commitments, .. } => commitments.iter().collect(),
+        }
+    }
+
+    /// Checks a command that came from another program, over the HTTP API or the daemon socket,
+    /// against the data before it is run, see `DataLayerCommands::CheckAndRun`. The menus only send
+    /// commands for items they have just loaded but another program could send anything, and the
+    /// handlers expect what a command refers to to be there and to fit.
+    pub(crate) fn check_against(
+        &self,
+        surreal_tables: &SurrealTables,
+    ) -> Result<(), CommandRejection> {
+        let find = |id: &RecordId| {
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.id.as_ref() == Some(id))
+        };
+        for id in self.get_existing_items_referenced() {
+            if find(id).is_none() {
+                return Err(CommandRejection::NotFound(format!(
+                    "No item with the id {}",
+                    id
+                )));
+            }
+        }
+
+        match self {
+            DataLayerCommands::ParentItemWithExistingItem {
+                child,
+                parent,
+                higher_importance_than_this: Some(higher_importance_than_this),
+            }
+            | DataLayerCommands::UpdateRelativeImportance {
+                parent,
+                update_this_child: child,
+                higher_importance_than_this_child: Some(higher_importance_than_this),
+            } => {
+                let parent_item = find(parent).expect("Checked above");
+                if higher_importance_than_this == child
+                    || !is_child_of(parent_item, higher_importance_than_this)
+                {
+                    return Err(CommandRejection::Invalid(format!(
+                        "{} is not one of the other smaller items of {}",
+                        higher_importance_than_this, parent
+                    )));
+                }
+            }
+            DataLayerCommands::ParentItemWithANewChildItem {
+                parent,
+                higher_importance_than_this: Some(higher_importance_than_this),
+                ..
+            } => {
+                if !is_child_of(
+                    find(parent).expect("Checked above"),
+                    higher_importance_than_this,
+                ) {
+                    return Err(CommandRejection::Invalid(format!(
+                        "{} is not one of the smaller items of {}",
+                        higher_importance_than_this, parent
+                    )));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
 }
 
 pub(crate) async fn data_storage_start_and_run(
@@ -247,266 +558,359 @@ pub(crate) async fn data_storage_start_and_run(
     //     .patch(PatchOp::replace("/urgency_plan", None::<Option<SurrealUrgencyPlan>>)).await.unwrap();
     // assert!(updated.is_some());
     // panic!("Finished");
-    loop {
-        let received = data_storage_layer_receive_rx.recv().await;
-        match received {
-            Some(DataLayerCommands::SendRawData(oneshot)) => {
+    while let Some(command) = data_storage_layer_receive_rx.recv().await {
+        match command {
+            DataLayerCommands::CheckAndRun(command, respond) => {
                 let surreal_tables = load_from_surrealdb_upgrade_if_needed(&db).await;
-                oneshot.send(surreal_tables).unwrap();
-            }
-            Some(DataLayerCommands::SendTimeSpentLog(sender)) => send_time_spent(sender, &db).await,
-            Some(DataLayerCommands::RecordTimeSpent(new_time_spent)) => {
-                record_time_spent(new_time_spent, &db).await
-            }
-            Some(DataLayerCommands::SetWorkingOn { item, when_started }) => {
-                set_working_on(item, when_started, &db).await
-            }
-            Some(DataLayerCommands::ClearWorkingOn) => clear_working_on(&db).await,
-            Some(DataLayerCommands::FinishItem {
-                item,
-                when_finished,
-            }) => finish_item(item, when_finished, &db).await,
-            Some(DataLayerCommands::ReactivateItem { item }) => reactivate_item(item, &db).await,
-            Some(DataLayerCommands::NewItem(new_item)) => {
-                create_new_item(new_item, &db).await;
-            }
-            Some(DataLayerCommands::CoverItemWithANewItem {
-                cover_this,
-                cover_with,
-            }) => cover_with_a_new_item(cover_this, cover_with, &db).await,
-            Some(DataLayerCommands::CoverItemWithAnExistingItem {
+                let result = match command.check_against(&surreal_tables) {
+                    Ok(()) => run_command(*command, &db).await,
+                    Err(rejection) => Err(rejection),
+                };
+                //The one asking may have stopped waiting, which is fine
+                respond.send(result).ok();
+            }
+            command => {
+                if let Err(rejection) = run_command(command, &db).await {
+                    eprintln!("Not running a command: {}", rejection);
+                }
+            }
+        }
+    }
+}
+
+/// Refuses a command for an item that is missing, see `check_against`
+async fn run_command(
+    command: DataLayerCommands,
+    db: &Surreal<Any>,
+) -> Result<(), CommandRejection> {
+    if let Some(missing) = find_missing_item(&command, db).await {
+        return Err(CommandRejection::NotFound(format!(
+            "No item with the id {}",
+            missing
+        )));
+    }
+    match command {
+        DataLayerCommands::CheckAndRun(..) => {
+            return Err(CommandRejection::Invalid(
+                "A command that is being checked cannot be checked again".to_string(),
+            ));
+        }
+        DataLayerCommands::SendRawData(oneshot) => {
+            let surreal_tables = load_from_surrealdb_upgrade_if_needed(db).await;
+            oneshot.send(surreal_tables).unwrap();
+        }
+        DataLayerCommands::SendTimeSpentLog(sender) => send_time_spent(sender, db).await,
+        DataLayerCommands::RecordTimeSpent(new_time_spent) => {
+            record_time_spent(new_time_spent, db).await
+        }
+        DataLayerCommands::SetWorkingOn { item, when_started } => {
+            set_working_on(item, when_started, db).await
+        }
+        DataLayerCommands::ClearWorkingOn => clear_working_on(db).await,
+        DataLayerCommands::FinishItem {
+            item,
+            when_finished,
+        } => finish_item(item, when_finished, db).await,
+        DataLayerCommands::ReactivateItem { item } => reactivate_item(item, db).await,
+        DataLayerCommands::DeleteItem(record_id) => delete_item(record_id, db).await?,
+        DataLayerCommands::NewItem(new_item) => {
+            create_new_item(new_item, db).await;
+        }
+        DataLayerCommands::CoverItemWithANewItem {
+            cover_this,
+            cover_with,
+        } => cover_with_a_new_item(cover_this, cover_with, db).await,
+        DataLayerCommands::CoverItemWithAnExistingItem {
+            item_to_be_covered,
+            item_that_should_do_the_covering,
+        } => {
+            cover_item_with_an_existing_item(
                 item_to_be_covered,
                 item_that_should_do_the_covering,
-            }) => {
-                cover_item_with_an_existing_item(
-                    item_to_be_covered,
-                    item_that_should_do_the_covering,
-                    &db,
-                )
+                db,
+            )
+            .await
+        }
+        DataLayerCommands::NewMode(new_mode) => {
+            let mut surreal_mode: SurrealMode = new_mode.into();
+            let created: SurrealMode = db
+                .create(surreal_mode::SurrealMode::TABLE_NAME)
+                .content(surreal_mode.clone())
                 .await
-            }
-            Some(DataLayerCommands::NewMode(new_mode)) => {
-                let mut surreal_mode: SurrealMode = new_mode.into();
-                let created: SurrealMode = db
-                    .create(surreal_mode::SurrealMode::TABLE_NAME)
-                    .content(surreal_mode.clone())
-                    .await
-                    .unwrap()
-                    .expect("Created");
-
-                surreal_mode.id = created.id.clone();
-                assert_eq!(surreal_mode, created);
-            }
-            Some(DataLayerCommands::ParentItemWithExistingItem {
-                child,
-                parent,
-                higher_importance_than_this,
-            }) => {
-                parent_item_with_existing_item(child, parent, higher_importance_than_this, &db)
-                    .await
-            }
-            Some(DataLayerCommands::ParentItemWithANewChildItem {
-                child,
-                parent,
-                higher_importance_than_this,
-            }) => {
-                parent_item_with_a_new_child(child, parent, higher_importance_than_this, &db).await
-            }
-            Some(DataLayerCommands::ParentNewItemWithAnExistingChildItem {
-                child,
-                parent_new_item,
-            }) => parent_new_item_with_an_existing_child_item(child, parent_new_item, &db).await,
-            Some(DataLayerCommands::ParentItemRemoveParent {
-                child,
-                parent_to_remove,
-            }) => {
-                let mut parent: SurrealItem =
-                    db.select(parent_to_remove.clone()).await.unwrap().unwrap();
+                .unwrap()
+                .expect("Created");
 
-                parent.smaller_items_in_priority_order = parent
-                    .smaller_items_in_priority_order
-                    .into_iter()
-                    .filter(|x| match x {
-                        SurrealOrderedSubItem::SubItem { surreal_item_id } => {
-                            surreal_item_id != &child
-                        }
-                    })
-                    .collect::<Vec<_>>();
-                let saved = db
-                    .update(&parent_to_remove)
-                    .content(parent.clone())
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(parent, saved);
-            }
-            Some(DataLayerCommands::AddItemDependency(record_id, new_ready)) => {
-                add_dependency(record_id, new_ready, &db).await
-            }
-            Some(DataLayerCommands::RemoveItemDependency(record_id, to_remove)) => {
-                remove_dependency(record_id, to_remove, &db).await
-            }
-            Some(DataLayerCommands::AddItemDependencyNewEvent(record_id, new_event)) => {
-                add_dependency_new_event(record_id, new_event, &db).await
-            }
-            Some(DataLayerCommands::UpdateRelativeImportance {
-                parent,
+            surreal_mode.id = created.id.clone();
+            assert_eq!(surreal_mode, created);
+        }
+        DataLayerCommands::ParentItemWithExistingItem {
+            child,
+            parent,
+            higher_importance_than_this,
+        } => parent_item_with_existing_item(child, parent, higher_importance_than_this, db).await,
+        DataLayerCommands::ParentItemWithANewChildItem {
+            child,
+            parent,
+            higher_importance_than_this,
+        } => {
+            parent_item_with_a_new_child(child, parent, higher_importance_than_this, db).await;
+        }
+        DataLayerCommands::ParentItemWithNewChildTrees { children, parent } => {
+            parent_item_with_new_child_trees(children, parent, db).await
+        }
+        DataLayerCommands::ParentNewItemWithAnExistingChildItem {
+            child,
+            parent_new_item,
+        } => parent_new_item_with_an_existing_child_item(child, parent_new_item, db).await,
+        DataLayerCommands::ParentItemRemoveParent {
+            child,
+            parent_to_remove,
+        } => parent_item_remove_parent(child, parent_to_remove, db).await,
+        DataLayerCommands::AddItemDependency(record_id, new_ready) => {
+            add_dependency(record_id, new_ready, db).await
+        }
+        DataLayerCommands::RemoveItemDependency(record_id, to_remove) => {
+            remove_dependency(record_id, to_remove, db).await
+        }
+        DataLayerCommands::AddItemDependencyNewEvent(record_id, new_event) => {
+            add_dependency_new_event(record_id, new_event, db).await
+        }
+        DataLayerCommands::SnoozeItem { item, until } => {
+            replace_after_date_time_dependency(item, Some(until), db).await
+        }
+        DataLayerCommands::UnsnoozeItem(item) => {
+            replace_after_date_time_dependency(item, None, db).await
+        }
+        DataLayerCommands::UpdateRelativeImportance {
+            parent,
+            update_this_child,
+            higher_importance_than_this_child,
+        } => {
+            parent_item_with_existing_item(
                 update_this_child,
+                parent,
                 higher_importance_than_this_child,
-            }) => {
-                parent_item_with_existing_item(
-                    update_this_child,
-                    parent,
-                    higher_importance_than_this_child,
-                    &db,
-                )
+                db,
+            )
+            .await
+        }
+        DataLayerCommands::UpdateItemLastReviewedDate(record_id, new_last_reviewed) => {
+            //TODO: I should probably fix this so it does the update all as one transaction rather than reading in the data and then changing it and writing it out again. That could cause issues if there are multiple writers. The reason why I didn't do it yet is because I only want to update part of the SurrealItemReview type and I need to experiment with the PatchOp::replace to see if and how to make it work with the nested type. Otherwise I might consider just making review_frequency and last_reviewed separate fields and then I can just update the review_frequency and not have to worry about the last_reviewed field.
+            let mut item: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
+
+            item.last_reviewed = Some(new_last_reviewed);
+            let updated = db
+                .update(&record_id)
+                .content(item.clone())
                 .await
-            }
-            Some(DataLayerCommands::UpdateItemLastReviewedDate(record_id, new_last_reviewed)) => {
-                //TODO: I should probably fix this so it does the update all as one transaction rather than reading in the data and then changing it and writing it out again. That could cause issues if there are multiple writers. The reason why I didn't do it yet is because I only want to update part of the SurrealItemReview type and I need to experiment with the PatchOp::replace to see if and how to make it work with the nested type. Otherwise I might consider just making review_frequency and last_reviewed separate fields and then I can just update the review_frequency and not have to worry about the last_reviewed field.
-                let mut item: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
-
-                item.last_reviewed = Some(new_last_reviewed);
-                let updated = db
-                    .update(&record_id)
-                    .content(item.clone())
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(item, updated);
-            }
-            Some(DataLayerCommands::UpdateItemReviewFrequency(
-                record_id,
-                surreal_frequency,
-                surreal_review_guidance,
-            )) => {
-                //TODO: I should probably fix this so it does the update all as one transaction rather than reading in the data and then changing it and writing it out again. That could cause issues if there are multiple writers. The reason why I didn't do it yet is because I only want to update part of the SurrealItemReview type and I need to experiment with the PatchOp::replace to see if and how to make it work with the nested type. Otherwise I might consider just making review_frequency and last_reviewed separate fields and then I can just update the review_frequency and not have to worry about the last_reviewed field.
-                let previous_value: SurrealItem =
-                    db.select(record_id.clone()).await.unwrap().unwrap();
-                let mut item = previous_value.clone();
-                item.review_frequency = Some(surreal_frequency);
-                item.review_guidance = Some(surreal_review_guidance);
-                let updated: SurrealItem = db
-                    .update(&record_id)
-                    .content(item.clone())
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(item, updated);
-            }
-            Some(DataLayerCommands::UpdateSummary(item, new_summary)) => {
-                update_item_summary(item, new_summary, &db).await
-            }
-            Some(DataLayerCommands::UpdateModeName(thing, new_name)) => {
-                let updated: SurrealMode = db
-                    .update(&thing)
-                    .patch(PatchOp::replace("/name", new_name.clone()))
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(updated.name, new_name);
-            }
-            Some(DataLayerCommands::UpdateResponsibilityAndItemType(
-                item,
-                new_responsibility,
-                new_item_type,
-            )) => {
-                let updated: SurrealItem = db
-                    .update(&item)
-                    .patch(PatchOp::replace(
-                        "/responsibility",
-                        new_responsibility.clone(),
-                    ))
-                    .patch(PatchOp::replace("/item_type", new_item_type.clone()))
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(updated.responsibility, new_responsibility);
-                assert_eq!(updated.item_type, new_item_type);
-            }
-            Some(DataLayerCommands::UpdateUrgencyPlan(record_id, new_urgency_plan)) => {
-                let updated: SurrealItem = db
-                    .update(&record_id)
-                    .patch(PatchOp::replace("/urgency_plan", new_urgency_plan.clone()))
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(updated.urgency_plan, new_urgency_plan);
-            }
-            Some(DataLayerCommands::DeclareInTheMomentPriority {
+                .unwrap()
+                .unwrap();
+            assert_eq!(item, updated);
+            record_item_history(record_id, SurrealItemChange::Reviewed, db).await;
+        }
+        DataLayerCommands::UpdateItemReviewFrequency(
+            record_id,
+            surreal_frequency,
+            surreal_review_guidance,
+        ) => {
+            //TODO: I should probably fix this so it does the update all as one transaction rather than reading in the data and then changing it and writing it out again. That could cause issues if there are multiple writers. The reason why I didn't do it yet is because I only want to update part of the SurrealItemReview type and I need to experiment with the PatchOp::replace to see if and how to make it work with the nested type. Otherwise I might consider just making review_frequency and last_reviewed separate fields and then I can just update the review_frequency and not have to worry about the last_reviewed field.
+            let previous_value: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
+            let mut item = previous_value.clone();
+            item.review_frequency = Some(surreal_frequency);
+            item.review_guidance = Some(surreal_review_guidance);
+            let updated: SurrealItem = db
+                .update(&record_id)
+                .content(item.clone())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(item, updated);
+        }
+        DataLayerCommands::UpdateSummary(item, new_summary) => {
+            update_item_summary(item, new_summary, db).await
+        }
+        DataLayerCommands::UpdateNotesLocation(item, notes_location) => {
+            let updated: SurrealItem = db
+                .update(&item)
+                .patch(PatchOp::replace("/notes_location", notes_location.clone()))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.notes_location, notes_location);
+        }
+        DataLayerCommands::UpdateTags(item, tags) => update_tags(item, tags, db).await,
+        DataLayerCommands::UpdateWaitingFor(item, waiting_for) => {
+            update_waiting_for(item, waiting_for, db).await
+        }
+        DataLayerCommands::WaitingForNewPerson {
+            item,
+            person,
+            requested,
+            follow_up,
+        } => {
+            let person = create_new_item(person, db).await;
+            let waiting_for =
+                SurrealWaitingFor::new(person.id.expect("In DB"), requested, follow_up);
+            update_waiting_for(item, Some(waiting_for), db).await
+        }
+        DataLayerCommands::UpdateModeName(thing, new_name) => {
+            let updated: SurrealMode = db
+                .update(&thing)
+                .patch(PatchOp::replace("/name", new_name.clone()))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.name, new_name);
+        }
+        DataLayerCommands::UpdateResponsibilityAndItemType(
+            item,
+            new_responsibility,
+            new_item_type,
+        ) => update_responsibility_and_item_type(item, new_responsibility, new_item_type, db).await,
+        DataLayerCommands::UpdateUrgencyPlan(record_id, new_urgency_plan) => {
+            update_urgency_plan(record_id, new_urgency_plan, db).await
+        }
+        DataLayerCommands::DeclareInTheMomentPriority {
+            choice,
+            kind,
+            for_mode,
+            in_effect_until,
+        } => {
+            let mut priority = SurrealInTheMomentPriority {
+                id: None,
+                in_effect_until,
+                for_mode,
+                created: Utc::now().into(),
                 choice,
                 kind,
-                for_mode,
-                in_effect_until,
-            }) => {
-                let mut priority = SurrealInTheMomentPriority {
-                    id: None,
-                    in_effect_until,
-                    for_mode,
-                    created: Utc::now().into(),
-                    choice,
-                    kind,
-                };
-                let updated = db
-                    .create(SurrealInTheMomentPriority::TABLE_NAME)
-                    .content(priority.clone())
-                    .await
-                    .unwrap();
-                let updated: SurrealInTheMomentPriority = updated.expect("Created");
-                priority.id = updated.id.clone();
-                assert_eq!(priority, updated);
-            }
-            Some(DataLayerCommands::ClearInTheMomentPriority(record_id)) => {
-                let updated: SurrealInTheMomentPriority =
-                    db.delete(&record_id).await.unwrap().unwrap();
-                assert_eq!(updated.id, Some(record_id));
-            }
-            Some(DataLayerCommands::SetCurrentMode(new_current_mode)) => {
-                let current_mode: SurrealCurrentMode = new_current_mode.into();
-                let mut updated: Vec<SurrealCurrentMode> = db
-                    .upsert(SurrealCurrentMode::TABLE_NAME)
+            };
+            let updated = db
+                .create(SurrealInTheMomentPriority::TABLE_NAME)
+                .content(priority.clone())
+                .await
+                .unwrap();
+            let updated: SurrealInTheMomentPriority = updated.expect("Created");
+            priority.id = updated.id.clone();
+            assert_eq!(priority, updated);
+        }
+        DataLayerCommands::ClearInTheMomentPriority(record_id) => {
+            let updated: SurrealInTheMomentPriority = db.delete(&record_id).await.unwrap().unwrap();
+            assert_eq!(updated.id, Some(record_id));
+        }
+        DataLayerCommands::RecordPriorityComparison {
+            winner,
+            loser,
+            for_mode,
+        } => record_priority_comparison(winner, loser, for_mode, db).await,
+        DataLayerCommands::NewSmartList { name, filter } => {
+            let mut smart_list = SurrealSmartList::new(name, filter);
+            let created: SurrealSmartList = db
+                .create(SurrealSmartList::TABLE_NAME)
+                .content(smart_list.clone())
+                .await
+                .unwrap()
+                .expect("Created");
+            smart_list.id = created.id.clone();
+            assert_eq!(smart_list, created);
+        }
+        DataLayerCommands::DeleteSmartList(record_id) => {
+            let deleted: SurrealSmartList = db.delete(&record_id).await.unwrap().unwrap();
+            assert_eq!(deleted.id, Some(record_id));
+        }
+        DataLayerCommands::RecordBreak {
+            when_started,
+            when_stopped,
+        } => record_break(when_started, when_stopped, db).await,
+        DataLayerCommands::NewResumeNote {
+            item,
+            doing,
+            next,
+            location,
+        } => {
+            let mut resume_note =
+                SurrealResumeNote::new(item, Utc::now().into(), doing, next, location);
+            let created: SurrealResumeNote = db
+                .create(SurrealResumeNote::TABLE_NAME)
+                .content(resume_note.clone())
+                .await
+                .unwrap()
+                .expect("Created");
+            resume_note.id = created.id.clone();
+            assert_eq!(resume_note, created);
+        }
+        DataLayerCommands::RecordInterruption {
+            interrupted,
+            source,
+            when_started,
+            when_stopped,
+        } => record_interruption(interrupted, source, when_started, when_stopped, db).await,
+        DataLayerCommands::SetDailyPlan { day, commitments } => {
+            set_daily_plan(day, commitments, db).await
+        }
+        DataLayerCommands::WriteDailySummary(daily_summary) => {
+            write_daily_summary(daily_summary, db).await
+        }
+        DataLayerCommands::SaveWeeklyReview(weekly_review) => {
+            save_weekly_review(weekly_review, db).await
+        }
+        DataLayerCommands::SetCurrentMode(new_current_mode) => {
+            let current_mode: SurrealCurrentMode = new_current_mode.into();
+            let mut updated: Vec<SurrealCurrentMode> = db
+                .upsert(SurrealCurrentMode::TABLE_NAME)
+                .content(current_mode.clone())
+                .await
+                .unwrap();
+            if updated.is_empty() {
+                //Annoyingly SurrealDB's upsert seems to just not work sometimes without giving an explicit error so I have to do this
+                updated = db
+                    .insert(SurrealCurrentMode::TABLE_NAME)
                     .content(current_mode.clone())
                     .await
                     .unwrap();
-                if updated.is_empty() {
-                    //Annoyingly SurrealDB's upsert seems to just not work sometimes without giving an explicit error so I have to do this
-                    updated = db
-                        .insert(SurrealCurrentMode::TABLE_NAME)
-                        .content(current_mode.clone())
-                        .await
-                        .unwrap();
-                }
-                assert_eq!(1, updated.len());
-                let updated = updated.into_iter().next().unwrap();
-                assert_eq!(current_mode, updated);
-            }
-            Some(DataLayerCommands::TriggerEvent { event, when }) => {
-                let updated: SurrealEvent = db
-                    .update(&event)
-                    .patch(PatchOp::replace("/triggered", true))
-                    .patch(PatchOp::replace("/last_updated", when.clone()))
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(updated.id, Some(event));
-                assert!(updated.triggered);
-                assert_eq!(updated.last_updated, when);
-            }
-            Some(DataLayerCommands::UntriggerEvent { event, when }) => {
-                let updated: SurrealEvent = db
-                    .update(&event)
-                    .patch(PatchOp::replace("/triggered", false))
-                    .patch(PatchOp::replace("/last_updated", when.clone()))
-                    .await
-                    .unwrap()
-                    .unwrap();
-                assert_eq!(updated.id, Some(event));
-                assert!(!updated.triggered);
-                assert_eq!(updated.last_updated, when);
             }
-            None => return, //Channel closed, time to shutdown down, exit
+            assert_eq!(1, updated.len());
+            let updated = updated.into_iter().next().unwrap();
+            assert_eq!(current_mode, updated);
+        }
+        DataLayerCommands::TriggerEvent { event, when } => {
+            let updated: SurrealEvent = db
+                .update(&event)
+                .patch(PatchOp::replace("/triggered", true))
+                .patch(PatchOp::replace("/last_updated", when.clone()))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.id, Some(event));
+            assert!(updated.triggered);
+            assert_eq!(updated.last_updated, when);
+        }
+        DataLayerCommands::UntriggerEvent { event, when } => {
+            let updated: SurrealEvent = db
+                .update(&event)
+                .patch(PatchOp::replace("/triggered", false))
+                .patch(PatchOp::replace("/last_updated", when.clone()))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(updated.id, Some(event));
+            assert!(!updated.triggered);
+            assert_eq!(updated.last_updated, when);
+        }
+    }
+    Ok(())
+}
+
+async fn find_missing_item<'c>(
+    command: &'c DataLayerCommands,
+    db: &Surreal<Any>,
+) -> Option<&'c RecordId> {
+    for id in command.get_existing_items_referenced() {
+        let found: Result<Option<SurrealItem>, _> = db.select(id.clone()).await;
+        if !matches!(found, Ok(Some(_))) {
+            return Some(id);
         }
     }
+    None
 }
 
 async fn authenticate_surrealdb(
@@ -569,6 +973,15 @@ fn surreal_tables_has_any_data(tables: &SurrealTables) -> bool {
         || !tables.surreal_current_modes.is_empty()
         || !tables.surreal_modes.is_empty()
         || !tables.surreal_events.is_empty()
+        || !tables.surreal_priority_comparisons.is_empty()
+        || !tables.surreal_item_history.is_empty()
+        || !tables.surreal_smart_lists.is_empty()
+        || !tables.surreal_breaks.is_empty()
+        || !tables.surreal_resume_notes.is_empty()
+        || !tables.surreal_interruptions.is_empty()
+        || !tables.surreal_daily_plans.is_empty()
+        || !tables.surreal_daily_summaries.is_empty()
+        || !tables.surreal_weekly_reviews.is_empty()
 }
 
 fn auth_configs_equivalent(a: &Option<SurrealAuthConfig>, b: &Option<SurrealAuthConfig>) -> bool {
@@ -887,104 +1300,105 @@ async fn copy_surreal_events_preserving_ids(
     Ok(())
 }
 
-async fn copy_surreal_tables_preserving_ids(
+async fn copy_surreal_priority_comparisons_preserving_ids(
     db: &Surreal<Any>,
-    tables: SurrealTables,
+    surreal_priority_comparisons: Vec<SurrealPriorityComparison>,
 ) -> Result<(), String> {
-    // Copy records preserving record IDs so references remain valid.
-    //Note that if a new table is added to the database then the below code needs to be updated to copy that table as well.
-    let (items, time_spent, priorities, modes, events, current_mode) = join!(
-        biased; // prefer earlier futures to run first as they should have more data
-        copy_surreal_items_preserving_ids(db, tables.surreal_items),
-        copy_surreal_time_spent_preserving_ids(db, tables.surreal_time_spent_log),
-        copy_surreal_in_the_moment_priorities_preserving_ids(
-            db,
-            tables.surreal_in_the_moment_priorities,
-        ),
-        copy_surreal_modes_preserving_ids(db, tables.surreal_modes),
-        copy_surreal_events_preserving_ids(db, tables.surreal_events),
-        copy_surreal_current_modes_preserving_ids(db, tables.surreal_current_modes),
-    );
-
-    // The `?` error propagation operator can't be used inside the join! macro, so apply it here.
-    items?;
-    time_spent?;
-    priorities?;
-    current_mode?;
-    modes?;
-    events?;
-
-    Ok(())
-}
+    stream::iter(surreal_priority_comparisons)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealPriorityComparison> = db
+                .upsert(SurrealPriorityComparison::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealPriorityComparison: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealPriorityComparison::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealPriorityComparison: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!(
+                    "Failed to copy SurrealPriorityComparison {:?}",
+                    record.id
+                ));
+            }
 
-async fn copy_between_databases_if_destination_empty_same_connection(
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn copy_surreal_item_history_preserving_ids(
     db: &Surreal<Any>,
-    source_ns: &str,
-    source_db: &str,
-    destination_ns: &str,
-    destination_db: &str,
-    behavior: CopyDestinationBehavior,
+    surreal_item_history: Vec<SurrealItemHistory>,
 ) -> Result<(), String> {
-    db.use_ns(destination_ns)
-        .use_db(destination_db)
-        .await
-        .map_err(|e| format!("Failed to use destination ns/db: {e:?}"))?;
-    let destination_tables = load_from_surrealdb_upgrade_if_needed(db).await;
-    if surreal_tables_has_any_data(&destination_tables) {
-        match behavior {
-            CopyDestinationBehavior::ForceDeleteExisting => {
-                clear_surreal_tables(db, destination_tables).await?;
+    stream::iter(surreal_item_history)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealItemHistory> = db
+                .upsert(SurrealItemHistory::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealItemHistory: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealItemHistory::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealItemHistory: {e:?}"))?;
             }
-            CopyDestinationBehavior::ErrorIfNotEmpty => {
-                return Err(format!(
-                    "Destination database is not empty (ns='{}' db='{}'). Use --force to delete existing data before copying.",
-                    destination_ns, destination_db
-                ));
+            if updated.is_empty() {
+                return Err(format!("Failed to copy SurrealItemHistory {:?}", record.id));
             }
-        }
-    }
-
-    db.use_ns(source_ns)
-        .use_db(source_db)
-        .await
-        .map_err(|e| format!("Failed to use source ns/db: {e:?}"))?;
-    let source_tables = load_from_surrealdb_upgrade_if_needed(db).await;
 
-    db.use_ns(destination_ns)
-        .use_db(destination_db)
-        .await
-        .map_err(|e| format!("Failed to re-select destination ns/db: {e:?}"))?;
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
 
-    copy_surreal_tables_preserving_ids(db, source_tables).await
+    Ok(())
 }
 
-async fn clear_surreal_tables(db: &Surreal<Any>, tables: SurrealTables) -> Result<(), String> {
-    create_delete_stream::<SurrealItem, _>(db, tables.surreal_items, SurrealItem::TABLE_NAME)
-        .chain(create_delete_stream::<SurrealTimeSpent, _>(
-            db,
-            tables.surreal_time_spent_log,
-            SurrealTimeSpent::TABLE_NAME,
-        ))
-        .chain(create_delete_stream::<SurrealInTheMomentPriority, _>(
-            db,
-            tables.surreal_in_the_moment_priorities,
-            SurrealInTheMomentPriority::TABLE_NAME,
-        ))
-        .chain(create_delete_stream::<SurrealCurrentMode, _>(
-            db,
-            tables.surreal_current_modes,
-            SurrealCurrentMode::TABLE_NAME,
-        ))
-        .chain(create_delete_stream::<SurrealMode, _>(
-            db,
-            tables.surreal_modes,
-            SurrealMode::TABLE_NAME,
-        ))
-        .chain(create_delete_stream::<SurrealEvent, _>(
-            db,
-            tables.surreal_events,
-            SurrealEvent::TABLE_NAME,
-        ))
+async fn copy_surreal_smart_lists_preserving_ids(
+    db: &Surreal<Any>,
+    surreal_smart_lists: Vec<SurrealSmartList>,
+) -> Result<(), String> {
+    stream::iter(surreal_smart_lists)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealSmartList> = db
+                .upsert(SurrealSmartList::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealSmartList: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealSmartList::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealSmartList: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!("Failed to copy SurrealSmartList {:?}", record.id));
+            }
+
+            Ok(())
+        })
         .buffer_unordered(100)
         .fold(Ok(()), |acc, res| async {
             match (acc, res) {
@@ -997,686 +1411,2692 @@ async fn clear_surreal_tables(db: &Surreal<Any>, tables: SurrealTables) -> Resul
     Ok(())
 }
 
-async fn clear_surreal_in_the_moment_priorities_table(db: &Surreal<Any>) -> Result<(), String> {
-    db.query(format!("DELETE {}", SurrealInTheMomentPriority::TABLE_NAME))
-        .await
-        .map_err(|e| {
-            format!(
-                "Failed to clear {} table: {e:?}",
-                SurrealInTheMomentPriority::TABLE_NAME
-            )
-        })?;
+async fn copy_surreal_breaks_preserving_ids(
+    db: &Surreal<Any>,
+    surreal_breaks: Vec<SurrealBreak>,
+) -> Result<(), String> {
+    stream::iter(surreal_breaks)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealBreak> = db
+                .upsert(SurrealBreak::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealBreak: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealBreak::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealBreak: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!("Failed to copy SurrealBreak {:?}", record.id));
+            }
+
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
 
     Ok(())
 }
 
-pub(crate) async fn copy_database_if_destination_empty(
-    source: SurrealDbConnectionConfig,
-    destination: SurrealDbConnectionConfig,
-    behavior: CopyDestinationBehavior,
+async fn copy_surreal_resume_notes_preserving_ids(
+    db: &Surreal<Any>,
+    surreal_resume_notes: Vec<SurrealResumeNote>,
 ) -> Result<(), String> {
-    if source.endpoint == destination.endpoint {
-        if !auth_configs_equivalent(&source.auth, &destination.auth) {
-            return Err(
-                "Source/destination auth configs differ for the same endpoint; this command currently requires them to match."
-                    .to_string(),
-            );
-        }
-
-        let db = connect_and_prepare(&destination).await?;
-        copy_between_databases_if_destination_empty_same_connection(
-            &db,
-            source.namespace.as_str(),
-            source.database.as_str(),
-            destination.namespace.as_str(),
-            destination.database.as_str(),
-            behavior,
-        )
-        .await
-    } else {
-        let source_db = connect_and_prepare(&source).await?;
-        source_db
-            .use_ns(source.namespace.as_str())
-            .use_db(source.database.as_str())
-            .await
-            .map_err(|e| format!("Failed to select source ns/db: {e:?}"))?;
-        let source_tables = load_from_surrealdb_upgrade_if_needed(&source_db).await;
-
-        let dest_db = connect_and_prepare(&destination).await?;
-        dest_db
-            .use_ns(destination.namespace.as_str())
-            .use_db(destination.database.as_str())
-            .await
-            .map_err(|e| format!("Failed to select destination ns/db: {e:?}"))?;
-        let dest_tables = load_from_surrealdb_upgrade_if_needed(&dest_db).await;
-        if surreal_tables_has_any_data(&dest_tables) {
-            match behavior {
-                CopyDestinationBehavior::ForceDeleteExisting => {
-                    clear_surreal_tables(&dest_db, dest_tables).await?;
-                }
-                CopyDestinationBehavior::ErrorIfNotEmpty => {
-                    return Err(format!(
-                        "Destination database is not empty (endpoint='{}' ns='{}' db='{}'). Use --force to delete existing data before copying.",
-                        destination.endpoint, destination.namespace, destination.database
-                    ));
-                }
+    stream::iter(surreal_resume_notes)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealResumeNote> = db
+                .upsert(SurrealResumeNote::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealResumeNote: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealResumeNote::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealResumeNote: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!("Failed to copy SurrealResumeNote {:?}", record.id));
             }
-        }
 
-        copy_surreal_tables_preserving_ids(&dest_db, source_tables).await
-    }
-}
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
 
-pub(crate) async fn database_has_any_data(
-    config: &SurrealDbConnectionConfig,
-) -> Result<bool, String> {
-    let db = connect_and_prepare(config).await?;
-    db.use_ns(config.namespace.as_str())
-        .use_db(config.database.as_str())
-        .await
-        .map_err(|e| format!("Failed to select ns/db: {e:?}"))?;
-    let tables = load_from_surrealdb_upgrade_if_needed(&db).await;
-    Ok(surreal_tables_has_any_data(&tables))
+    Ok(())
 }
 
-async fn ensure_namespace_and_migrate_if_needed(
+async fn copy_surreal_interruptions_preserving_ids(
     db: &Surreal<Any>,
-    config: &SurrealDbConnectionConfig,
-) {
-    let target_ns = config.namespace.as_str();
-    let db_name = config.database.as_str();
+    surreal_interruptions: Vec<SurrealInterruption>,
+) -> Result<(), String> {
+    stream::iter(surreal_interruptions)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealInterruption> = db
+                .upsert(SurrealInterruption::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealInterruption: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealInterruption::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealInterruption: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!(
+                    "Failed to copy SurrealInterruption {:?}",
+                    record.id
+                ));
+            }
 
-    // Always use target namespace/db going forward.
-    db.use_ns(target_ns).use_db(db_name).await.unwrap();
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
 
-    // If the target namespace already has data, nothing to do.
+    Ok(())
+}
+
+async fn copy_surreal_daily_plans_preserving_ids(
+    db: &Surreal<Any>,
+    surreal_daily_plans: Vec<SurrealDailyPlan>,
+) -> Result<(), String> {
+    stream::iter(surreal_daily_plans)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealDailyPlan> = db
+                .upsert(SurrealDailyPlan::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealDailyPlan: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealDailyPlan::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealDailyPlan: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!("Failed to copy SurrealDailyPlan {:?}", record.id));
+            }
+
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn copy_surreal_daily_summaries_preserving_ids(
+    db: &Surreal<Any>,
+    surreal_daily_summaries: Vec<SurrealDailySummary>,
+) -> Result<(), String> {
+    stream::iter(surreal_daily_summaries)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealDailySummary> = db
+                .upsert(SurrealDailySummary::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealDailySummary: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealDailySummary::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealDailySummary: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!(
+                    "Failed to copy SurrealDailySummary {:?}",
+                    record.id
+                ));
+            }
+
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn copy_surreal_weekly_reviews_preserving_ids(
+    db: &Surreal<Any>,
+    surreal_weekly_reviews: Vec<SurrealWeeklyReview>,
+) -> Result<(), String> {
+    stream::iter(surreal_weekly_reviews)
+        .map(|record| async move {
+            let mut updated: Vec<SurrealWeeklyReview> = db
+                .upsert(SurrealWeeklyReview::TABLE_NAME)
+                .content(record.clone())
+                .await
+                .map_err(|e| format!("Failed to upsert SurrealWeeklyReview: {e:?}"))?;
+            if updated.is_empty() {
+                updated = db
+                    .insert(SurrealWeeklyReview::TABLE_NAME)
+                    .content(record.clone())
+                    .await
+                    .map_err(|e| format!("Failed to insert SurrealWeeklyReview: {e:?}"))?;
+            }
+            if updated.is_empty() {
+                return Err(format!(
+                    "Failed to copy SurrealWeeklyReview {:?}",
+                    record.id
+                ));
+            }
+
+            Ok(())
+        })
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn copy_surreal_tables_preserving_ids(
+    db: &Surreal<Any>,
+    tables: SurrealTables,
+) -> Result<(), String> {
+    // Copy records preserving record IDs so references remain valid.
+    //Note that if a new table is added to the database then the below code needs to be updated to copy that table as well.
+    let (
+        items,
+        time_spent,
+        priorities,
+        modes,
+        events,
+        current_mode,
+        priority_comparisons,
+        item_history,
+        smart_lists,
+        breaks,
+        resume_notes,
+        interruptions,
+        daily_plans,
+        daily_summaries,
+        weekly_reviews,
+    ) = join!(
+        biased; // prefer earlier futures to run first as they should have more data
+        copy_surreal_items_preserving_ids(db, tables.surreal_items),
+        copy_surreal_time_spent_preserving_ids(db, tables.surreal_time_spent_log),
+        copy_surreal_in_the_moment_priorities_preserving_ids(
+            db,
+            tables.surreal_in_the_moment_priorities,
+        ),
+        copy_surreal_modes_preserving_ids(db, tables.surreal_modes),
+        copy_surreal_events_preserving_ids(db, tables.surreal_events),
+        copy_surreal_current_modes_preserving_ids(db, tables.surreal_current_modes),
+        copy_surreal_priority_comparisons_preserving_ids(db, tables.surreal_priority_comparisons),
+        copy_surreal_item_history_preserving_ids(db, tables.surreal_item_history),
+        copy_surreal_smart_lists_preserving_ids(db, tables.surreal_smart_lists),
+        copy_surreal_breaks_preserving_ids(db, tables.surreal_breaks),
+        copy_surreal_resume_notes_preserving_ids(db, tables.surreal_resume_notes),
+        copy_surreal_interruptions_preserving_ids(db, tables.surreal_interruptions),
+        copy_surreal_daily_plans_preserving_ids(db, tables.surreal_daily_plans),
+        copy_surreal_daily_summaries_preserving_ids(db, tables.surreal_daily_summaries),
+        copy_surreal_weekly_reviews_preserving_ids(db, tables.surreal_weekly_reviews),
+    );
+
+    // The `?` error propagation operator can't be used inside the join! macro, so apply it here.
+    items?;
+    time_spent?;
+    priorities?;
+    current_mode?;
+    modes?;
+    events?;
+    priority_comparisons?;
+    item_history?;
+    smart_lists?;
+    breaks?;
+    resume_notes?;
+    interruptions?;
+    daily_plans?;
+    daily_summaries?;
+    weekly_reviews?;
+
+    Ok(())
+}
+
+async fn copy_between_databases_if_destination_empty_same_connection(
+    db: &Surreal<Any>,
+    source_ns: &str,
+    source_db: &str,
+    destination_ns: &str,
+    destination_db: &str,
+    behavior: CopyDestinationBehavior,
+) -> Result<(), String> {
+    db.use_ns(destination_ns)
+        .use_db(destination_db)
+        .await
+        .map_err(|e| format!("Failed to use destination ns/db: {e:?}"))?;
+    let destination_tables = load_from_surrealdb_upgrade_if_needed(db).await;
+    if surreal_tables_has_any_data(&destination_tables) {
+        match behavior {
+            CopyDestinationBehavior::ForceDeleteExisting => {
+                clear_surreal_tables(db, destination_tables).await?;
+            }
+            CopyDestinationBehavior::ErrorIfNotEmpty => {
+                return Err(format!(
+                    "Destination database is not empty (ns='{}' db='{}'). Use --force to delete existing data before copying.",
+                    destination_ns, destination_db
+                ));
+            }
+        }
+    }
+
+    db.use_ns(source_ns)
+        .use_db(source_db)
+        .await
+        .map_err(|e| format!("Failed to use source ns/db: {e:?}"))?;
+    let source_tables = load_from_surrealdb_upgrade_if_needed(db).await;
+
+    db.use_ns(destination_ns)
+        .use_db(destination_db)
+        .await
+        .map_err(|e| format!("Failed to re-select destination ns/db: {e:?}"))?;
+
+    copy_surreal_tables_preserving_ids(db, source_tables).await
+}
+
+async fn clear_surreal_tables(db: &Surreal<Any>, tables: SurrealTables) -> Result<(), String> {
+    create_delete_stream::<SurrealItem, _>(db, tables.surreal_items, SurrealItem::TABLE_NAME)
+        .chain(create_delete_stream::<SurrealTimeSpent, _>(
+            db,
+            tables.surreal_time_spent_log,
+            SurrealTimeSpent::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealInTheMomentPriority, _>(
+            db,
+            tables.surreal_in_the_moment_priorities,
+            SurrealInTheMomentPriority::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealCurrentMode, _>(
+            db,
+            tables.surreal_current_modes,
+            SurrealCurrentMode::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealMode, _>(
+            db,
+            tables.surreal_modes,
+            SurrealMode::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealEvent, _>(
+            db,
+            tables.surreal_events,
+            SurrealEvent::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealPriorityComparison, _>(
+            db,
+            tables.surreal_priority_comparisons,
+            SurrealPriorityComparison::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealItemHistory, _>(
+            db,
+            tables.surreal_item_history,
+            SurrealItemHistory::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealSmartList, _>(
+            db,
+            tables.surreal_smart_lists,
+            SurrealSmartList::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealBreak, _>(
+            db,
+            tables.surreal_breaks,
+            SurrealBreak::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealResumeNote, _>(
+            db,
+            tables.surreal_resume_notes,
+            SurrealResumeNote::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealInterruption, _>(
+            db,
+            tables.surreal_interruptions,
+            SurrealInterruption::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealDailyPlan, _>(
+            db,
+            tables.surreal_daily_plans,
+            SurrealDailyPlan::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealDailySummary, _>(
+            db,
+            tables.surreal_daily_summaries,
+            SurrealDailySummary::TABLE_NAME,
+        ))
+        .chain(create_delete_stream::<SurrealWeeklyReview, _>(
+            db,
+            tables.surreal_weekly_reviews,
+            SurrealWeeklyReview::TABLE_NAME,
+        ))
+        .buffer_unordered(100)
+        .fold(Ok(()), |acc, res| async {
+            match (acc, res) {
+                (Ok(_), Ok(())) => Ok(()),
+                (Err(e), _) | (Ok(()), Err(e)) => Err(e),
+            }
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn clear_surreal_in_the_moment_priorities_table(db: &Surreal<Any>) -> Result<(), String> {
+    db.query(format!("DELETE {}", SurrealInTheMomentPriority::TABLE_NAME))
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to clear {} table: {e:?}",
+                SurrealInTheMomentPriority::TABLE_NAME
+            )
+        })?;
+
+    Ok(())
+}
+
+pub(crate) async fn copy_database_if_destination_empty(
+    source: SurrealDbConnectionConfig,
+    destination: SurrealDbConnectionConfig,
+    behavior: CopyDestinationBehavior,
+) -> Result<(), String> {
+    if source.endpoint == destination.endpoint {
+        if !auth_configs_equivalent(&source.auth, &destination.auth) {
+            return Err(
+                "Source/destination auth configs differ for the same endpoint; this command currently requires them to match."
+                    .to_string(),
+            );
+        }
+
+        let db = connect_and_prepare(&destination).await?;
+        copy_between_databases_if_destination_empty_same_connection(
+            &db,
+            source.namespace.as_str(),
+            source.database.as_str(),
+            destination.namespace.as_str(),
+            destination.database.as_str(),
+            behavior,
+        )
+        .await
+    } else {
+        let source_db = connect_and_prepare(&source).await?;
+        source_db
+            .use_ns(source.namespace.as_str())
+            .use_db(source.database.as_str())
+            .await
+            .map_err(|e| format!("Failed to select source ns/db: {e:?}"))?;
+        let source_tables = load_from_surrealdb_upgrade_if_needed(&source_db).await;
+
+        let dest_db = connect_and_prepare(&destination).await?;
+        dest_db
+            .use_ns(destination.namespace.as_str())
+            .use_db(destination.database.as_str())
+            .await
+            .map_err(|e| format!("Failed to select destination ns/db: {e:?}"))?;
+        let dest_tables = load_from_surrealdb_upgrade_if_needed(&dest_db).await;
+        if surreal_tables_has_any_data(&dest_tables) {
+            match behavior {
+                CopyDestinationBehavior::ForceDeleteExisting => {
+                    clear_surreal_tables(&dest_db, dest_tables).await?;
+                }
+                CopyDestinationBehavior::ErrorIfNotEmpty => {
+                    return Err(format!(
+                        "Destination database is not empty (endpoint='{}' ns='{}' db='{}'). Use --force to delete existing data before copying.",
+                        destination.endpoint, destination.namespace, destination.database
+                    ));
+                }
+            }
+        }
+
+        copy_surreal_tables_preserving_ids(&dest_db, source_tables).await
+    }
+}
+
+pub(crate) async fn database_has_any_data(
+    config: &SurrealDbConnectionConfig,
+) -> Result<bool, String> {
+    let db = connect_and_prepare(config).await?;
+    db.use_ns(config.namespace.as_str())
+        .use_db(config.database.as_str())
+        .await
+        .map_err(|e| format!("Failed to select ns/db: {e:?}"))?;
+    let tables = load_from_surrealdb_upgrade_if_needed(&db).await;
+    Ok(surreal_tables_has_any_data(&tables))
+}
+
+async fn ensure_namespace_and_migrate_if_needed(
+    db: &Surreal<Any>,
+    config: &SurrealDbConnectionConfig,
+) {
+    let target_ns = config.namespace.as_str();
+    let db_name = config.database.as_str();
+
+    // Always use target namespace/db going forward.
+    db.use_ns(target_ns).use_db(db_name).await.unwrap();
+
+    // If the target namespace already has data, nothing to do.
     let target_tables = load_from_surrealdb_upgrade_if_needed(db).await;
     if surreal_tables_has_any_data(&target_tables) {
         return;
     }
 
-    // Legacy fallback: copy from `OnPurpose` if it has data.
-    // Old data may have been stored under the hardcoded db name "Russ", or under the current username.
-    let legacy_ns = "OnPurpose";
-    let legacy_db_candidates = if db_name == "Russ" {
-        vec![db_name]
+    // Legacy fallback: copy from `OnPurpose` if it has data.
+    // Old data may have been stored under the hardcoded db name "Russ", or under the current username.
+    let legacy_ns = "OnPurpose";
+    let legacy_db_candidates = if db_name == "Russ" {
+        vec![db_name]
+    } else {
+        vec![db_name, "Russ"]
+    };
+
+    let mut legacy_tables: Option<SurrealTables> = None;
+    let mut legacy_db_used: Option<&str> = None;
+    for legacy_db in legacy_db_candidates {
+        db.use_ns(legacy_ns).use_db(legacy_db).await.unwrap();
+        let tables = load_from_surrealdb_upgrade_if_needed(db).await;
+        if surreal_tables_has_any_data(&tables) {
+            legacy_tables = Some(tables);
+            legacy_db_used = Some(legacy_db);
+            break;
+        }
+    }
+
+    let Some(legacy_tables) = legacy_tables else {
+        // Restore target context.
+        db.use_ns(target_ns).use_db(db_name).await.unwrap();
+        return;
+    };
+
+    println!(
+        "No data found in SurrealDB namespace '{}'. Copying existing data from legacy namespace '{}' (db='{}') into '{}' (db='{}')...",
+        target_ns,
+        legacy_ns,
+        legacy_db_used.unwrap_or("UNKNOWN"),
+        target_ns,
+        db_name
+    );
+
+    db.use_ns(target_ns).use_db(db_name).await.unwrap();
+
+    copy_surreal_tables_preserving_ids(db, legacy_tables)
+        .await
+        .unwrap();
+
+    println!(
+        "SurrealDB namespace copy complete. Continuing with namespace '{}' (db='{}').",
+        target_ns, db_name
+    );
+}
+
+/// Upgrade an embedded/local SurrealDB datastore to the latest storage version.
+/// This uses SurrealDB-core's `Version::fix` to apply any required on-disk migrations.
+async fn upgrade_surreal_storage(endpoint: &str) -> Result<(), CoreError> {
+    #[allow(deprecated)]
+    let ep = endpoint.into_endpoint().map_err(|e| match e {
+        SurrealError::Db(db) => db,
+        other => CoreError::Ds(other.to_string()),
+    })?;
+
+    // Match SurrealDB embedded engine behavior: TiKV uses full URL, others use parsed path.
+    let ds_endpoint = if ep.url.scheme() == "tikv" {
+        ep.url.as_str().to_owned()
+    } else {
+        ep.path.clone()
+    };
+
+    let ds = Datastore::new(&ds_endpoint).await?;
+    let ds = std::sync::Arc::new(ds);
+    let version = ds.get_version().await?;
+    if version.is_latest() {
+        return Ok(());
+    }
+    version.fix(ds).await?;
+    Ok(())
+}
+
+pub(crate) async fn load_from_surrealdb_upgrade_if_needed(db: &Surreal<Any>) -> SurrealTables {
+    //TODO: I should do some timings to see if starting all of these get_all requests and then doing awaits on them later really is faster in Rust. Or if they just for sure don't start until the await. For example I could call this function as many times as possible in 10 sec and time that and then see how many times I can call that function written like this and then again with the get_all being right with the await to make sure that code like this is worth it perf wise.
+    let all_items = db.select(SurrealItem::TABLE_NAME);
+    let time_spent_log = db.select(SurrealTimeSpent::TABLE_NAME);
+    let surreal_in_the_moment_priorities = db.select(SurrealInTheMomentPriority::TABLE_NAME);
+    let surreal_current_modes = db.select(SurrealCurrentMode::TABLE_NAME);
+    let surreal_modes = db.select(surreal_mode::SurrealMode::TABLE_NAME);
+    let surreal_events = db.select(SurrealEvent::TABLE_NAME);
+    let surreal_working_on = db.select(SurrealWorkingOn::TABLE_NAME);
+    let surreal_priority_comparisons = db.select(SurrealPriorityComparison::TABLE_NAME);
+    let surreal_item_history = db.select(SurrealItemHistory::TABLE_NAME);
+    let surreal_smart_lists = db.select(SurrealSmartList::TABLE_NAME);
+    let surreal_breaks = db.select(SurrealBreak::TABLE_NAME);
+    let surreal_resume_notes = db.select(SurrealResumeNote::TABLE_NAME);
+    let surreal_interruptions = db.select(SurrealInterruption::TABLE_NAME);
+    let surreal_daily_plans = db.select(SurrealDailyPlan::TABLE_NAME);
+    let surreal_daily_summaries = db.select(SurrealDailySummary::TABLE_NAME);
+    let surreal_weekly_reviews = db.select(SurrealWeeklyReview::TABLE_NAME);
+
+    let all_items: Vec<SurrealItem> = match all_items.await {
+        Ok(all_items) => {
+            if all_items.iter().any(|x: &SurrealItem| x.version == 1) {
+                upgrade_items_table_version1_to_version2(db).await;
+                db.select(SurrealItem::TABLE_NAME).await.unwrap()
+            } else {
+                all_items
+            }
+        }
+        Err(err) => {
+            let err_string = err.to_string();
+            if err_string.contains("IAM error")
+                || err_string.contains("Not enough permissions")
+                || err_string.contains("not enough permissions")
+            {
+                panic!(
+                    "SurrealDB permissions error while reading items table: {}\n\
+                     If connecting to a remote SurrealDB, you likely need to authenticate.\n\
+                     Try: --surreal-auth-username <user> --surreal-auth-password <pass> [--surreal-auth-level root|ns|db]\n",
+                    err_string
+                );
+            }
+            println!("Upgrading items table because of issue: {}", err);
+            upgrade_items_table(db).await;
+            db.select(SurrealItem::TABLE_NAME).await.unwrap()
+        }
+    };
+
+    let time_spent_log = match time_spent_log.await {
+        Ok(time_spent_log) => time_spent_log,
+        Err(err) => {
+            println!("Time spent log is missing because of issue: {}", err);
+            upgrade_time_spent_log(db).await;
+            db.select(SurrealTimeSpent::TABLE_NAME).await.unwrap()
+        }
+    };
+
+    let surreal_in_the_moment_priorities = match surreal_in_the_moment_priorities.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => {
+            println!(
+                "Clearing In The Moment Priorities, they are from an earlier version of Task On Purpose: {}",
+                err
+            );
+            clear_surreal_in_the_moment_priorities_table(db)
+                .await
+                .unwrap_or_else(|clear_err| panic!("{}", clear_err));
+            println!("Done");
+            Vec::new()
+        }
+    };
+
+    let surreal_current_modes = match surreal_current_modes.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load current modes: {}", err),
+    };
+
+    let surreal_modes = surreal_modes.await.unwrap();
+
+    let surreal_priority_comparisons = match surreal_priority_comparisons.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load priority comparisons: {}", err),
+    };
+
+    let surreal_item_history = match surreal_item_history.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load item history: {}", err),
+    };
+
+    let surreal_smart_lists = match surreal_smart_lists.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load smart lists: {}", err),
+    };
+
+    let surreal_breaks = match surreal_breaks.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load breaks: {}", err),
+    };
+
+    let surreal_resume_notes = match surreal_resume_notes.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load resume notes: {}", err),
+    };
+
+    let surreal_interruptions = match surreal_interruptions.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load interruptions: {}", err),
+    };
+
+    let surreal_daily_plans = match surreal_daily_plans.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load daily plans: {}", err),
+    };
+
+    let surreal_daily_summaries = match surreal_daily_summaries.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load daily summaries: {}", err),
+    };
+
+    let surreal_weekly_reviews = match surreal_weekly_reviews.await {
+        Ok(values) => values,
+        Err(err) if is_missing_table_error(&err) => Vec::new(),
+        Err(err) => panic!("Unable to load weekly reviews: {}", err),
+    };
+
+    SurrealTables {
+        surreal_items: all_items,
+        surreal_time_spent_log: time_spent_log,
+        surreal_in_the_moment_priorities,
+        surreal_current_modes,
+        surreal_modes,
+        surreal_events: surreal_events.await.unwrap(),
+        surreal_working_on: surreal_working_on.await.unwrap(),
+        surreal_priority_comparisons,
+        surreal_item_history,
+        surreal_smart_lists,
+        surreal_breaks,
+        surreal_resume_notes,
+        surreal_interruptions,
+        surreal_daily_plans,
+        surreal_daily_summaries,
+        surreal_weekly_reviews,
+    }
+}
+
+async fn upgrade_items_table_version1_to_version2(db: &Surreal<Any>) {
+    let a: Vec<SurrealItem> = db.select(SurrealItemOldVersion::TABLE_NAME).await.unwrap();
+    for mut item_old_version in a.into_iter() {
+        let item: SurrealItem =
+            if matches!(item_old_version.item_type, SurrealItemType::Motivation(_)) {
+                item_old_version.responsibility = Responsibility::ReactiveBeAvailableToAct;
+                item_old_version.version = 2;
+                item_old_version
+            } else {
+                item_old_version.version = 2;
+                item_old_version
+            };
+        let item_record_id = item.id.clone().expect("In DB");
+        let updated: SurrealItem = db
+            .update(&item_record_id)
+            .content(item.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(item, updated);
+    }
+}
+
+async fn upgrade_items_table(db: &Surreal<Any>) {
+    let a: Vec<SurrealItemOldVersion> = db.select(SurrealItemOldVersion::TABLE_NAME).await.unwrap();
+    for item_old_version in a.into_iter() {
+        let item: SurrealItem = item_old_version.into();
+        let item_record_id = item.id.clone().expect("In DB");
+        let updated: SurrealItem = db
+            .update(&item_record_id)
+            .content(item.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(item, updated);
+    }
+}
+
+async fn upgrade_time_spent_log(db: &Surreal<Any>) {
+    let a: Vec<SurrealTimeSpentVersion0> = db.select(SurrealTimeSpent::TABLE_NAME).await.unwrap();
+    for time_spent_old in a.into_iter() {
+        let time_spent: SurrealTimeSpent = time_spent_old.into();
+        let time_spent_record_id = time_spent.id.clone().expect("In DB");
+        let updated: SurrealTimeSpent = db
+            .update(&time_spent_record_id)
+            .content(time_spent.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(time_spent, updated);
+    }
+}
+
+async fn send_time_spent(sender: oneshot::Sender<Vec<SurrealTimeSpent>>, db: &Surreal<Any>) {
+    let time_spent = db.select(SurrealTimeSpent::TABLE_NAME).await.unwrap();
+    sender.send(time_spent).unwrap();
+}
+
+async fn set_working_on(item: RecordId, when_started: Datetime, db: &Surreal<Any>) {
+    let record = SurrealWorkingOn::new(item, when_started);
+    let mut updated: Vec<SurrealWorkingOn> = db
+        .upsert(SurrealWorkingOn::TABLE_NAME)
+        .content(record.clone())
+        .await
+        .unwrap();
+    if updated.is_empty() {
+        // Annoyingly SurrealDB's upsert seems to just not work sometimes without giving an explicit
+        // error so we fall back to insert.
+        updated = db
+            .insert(SurrealWorkingOn::TABLE_NAME)
+            .content(record.clone())
+            .await
+            .unwrap();
+    }
+    assert!(!updated.is_empty());
+}
+
+async fn clear_working_on(db: &Surreal<Any>) {
+    let id: RecordId = (SurrealWorkingOn::TABLE_NAME, "working_on").into();
+    // Ignore if it doesn't exist.
+    let _deleted: Option<SurrealWorkingOn> = db.delete(id).await.unwrap();
+}
+
+async fn record_priority_comparison(
+    winner: RecordId,
+    loser: RecordId,
+    for_mode: Option<RecordId>,
+    db: &Surreal<Any>,
+) {
+    let mut comparison = SurrealPriorityComparison::new(winner, loser, for_mode, Utc::now().into());
+    let created: SurrealPriorityComparison = db
+        .create(SurrealPriorityComparison::TABLE_NAME)
+        .content(comparison.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    comparison.id = created.id.clone();
+    assert_eq!(comparison, created);
+}
+
+async fn record_break(when_started: Datetime, when_stopped: Datetime, db: &Surreal<Any>) {
+    let mut surreal_break = SurrealBreak::new(when_started, when_stopped);
+    let created: SurrealBreak = db
+        .create(SurrealBreak::TABLE_NAME)
+        .content(surreal_break.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    surreal_break.id = created.id.clone();
+    assert_eq!(surreal_break, created);
+}
+
+async fn record_interruption(
+    interrupted: Option<RecordId>,
+    source: String,
+    when_started: Datetime,
+    when_stopped: Datetime,
+    db: &Surreal<Any>,
+) {
+    let mut interruption =
+        SurrealInterruption::new(interrupted, source, when_started, when_stopped);
+    let created: SurrealInterruption = db
+        .create(SurrealInterruption::TABLE_NAME)
+        .content(interruption.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    interruption.id = created.id.clone();
+    assert_eq!(interruption, created);
+}
+
+async fn set_daily_plan(day: NaiveDate, commitments: Vec<RecordId>, db: &Surreal<Any>) {
+    let record = SurrealDailyPlan::new(day, Utc::now().into(), commitments);
+    let mut updated: Vec<SurrealDailyPlan> = db
+        .upsert(SurrealDailyPlan::TABLE_NAME)
+        .content(record.clone())
+        .await
+        .unwrap();
+    if updated.is_empty() {
+        // Annoyingly SurrealDB's upsert seems to just not work sometimes without giving an explicit
+        // error so we fall back to insert.
+        updated = db
+            .insert(SurrealDailyPlan::TABLE_NAME)
+            .content(record.clone())
+            .await
+            .unwrap();
+    }
+    assert!(!updated.is_empty());
+}
+
+async fn write_daily_summary(daily_summary: SurrealDailySummary, db: &Surreal<Any>) {
+    let mut updated: Vec<SurrealDailySummary> = db
+        .upsert(SurrealDailySummary::TABLE_NAME)
+        .content(daily_summary.clone())
+        .await
+        .unwrap();
+    if updated.is_empty() {
+        // Annoyingly SurrealDB's upsert seems to just not work sometimes without giving an explicit
+        // error so we fall back to insert.
+        updated = db
+            .insert(SurrealDailySummary::TABLE_NAME)
+            .content(daily_summary.clone())
+            .await
+            .unwrap();
+    }
+    assert!(!updated.is_empty());
+}
+
+async fn save_weekly_review(weekly_review: SurrealWeeklyReview, db: &Surreal<Any>) {
+    let mut updated: Vec<SurrealWeeklyReview> = db
+        .upsert(SurrealWeeklyReview::TABLE_NAME)
+        .content(weekly_review.clone())
+        .await
+        .unwrap();
+    if updated.is_empty() {
+        // Annoyingly SurrealDB's upsert seems to just not work sometimes without giving an explicit
+        // error so we fall back to insert.
+        updated = db
+            .insert(SurrealWeeklyReview::TABLE_NAME)
+            .content(weekly_review.clone())
+            .await
+            .unwrap();
+    }
+    assert!(!updated.is_empty());
+}
+
+async fn record_item_history(item: RecordId, change: SurrealItemChange, db: &Surreal<Any>) {
+    let mut history = SurrealItemHistory::new(item, Utc::now().into(), change);
+    let created: SurrealItemHistory = db
+        .create(SurrealItemHistory::TABLE_NAME)
+        .content(history.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    history.id = created.id.clone();
+    assert_eq!(history, created);
+}
+
+async fn record_time_spent(new_time_spent: NewTimeSpent, db: &Surreal<Any>) {
+    let mut new_time_spent: SurrealTimeSpent = new_time_spent.into();
+    let saved: SurrealTimeSpent = db
+        .create(SurrealTimeSpent::TABLE_NAME)
+        .content(new_time_spent.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    new_time_spent.id = saved.id.clone();
+    assert_eq!(new_time_spent, saved);
+}
+
+pub(crate) async fn finish_item(finish_this: RecordId, when_finished: Datetime, db: &Surreal<Any>) {
+    let updated: SurrealItem = db
+        .update(&finish_this)
+        .patch(PatchOp::replace("/finished", Some(when_finished.clone())))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.finished, Some(when_finished));
+}
+
+pub(crate) async fn reactivate_item(reactivate_this: RecordId, db: &Surreal<Any>) {
+    let updated: SurrealItem = db
+        .update(&reactivate_this)
+        .patch(PatchOp::replace("/finished", None::<Datetime>))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.finished, None);
+}
+
+async fn create_new_item(mut new_item: NewItem, db: &Surreal<Any>) -> SurrealItem {
+    for dependency in new_item.dependencies.iter_mut() {
+        match dependency {
+            NewDependency::NewEvent(new_event) => {
+                let created = create_new_event(new_event.clone(), db).await;
+                let created_event_record_id = created.id.expect("In DB");
+                *dependency =
+                    NewDependency::Existing(SurrealDependency::AfterEvent(created_event_record_id));
+            }
+            NewDependency::Existing(_) => {}
+        }
+    }
+    let mut surreal_item: SurrealItem = SurrealItem::new(new_item, vec![])
+        .expect("We fix up NewDependency::NewEvent above so it will never happen here");
+    let created: SurrealItem = db
+        .create(SurrealItem::TABLE_NAME)
+        .content(surreal_item.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    surreal_item.id = created.id.clone();
+    assert_eq!(surreal_item, created);
+
+    created
+}
+
+async fn cover_with_a_new_item(cover_this: RecordId, cover_with: NewItem, db: &Surreal<Any>) {
+    let cover_with = create_new_item(cover_with, db).await;
+
+    let cover_with_record_id = cover_with.id.expect("In DB");
+    let new_dependency = SurrealDependency::AfterItem(cover_with_record_id);
+    add_dependency(cover_this, new_dependency, db).await;
+}
+
+async fn cover_item_with_an_existing_item(
+    existing_item_to_be_covered: RecordId,
+    existing_item_that_is_doing_the_covering: RecordId,
+    db: &Surreal<Any>,
+) {
+    let new_dependency = SurrealDependency::AfterItem(existing_item_that_is_doing_the_covering);
+    add_dependency(existing_item_to_be_covered, new_dependency, db).await;
+}
+
+async fn parent_item_with_existing_item(
+    child_record_id: RecordId,
+    parent_record_id: RecordId,
+    higher_importance_than_this: Option<RecordId>,
+    db: &Surreal<Any>,
+) {
+    //TODO: This should be refactored so it happens inside of a transaction and ideally as one query because if the data is modified between the time that the data is read and the time that the data is written back out then the data could be lost. I haven't done this yet because I need to figure out how to do this inside of a SurrealDB query and I haven't done that yet.
+    let mut parent: SurrealItem = db.select(parent_record_id.clone()).await.unwrap().unwrap();
+    let child_count_before = parent.smaller_items_in_priority_order.len();
+    parent.smaller_items_in_priority_order = parent
+        .smaller_items_in_priority_order
+        .into_iter()
+        .filter(|x| match x {
+            SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                surreal_item_id != &child_record_id
+            }
+        })
+        .collect::<Vec<_>>();
+    let was_already_a_child = parent.smaller_items_in_priority_order.len() != child_count_before;
+    if let Some(higher_priority_than_this) = higher_importance_than_this {
+        let index_of_higher_priority = parent
+            .smaller_items_in_priority_order
+            .iter()
+            .position(|x| match x {
+                //Note that position() is short-circuiting. If there are multiple matches it could be argued that I should panic or assert but
+                //I am just matching the first one and then I just keep going. Because I am still figuring out the design and this is
+                //more in the vein of hardening work I think this is fine but feel free to revisit this.
+                SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                    surreal_item_id == &higher_priority_than_this
+                }
+            })
+            .expect("Should already be in the list");
+        parent.smaller_items_in_priority_order.insert(
+            index_of_higher_priority,
+            SurrealOrderedSubItem::SubItem {
+                surreal_item_id: child_record_id.clone(),
+            },
+        );
+    } else {
+        parent
+            .smaller_items_in_priority_order
+            .push(SurrealOrderedSubItem::SubItem {
+                surreal_item_id: child_record_id.clone(),
+            });
+    }
+    let saved = db
+        .update(&parent_record_id)
+        .content(parent.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(parent, saved);
+
+    if was_already_a_child {
+        record_item_history(
+            parent_record_id,
+            SurrealItemChange::ChildReordered(child_record_id),
+            db,
+        )
+        .await;
     } else {
-        vec![db_name, "Russ"]
-    };
+        record_item_history(
+            parent_record_id.clone(),
+            SurrealItemChange::ChildAdded(child_record_id.clone()),
+            db,
+        )
+        .await;
+        record_item_history(
+            child_record_id,
+            SurrealItemChange::ParentAdded(parent_record_id),
+            db,
+        )
+        .await;
+    }
+}
+
+async fn parent_item_remove_parent(child: RecordId, parent_to_remove: RecordId, db: &Surreal<Any>) {
+    let mut parent: SurrealItem = db.select(parent_to_remove.clone()).await.unwrap().unwrap();
+    let child_count_before = parent.smaller_items_in_priority_order.len();
+
+    parent.smaller_items_in_priority_order = parent
+        .smaller_items_in_priority_order
+        .into_iter()
+        .filter(|x| match x {
+            SurrealOrderedSubItem::SubItem { surreal_item_id } => surreal_item_id != &child,
+        })
+        .collect::<Vec<_>>();
+    let saved = db
+        .update(&parent_to_remove)
+        .content(parent.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(parent, saved);
+
+    if parent.smaller_items_in_priority_order.len() != child_count_before {
+        record_item_history(
+            parent_to_remove.clone(),
+            SurrealItemChange::ChildRemoved(child.clone()),
+            db,
+        )
+        .await;
+        record_item_history(
+            child,
+            SurrealItemChange::ParentRemoved(parent_to_remove),
+            db,
+        )
+        .await;
+    }
+}
+
+/// Removes the item from under its parents, from what other items are waiting on, and from any in
+/// the moment priorities and priority comparisons before deleting it so nothing is left pointing at
+/// an item that no longer exists. The history of the parents keeps a record that the item was
+/// removed. An item with time spent on it or with smaller items is refused, whoever asks.
+async fn delete_item(delete_this: RecordId, db: &Surreal<Any>) -> Result<(), CommandRejection> {
+    let time_spent_log: Vec<SurrealTimeSpent> =
+        db.select(SurrealTimeSpent::TABLE_NAME).await.unwrap();
+    if time_spent_log.iter().any(|x| {
+        x.working_on
+            .iter()
+            .any(|x| x.get_record_id() == &delete_this)
+    }) {
+        return Err(CommandRejection::Conflict(format!(
+            "{} has time spent on it, finish it instead of deleting it",
+            delete_this
+        )));
+    }
+    let items: Vec<SurrealItem> = db.select(SurrealItem::TABLE_NAME).await.unwrap();
+    if items.iter().any(|x| {
+        x.id.as_ref() == Some(&delete_this) && !x.smaller_items_in_priority_order.is_empty()
+    }) {
+        return Err(CommandRejection::Conflict(format!(
+            "{} has smaller items, move or delete them first",
+            delete_this
+        )));
+    }
+    for item in items.iter() {
+        let item_id = item.id.clone().expect("In DB");
+        if item
+            .smaller_items_in_priority_order
+            .iter()
+            .any(|x| match x {
+                SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                    surreal_item_id == &delete_this
+                }
+            })
+        {
+            parent_item_remove_parent(delete_this.clone(), item_id.clone(), db).await;
+        }
+        for dependency in item.dependencies.iter() {
+            match dependency {
+                SurrealDependency::AfterItem(waiting_on)
+                | SurrealDependency::DuringItem(waiting_on)
+                    if waiting_on == &delete_this =>
+                {
+                    remove_dependency(item_id.clone(), dependency.clone(), db).await;
+                }
+                SurrealDependency::AfterItem(_)
+                | SurrealDependency::DuringItem(_)
+                | SurrealDependency::AfterDateTime(_)
+                | SurrealDependency::AfterEvent(_) => {}
+            }
+        }
+        if item
+            .waiting_for
+            .as_ref()
+            .is_some_and(|x| x.person == delete_this)
+        {
+            update_waiting_for(item_id, None, db).await;
+        }
+    }
+
+    let priorities: Vec<SurrealInTheMomentPriority> = db
+        .select(SurrealInTheMomentPriority::TABLE_NAME)
+        .await
+        .unwrap();
+    for priority in priorities.into_iter() {
+        let not_chosen = match &priority.kind {
+            SurrealPriorityKind::HighestPriority { not_chosen }
+            | SurrealPriorityKind::LowestPriority { not_chosen } => not_chosen.as_slice(),
+            SurrealPriorityKind::NotInMode => &[],
+        };
+        if priority.choice.get_record_id() == &delete_this
+            || not_chosen.iter().any(|x| x.get_record_id() == &delete_this)
+        {
+            let _deleted: Option<SurrealInTheMomentPriority> =
+                db.delete(priority.id.expect("In DB")).await.unwrap();
+        }
+    }
+
+    let priority_comparisons: Vec<SurrealPriorityComparison> = db
+        .select(SurrealPriorityComparison::TABLE_NAME)
+        .await
+        .unwrap();
+    for priority_comparison in priority_comparisons
+        .into_iter()
+        .filter(|x| x.winner == delete_this || x.loser == delete_this)
+    {
+        let _deleted: Option<SurrealPriorityComparison> = db
+            .delete(priority_comparison.id.expect("In DB"))
+            .await
+            .unwrap();
+    }
+
+    let working_on: Vec<SurrealWorkingOn> = db.select(SurrealWorkingOn::TABLE_NAME).await.unwrap();
+    if working_on.iter().any(|x| x.item == delete_this) {
+        clear_working_on(db).await;
+    }
+
+    //The item's own history and resume notes go with it. History that other items keep, like the
+    //parent's record of the child being removed just above, is about them so it stays.
+    let item_history: Vec<SurrealItemHistory> =
+        db.select(SurrealItemHistory::TABLE_NAME).await.unwrap();
+    for history in item_history.into_iter().filter(|x| x.item == delete_this) {
+        let _deleted: Option<SurrealItemHistory> =
+            db.delete(history.id.expect("In DB")).await.unwrap();
+    }
+    let resume_notes: Vec<SurrealResumeNote> =
+        db.select(SurrealResumeNote::TABLE_NAME).await.unwrap();
+    for resume_note in resume_notes.into_iter().filter(|x| x.item == delete_this) {
+        let _deleted: Option<SurrealResumeNote> =
+            db.delete(resume_note.id.expect("In DB")).await.unwrap();
+    }
+
+    //Interruptions and daily plans still happened so they are kept without the item
+    let interruptions: Vec<SurrealInterruption> =
+        db.select(SurrealInterruption::TABLE_NAME).await.unwrap();
+    for mut interruption in interruptions
+        .into_iter()
+        .filter(|x| x.interrupted.as_ref() == Some(&delete_this))
+    {
+        interruption.interrupted = None;
+        let saved: SurrealInterruption = db
+            .update(interruption.id.clone().expect("In DB"))
+            .content(interruption.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(interruption, saved);
+    }
+    let daily_plans: Vec<SurrealDailyPlan> = db.select(SurrealDailyPlan::TABLE_NAME).await.unwrap();
+    for mut daily_plan in daily_plans
+        .into_iter()
+        .filter(|x| x.commitments.contains(&delete_this))
+    {
+        daily_plan.commitments.retain(|x| x != &delete_this);
+        let saved: SurrealDailyPlan = db
+            .update(daily_plan.id.clone().expect("In DB"))
+            .content(daily_plan.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(daily_plan, saved);
+    }
+
+    let deleted: SurrealItem = db
+        .delete(&delete_this)
+        .await
+        .unwrap()
+        .expect("Record exists");
+    assert_eq!(deleted.id, Some(delete_this));
+    Ok(())
+}
+
+async fn parent_item_with_a_new_child(
+    child: NewItem,
+    parent: RecordId,
+    higher_importance_than_this: Option<RecordId>,
+    db: &Surreal<Any>,
+) -> RecordId {
+    let child = create_new_item(child, db).await;
+    let child_record_id = child.id.expect("In DB");
+    parent_item_with_existing_item(
+        child_record_id.clone(),
+        parent,
+        higher_importance_than_this,
+        db,
+    )
+    .await;
+    child_record_id
+}
+
+async fn parent_item_with_new_child_trees(
+    children: Vec<NewItemTree>,
+    parent: RecordId,
+    db: &Surreal<Any>,
+) {
+    //Breadth first so each item exists before the items under it are added. With no item to be more
+    //important than each child goes to the end of the list, which keeps the order of the outline.
+    let mut to_add = children
+        .into_iter()
+        .map(|x| (x, parent.clone()))
+        .collect::<VecDeque<_>>();
+    while let Some((tree, parent)) = to_add.pop_front() {
+        let child = parent_item_with_a_new_child(tree.item, parent, None, db).await;
+        to_add.extend(tree.smaller_items.into_iter().map(|x| (x, child.clone())));
+    }
+}
+
+async fn parent_new_item_with_an_existing_child_item(
+    child: RecordId,
+    mut parent_new_item: NewItem,
+    db: &Surreal<Any>,
+) {
+    for dependency in parent_new_item.dependencies.iter_mut() {
+        match dependency {
+            NewDependency::NewEvent(new_event) => {
+                let created = create_new_event(new_event.clone(), db).await;
+                let created_event_record_id = created.id.expect("In DB");
+                *dependency =
+                    NewDependency::Existing(SurrealDependency::AfterEvent(created_event_record_id));
+            }
+            NewDependency::Existing(_) => {}
+        }
+    }
+
+    //TODO: Write a Unit Test for this
+    let smaller_items_in_priority_order = vec![SurrealOrderedSubItem::SubItem {
+        surreal_item_id: child.clone(),
+    }];
+
+    let mut parent_surreal_item =
+        SurrealItem::new(parent_new_item, smaller_items_in_priority_order)
+            .expect("We deal with new events above so it will never happen here");
+    let created: SurrealItem = db
+        .create(SurrealItem::TABLE_NAME)
+        .content(parent_surreal_item.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    parent_surreal_item.id = created.id.clone();
+    assert_eq!(parent_surreal_item, created);
+
+    let parent = created.id.expect("In DB");
+    record_item_history(
+        parent.clone(),
+        SurrealItemChange::ChildAdded(child.clone()),
+        db,
+    )
+    .await;
+    record_item_history(child, SurrealItemChange::ParentAdded(parent), db).await;
+}
+
+async fn add_dependency(record_id: RecordId, new_dependency: SurrealDependency, db: &Surreal<Any>) {
+    //TODO: This should be refactored so it happens inside of a transaction and ideally as one query because if the data is modified between the time that the data is read and the time that the data is written back out then the data could be lost. I haven't done this yet because I need to figure out how to do this inside of a SurrealDB query and I haven't done that yet.
+
+    let mut surreal_item: SurrealItem = db
+        .select(record_id.clone())
+        .await
+        .unwrap()
+        .expect("Record exists");
+    if surreal_item.dependencies.contains(&new_dependency) {
+        //Is already there, nothing to do
+    } else {
+        surreal_item.dependencies.push(new_dependency.clone());
+
+        let updated: SurrealItem = db
+            .update(&record_id)
+            .content(surreal_item.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(surreal_item, updated);
+        record_item_history(
+            record_id,
+            SurrealItemChange::DependencyAdded(new_dependency),
+            db,
+        )
+        .await;
+    }
+}
+
+async fn remove_dependency(record_id: RecordId, to_remove: SurrealDependency, db: &Surreal<Any>) {
+    let mut surreal_item: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
+    let was_a_dependency = surreal_item.dependencies.contains(&to_remove);
+    surreal_item.dependencies.retain(|x| x != &to_remove);
+
+    let update = db
+        .update(&record_id)
+        .content(surreal_item.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(surreal_item, update);
+    if was_a_dependency {
+        record_item_history(
+            record_id,
+            SurrealItemChange::DependencyRemoved(to_remove),
+            db,
+        )
+        .await;
+    }
+}
+
+/// Snoozing replaces any existing AfterDateTime dependency so an item is never waiting on more than
+/// one date at a time. Passing None removes the AfterDateTime dependency which unsnoozes the item.
+async fn replace_after_date_time_dependency(
+    record_id: RecordId,
+    after: Option<Datetime>,
+    db: &Surreal<Any>,
+) {
+    let item: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
+    let (removed, mut dependencies): (Vec<_>, Vec<_>) = item
+        .dependencies
+        .into_iter()
+        .partition(|x| matches!(x, SurrealDependency::AfterDateTime(_)));
+    let added = after.map(SurrealDependency::AfterDateTime);
+    if let Some(added) = &added {
+        dependencies.push(added.clone());
+    }
+    let updated: SurrealItem = db
+        .update(&record_id)
+        .patch(PatchOp::replace("/dependencies", dependencies.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.dependencies, dependencies);
 
-    let mut legacy_tables: Option<SurrealTables> = None;
-    let mut legacy_db_used: Option<&str> = None;
-    for legacy_db in legacy_db_candidates {
-        db.use_ns(legacy_ns).use_db(legacy_db).await.unwrap();
-        let tables = load_from_surrealdb_upgrade_if_needed(db).await;
-        if surreal_tables_has_any_data(&tables) {
-            legacy_tables = Some(tables);
-            legacy_db_used = Some(legacy_db);
-            break;
-        }
+    for removed in removed {
+        record_item_history(
+            record_id.clone(),
+            SurrealItemChange::DependencyRemoved(removed),
+            db,
+        )
+        .await;
     }
+    if let Some(added) = added {
+        record_item_history(record_id, SurrealItemChange::DependencyAdded(added), db).await;
+    }
+}
 
-    let Some(legacy_tables) = legacy_tables else {
-        // Restore target context.
-        db.use_ns(target_ns).use_db(db_name).await.unwrap();
-        return;
-    };
+async fn add_dependency_new_event(record_id: RecordId, new_event: NewEvent, db: &Surreal<Any>) {
+    let created: SurrealEvent = create_new_event(new_event, db).await;
+    let new_dependency = SurrealDependency::AfterEvent(created.id.expect("In DB"));
 
-    println!(
-        "No data found in SurrealDB namespace '{}'. Copying existing data from legacy namespace '{}' (db='{}') into '{}' (db='{}')...",
-        target_ns,
-        legacy_ns,
-        legacy_db_used.unwrap_or("UNKNOWN"),
-        target_ns,
-        db_name
-    );
+    add_dependency(record_id, new_dependency, db).await
+}
 
-    db.use_ns(target_ns).use_db(db_name).await.unwrap();
+async fn create_new_event(new_event: NewEvent, db: &Surreal<Any>) -> SurrealEvent {
+    let event: SurrealEvent = new_event.into();
+    let created: SurrealEvent = db
+        .create(SurrealEvent::TABLE_NAME)
+        .content(event.clone())
+        .await
+        .unwrap()
+        .expect("Created");
+    assert_eq!(created.last_updated, event.last_updated);
+    assert_eq!(created.summary, event.summary);
+    created
+}
 
-    copy_surreal_tables_preserving_ids(db, legacy_tables)
+async fn update_item_summary(item_to_update: RecordId, new_summary: String, db: &Surreal<Any>) {
+    let previous: SurrealItem = db.select(item_to_update.clone()).await.unwrap().unwrap();
+    let updated: SurrealItem = db
+        .update(&item_to_update)
+        .patch(PatchOp::replace("/summary", new_summary.clone()))
         .await
+        .unwrap()
         .unwrap();
+    assert_eq!(updated.summary, new_summary);
+    if previous.summary != new_summary {
+        record_item_history(
+            item_to_update,
+            SurrealItemChange::SummaryChanged {
+                from: previous.summary,
+                to: new_summary,
+            },
+            db,
+        )
+        .await;
+    }
+}
 
-    println!(
-        "SurrealDB namespace copy complete. Continuing with namespace '{}' (db='{}').",
-        target_ns, db_name
-    );
+async fn update_responsibility_and_item_type(
+    item: RecordId,
+    new_responsibility: Responsibility,
+    new_item_type: SurrealItemType,
+    db: &Surreal<Any>,
+) {
+    let previous: SurrealItem = db.select(item.clone()).await.unwrap().unwrap();
+    let updated: SurrealItem = db
+        .update(&item)
+        .patch(PatchOp::replace(
+            "/responsibility",
+            new_responsibility.clone(),
+        ))
+        .patch(PatchOp::replace("/item_type", new_item_type.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.responsibility, new_responsibility);
+    assert_eq!(updated.item_type, new_item_type);
+
+    if previous.responsibility != new_responsibility {
+        record_item_history(
+            item.clone(),
+            SurrealItemChange::ResponsibilityChanged {
+                from: previous.responsibility,
+                to: new_responsibility,
+            },
+            db,
+        )
+        .await;
+    }
+    if previous.item_type != new_item_type {
+        record_item_history(
+            item,
+            SurrealItemChange::ItemTypeChanged {
+                from: previous.item_type,
+                to: new_item_type,
+            },
+            db,
+        )
+        .await;
+    }
 }
 
-/// Upgrade an embedded/local SurrealDB datastore to the latest storage version.
-/// This uses SurrealDB-core's `Version::fix` to apply any required on-disk migrations.
-async fn upgrade_surreal_storage(endpoint: &str) -> Result<(), CoreError> {
-    #[allow(deprecated)]
-    let ep = endpoint.into_endpoint().map_err(|e| match e {
-        SurrealError::Db(db) => db,
-        other => CoreError::Ds(other.to_string()),
-    })?;
+async fn update_urgency_plan(
+    record_id: RecordId,
+    new_urgency_plan: Option<SurrealUrgencyPlan>,
+    db: &Surreal<Any>,
+) {
+    let previous: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
+    let updated: SurrealItem = db
+        .update(&record_id)
+        .patch(PatchOp::replace("/urgency_plan", new_urgency_plan.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.urgency_plan, new_urgency_plan);
+    if previous.urgency_plan != new_urgency_plan {
+        record_item_history(
+            record_id,
+            SurrealItemChange::UrgencyPlanChanged {
+                from: previous.urgency_plan,
+                to: new_urgency_plan,
+            },
+            db,
+        )
+        .await;
+    }
+}
 
-    // Match SurrealDB embedded engine behavior: TiKV uses full URL, others use parsed path.
-    let ds_endpoint = if ep.url.scheme() == "tikv" {
-        ep.url.as_str().to_owned()
+async fn update_tags(record_id: RecordId, new_tags: Vec<String>, db: &Surreal<Any>) {
+    let previous: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
+    let new_tags_value = if new_tags.is_empty() {
+        None
     } else {
-        ep.path.clone()
+        Some(new_tags.clone())
     };
-
-    let ds = Datastore::new(&ds_endpoint).await?;
-    let ds = std::sync::Arc::new(ds);
-    let version = ds.get_version().await?;
-    if version.is_latest() {
-        return Ok(());
+    let updated: SurrealItem = db
+        .update(&record_id)
+        .patch(PatchOp::replace("/tags", new_tags_value.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.tags, new_tags_value);
+    let previous_tags = previous.tags.unwrap_or_default();
+    if previous_tags != new_tags {
+        record_item_history(
+            record_id,
+            SurrealItemChange::TagsChanged {
+                from: previous_tags,
+                to: new_tags,
+            },
+            db,
+        )
+        .await;
     }
-    version.fix(ds).await?;
-    Ok(())
 }
 
-pub(crate) async fn load_from_surrealdb_upgrade_if_needed(db: &Surreal<Any>) -> SurrealTables {
-    //TODO: I should do some timings to see if starting all of these get_all requests and then doing awaits on them later really is faster in Rust. Or if they just for sure don't start until the await. For example I could call this function as many times as possible in 10 sec and time that and then see how many times I can call that function written like this and then again with the get_all being right with the await to make sure that code like this is worth it perf wise.
-    let all_items = db.select(SurrealItem::TABLE_NAME);
-    let time_spent_log = db.select(SurrealTimeSpent::TABLE_NAME);
-    let surreal_in_the_moment_priorities = db.select(SurrealInTheMomentPriority::TABLE_NAME);
-    let surreal_current_modes = db.select(SurrealCurrentMode::TABLE_NAME);
-    let surreal_modes = db.select(surreal_mode::SurrealMode::TABLE_NAME);
-    let surreal_events = db.select(SurrealEvent::TABLE_NAME);
-    let surreal_working_on = db.select(SurrealWorkingOn::TABLE_NAME);
+/// Waiting on someone snoozes the item until the follow up date and escalates its urgency at that
+/// same time so following up shows up in the Do Now list once the date passes. When it is no longer
+/// waiting only what the waiting added is taken away again, the responsibility and urgency plan from
+/// before are put back unless they have been changed since, and a snooze of the user's own stays.
+async fn update_waiting_for(
+    record_id: RecordId,
+    waiting_for: Option<SurrealWaitingFor>,
+    db: &Surreal<Any>,
+) {
+    let previous: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
+    let waiting_for = waiting_for.map(|waiting_for| match &previous.waiting_for {
+        Some(already_waiting) => SurrealWaitingFor {
+            responsibility_before: already_waiting.responsibility_before.clone(),
+            urgency_plan_before: already_waiting.urgency_plan_before.clone(),
+            ..waiting_for
+        },
+        None => SurrealWaitingFor {
+            responsibility_before: Some(previous.responsibility.clone()),
+            urgency_plan_before: previous.urgency_plan.clone(),
+            ..waiting_for
+        },
+    });
+    if previous.waiting_for == waiting_for {
+        return;
+    }
+    let updated: SurrealItem = db
+        .update(&record_id)
+        .patch(PatchOp::replace("/waiting_for", waiting_for.clone()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.waiting_for, waiting_for);
+    record_item_history(
+        record_id.clone(),
+        SurrealItemChange::WaitingForChanged {
+            from: previous.waiting_for.clone(),
+            to: waiting_for.clone(),
+        },
+        db,
+    )
+    .await;
+
+    let previous_follow_up = previous.waiting_for.as_ref().map(|x| &x.follow_up);
+    let follow_up = waiting_for.as_ref().map(|x| &x.follow_up);
+    if previous_follow_up != follow_up {
+        if let Some(previous_follow_up) = previous_follow_up {
+            remove_dependency(
+                record_id.clone(),
+                SurrealDependency::AfterDateTime(previous_follow_up.clone()),
+                db,
+            )
+            .await;
+        }
+        if let Some(follow_up) = follow_up {
+            add_dependency(
+                record_id.clone(),
+                SurrealDependency::AfterDateTime(follow_up.clone()),
+                db,
+            )
+            .await;
+        }
+    }
 
-    let all_items: Vec<SurrealItem> = match all_items.await {
-        Ok(all_items) => {
-            if all_items.iter().any(|x: &SurrealItem| x.version == 1) {
-                upgrade_items_table_version1_to_version2(db).await;
-                db.select(SurrealItem::TABLE_NAME).await.unwrap()
-            } else {
-                all_items
+    match (&previous.waiting_for, &waiting_for) {
+        (None, Some(waiting_for)) => {
+            update_responsibility_and_item_type(
+                record_id.clone(),
+                Responsibility::WaitingFor,
+                previous.item_type,
+                db,
+            )
+            .await;
+            update_urgency_plan(record_id, Some(waiting_for.follow_up_urgency_plan()), db).await;
+        }
+        (Some(already_waiting), Some(waiting_for)) => {
+            if previous.urgency_plan == Some(already_waiting.follow_up_urgency_plan()) {
+                update_urgency_plan(record_id, Some(waiting_for.follow_up_urgency_plan()), db)
+                    .await;
             }
         }
-        Err(err) => {
-            let err_string = err.to_string();
-            if err_string.contains("IAM error")
-                || err_string.contains("Not enough permissions")
-                || err_string.contains("not enough permissions")
-            {
-                panic!(
-                    "SurrealDB permissions error while reading items table: {}\n\
-                     If connecting to a remote SurrealDB, you likely need to authenticate.\n\
-                     Try: --surreal-auth-username <user> --surreal-auth-password <pass> [--surreal-auth-level root|ns|db]\n",
-                    err_string
-                );
+        (Some(was_waiting), None) => {
+            if previous.responsibility == Responsibility::WaitingFor {
+                update_responsibility_and_item_type(
+                    record_id.clone(),
+                    was_waiting
+                        .responsibility_before
+                        .clone()
+                        .unwrap_or_default(),
+                    previous.item_type,
+                    db,
+                )
+                .await;
+            }
+            if previous.urgency_plan == Some(was_waiting.follow_up_urgency_plan()) {
+                update_urgency_plan(record_id, was_waiting.urgency_plan_before.clone(), db).await;
             }
-            println!("Upgrading items table because of issue: {}", err);
-            upgrade_items_table(db).await;
-            db.select(SurrealItem::TABLE_NAME).await.unwrap()
         }
-    };
+        (None, None) => {}
+    }
+}
 
-    let time_spent_log = match time_spent_log.await {
-        Ok(time_spent_log) => time_spent_log,
-        Err(err) => {
-            println!("Time spent log is missing because of issue: {}", err);
-            upgrade_time_spent_log(db).await;
-            db.select(SurrealTimeSpent::TABLE_NAME).await.unwrap()
-        }
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    use crate::{
+        data_storage::surrealdb_layer::{
+            surreal_item::{SurrealHowMuchIsInMyControl, SurrealUrgency},
+            surreal_weekly_review::SurrealWeeklyReviewStep,
+        },
+        new_item::NewItemBuilder,
     };
 
-    let surreal_in_the_moment_priorities = match surreal_in_the_moment_priorities.await {
-        Ok(values) => values,
-        Err(err) if is_missing_table_error(&err) => Vec::new(),
-        Err(err) => {
-            println!(
-                "Clearing In The Moment Priorities, they are from an earlier version of Task On Purpose: {}",
-                err
-            );
-            clear_surreal_in_the_moment_priorities_table(db)
-                .await
-                .unwrap_or_else(|clear_err| panic!("{}", clear_err));
-            println!("Done");
-            Vec::new()
+    fn mem_config() -> SurrealDbConnectionConfig {
+        SurrealDbConnectionConfig {
+            endpoint: "mem://".to_string(),
+            namespace: "TaskOnPurpose".to_string(),
+            database: "test".to_string(),
+            auth: None,
         }
-    };
+    }
 
-    let surreal_current_modes = match surreal_current_modes.await {
-        Ok(values) => values,
-        Err(err) if is_missing_table_error(&err) => Vec::new(),
-        Err(err) => panic!("Unable to load current modes: {}", err),
-    };
+    #[tokio::test]
+    async fn data_starts_empty() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert!(surreal_tables.surreal_items.is_empty());
+        assert!(surreal_tables.surreal_time_spent_log.is_empty());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn add_new_item() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
+
+        let new_item = NewItem::new("New item".into(), Utc::now());
+        sender
+            .send(DataLayerCommands::NewItem(new_item))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+
+        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        assert_eq!(
+            SurrealItemType::Undeclared,
+            surreal_tables.surreal_items.first().unwrap().item_type
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn finish_item() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-    let surreal_modes = surreal_modes.await.unwrap();
+        let new_next_step = NewItemBuilder::default()
+            .summary("New next step")
+            .item_type(SurrealItemType::Action)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_next_step))
+            .await
+            .unwrap();
 
-    SurrealTables {
-        surreal_items: all_items,
-        surreal_time_spent_log: time_spent_log,
-        surreal_in_the_moment_priorities,
-        surreal_current_modes,
-        surreal_modes,
-        surreal_events: surreal_events.await.unwrap(),
-        surreal_working_on: surreal_working_on.await.unwrap(),
-    }
-}
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let now = Utc::now();
+        let items = surreal_tables.make_items(&now);
 
-async fn upgrade_items_table_version1_to_version2(db: &Surreal<Any>) {
-    let a: Vec<SurrealItem> = db.select(SurrealItemOldVersion::TABLE_NAME).await.unwrap();
-    for mut item_old_version in a.into_iter() {
-        let item: SurrealItem =
-            if matches!(item_old_version.item_type, SurrealItemType::Motivation(_)) {
-                item_old_version.responsibility = Responsibility::ReactiveBeAvailableToAct;
-                item_old_version.version = 2;
-                item_old_version
-            } else {
-                item_old_version.version = 2;
-                item_old_version
-            };
-        let item_record_id = item.id.clone().expect("In DB");
-        let updated: SurrealItem = db
-            .update(&item_record_id)
-            .content(item.clone())
+        assert_eq!(items.len(), 1);
+        let next_step_item = items.iter().next().map(|(_, v)| v).unwrap();
+        assert!(!next_step_item.is_finished());
+
+        let when_finished = Utc::now();
+        sender
+            .send(DataLayerCommands::FinishItem {
+                item: next_step_item.get_surreal_record_id().clone(),
+                when_finished: when_finished.into(),
+            })
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(item, updated);
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let now = Utc::now();
+        let items = surreal_tables.make_items(&now);
+
+        assert_eq!(items.len(), 1);
+        let next_step_item = items.iter().next().map(|(_, v)| v).unwrap();
+        assert!(next_step_item.is_finished());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
     }
-}
 
-async fn upgrade_items_table(db: &Surreal<Any>) {
-    let a: Vec<SurrealItemOldVersion> = db.select(SurrealItemOldVersion::TABLE_NAME).await.unwrap();
-    for item_old_version in a.into_iter() {
-        let item: SurrealItem = item_old_version.into();
-        let item_record_id = item.id.clone().expect("In DB");
-        let updated: SurrealItem = db
-            .update(&item_record_id)
-            .content(item.clone())
+    #[tokio::test]
+    async fn snooze_replaces_after_date_time_and_unsnooze_removes_it() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
+
+        let now = Utc::now();
+        let event_id: RecordId = ("events", "1").into();
+        let new_item = NewItemBuilder::default()
+            .summary("Item to snooze")
+            .item_type(SurrealItemType::Action)
+            .dependencies(vec![
+                NewDependency::Existing(SurrealDependency::AfterDateTime(now.into())),
+                NewDependency::Existing(SurrealDependency::AfterEvent(event_id.clone())),
+            ])
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_item))
             .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let item_id = surreal_tables
+            .surreal_items
+            .first()
             .unwrap()
+            .id
+            .clone()
             .unwrap();
-        assert_eq!(item, updated);
-    }
-}
 
-async fn upgrade_time_spent_log(db: &Surreal<Any>) {
-    let a: Vec<SurrealTimeSpentVersion0> = db.select(SurrealTimeSpent::TABLE_NAME).await.unwrap();
-    for time_spent_old in a.into_iter() {
-        let time_spent: SurrealTimeSpent = time_spent_old.into();
-        let time_spent_record_id = time_spent.id.clone().expect("In DB");
-        let updated: SurrealTimeSpent = db
-            .update(&time_spent_record_id)
-            .content(time_spent.clone())
+        let snooze_until: Datetime = (now + chrono::Duration::days(2)).into();
+        sender
+            .send(DataLayerCommands::SnoozeItem {
+                item: item_id.clone(),
+                until: snooze_until.clone(),
+            })
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(time_spent, updated);
-    }
-}
 
-async fn send_time_spent(sender: oneshot::Sender<Vec<SurrealTimeSpent>>, db: &Surreal<Any>) {
-    let time_spent = db.select(SurrealTimeSpent::TABLE_NAME).await.unwrap();
-    sender.send(time_spent).unwrap();
-}
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(
+            surreal_tables.surreal_items.first().unwrap().dependencies,
+            vec![
+                SurrealDependency::AfterEvent(event_id.clone()),
+                SurrealDependency::AfterDateTime(snooze_until),
+            ]
+        );
 
-async fn set_working_on(item: RecordId, when_started: Datetime, db: &Surreal<Any>) {
-    let record = SurrealWorkingOn::new(item, when_started);
-    let mut updated: Vec<SurrealWorkingOn> = db
-        .upsert(SurrealWorkingOn::TABLE_NAME)
-        .content(record.clone())
-        .await
-        .unwrap();
-    if updated.is_empty() {
-        // Annoyingly SurrealDB's upsert seems to just not work sometimes without giving an explicit
-        // error so we fall back to insert.
-        updated = db
-            .insert(SurrealWorkingOn::TABLE_NAME)
-            .content(record.clone())
+        sender
+            .send(DataLayerCommands::UnsnoozeItem(item_id))
             .await
             .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(
+            surreal_tables.surreal_items.first().unwrap().dependencies,
+            vec![SurrealDependency::AfterEvent(event_id)]
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
     }
-    assert!(!updated.is_empty());
-}
 
-async fn clear_working_on(db: &Surreal<Any>) {
-    let id: RecordId = (SurrealWorkingOn::TABLE_NAME, "working_on").into();
-    // Ignore if it doesn't exist.
-    let _deleted: Option<SurrealWorkingOn> = db.delete(id).await.unwrap();
-}
+    #[tokio::test]
+    async fn changes_to_an_item_are_recorded_in_its_history() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-async fn record_time_spent(new_time_spent: NewTimeSpent, db: &Surreal<Any>) {
-    let mut new_time_spent: SurrealTimeSpent = new_time_spent.into();
-    let saved: SurrealTimeSpent = db
-        .create(SurrealTimeSpent::TABLE_NAME)
-        .content(new_time_spent.clone())
-        .await
-        .unwrap()
-        .expect("Created");
-    new_time_spent.id = saved.id.clone();
-    assert_eq!(new_time_spent, saved);
-}
+        for summary in ["Parent", "Child"] {
+            let new_item = NewItemBuilder::default()
+                .summary(summary)
+                .item_type(SurrealItemType::Action)
+                .build()
+                .expect("Filled out required fields");
+            sender
+                .send(DataLayerCommands::NewItem(new_item))
+                .await
+                .unwrap();
+        }
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let record_id_of = |summary: &str| {
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == summary)
+                .and_then(|x| x.id.clone())
+                .expect("Created above")
+        };
+        let parent = record_id_of("Parent");
+        let child = record_id_of("Child");
+        assert!(surreal_tables.surreal_item_history.is_empty());
 
-pub(crate) async fn finish_item(finish_this: RecordId, when_finished: Datetime, db: &Surreal<Any>) {
-    let updated: SurrealItem = db
-        .update(&finish_this)
-        .patch(PatchOp::replace("/finished", Some(when_finished.clone())))
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(updated.finished, Some(when_finished));
-}
+        sender
+            .send(DataLayerCommands::UpdateSummary(
+                child.clone(),
+                "Renamed child".to_string(),
+            ))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::ParentItemWithExistingItem {
+                child: child.clone(),
+                parent: parent.clone(),
+                higher_importance_than_this: None,
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::UpdateItemLastReviewedDate(
+                child.clone(),
+                Utc::now().into(),
+            ))
+            .await
+            .unwrap();
+        //Setting the same summary again is not a change
+        sender
+            .send(DataLayerCommands::UpdateSummary(
+                child.clone(),
+                "Renamed child".to_string(),
+            ))
+            .await
+            .unwrap();
 
-pub(crate) async fn reactivate_item(reactivate_this: RecordId, db: &Surreal<Any>) {
-    let updated: SurrealItem = db
-        .update(&reactivate_this)
-        .patch(PatchOp::replace("/finished", None::<Datetime>))
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(updated.finished, None);
-}
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let mut child_history = surreal_tables
+            .surreal_item_history
+            .iter()
+            .filter(|x| x.item == child)
+            .collect::<Vec<_>>();
+        child_history.sort_by(|a, b| a.when.cmp(&b.when));
+        let child_changes = child_history
+            .into_iter()
+            .map(|x| x.change.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            child_changes,
+            vec![
+                SurrealItemChange::SummaryChanged {
+                    from: "Child".to_string(),
+                    to: "Renamed child".to_string(),
+                },
+                SurrealItemChange::ParentAdded(parent.clone()),
+                SurrealItemChange::Reviewed,
+            ]
+        );
+        let parent_changes = surreal_tables
+            .surreal_item_history
+            .iter()
+            .filter(|x| x.item == parent)
+            .map(|x| x.change.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(parent_changes, vec![SurrealItemChange::ChildAdded(child)]);
 
-async fn create_new_item(mut new_item: NewItem, db: &Surreal<Any>) -> SurrealItem {
-    for dependency in new_item.dependencies.iter_mut() {
-        match dependency {
-            NewDependency::NewEvent(new_event) => {
-                let created = create_new_event(new_event.clone(), db).await;
-                let created_event_record_id = created.id.expect("In DB");
-                *dependency =
-                    NewDependency::Existing(SurrealDependency::AfterEvent(created_event_record_id));
-            }
-            NewDependency::Existing(_) => {}
-        }
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
     }
-    let mut surreal_item: SurrealItem = SurrealItem::new(new_item, vec![])
-        .expect("We fix up NewDependency::NewEvent above so it will never happen here");
-    let created: SurrealItem = db
-        .create(SurrealItem::TABLE_NAME)
-        .content(surreal_item.clone())
-        .await
-        .unwrap()
-        .expect("Created");
-    surreal_item.id = created.id.clone();
-    assert_eq!(surreal_item, created);
 
-    created
-}
+    #[tokio::test]
+    async fn tags_are_saved_updated_and_recorded_in_history() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
+
+        let new_item = NewItemBuilder::default()
+            .summary("Call the plumber")
+            .item_type(SurrealItemType::Action)
+            .tags(vec!["phone".to_string()])
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::NewItem(new_item))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(1, surreal_tables.surreal_items.len());
+        let item = &surreal_tables.surreal_items[0];
+        assert_eq!(item.tags, Some(vec!["phone".to_string()]));
+        let record_id = item.id.clone().expect("In DB");
+
+        sender
+            .send(DataLayerCommands::UpdateTags(
+                record_id.clone(),
+                vec!["phone".to_string(), "errand".to_string()],
+            ))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::UpdateTags(
+                record_id.clone(),
+                Vec::default(),
+            ))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items[0].tags, None);
+        let mut history = surreal_tables
+            .surreal_item_history
+            .iter()
+            .collect::<Vec<_>>();
+        history.sort_by(|a, b| a.when.cmp(&b.when));
+        let changes = history
+            .into_iter()
+            .map(|x| x.change.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                SurrealItemChange::TagsChanged {
+                    from: vec!["phone".to_string()],
+                    to: vec!["phone".to_string(), "errand".to_string()],
+                },
+                SurrealItemChange::TagsChanged {
+                    from: vec!["phone".to_string(), "errand".to_string()],
+                    to: Vec::default(),
+                },
+            ]
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
 
-async fn cover_with_a_new_item(cover_this: RecordId, cover_with: NewItem, db: &Surreal<Any>) {
-    let cover_with = create_new_item(cover_with, db).await;
+    #[tokio::test]
+    async fn smart_lists_can_be_created_and_deleted() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-    let cover_with_record_id = cover_with.id.expect("In DB");
-    let new_dependency = SurrealDependency::AfterItem(cover_with_record_id);
-    add_dependency(cover_this, new_dependency, db).await;
-}
+        let filter = SurrealSmartListFilter {
+            tags: vec!["errand".to_string()],
+            ..Default::default()
+        };
+        sender
+            .send(DataLayerCommands::NewSmartList {
+                name: "Errands".to_string(),
+                filter: filter.clone(),
+            })
+            .await
+            .unwrap();
 
-async fn cover_item_with_an_existing_item(
-    existing_item_to_be_covered: RecordId,
-    existing_item_that_is_doing_the_covering: RecordId,
-    db: &Surreal<Any>,
-) {
-    let new_dependency = SurrealDependency::AfterItem(existing_item_that_is_doing_the_covering);
-    add_dependency(existing_item_to_be_covered, new_dependency, db).await;
-}
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(1, surreal_tables.surreal_smart_lists.len());
+        let smart_list = &surreal_tables.surreal_smart_lists[0];
+        assert_eq!(smart_list.name, "Errands");
+        assert_eq!(smart_list.filter, filter);
 
-async fn parent_item_with_existing_item(
-    child_record_id: RecordId,
-    parent_record_id: RecordId,
-    higher_importance_than_this: Option<RecordId>,
-    db: &Surreal<Any>,
-) {
-    //TODO: This should be refactored so it happens inside of a transaction and ideally as one query because if the data is modified between the time that the data is read and the time that the data is written back out then the data could be lost. I haven't done this yet because I need to figure out how to do this inside of a SurrealDB query and I haven't done that yet.
-    let mut parent: SurrealItem = db.select(parent_record_id.clone()).await.unwrap().unwrap();
-    parent.smaller_items_in_priority_order = parent
-        .smaller_items_in_priority_order
-        .into_iter()
-        .filter(|x| match x {
-            SurrealOrderedSubItem::SubItem { surreal_item_id } => {
-                surreal_item_id != &child_record_id
-            }
-        })
-        .collect::<Vec<_>>();
-    if let Some(higher_priority_than_this) = higher_importance_than_this {
-        let index_of_higher_priority = parent
-            .smaller_items_in_priority_order
-            .iter()
-            .position(|x| match x {
-                //Note that position() is short-circuiting. If there are multiple matches it could be argued that I should panic or assert but
-                //I am just matching the first one and then I just keep going. Because I am still figuring out the design and this is
-                //more in the vein of hardening work I think this is fine but feel free to revisit this.
-                SurrealOrderedSubItem::SubItem { surreal_item_id } => {
-                    surreal_item_id == &higher_priority_than_this
-                }
+        sender
+            .send(DataLayerCommands::DeleteSmartList(
+                smart_list.id.clone().expect("In DB"),
+            ))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_smart_lists.is_empty());
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn breaks_are_logged_apart_from_time_spent() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
+
+        let when_stopped = Utc::now();
+        let when_started = when_stopped - chrono::Duration::minutes(5);
+        sender
+            .send(DataLayerCommands::RecordBreak {
+                when_started: when_started.into(),
+                when_stopped: when_stopped.into(),
             })
-            .expect("Should already be in the list");
-        parent.smaller_items_in_priority_order.insert(
-            index_of_higher_priority,
-            SurrealOrderedSubItem::SubItem {
-                surreal_item_id: child_record_id,
-            },
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_time_spent_log.is_empty());
+        assert_eq!(1, surreal_tables.surreal_breaks.len());
+        assert_eq!(
+            surreal_tables.surreal_breaks[0].when_started,
+            when_started.into()
         );
-    } else {
-        parent
-            .smaller_items_in_priority_order
-            .push(SurrealOrderedSubItem::SubItem {
-                surreal_item_id: child_record_id,
-            });
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
     }
-    let saved = db
-        .update(&parent_record_id)
-        .content(parent.clone())
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(parent, saved);
-}
 
-async fn parent_item_with_a_new_child(
-    child: NewItem,
-    parent: RecordId,
-    higher_importance_than_this: Option<RecordId>,
-    db: &Surreal<Any>,
-) {
-    let child = create_new_item(child, db).await;
-    parent_item_with_existing_item(
-        child.id.expect("In DB"),
-        parent,
-        higher_importance_than_this,
-        db,
-    )
-    .await
-}
+    #[tokio::test]
+    async fn resume_notes_are_kept_as_a_history() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-async fn parent_new_item_with_an_existing_child_item(
-    child: RecordId,
-    mut parent_new_item: NewItem,
-    db: &Surreal<Any>,
-) {
-    for dependency in parent_new_item.dependencies.iter_mut() {
-        match dependency {
-            NewDependency::NewEvent(new_event) => {
-                let created = create_new_event(new_event.clone(), db).await;
-                let created_event_record_id = created.id.expect("In DB");
-                *dependency =
-                    NewDependency::Existing(SurrealDependency::AfterEvent(created_event_record_id));
-            }
-            NewDependency::Existing(_) => {}
+        let item: RecordId = ("item", "report").into();
+        for doing in ["Outlining", "Writing the summary"] {
+            sender
+                .send(DataLayerCommands::NewResumeNote {
+                    item: item.clone(),
+                    doing: doing.to_string(),
+                    next: "Keep going".to_string(),
+                    location: "~/report.md".to_string(),
+                })
+                .await
+                .unwrap();
         }
-    }
-
-    //TODO: Write a Unit Test for this
-    let smaller_items_in_priority_order = vec![SurrealOrderedSubItem::SubItem {
-        surreal_item_id: child,
-    }];
 
-    let mut parent_surreal_item =
-        SurrealItem::new(parent_new_item, smaller_items_in_priority_order)
-            .expect("We deal with new events above so it will never happen here");
-    let created: SurrealItem = db
-        .create(SurrealItem::TABLE_NAME)
-        .content(parent_surreal_item.clone())
-        .await
-        .unwrap()
-        .expect("Created");
-    parent_surreal_item.id = created.id.clone();
-    assert_eq!(parent_surreal_item, created);
-}
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(2, surreal_tables.surreal_resume_notes.len());
+        assert!(
+            surreal_tables
+                .surreal_resume_notes
+                .iter()
+                .all(|x| x.item == item && x.location == "~/report.md")
+        );
 
-async fn add_dependency(record_id: RecordId, new_dependency: SurrealDependency, db: &Surreal<Any>) {
-    //TODO: This should be refactored so it happens inside of a transaction and ideally as one query because if the data is modified between the time that the data is read and the time that the data is written back out then the data could be lost. I haven't done this yet because I need to figure out how to do this inside of a SurrealDB query and I haven't done that yet.
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
 
-    let mut surreal_item: SurrealItem = db
-        .select(record_id.clone())
-        .await
-        .unwrap()
-        .expect("Record exists");
-    if surreal_item.dependencies.contains(&new_dependency) {
-        //Is already there, nothing to do
-    } else {
-        surreal_item.dependencies.push(new_dependency);
+    #[tokio::test]
+    async fn interruptions_are_logged_with_their_source() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-        let updated: SurrealItem = db
-            .update(&record_id)
-            .content(surreal_item.clone())
+        let interrupted: RecordId = ("item", "report").into();
+        let when_stopped = Utc::now();
+        let when_started = when_stopped - chrono::Duration::minutes(10);
+        sender
+            .send(DataLayerCommands::RecordInterruption {
+                interrupted: Some(interrupted.clone()),
+                source: "Phone call".to_string(),
+                when_started: when_started.into(),
+                when_stopped: when_stopped.into(),
+            })
             .await
-            .unwrap()
             .unwrap();
-        assert_eq!(surreal_item, updated);
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_time_spent_log.is_empty());
+        assert_eq!(1, surreal_tables.surreal_interruptions.len());
+        assert_eq!(
+            surreal_tables.surreal_interruptions[0].interrupted,
+            Some(interrupted)
+        );
+        assert_eq!(surreal_tables.surreal_interruptions[0].source, "Phone call");
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
     }
-}
 
-async fn remove_dependency(record_id: RecordId, to_remove: SurrealDependency, db: &Surreal<Any>) {
-    let mut surreal_item: SurrealItem = db.select(record_id.clone()).await.unwrap().unwrap();
-    surreal_item.dependencies.retain(|x| x != &to_remove);
+    #[tokio::test]
+    async fn planning_the_same_day_again_replaces_the_plan() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-    let update = db
-        .update(&record_id)
-        .content(surreal_item.clone())
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(surreal_item, update);
-}
+        let day = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        let first: RecordId = ("item", "first").into();
+        let second: RecordId = ("item", "second").into();
+        sender
+            .send(DataLayerCommands::SetDailyPlan {
+                day,
+                commitments: vec![first.clone()],
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::SetDailyPlan {
+                day,
+                commitments: vec![second.clone(), first.clone()],
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::SetDailyPlan {
+                day: day.succ_opt().unwrap(),
+                commitments: vec![first.clone()],
+            })
+            .await
+            .unwrap();
 
-async fn add_dependency_new_event(record_id: RecordId, new_event: NewEvent, db: &Surreal<Any>) {
-    let created: SurrealEvent = create_new_event(new_event, db).await;
-    let new_dependency = SurrealDependency::AfterEvent(created.id.expect("In DB"));
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(2, surreal_tables.surreal_daily_plans.len());
+        let plan = surreal_tables
+            .surreal_daily_plans
+            .iter()
+            .find(|x| x.is_for(&day))
+            .unwrap();
+        assert_eq!(plan.commitments, vec![second, first]);
 
-    add_dependency(record_id, new_dependency, db).await
-}
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
 
-async fn create_new_event(new_event: NewEvent, db: &Surreal<Any>) -> SurrealEvent {
-    let event: SurrealEvent = new_event.into();
-    let created: SurrealEvent = db
-        .create(SurrealEvent::TABLE_NAME)
-        .content(event.clone())
-        .await
-        .unwrap()
-        .expect("Created");
-    assert_eq!(created.last_updated, event.last_updated);
-    assert_eq!(created.summary, event.summary);
-    created
-}
+    #[tokio::test]
+    async fn shutting_down_the_same_day_again_replaces_the_summary() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-async fn update_item_summary(item_to_update: RecordId, new_summary: String, db: &Surreal<Any>) {
-    let updated: SurrealItem = db
-        .update(&item_to_update)
-        .patch(PatchOp::replace("/summary", new_summary.clone()))
-        .await
-        .unwrap()
-        .unwrap();
-    assert_eq!(updated.summary, new_summary);
-}
+        let day = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        for notes in ["First try", "Second try"] {
+            sender
+                .send(DataLayerCommands::WriteDailySummary(SurrealDailySummary {
+                    id: Some(SurrealDailySummary::id_for(&day)),
+                    version: 0,
+                    day: SurrealDailySummary::day_key(&day),
+                    written: Utc::now().into(),
+                    finished: vec!["Write report".to_string()],
+                    commitments_made: 2,
+                    commitments_done: 1,
+                    core_work: Duration::from_secs(60 * 60).into(),
+                    non_core_work: Duration::from_secs(30 * 60).into(),
+                    other_work: Duration::default().into(),
+                    notes: notes.to_string(),
+                }))
+                .await
+                .unwrap();
+        }
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(1, surreal_tables.surreal_daily_summaries.len());
+        assert_eq!(
+            surreal_tables.surreal_daily_summaries[0].notes,
+            "Second try"
+        );
 
-#[cfg(test)]
-mod tests {
-    use tokio::sync::mpsc;
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
 
-    use super::*;
+    #[tokio::test]
+    async fn weekly_review_progress_is_saved_over_the_same_review() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-    use crate::{
-        data_storage::surrealdb_layer::surreal_item::SurrealHowMuchIsInMyControl,
-        new_item::NewItemBuilder,
-    };
+        let day = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        let mut weekly_review = SurrealWeeklyReview::new(day, Utc::now().into());
+        sender
+            .send(DataLayerCommands::SaveWeeklyReview(weekly_review.clone()))
+            .await
+            .unwrap();
+        weekly_review.next_step = Some(SurrealWeeklyReviewStep::LookAhead);
+        sender
+            .send(DataLayerCommands::SaveWeeklyReview(weekly_review.clone()))
+            .await
+            .unwrap();
 
-    fn mem_config() -> SurrealDbConnectionConfig {
-        SurrealDbConnectionConfig {
-            endpoint: "mem://".to_string(),
-            namespace: "TaskOnPurpose".to_string(),
-            database: "test".to_string(),
-            auth: None,
-        }
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_weekly_reviews, vec![weekly_review]);
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
     }
 
     #[tokio::test]
-    async fn data_starts_empty() {
+    async fn delete_item_removes_it_from_parents_and_dependencies() {
         let (sender, receiver) = mpsc::channel(1);
         let data_storage_join_handle =
             tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
+        for summary in ["Parent", "Waiting", "Delete me"] {
+            let new_item = NewItemBuilder::default()
+                .summary(summary)
+                .build()
+                .expect("Filled out required fields");
+            sender
+                .send(DataLayerCommands::NewItem(new_item))
+                .await
+                .unwrap();
+        }
         let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let find_id = |summary: &str| {
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == summary)
+                .expect("Item exists")
+                .id
+                .clone()
+                .expect("In DB")
+        };
+        let parent = find_id("Parent");
+        let waiting = find_id("Waiting");
+        let delete_me = find_id("Delete me");
 
-        assert!(surreal_tables.surreal_items.is_empty());
-        assert!(surreal_tables.surreal_time_spent_log.is_empty());
+        sender
+            .send(DataLayerCommands::ParentItemWithExistingItem {
+                child: delete_me.clone(),
+                parent: parent.clone(),
+                higher_importance_than_this: None,
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::AddItemDependency(
+                waiting.clone(),
+                SurrealDependency::AfterItem(delete_me.clone()),
+            ))
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::NewResumeNote {
+                item: delete_me.clone(),
+                doing: "Drafting".to_string(),
+                next: "Finish the draft".to_string(),
+                location: String::default(),
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::RecordInterruption {
+                interrupted: Some(delete_me.clone()),
+                source: "Phone call".to_string(),
+                when_started: Utc::now().into(),
+                when_stopped: Utc::now().into(),
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::SetDailyPlan {
+                day: Utc::now().date_naive(),
+                commitments: vec![delete_me.clone(), waiting.clone()],
+            })
+            .await
+            .unwrap();
+        for (winner, loser) in [
+            (delete_me.clone(), waiting.clone()),
+            (parent.clone(), delete_me.clone()),
+            (parent.clone(), waiting.clone()),
+        ] {
+            sender
+                .send(DataLayerCommands::RecordPriorityComparison {
+                    winner,
+                    loser,
+                    for_mode: None,
+                })
+                .await
+                .unwrap();
+        }
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(
+            surreal_tables
+                .surreal_item_history
+                .iter()
+                .any(|x| x.item == delete_me)
+        );
+
+        assert_eq!(
+            Err(CommandRejection::Conflict(format!(
+                "{} has smaller items, move or delete them first",
+                parent
+            ))),
+            DataLayerCommands::DeleteItem(parent.clone())
+                .check_and_run(&sender)
+                .await
+                .unwrap()
+        );
+
+        sender
+            .send(DataLayerCommands::DeleteItem(delete_me.clone()))
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(2, surreal_tables.surreal_items.len());
+        assert!(
+            !surreal_tables
+                .surreal_items
+                .iter()
+                .any(|x| x.id.as_ref() == Some(&delete_me))
+        );
+        for item in surreal_tables.surreal_items.iter() {
+            assert!(item.smaller_items_in_priority_order.is_empty());
+            assert!(item.dependencies.is_empty());
+        }
+        assert!(
+            surreal_tables
+                .surreal_item_history
+                .iter()
+                .any(|x| x.item == parent
+                    && x.change == SurrealItemChange::ChildRemoved(delete_me.clone()))
+        );
+        assert!(
+            !surreal_tables
+                .surreal_item_history
+                .iter()
+                .any(|x| x.item == delete_me)
+        );
+        assert!(surreal_tables.surreal_resume_notes.is_empty());
+        assert_eq!(1, surreal_tables.surreal_interruptions.len());
+        assert_eq!(None, surreal_tables.surreal_interruptions[0].interrupted);
+        assert_eq!(1, surreal_tables.surreal_daily_plans.len());
+        assert_eq!(
+            vec![waiting.clone()],
+            surreal_tables.surreal_daily_plans[0].commitments
+        );
+        assert_eq!(1, surreal_tables.surreal_priority_comparisons.len());
+        assert_eq!(
+            parent,
+            surreal_tables.surreal_priority_comparisons[0].winner
+        );
+        assert_eq!(
+            waiting,
+            surreal_tables.surreal_priority_comparisons[0].loser
+        );
 
         drop(sender);
         data_storage_join_handle.await.unwrap();
     }
 
     #[tokio::test]
-    async fn add_new_item() {
+    async fn a_command_for_an_item_deleted_just_before_is_refused_and_the_data_layer_keeps_running()
+    {
         let (sender, receiver) = mpsc::channel(1);
         let data_storage_join_handle =
             tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-        let new_item = NewItem::new("New item".into(), Utc::now());
         sender
-            .send(DataLayerCommands::NewItem(new_item))
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Delete me".into(),
+                Utc::now(),
+            )))
             .await
             .unwrap();
-
         let surreal_tables = SurrealTables::new(&sender).await.unwrap();
-
-        assert_eq!(surreal_tables.surreal_items.len(), 1);
+        let delete_me = surreal_tables.surreal_items[0].id.clone().unwrap();
+
+        //Sent at the same time like two front ends would, each is checked right before it is run
+        let (deleted, finished) = tokio::join!(
+            DataLayerCommands::DeleteItem(delete_me.clone()).check_and_run(&sender),
+            DataLayerCommands::FinishItem {
+                item: delete_me.clone(),
+                when_finished: Utc::now().into(),
+            }
+            .check_and_run(&sender),
+        );
+        assert_eq!(Ok(()), deleted.unwrap());
         assert_eq!(
-            SurrealItemType::Undeclared,
-            surreal_tables.surreal_items.first().unwrap().item_type
+            Err(CommandRejection::NotFound(format!(
+                "No item with the id {}",
+                delete_me
+            ))),
+            finished.unwrap()
         );
 
+        sender
+            .send(DataLayerCommands::FinishItem {
+                item: delete_me,
+                when_finished: Utc::now().into(),
+            })
+            .await
+            .unwrap();
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Still running".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(1, surreal_tables.surreal_items.len());
+        assert_eq!("Still running", surreal_tables.surreal_items[0].summary);
+
         drop(sender);
         data_storage_join_handle.await.unwrap();
     }
 
     #[tokio::test]
-    async fn finish_item() {
+    async fn waiting_for_snoozes_until_follow_up_and_escalates() {
         let (sender, receiver) = mpsc::channel(1);
         let data_storage_join_handle =
             tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
 
-        let new_next_step = NewItemBuilder::default()
-            .summary("New next step")
+        let new_item = NewItemBuilder::default()
+            .summary("Get the quote back")
             .item_type(SurrealItemType::Action)
             .build()
             .expect("Filled out required fields");
         sender
-            .send(DataLayerCommands::NewItem(new_next_step))
+            .send(DataLayerCommands::NewItem(new_item))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let item = surreal_tables.surreal_items[0].id.clone().expect("In DB");
+
+        let requested: Datetime = Utc::now().into();
+        let follow_up: Datetime = (Utc::now() + chrono::TimeDelta::days(3)).into();
+        let person = NewItemBuilder::default()
+            .summary("Bob the builder")
+            .item_type(SurrealItemType::PersonOrGroup)
+            .build()
+            .expect("Filled out required fields");
+        sender
+            .send(DataLayerCommands::WaitingForNewPerson {
+                item: item.clone(),
+                person,
+                requested: requested.clone(),
+                follow_up: follow_up.clone(),
+            })
             .await
             .unwrap();
 
         let surreal_tables = SurrealTables::new(&sender).await.unwrap();
-        let now = Utc::now();
-        let items = surreal_tables.make_items(&now);
+        assert_eq!(2, surreal_tables.surreal_items.len());
+        let person = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.item_type == SurrealItemType::PersonOrGroup)
+            .expect("Person was created");
+        let waiting = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.id.as_ref() == Some(&item))
+            .unwrap();
+        assert_eq!(waiting.responsibility, Responsibility::WaitingFor);
+        assert_eq!(
+            waiting.waiting_for,
+            Some(SurrealWaitingFor {
+                responsibility_before: Some(Responsibility::ProactiveActionToTake),
+                urgency_plan_before: None,
+                ..SurrealWaitingFor::new(
+                    person.id.clone().expect("In DB"),
+                    requested,
+                    follow_up.clone()
+                )
+            })
+        );
+        assert_eq!(
+            waiting.dependencies,
+            vec![SurrealDependency::AfterDateTime(follow_up.clone())]
+        );
+        assert_eq!(
+            waiting.urgency_plan,
+            Some(SurrealUrgencyPlan::WillEscalate {
+                initial: SurrealUrgency::InTheModeByImportance,
+                triggers: vec![SurrealTrigger::WallClockDateTime(follow_up)],
+                later: SurrealUrgency::InTheModeDefinitelyUrgent,
+            })
+        );
 
-        assert_eq!(items.len(), 1);
-        let next_step_item = items.iter().next().map(|(_, v)| v).unwrap();
-        assert!(!next_step_item.is_finished());
+        sender
+            .send(DataLayerCommands::UpdateWaitingFor(item.clone(), None))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let waiting = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.id.as_ref() == Some(&item))
+            .unwrap();
+        assert_eq!(
+            waiting.responsibility,
+            Responsibility::ProactiveActionToTake
+        );
+        assert_eq!(waiting.waiting_for, None);
+        assert!(waiting.dependencies.is_empty());
+        assert_eq!(waiting.urgency_plan, None);
+        let waiting_for_changes = surreal_tables
+            .surreal_item_history
+            .iter()
+            .filter(|x| matches!(x.change, SurrealItemChange::WaitingForChanged { .. }))
+            .count();
+        assert_eq!(waiting_for_changes, 2);
 
-        let when_finished = Utc::now();
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn no_longer_waiting_puts_back_what_was_there_before() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
+
+        let urgency_plan = SurrealUrgencyPlan::StaysTheSame(SurrealUrgency::MoreUrgentThanMode);
+        for (summary, item_type) in [
+            ("Get the quote back", SurrealItemType::Action),
+            ("Bob the builder", SurrealItemType::PersonOrGroup),
+        ] {
+            let new_item = NewItemBuilder::default()
+                .summary(summary)
+                .item_type(item_type)
+                .responsibility(Responsibility::ReactiveBeAvailableToAct)
+                .urgency_plan(Some(urgency_plan.clone()))
+                .build()
+                .expect("Filled out required fields");
+            sender
+                .send(DataLayerCommands::NewItem(new_item))
+                .await
+                .unwrap();
+        }
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let find = |summary: &str| {
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.summary == summary)
+                .unwrap()
+                .id
+                .clone()
+                .expect("In DB")
+        };
+        let item = find("Get the quote back");
+        let person = find("Bob the builder");
+
+        let snoozed_until: Datetime = (Utc::now() + chrono::TimeDelta::days(30)).into();
         sender
-            .send(DataLayerCommands::FinishItem {
-                item: next_step_item.get_surreal_record_id().clone(),
-                when_finished: when_finished.into(),
+            .send(DataLayerCommands::SnoozeItem {
+                item: item.clone(),
+                until: snoozed_until.clone(),
             })
             .await
             .unwrap();
+        let follow_up: Datetime = (Utc::now() + chrono::TimeDelta::days(3)).into();
+        let waiting_for = SurrealWaitingFor::new(person, Utc::now().into(), follow_up);
+        sender
+            .send(DataLayerCommands::UpdateWaitingFor(
+                item.clone(),
+                Some(waiting_for.clone()),
+            ))
+            .await
+            .unwrap();
+        let later_follow_up: Datetime = (Utc::now() + chrono::TimeDelta::days(7)).into();
+        sender
+            .send(DataLayerCommands::UpdateWaitingFor(
+                item.clone(),
+                Some(SurrealWaitingFor {
+                    follow_up: later_follow_up.clone(),
+                    ..waiting_for
+                }),
+            ))
+            .await
+            .unwrap();
 
         let surreal_tables = SurrealTables::new(&sender).await.unwrap();
-        let now = Utc::now();
-        let items = surreal_tables.make_items(&now);
+        let waiting = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.id.as_ref() == Some(&item))
+            .unwrap();
+        assert_eq!(Responsibility::WaitingFor, waiting.responsibility);
+        assert_eq!(
+            vec![
+                SurrealDependency::AfterDateTime(snoozed_until.clone()),
+                SurrealDependency::AfterDateTime(later_follow_up.clone()),
+            ],
+            waiting.dependencies
+        );
+        assert_eq!(
+            Some(
+                waiting
+                    .waiting_for
+                    .as_ref()
+                    .unwrap()
+                    .follow_up_urgency_plan()
+            ),
+            waiting.urgency_plan
+        );
 
-        assert_eq!(items.len(), 1);
-        let next_step_item = items.iter().next().map(|(_, v)| v).unwrap();
-        assert!(next_step_item.is_finished());
+        for _ in 0..2 {
+            sender
+                .send(DataLayerCommands::UpdateWaitingFor(item.clone(), None))
+                .await
+                .unwrap();
+        }
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let waiting = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.id.as_ref() == Some(&item))
+            .unwrap();
+        assert_eq!(None, waiting.waiting_for);
+        assert_eq!(
+            Responsibility::ReactiveBeAvailableToAct,
+            waiting.responsibility
+        );
+        assert_eq!(Some(urgency_plan), waiting.urgency_plan);
+        assert_eq!(
+            vec![SurrealDependency::AfterDateTime(snoozed_until)],
+            waiting.dependencies
+        );
+        let waiting_for_changes = surreal_tables
+            .surreal_item_history
+            .iter()
+            .filter(|x| matches!(x.change, SurrealItemChange::WaitingForChanged { .. }))
+            .count();
+        assert_eq!(3, waiting_for_changes);
 
         drop(sender);
         data_storage_join_handle.await.unwrap();
@@ -1904,6 +4324,75 @@ mod tests {
         data_storage_join_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn parent_item_with_new_child_trees_keeps_the_order() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle =
+            tokio::spawn(async move { data_storage_start_and_run(receiver, mem_config()).await });
+
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Project".into(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let project = surreal_tables.surreal_items[0].id.clone().expect("In DB");
+
+        let tree = |summary: &str, smaller_items: Vec<NewItemTree>| NewItemTree {
+            item: NewItem::new(summary.into(), Utc::now()),
+            smaller_items,
+        };
+        sender
+            .send(DataLayerCommands::ParentItemWithNewChildTrees {
+                children: vec![
+                    tree(
+                        "First",
+                        vec![tree("First a", vec![]), tree("First b", vec![])],
+                    ),
+                    tree("Second", vec![]),
+                ],
+                parent: project.clone(),
+            })
+            .await
+            .unwrap();
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(5, surreal_tables.surreal_items.len());
+        let find = |id: &RecordId| {
+            surreal_tables
+                .surreal_items
+                .iter()
+                .find(|x| x.id.as_ref() == Some(id))
+                .unwrap()
+        };
+        let smaller_summaries = |id: &RecordId| {
+            find(id)
+                .smaller_items_in_priority_order
+                .iter()
+                .map(|x| match x {
+                    SurrealOrderedSubItem::SubItem { surreal_item_id } => {
+                        find(surreal_item_id).summary.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(smaller_summaries(&project), vec!["First", "Second"]);
+        let first = surreal_tables
+            .surreal_items
+            .iter()
+            .find(|x| x.summary == "First")
+            .unwrap();
+        assert_eq!(
+            smaller_summaries(first.id.as_ref().unwrap()),
+            vec!["First a", "First b"]
+        );
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn parent_item_with_an_existing_item_that_has_no_children() {
         let (sender, receiver) = mpsc::channel(1);
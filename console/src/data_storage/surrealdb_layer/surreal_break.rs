@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::{RecordId, sql::Datetime};
+
+/// Time spent resting, kept apart from the time spent log so it never counts as work towards an
+/// item but reflection can still show work next to rest
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealBreak {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    pub(crate) when_started: Datetime,
+    pub(crate) when_stopped: Datetime,
+}
+
+impl From<SurrealBreak> for Option<RecordId> {
+    fn from(value: SurrealBreak) -> Self {
+        value.id
+    }
+}
+
+impl SurrealBreak {
+    pub(crate) const TABLE_NAME: &'static str = "break_log";
+
+    pub(crate) fn new(when_started: Datetime, when_stopped: Datetime) -> Self {
+        SurrealBreak {
+            id: None,
+            version: 0,
+            when_started,
+            when_stopped,
+        }
+    }
+}
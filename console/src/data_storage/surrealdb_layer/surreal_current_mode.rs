@@ -17,6 +17,7 @@ impl From<SurrealCurrentMode> for Option<RecordId> {
 impl SurrealCurrentMode {
     pub(crate) const TABLE_NAME: &'static str = "current_modes";
 }
+#[derive(Serialize, Deserialize)]
 pub(crate) struct NewCurrentMode {
     current_mode: Option<RecordId>,
 }
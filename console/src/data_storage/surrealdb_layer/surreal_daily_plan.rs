@@ -0,0 +1,45 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use surrealdb::{RecordId, sql::Datetime};
+
+/// What was committed to for one day, made during daily planning. There is at most one per day,
+/// planning the same day again replaces it.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealDailyPlan {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    /// The local date, for example "2024-05-31"
+    pub(crate) day: String,
+    pub(crate) planned: Datetime,
+    /// The items committed to, in the order they were picked
+    pub(crate) commitments: Vec<RecordId>,
+}
+
+impl From<SurrealDailyPlan> for Option<RecordId> {
+    fn from(value: SurrealDailyPlan) -> Self {
+        value.id
+    }
+}
+
+impl SurrealDailyPlan {
+    pub(crate) const TABLE_NAME: &'static str = "daily_plans";
+
+    pub(crate) fn new(day: NaiveDate, planned: Datetime, commitments: Vec<RecordId>) -> Self {
+        let day = Self::day_key(&day);
+        SurrealDailyPlan {
+            id: Some((SurrealDailyPlan::TABLE_NAME, day.as_str()).into()),
+            version: 0,
+            day,
+            planned,
+            commitments,
+        }
+    }
+
+    pub(crate) fn day_key(day: &NaiveDate) -> String {
+        day.format("%Y-%m-%d").to_string()
+    }
+
+    pub(crate) fn is_for(&self, day: &NaiveDate) -> bool {
+        self.day == Self::day_key(day)
+    }
+}
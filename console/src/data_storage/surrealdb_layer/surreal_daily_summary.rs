@@ -0,0 +1,44 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    RecordId,
+    sql::{Datetime, Duration},
+};
+
+/// Written by the end of day shutdown so the day can be looked back on later from Reflection. The
+/// summaries of finished items are copied in so the record reads the same after items are renamed or
+/// deleted. Shutting down the same day again replaces it.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealDailySummary {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    /// The local date, for example "2024-05-31"
+    pub(crate) day: String,
+    pub(crate) written: Datetime,
+    pub(crate) finished: Vec<String>,
+    pub(crate) commitments_made: u32,
+    pub(crate) commitments_done: u32,
+    pub(crate) core_work: Duration,
+    pub(crate) non_core_work: Duration,
+    /// Work towards items under a motivation that is neither core nor non-core
+    pub(crate) other_work: Duration,
+    pub(crate) notes: String,
+}
+
+impl From<SurrealDailySummary> for Option<RecordId> {
+    fn from(value: SurrealDailySummary) -> Self {
+        value.id
+    }
+}
+
+impl SurrealDailySummary {
+    pub(crate) const TABLE_NAME: &'static str = "daily_summaries";
+
+    pub(crate) fn id_for(day: &NaiveDate) -> RecordId {
+        (Self::TABLE_NAME, Self::day_key(day)).into()
+    }
+
+    pub(crate) fn day_key(day: &NaiveDate) -> String {
+        day.format("%Y-%m-%d").to_string()
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::{RecordId, sql::Datetime};
+
+/// Something that pulled attention away from the item being worked on. The time spent log is split
+/// around it so the time lost is not counted as work.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealInterruption {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    /// The item that was being worked on
+    pub(crate) interrupted: Option<RecordId>,
+    /// Who or what interrupted, for example "Phone call" or "Slack"
+    pub(crate) source: String,
+    pub(crate) when_started: Datetime,
+    pub(crate) when_stopped: Datetime,
+}
+
+impl From<SurrealInterruption> for Option<RecordId> {
+    fn from(value: SurrealInterruption) -> Self {
+        value.id
+    }
+}
+
+impl SurrealInterruption {
+    pub(crate) const TABLE_NAME: &'static str = "interruptions";
+
+    pub(crate) fn new(
+        interrupted: Option<RecordId>,
+        source: String,
+        when_started: Datetime,
+        when_stopped: Datetime,
+    ) -> Self {
+        SurrealInterruption {
+            id: None,
+            version: 0,
+            interrupted,
+            source,
+            when_started,
+            when_stopped,
+        }
+    }
+}
@@ -69,6 +69,15 @@ pub(crate) struct SurrealItem {
 
     #[cfg_attr(test, builder(default))]
     pub(crate) urgency_plan: Option<SurrealUrgencyPlan>,
+
+    /// Free-form tags like "phone" or "errand", stored lowercase and without the leading '#'
+    #[cfg_attr(test, builder(default))]
+    pub(crate) tags: Option<Vec<String>>,
+
+    /// Who a delegated item is waiting on and when to chase them, set along with
+    /// `Responsibility::WaitingFor`
+    #[cfg_attr(test, builder(default))]
+    pub(crate) waiting_for: Option<SurrealWaitingFor>,
 }
 
 impl From<SurrealItem> for Option<RecordId> {
@@ -124,6 +133,12 @@ impl SurrealItem {
             last_reviewed,
             review_frequency: new_item.review_frequency,
             review_guidance: new_item.review_guidance,
+            tags: if new_item.tags.is_empty() {
+                None
+            } else {
+                Some(new_item.tags)
+            },
+            waiting_for: None,
         })
     }
 
@@ -167,10 +182,47 @@ pub(crate) enum Responsibility {
     #[default]
     ProactiveActionToTake,
     ReactiveBeAvailableToAct,
-    WaitingFor, //TODO: This should not exist it should just be a TrackingToBeAwareOf that could be a Question or have some kind of automated way to track and watch and know
+    /// Someone else is doing this, `SurrealItem::waiting_for` says who and when to follow up
+    WaitingFor,
     TrackingToBeAwareOf,
 }
 
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealWaitingFor {
+    /// The `PersonOrGroup` item this is waiting on
+    pub(crate) person: RecordId,
+    pub(crate) requested: Datetime,
+    pub(crate) follow_up: Datetime,
+    /// Filled in by the data storage layer when the item starts waiting and put back when it stops,
+    /// None for waits started before this was kept
+    #[serde(default)]
+    pub(crate) responsibility_before: Option<Responsibility>,
+    /// See `responsibility_before`
+    #[serde(default)]
+    pub(crate) urgency_plan_before: Option<SurrealUrgencyPlan>,
+}
+
+impl SurrealWaitingFor {
+    pub(crate) fn new(person: RecordId, requested: Datetime, follow_up: Datetime) -> Self {
+        Self {
+            person,
+            requested,
+            follow_up,
+            responsibility_before: None,
+            urgency_plan_before: None,
+        }
+    }
+
+    /// Escalates once it is time to follow up so following up shows up in the Do Now list
+    pub(crate) fn follow_up_urgency_plan(&self) -> SurrealUrgencyPlan {
+        SurrealUrgencyPlan::WillEscalate {
+            initial: SurrealUrgency::InTheModeByImportance,
+            triggers: vec![SurrealTrigger::WallClockDateTime(self.follow_up.clone())],
+            later: SurrealUrgency::InTheModeDefinitelyUrgent,
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
 pub(crate) enum SurrealDependency {
     AfterDateTime(Datetime),
@@ -393,6 +445,8 @@ pub(crate) enum NotesLocation {
     None,
     OneNoteLink(String),
     WebLink(String),
+    /// Notes that are kept with the item rather than somewhere else, written in markdown
+    Markdown(String),
 }
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
@@ -551,6 +605,8 @@ impl From<SurrealItemOldVersion> for SurrealItem {
             review_guidance: value.review_guidance,
             last_reviewed,
             review_frequency,
+            tags: None,
+            waiting_for: None,
         }
     }
 }
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::{RecordId, sql::Datetime};
+
+use super::surreal_item::{
+    Responsibility, SurrealDependency, SurrealItemType, SurrealUrgencyPlan, SurrealWaitingFor,
+};
+
+/// One change made to an item. Rows in this table are never updated, so together they are the
+/// history of how an item got to be the way it is now. They are only removed along with the item
+/// when it is deleted.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealItemHistory {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    pub(crate) item: RecordId,
+    pub(crate) when: Datetime,
+    pub(crate) change: SurrealItemChange,
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) enum SurrealItemChange {
+    SummaryChanged {
+        from: String,
+        to: String,
+    },
+    ItemTypeChanged {
+        from: SurrealItemType,
+        to: SurrealItemType,
+    },
+    ResponsibilityChanged {
+        from: Responsibility,
+        to: Responsibility,
+    },
+    ParentAdded(RecordId),
+    ParentRemoved(RecordId),
+    ChildAdded(RecordId),
+    ChildRemoved(RecordId),
+    ChildReordered(RecordId),
+    DependencyAdded(SurrealDependency),
+    DependencyRemoved(SurrealDependency),
+    UrgencyPlanChanged {
+        from: Option<SurrealUrgencyPlan>,
+        to: Option<SurrealUrgencyPlan>,
+    },
+    Reviewed,
+    TagsChanged {
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+    WaitingForChanged {
+        from: Option<SurrealWaitingFor>,
+        to: Option<SurrealWaitingFor>,
+    },
+}
+
+impl From<SurrealItemHistory> for Option<RecordId> {
+    fn from(value: SurrealItemHistory) -> Self {
+        value.id
+    }
+}
+
+impl SurrealItemHistory {
+    pub(crate) const TABLE_NAME: &'static str = "item_history";
+
+    pub(crate) fn new(item: RecordId, when: Datetime, change: SurrealItemChange) -> Self {
+        SurrealItemHistory {
+            id: None,
+            version: 0,
+            item,
+            when,
+            change,
+        }
+    }
+}
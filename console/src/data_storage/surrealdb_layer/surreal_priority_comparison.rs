@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::{RecordId, sql::Datetime};
+
+/// A single answer from the priority wizard that one item is more important than another. Unlike
+/// an in the moment priority these are never cleared so they build up a history that can be used
+/// to learn a ranking of the items. They only go when the winner or the loser is deleted.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealPriorityComparison {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    pub(crate) winner: RecordId,
+    pub(crate) loser: RecordId,
+    pub(crate) for_mode: Option<RecordId>,
+    pub(crate) created: Datetime,
+}
+
+impl From<SurrealPriorityComparison> for Option<RecordId> {
+    fn from(value: SurrealPriorityComparison) -> Self {
+        value.id
+    }
+}
+
+impl SurrealPriorityComparison {
+    pub(crate) const TABLE_NAME: &'static str = "priority_comparisons";
+
+    pub(crate) fn new(
+        winner: RecordId,
+        loser: RecordId,
+        for_mode: Option<RecordId>,
+        created: Datetime,
+    ) -> Self {
+        SurrealPriorityComparison {
+            id: None,
+            version: 0,
+            winner,
+            loser,
+            for_mode,
+            created,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::{RecordId, sql::Datetime};
+
+/// Written when stopping work on an item so it is quick to pick back up. Notes are only ever added,
+/// the newest one for an item is where it was left off and the older ones are its history.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealResumeNote {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    pub(crate) item: RecordId,
+    pub(crate) created: Datetime,
+    /// What I was doing
+    pub(crate) doing: String,
+    /// What's next
+    pub(crate) next: String,
+    /// Where the files or links are
+    pub(crate) location: String,
+}
+
+impl From<SurrealResumeNote> for Option<RecordId> {
+    fn from(value: SurrealResumeNote) -> Self {
+        value.id
+    }
+}
+
+impl SurrealResumeNote {
+    pub(crate) const TABLE_NAME: &'static str = "resume_notes";
+
+    pub(crate) fn new(
+        item: RecordId,
+        created: Datetime,
+        doing: String,
+        next: String,
+        location: String,
+    ) -> Self {
+        SurrealResumeNote {
+            id: None,
+            version: 0,
+            item,
+            created,
+            doing,
+            next,
+            location,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use super::surreal_item::{SurrealItemType, SurrealUrgency};
+
+/// A saved, named filter over the active items that shows up in the search menu.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealSmartList {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    pub(crate) name: String,
+    pub(crate) filter: SurrealSmartListFilter,
+}
+
+/// Every part of the filter that is set must match for an item to be in the smart list. An empty
+/// list or None means that part of the filter is not used.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug, Default)]
+pub(crate) struct SurrealSmartListFilter {
+    /// The item must have all of these tags
+    pub(crate) tags: Vec<String>,
+    /// The item must be one of these types
+    pub(crate) item_types: Vec<SurrealSmartListItemType>,
+    pub(crate) urgency: Option<SurrealSmartListUrgency>,
+    pub(crate) readiness: Option<SurrealSmartListReadiness>,
+    /// The item must be a smaller item of this motivation, either directly or through other items
+    pub(crate) under_motivation: Option<RecordId>,
+    /// An item query like `type:action urgency>=definitely`, see `systems::item_query`
+    pub(crate) query: Option<String>,
+}
+
+/// SurrealItemType without the extra details so a filter can match, for example, any kind of goal
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub(crate) enum SurrealSmartListItemType {
+    Undeclared,
+    Action,
+    Goal,
+    IdeaOrThought,
+    Motivation,
+    PersonOrGroup,
+}
+
+impl From<&SurrealItemType> for SurrealSmartListItemType {
+    fn from(value: &SurrealItemType) -> Self {
+        match value {
+            SurrealItemType::Undeclared => SurrealSmartListItemType::Undeclared,
+            SurrealItemType::Action => SurrealSmartListItemType::Action,
+            SurrealItemType::Goal(_) => SurrealSmartListItemType::Goal,
+            SurrealItemType::IdeaOrThought => SurrealSmartListItemType::IdeaOrThought,
+            SurrealItemType::Motivation(_) => SurrealSmartListItemType::Motivation,
+            SurrealItemType::PersonOrGroup => SurrealSmartListItemType::PersonOrGroup,
+        }
+    }
+}
+
+/// SurrealUrgency without the schedule so a filter can match any scheduled item
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub(crate) enum SurrealSmartListUrgency {
+    MoreUrgentThanAnythingIncludingScheduled,
+    ScheduledAnyMode,
+    MoreUrgentThanMode,
+    InTheModeScheduled,
+    InTheModeDefinitelyUrgent,
+    InTheModeMaybeUrgent,
+    InTheModeByImportance,
+}
+
+impl From<&SurrealUrgency> for SurrealSmartListUrgency {
+    fn from(value: &SurrealUrgency) -> Self {
+        match value {
+            SurrealUrgency::MoreUrgentThanAnythingIncludingScheduled => {
+                SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled
+            }
+            SurrealUrgency::ScheduledAnyMode(_) => SurrealSmartListUrgency::ScheduledAnyMode,
+            SurrealUrgency::MoreUrgentThanMode => SurrealSmartListUrgency::MoreUrgentThanMode,
+            SurrealUrgency::InTheModeScheduled(_) => SurrealSmartListUrgency::InTheModeScheduled,
+            SurrealUrgency::InTheModeDefinitelyUrgent => {
+                SurrealSmartListUrgency::InTheModeDefinitelyUrgent
+            }
+            SurrealUrgency::InTheModeMaybeUrgent => SurrealSmartListUrgency::InTheModeMaybeUrgent,
+            SurrealUrgency::InTheModeByImportance => SurrealSmartListUrgency::InTheModeByImportance,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub(crate) enum SurrealSmartListReadiness {
+    Ready,
+    NotReady,
+}
+
+impl From<SurrealSmartList> for Option<RecordId> {
+    fn from(value: SurrealSmartList) -> Self {
+        value.id
+    }
+}
+
+impl SurrealSmartList {
+    pub(crate) const TABLE_NAME: &'static str = "smart_list";
+
+    pub(crate) fn new(name: String, filter: SurrealSmartListFilter) -> Self {
+        SurrealSmartList {
+            id: None,
+            version: 0,
+            name,
+            filter,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use ahash::HashMap;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use surrealdb::RecordId;
 use tokio::sync::mpsc::Sender;
 
@@ -9,13 +10,18 @@ use derive_builder::Builder;
 use crate::base_data::{event::Event, item::Item, mode::Mode, time_spent::TimeSpent};
 
 use super::{
-    data_layer_commands::DataLayerCommands, surreal_current_mode::SurrealCurrentMode,
-    surreal_event::SurrealEvent, surreal_in_the_moment_priority::SurrealInTheMomentPriority,
-    surreal_item::SurrealItem, surreal_mode::SurrealMode, surreal_time_spent::SurrealTimeSpent,
-    surreal_working_on::SurrealWorkingOn,
+    data_layer_commands::DataLayerCommands, surreal_break::SurrealBreak,
+    surreal_current_mode::SurrealCurrentMode, surreal_daily_plan::SurrealDailyPlan,
+    surreal_daily_summary::SurrealDailySummary, surreal_event::SurrealEvent,
+    surreal_in_the_moment_priority::SurrealInTheMomentPriority,
+    surreal_interruption::SurrealInterruption, surreal_item::SurrealItem,
+    surreal_item_history::SurrealItemHistory, surreal_mode::SurrealMode,
+    surreal_priority_comparison::SurrealPriorityComparison, surreal_resume_note::SurrealResumeNote,
+    surreal_smart_list::SurrealSmartList, surreal_time_spent::SurrealTimeSpent,
+    surreal_weekly_review::SurrealWeeklyReview, surreal_working_on::SurrealWorkingOn,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(test, derive(Builder), builder(setter(into)))]
 pub(crate) struct SurrealTables {
     #[cfg_attr(test, builder(default))]
@@ -38,6 +44,33 @@ pub(crate) struct SurrealTables {
 
     #[cfg_attr(test, builder(default))]
     pub(crate) surreal_working_on: Vec<SurrealWorkingOn>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_priority_comparisons: Vec<SurrealPriorityComparison>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_item_history: Vec<SurrealItemHistory>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_smart_lists: Vec<SurrealSmartList>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_breaks: Vec<SurrealBreak>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_interruptions: Vec<SurrealInterruption>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_daily_plans: Vec<SurrealDailyPlan>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_daily_summaries: Vec<SurrealDailySummary>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_weekly_reviews: Vec<SurrealWeeklyReview>,
+
+    #[cfg_attr(test, builder(default))]
+    pub(crate) surreal_resume_notes: Vec<SurrealResumeNote>,
 }
 
 impl SurrealTables {
@@ -83,4 +116,40 @@ impl SurrealTables {
     pub(crate) fn get_surreal_working_on(&self) -> Option<&SurrealWorkingOn> {
         self.surreal_working_on.first()
     }
+
+    pub(crate) fn get_surreal_priority_comparisons(&self) -> &[SurrealPriorityComparison] {
+        &self.surreal_priority_comparisons
+    }
+
+    pub(crate) fn get_surreal_item_history(&self) -> &[SurrealItemHistory] {
+        &self.surreal_item_history
+    }
+
+    pub(crate) fn get_surreal_smart_lists(&self) -> &[SurrealSmartList] {
+        &self.surreal_smart_lists
+    }
+
+    pub(crate) fn get_surreal_breaks(&self) -> &[SurrealBreak] {
+        &self.surreal_breaks
+    }
+
+    pub(crate) fn get_surreal_interruptions(&self) -> &[SurrealInterruption] {
+        &self.surreal_interruptions
+    }
+
+    pub(crate) fn get_surreal_daily_plans(&self) -> &[SurrealDailyPlan] {
+        &self.surreal_daily_plans
+    }
+
+    pub(crate) fn get_surreal_daily_summaries(&self) -> &[SurrealDailySummary] {
+        &self.surreal_daily_summaries
+    }
+
+    pub(crate) fn get_surreal_weekly_reviews(&self) -> &[SurrealWeeklyReview] {
+        &self.surreal_weekly_reviews
+    }
+
+    pub(crate) fn get_surreal_resume_notes(&self) -> &[SurrealResumeNote] {
+        &self.surreal_resume_notes
+    }
 }
@@ -0,0 +1,79 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use surrealdb::{RecordId, sql::Datetime};
+
+/// The steps of a weekly review in the order they are done
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub(crate) enum SurrealWeeklyReviewStep {
+    EmptyTheInbox,
+    ReviewsDue,
+    WaitingForAndEvents,
+    LookAhead,
+    TimeBalance,
+    Celebrate,
+}
+
+impl SurrealWeeklyReviewStep {
+    pub(crate) const ALL: [SurrealWeeklyReviewStep; 6] = [
+        SurrealWeeklyReviewStep::EmptyTheInbox,
+        SurrealWeeklyReviewStep::ReviewsDue,
+        SurrealWeeklyReviewStep::WaitingForAndEvents,
+        SurrealWeeklyReviewStep::LookAhead,
+        SurrealWeeklyReviewStep::TimeBalance,
+        SurrealWeeklyReviewStep::Celebrate,
+    ];
+
+    /// Counting from 1
+    pub(crate) fn number(&self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|x| x == self)
+            .expect("Every step is in ALL")
+            + 1
+    }
+
+    /// None after the last step
+    pub(crate) fn next(&self) -> Option<SurrealWeeklyReviewStep> {
+        Self::ALL.get(self.number()).copied()
+    }
+}
+
+/// A weekly review and how far it got, saved after each step so a review that was stopped part way
+/// through can be resumed. There is at most one per day that a review was started on.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct SurrealWeeklyReview {
+    pub(crate) id: Option<RecordId>,
+    pub(crate) version: u32,
+    /// The local date the review was started, for example "2024-05-31"
+    pub(crate) day: String,
+    pub(crate) started: Datetime,
+    /// None once every step is done
+    pub(crate) next_step: Option<SurrealWeeklyReviewStep>,
+    pub(crate) finished: Option<Datetime>,
+}
+
+impl From<SurrealWeeklyReview> for Option<RecordId> {
+    fn from(value: SurrealWeeklyReview) -> Self {
+        value.id
+    }
+}
+
+impl SurrealWeeklyReview {
+    pub(crate) const TABLE_NAME: &'static str = "weekly_reviews";
+
+    pub(crate) fn new(day: NaiveDate, started: Datetime) -> Self {
+        let day = day.format("%Y-%m-%d").to_string();
+        SurrealWeeklyReview {
+            id: Some((SurrealWeeklyReview::TABLE_NAME, day.as_str()).into()),
+            version: 0,
+            day,
+            started,
+            next_step: SurrealWeeklyReviewStep::ALL.first().copied(),
+            finished: None,
+        }
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.next_step.is_none()
+    }
+}
@@ -17,6 +17,22 @@ pub(crate) struct DisplayActionWithItemStatus<'s> {
 impl Display for DisplayActionWithItemStatus<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self.item {
+            ActionWithItemStatus::MakeProgress(item_status)
+                if item_status.get_item().is_follow_up_due() =>
+            {
+                let display = DisplayItemStatus::new(item_status, self.filter, self.display_format);
+                match item_status.get_waiting_on() {
+                    Some(person) => {
+                        write!(
+                            f,
+                            "[📞 Follow up with {}] {}",
+                            person.get_summary(),
+                            display
+                        )
+                    }
+                    None => write!(f, "[📞 Follow up] {}", display),
+                }
+            }
             ActionWithItemStatus::MakeProgress(item_status) => {
                 let display = DisplayItemStatus::new(item_status, self.filter, self.display_format);
                 write!(f, "[🏃 Do Now] {}", display)
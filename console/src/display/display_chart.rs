@@ -0,0 +1,363 @@
+use std::{
+    f32::consts::TAU,
+    fmt::{Display, Formatter},
+};
+
+use image::{Rgba, RgbaImage};
+
+use super::display_sixel::{print_sixel, terminal_supports_sixel};
+
+/// How many characters wide the longest bar is in the text version of a chart
+const TEXT_WIDTH: usize = 40;
+
+const TRANSPARENT: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+const AXIS: Rgba<u8> = Rgba([128, 128, 128, 255]);
+
+/// One kind of thing that is measured in a chart, for example core work. The symbol draws it in the
+/// text version and the color in the image version.
+pub(crate) struct ChartSeries {
+    pub(crate) name: &'static str,
+    pub(crate) symbol: char,
+    pub(crate) color: Rgba<u8>,
+}
+
+/// A chart that can be drawn as an image or, for terminals without sixel, as text
+pub(crate) trait Chart: Display {
+    fn get_title(&self) -> &str;
+
+    fn get_legend(&self) -> String;
+
+    fn to_image(&self) -> RgbaImage;
+}
+
+/// Prints the chart as a sixel image when the terminal looks like it supports sixel and as Unicode
+/// blocks otherwise, or if drawing the image fails.
+pub(crate) fn print_chart(chart: &impl Chart) {
+    println!("{}", chart.get_title());
+    if terminal_supports_sixel() {
+        match print_sixel(&chart.to_image()) {
+            Ok(()) => {
+                println!("{}", chart.get_legend());
+                return;
+            }
+            Err(err) => eprintln!("Unable to display chart (sixel): {err}"),
+        }
+    }
+    println!("{}", chart);
+}
+
+fn legend(series: &[ChartSeries]) -> String {
+    series
+        .iter()
+        .map(|x| format!("{} {}", x.symbol, x.name))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// How many cells each value gets when the values are laid end to end and `scale` fills `cells`.
+/// The running total is rounded rather than each value so the parts always add up to the whole.
+fn split_into_cells(values: &[f64], scale: f64, cells: usize) -> Vec<usize> {
+    let mut running_total = 0.0;
+    let mut used = 0;
+    values
+        .iter()
+        .map(|value| {
+            running_total += value.max(0.0);
+            let end = if scale > 0.0 {
+                ((running_total / scale * cells as f64).round() as usize).min(cells)
+            } else {
+                0
+            };
+            let result = end.saturating_sub(used);
+            used = used.max(end);
+            result
+        })
+        .collect()
+}
+
+/// Bars made of one segment for each series stacked on top of each other, for example the hours
+/// of core, non-core, and neither work for each day.
+pub(crate) struct DisplayStackedBarChart<'s> {
+    title: String,
+    unit: &'s str,
+    series: &'s [ChartSeries],
+    /// A label and a value for each series
+    bars: Vec<(String, Vec<f64>)>,
+}
+
+impl<'s> DisplayStackedBarChart<'s> {
+    pub(crate) fn new(
+        title: String,
+        unit: &'s str,
+        series: &'s [ChartSeries],
+        bars: Vec<(String, Vec<f64>)>,
+    ) -> Self {
+        Self {
+            title,
+            unit,
+            series,
+            bars,
+        }
+    }
+
+    fn get_biggest_total(&self) -> f64 {
+        self.bars
+            .iter()
+            .map(|(_, values)| values.iter().sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+}
+
+impl Display for DisplayStackedBarChart<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label_width = self
+            .bars
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let biggest_total = self.get_biggest_total();
+        for (label, values) in self.bars.iter() {
+            let bar = split_into_cells(values, biggest_total, TEXT_WIDTH)
+                .into_iter()
+                .zip(self.series.iter())
+                .map(|(cells, series)| series.symbol.to_string().repeat(cells))
+                .collect::<String>();
+            writeln!(
+                f,
+                "{:<label_width$} │{:<TEXT_WIDTH$} {:.1}{}",
+                label,
+                bar,
+                values.iter().sum::<f64>(),
+                self.unit
+            )?;
+        }
+        write!(f, "{}", self.get_legend())
+    }
+}
+
+impl Chart for DisplayStackedBarChart<'_> {
+    fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    fn get_legend(&self) -> String {
+        legend(self.series)
+    }
+
+    fn to_image(&self) -> RgbaImage {
+        const HEIGHT: u32 = 160;
+        const MAX_WIDTH: u32 = 640;
+        let slot_width = (MAX_WIDTH / self.bars.len().max(1) as u32).clamp(3, 32);
+        let bar_width = (slot_width * 3 / 4).max(2);
+        let mut image = RgbaImage::from_pixel(
+            slot_width * self.bars.len().max(1) as u32,
+            HEIGHT,
+            TRANSPARENT,
+        );
+        let biggest_total = self.get_biggest_total();
+        for (i, (_, values)) in self.bars.iter().enumerate() {
+            let left = i as u32 * slot_width + (slot_width - bar_width) / 2;
+            let mut top = HEIGHT - 1;
+            for (pixels, series) in split_into_cells(values, biggest_total, HEIGHT as usize - 1)
+                .into_iter()
+                .zip(self.series.iter())
+            {
+                for _ in 0..pixels {
+                    top -= 1;
+                    for x in left..left + bar_width {
+                        image.put_pixel(x, top, series.color);
+                    }
+                }
+            }
+        }
+        for x in 0..image.width() {
+            image.put_pixel(x, HEIGHT - 1, AXIS);
+        }
+        image
+    }
+}
+
+/// How a whole is shared between the series, drawn as a donut or in text as a single bar
+pub(crate) struct DisplayDonutChart<'s> {
+    title: String,
+    unit: &'s str,
+    series: &'s [ChartSeries],
+    /// One for each series
+    values: Vec<f64>,
+}
+
+impl<'s> DisplayDonutChart<'s> {
+    pub(crate) fn new(
+        title: String,
+        unit: &'s str,
+        series: &'s [ChartSeries],
+        values: Vec<f64>,
+    ) -> Self {
+        Self {
+            title,
+            unit,
+            series,
+            values,
+        }
+    }
+
+    fn get_total(&self) -> f64 {
+        self.values.iter().map(|x| x.max(0.0)).sum()
+    }
+
+    fn percent(&self, value: f64) -> f64 {
+        let total = self.get_total();
+        if total > 0.0 {
+            value.max(0.0) * 100.0 / total
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Display for DisplayDonutChart<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bar = split_into_cells(&self.values, self.get_total(), TEXT_WIDTH)
+            .into_iter()
+            .zip(self.series.iter())
+            .map(|(cells, series)| series.symbol.to_string().repeat(cells))
+            .collect::<String>();
+        write!(f, "{}", bar)?;
+        write!(f, "\n{}", self.get_legend())
+    }
+}
+
+impl Chart for DisplayDonutChart<'_> {
+    fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    fn get_legend(&self) -> String {
+        self.series
+            .iter()
+            .zip(self.values.iter())
+            .map(|(series, value)| {
+                format!(
+                    "{} {} {:.1}{} ({:.0}%)",
+                    series.symbol,
+                    series.name,
+                    value,
+                    self.unit,
+                    self.percent(*value)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_image(&self) -> RgbaImage {
+        const SIZE: u32 = 160;
+        let outer = (SIZE as f32 - 1.0) / 2.0;
+        let inner = outer * 0.55;
+        let total = self.get_total() as f32;
+        RgbaImage::from_fn(SIZE, SIZE, |x, y| {
+            let (dx, dy) = (x as f32 - outer, y as f32 - outer);
+            let distance = (dx * dx + dy * dy).sqrt();
+            if distance > outer || distance < inner {
+                return TRANSPARENT;
+            }
+            if total <= 0.0 {
+                return AXIS;
+            }
+            //Clockwise starting from the top like a clock
+            let turned = dx.atan2(-dy).rem_euclid(TAU) / TAU;
+            let mut running_total = 0.0;
+            for (series, value) in self.series.iter().zip(self.values.iter()) {
+                running_total += value.max(0.0) as f32 / total;
+                if turned < running_total {
+                    return series.color;
+                }
+            }
+            self.series.last().map(|x| x.color).unwrap_or(AXIS)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use super::{Chart, ChartSeries, DisplayDonutChart, DisplayStackedBarChart};
+
+    const SERIES: [ChartSeries; 2] = [
+        ChartSeries {
+            name: "Core",
+            symbol: '█',
+            color: Rgba([0, 0, 255, 255]),
+        },
+        ChartSeries {
+            name: "Non-core",
+            symbol: '░',
+            color: Rgba([255, 128, 0, 255]),
+        },
+    ];
+
+    #[test]
+    fn stacked_bars_are_scaled_to_the_biggest_total_and_line_up() {
+        let chart = DisplayStackedBarChart::new(
+            "Hours per day".to_string(),
+            "h",
+            &SERIES,
+            vec![
+                ("Mon".to_string(), vec![6.0, 2.0]),
+                ("Tue".to_string(), vec![1.0, 1.0]),
+                ("Wed".to_string(), vec![0.0, 0.0]),
+            ],
+        );
+
+        let text = chart.to_string();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            format!("Mon │{}{} 8.0h", "█".repeat(30), "░".repeat(10))
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "Tue │{}{}{} 2.0h",
+                "█".repeat(5),
+                "░".repeat(5),
+                " ".repeat(30)
+            )
+        );
+        assert_eq!(lines[2], format!("Wed │{} 0.0h", " ".repeat(40)));
+        assert_eq!(lines[3], "█ Core  ░ Non-core");
+    }
+
+    #[test]
+    fn donut_falls_back_to_one_bar_split_by_share_with_percentages() {
+        let chart = DisplayDonutChart::new(
+            "Urgency vs importance".to_string(),
+            "h",
+            &SERIES,
+            vec![3.0, 1.0],
+        );
+
+        assert_eq!(
+            chart.to_string(),
+            format!(
+                "{}{}\n█ Core 3.0h (75%)\n░ Non-core 1.0h (25%)",
+                "█".repeat(30),
+                "░".repeat(10)
+            )
+        );
+        assert_eq!(chart.get_title(), "Urgency vs importance");
+    }
+
+    #[test]
+    fn an_empty_donut_does_not_divide_by_zero() {
+        let chart = DisplayDonutChart::new("Nothing".to_string(), "h", &SERIES, vec![0.0, 0.0]);
+
+        assert_eq!(
+            chart.to_string(),
+            "\n█ Core 0.0h (0%)\n░ Non-core 0.0h (0%)"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDate;
+
+use crate::data_storage::surrealdb_layer::surreal_daily_summary::SurrealDailySummary;
+
+use super::display_duration::DisplayDuration;
+
+/// Shows everything in a daily summary, a section is left out when there is nothing in it
+pub(crate) struct DisplayDailySummary<'s> {
+    daily_summary: &'s SurrealDailySummary,
+}
+
+impl Display for DisplayDailySummary<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let daily_summary = self.daily_summary;
+        match NaiveDate::parse_from_str(&daily_summary.day, "%Y-%m-%d") {
+            Ok(day) => write!(f, "📔 {}", day.format("%A %d %B %Y"))?,
+            Err(_) => write!(f, "📔 {}", daily_summary.day)?,
+        }
+        if daily_summary.commitments_made != 0 {
+            write!(
+                f,
+                "\nCommitments: {} of {} done",
+                daily_summary.commitments_done, daily_summary.commitments_made
+            )?;
+        }
+        if !daily_summary.finished.is_empty() {
+            write!(f, "\nFinished:")?;
+            for finished in daily_summary.finished.iter() {
+                write!(f, "\n  ✅ {}", finished)?;
+            }
+        }
+        let time_by_motivation = [
+            ("🏢 Core work", &daily_summary.core_work),
+            ("🧹 Non-core work", &daily_summary.non_core_work),
+            ("🚫 Neither", &daily_summary.other_work),
+        ];
+        for (name, time) in time_by_motivation {
+            let time: std::time::Duration = (*time).into();
+            if !time.is_zero() {
+                write!(f, "\n{}: {}", name, DisplayDuration::new(&time))?;
+            }
+        }
+        if !daily_summary.notes.is_empty() {
+            write!(f, "\nNotes: {}", daily_summary.notes)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'s> DisplayDailySummary<'s> {
+    pub(crate) fn new(daily_summary: &'s SurrealDailySummary) -> Self {
+        Self { daily_summary }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{NaiveDate, Utc};
+
+    use crate::data_storage::surrealdb_layer::surreal_daily_summary::SurrealDailySummary;
+
+    use super::DisplayDailySummary;
+
+    #[test]
+    fn empty_sections_are_left_out() {
+        let day = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        let daily_summary = SurrealDailySummary {
+            id: Some(SurrealDailySummary::id_for(&day)),
+            version: 0,
+            day: SurrealDailySummary::day_key(&day),
+            written: Utc::now().into(),
+            finished: vec!["Write report".to_string()],
+            commitments_made: 0,
+            commitments_done: 0,
+            core_work: Duration::from_secs(90 * 60).into(),
+            non_core_work: Duration::default().into(),
+            other_work: Duration::default().into(),
+            notes: String::new(),
+        };
+        let display = format!("{}", DisplayDailySummary::new(&daily_summary));
+        let lines = display.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "📔 Friday 31 May 2024");
+        assert_eq!(lines[1], "Finished:");
+        assert_eq!(lines[2], "  ✅ Write report");
+        assert!(lines[3].starts_with("🏢 Core work: "));
+        assert_eq!(lines.len(), 4);
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+/// Writes a link using the OSC 8 escape sequence so terminals that support it show the text as a
+/// clickable link. Terminals that do not support it ignore the escape sequence and just show the text.
+pub(crate) struct DisplayHyperlink<'s> {
+    url: &'s str,
+    text: &'s str,
+}
+
+impl Display for DisplayHyperlink<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", self.url, self.text)
+    }
+}
+
+impl<'s> DisplayHyperlink<'s> {
+    pub(crate) fn new(url: &'s str, text: &'s str) -> Self {
+        DisplayHyperlink { url, text }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisplayHyperlink;
+
+    #[test]
+    fn hyperlink_wraps_text_in_osc_8_escape_sequence() {
+        let display = DisplayHyperlink::new("https://example.com/notes", "My notes");
+        assert_eq!(
+            format!("{}", display),
+            "\x1b]8;;https://example.com/notes\x1b\\My notes\x1b]8;;\x1b\\"
+        );
+    }
+}
@@ -43,7 +43,14 @@ impl Display for DisplayItem<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let display_item_type =
             DisplayItemType::new(DisplayStyle::Abbreviated, self.item.get_item_type());
-        write!(f, "{} {}", display_item_type, self.item.get_summary())
+        write!(f, "{} {}", display_item_type, self.item.get_summary())?;
+        for tag in self.item.get_tags() {
+            write!(f, " #{}", tag)?;
+        }
+        if self.item.has_notes() {
+            write!(f, " 📝")?;
+        }
+        Ok(())
     }
 }
 
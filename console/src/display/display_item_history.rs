@@ -0,0 +1,253 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Local, Utc};
+use surrealdb::RecordId;
+
+use crate::{
+    base_data::BaseData,
+    data_storage::surrealdb_layer::{
+        surreal_item::{Responsibility, SurrealDependency, SurrealUrgencyPlan},
+        surreal_item_history::{SurrealItemChange, SurrealItemHistory},
+    },
+};
+
+use super::{
+    DisplayStyle, display_item_type::DisplayItemType, display_urgency_plan::DisplayUrgency,
+};
+
+/// Shows the history of an item as a timeline, one change per line, oldest first.
+pub(crate) struct DisplayItemHistory<'s> {
+    history: &'s [&'s SurrealItemHistory],
+    base_data: &'s BaseData,
+}
+
+impl Display for DisplayItemHistory<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.history.is_empty() {
+            return write!(f, "No changes have been recorded for this item");
+        }
+        for (index, entry) in self.history.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            let when: DateTime<Utc> = entry.when.clone().into();
+            let when: DateTime<Local> = when.into();
+            write!(f, "{}  ", when.format("%a %d %b %Y %I:%M%P"))?;
+            self.fmt_change(f, &entry.change)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'s> DisplayItemHistory<'s> {
+    pub(crate) fn new(history: &'s [&'s SurrealItemHistory], base_data: &'s BaseData) -> Self {
+        Self { history, base_data }
+    }
+
+    fn fmt_change(&self, f: &mut Formatter<'_>, change: &SurrealItemChange) -> std::fmt::Result {
+        match change {
+            SurrealItemChange::SummaryChanged { from, to } => {
+                write!(f, "✏️ Summary changed from \"{}\" to \"{}\"", from, to)
+            }
+            SurrealItemChange::ItemTypeChanged { from, to } => write!(
+                f,
+                "🔀 Type changed from {} to {}",
+                DisplayItemType::new(DisplayStyle::Full, from),
+                DisplayItemType::new(DisplayStyle::Full, to)
+            ),
+            SurrealItemChange::ResponsibilityChanged { from, to } => write!(
+                f,
+                "🔀 Responsibility changed from {} to {}",
+                responsibility_name(from),
+                responsibility_name(to)
+            ),
+            SurrealItemChange::ParentAdded(parent) => {
+                write!(f, "⭱ Added under: {}", self.item_summary(parent))
+            }
+            SurrealItemChange::ParentRemoved(parent) => {
+                write!(f, "🚫 Removed from under: {}", self.item_summary(parent))
+            }
+            SurrealItemChange::ChildAdded(child) => {
+                write!(f, "⭳ Added smaller item: {}", self.item_summary(child))
+            }
+            SurrealItemChange::ChildRemoved(child) => {
+                write!(f, "🚫 Removed smaller item: {}", self.item_summary(child))
+            }
+            SurrealItemChange::ChildReordered(child) => {
+                write!(
+                    f,
+                    "↕️ Changed importance of smaller item: {}",
+                    self.item_summary(child)
+                )
+            }
+            SurrealItemChange::DependencyAdded(dependency) => {
+                write!(f, "⏳ Now waiting ")?;
+                self.fmt_dependency(f, dependency)
+            }
+            SurrealItemChange::DependencyRemoved(dependency) => {
+                write!(f, "✅ No longer waiting ")?;
+                self.fmt_dependency(f, dependency)
+            }
+            SurrealItemChange::UrgencyPlanChanged { from, to } => {
+                write!(f, "🚦 Urgency changed from ")?;
+                fmt_urgency_plan(f, from)?;
+                write!(f, " to ")?;
+                fmt_urgency_plan(f, to)
+            }
+            SurrealItemChange::Reviewed => write!(f, "🔍 Reviewed"),
+            SurrealItemChange::TagsChanged { from, to } => {
+                write!(f, "🏷️ Tags changed from ")?;
+                fmt_tags(f, from)?;
+                write!(f, " to ")?;
+                fmt_tags(f, to)
+            }
+            SurrealItemChange::WaitingForChanged { to: Some(to), .. } => {
+                let follow_up: DateTime<Utc> = to.follow_up.clone().into();
+                let follow_up: DateTime<Local> = follow_up.into();
+                write!(
+                    f,
+                    "⏳ Waiting for: {}, follow up {}",
+                    self.item_summary(&to.person),
+                    follow_up.format("%a %d %b %Y %I:%M%P")
+                )
+            }
+            SurrealItemChange::WaitingForChanged { to: None, .. } => {
+                write!(f, "✋ No longer waiting on anyone")
+            }
+        }
+    }
+
+    fn fmt_dependency(
+        &self,
+        f: &mut Formatter<'_>,
+        dependency: &SurrealDependency,
+    ) -> std::fmt::Result {
+        match dependency {
+            SurrealDependency::AfterDateTime(after) => {
+                let after: DateTime<Utc> = after.clone().into();
+                let after: DateTime<Local> = after.into();
+                write!(f, "until {}", after.format("%a %d %b %Y %I:%M%P"))
+            }
+            SurrealDependency::AfterItem(item) | SurrealDependency::DuringItem(item) => {
+                write!(f, "on: {}", self.item_summary(item))
+            }
+            SurrealDependency::AfterEvent(event) => match self.base_data.get_events().get(event) {
+                Some(event) => write!(f, "on event: {}", event.get_summary()),
+                None => write!(f, "on an event that no longer exists"),
+            },
+        }
+    }
+
+    fn item_summary(&self, record_id: &RecordId) -> &str {
+        match self.base_data.get_items().get(record_id) {
+            Some(item) => item.get_summary(),
+            None => "(item no longer exists)",
+        }
+    }
+}
+
+fn responsibility_name(responsibility: &Responsibility) -> &'static str {
+    match responsibility {
+        Responsibility::ProactiveActionToTake => "Proactive",
+        Responsibility::ReactiveBeAvailableToAct => "Reactive",
+        Responsibility::WaitingFor => "Waiting for",
+        Responsibility::TrackingToBeAwareOf => "Tracking to be aware of",
+    }
+}
+
+fn fmt_tags(f: &mut Formatter<'_>, tags: &[String]) -> std::fmt::Result {
+    if tags.is_empty() {
+        return write!(f, "none");
+    }
+    for (index, tag) in tags.iter().enumerate() {
+        if index > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "#{}", tag)?;
+    }
+    Ok(())
+}
+
+fn fmt_urgency_plan(
+    f: &mut Formatter<'_>,
+    urgency_plan: &Option<SurrealUrgencyPlan>,
+) -> std::fmt::Result {
+    match urgency_plan {
+        None => write!(f, "not set"),
+        Some(SurrealUrgencyPlan::StaysTheSame(urgency)) => {
+            write!(f, "{}", DisplayUrgency::new(urgency, DisplayStyle::Full))
+        }
+        Some(SurrealUrgencyPlan::WillEscalate { initial, later, .. }) => write!(
+            f,
+            "{} escalating to {}",
+            DisplayUrgency::new(initial, DisplayStyle::Full),
+            DisplayUrgency::new(later, DisplayStyle::Full)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{SurrealItemBuilder, SurrealItemType},
+            surreal_item_history::{SurrealItemChange, SurrealItemHistory},
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::DisplayItemHistory;
+
+    #[test]
+    fn history_is_shown_oldest_first_with_item_summaries() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "project").into()))
+                    .summary("Renovate kitchen")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "action").into()))
+                    .summary("Buy paint")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_item_history(vec![
+                SurrealItemHistory::new(
+                    ("item", "action").into(),
+                    now.into(),
+                    SurrealItemChange::ParentAdded(("item", "project").into()),
+                ),
+                SurrealItemHistory::new(
+                    ("item", "action").into(),
+                    (now - TimeDelta::days(1)).into(),
+                    SurrealItemChange::SummaryChanged {
+                        from: "Paint".to_string(),
+                        to: "Buy paint".to_string(),
+                    },
+                ),
+                SurrealItemHistory::new(
+                    ("item", "project").into(),
+                    now.into(),
+                    SurrealItemChange::Reviewed,
+                ),
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+
+        let history = base_data.get_item_history(&("item", "action").into());
+        let display = format!("{}", DisplayItemHistory::new(&history, &base_data));
+        let lines = display.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("Summary changed from \"Paint\" to \"Buy paint\""));
+        assert!(lines[1].ends_with("Added under: Renovate kitchen"));
+    }
+}
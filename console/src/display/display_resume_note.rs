@@ -0,0 +1,70 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::data_storage::surrealdb_layer::surreal_resume_note::SurrealResumeNote;
+
+use super::display_hyperlink::DisplayHyperlink;
+
+/// Shows a resume note with one line for each part that was filled in, a location that is a web
+/// link can be clicked on in terminals that support it.
+pub(crate) struct DisplayResumeNote<'s> {
+    resume_note: &'s SurrealResumeNote,
+}
+
+impl Display for DisplayResumeNote<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let created: DateTime<Utc> = self.resume_note.created.clone().into();
+        let created: DateTime<Local> = created.into();
+        write!(f, "{}", created.format("%a %d %b %Y %I:%M%P"))?;
+        if !self.resume_note.doing.is_empty() {
+            write!(f, "\n  Was doing: {}", self.resume_note.doing)?;
+        }
+        if !self.resume_note.next.is_empty() {
+            write!(f, "\n  Next: {}", self.resume_note.next)?;
+        }
+        let location = &self.resume_note.location;
+        if location.starts_with("http://") || location.starts_with("https://") {
+            write!(
+                f,
+                "\n  Where: {}",
+                DisplayHyperlink::new(location, location)
+            )?;
+        } else if !location.is_empty() {
+            write!(f, "\n  Where: {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'s> DisplayResumeNote<'s> {
+    pub(crate) fn new(resume_note: &'s SurrealResumeNote) -> Self {
+        Self { resume_note }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use crate::data_storage::surrealdb_layer::surreal_resume_note::SurrealResumeNote;
+
+    use super::DisplayResumeNote;
+
+    #[test]
+    fn only_the_parts_that_were_filled_in_are_shown() {
+        let resume_note = SurrealResumeNote::new(
+            ("item", "report").into(),
+            Utc::now().into(),
+            "Outlining section 2".to_string(),
+            String::new(),
+            "~/report.md".to_string(),
+        );
+        let display = format!("{}", DisplayResumeNote::new(&resume_note));
+        let lines = display.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec!["  Was doing: Outlining section 2", "  Where: ~/report.md"]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+};
+
+use icy_sixel::{EncodeOptions, QuantizeMethod, sixel_encode};
+use image::{
+    RgbaImage,
+    imageops::{self, FilterType},
+};
+
+/// A best guess from the environment. Asking the terminal directly means reading its reply from
+/// stdin which would get mixed up with the menus.
+pub(crate) fn terminal_supports_sixel() -> bool {
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    let term = env::var("TERM").unwrap_or_default();
+    let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+    env::var_os("WT_SESSION").is_some()
+        || ["WezTerm", "mlterm", "contour"].contains(&term_program.as_str())
+        || ["foot", "mlterm", "yaft", "contour", "sixel"]
+            .iter()
+            .any(|x| term.contains(x))
+}
+
+/// Writes the image to stdout as sixel graphics followed by a newline. Only terminals with sixel
+/// support show the image, for example Windows Terminal with the Atlas rendering engine, mlterm,
+/// wezterm, or xterm with sixel support.
+pub(crate) fn print_sixel(image: &RgbaImage) -> Result<(), Box<dyn std::error::Error>> {
+    let encode_opts = EncodeOptions {
+        max_colors: 256,
+        diffusion: 0.5, //Reduced dithering, less noise, good for graphics Higher values produce smoother gradients but may introduce noise. Lower values preserve sharp edges but may show color banding. Values are clamped to the range 0.0-1.0.
+        quantize_method: QuantizeMethod::Wu,
+    };
+    let sixel = sixel_encode(
+        image.as_raw(),
+        image.width() as usize,
+        image.height() as usize,
+        &encode_opts,
+    )?;
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(sixel.as_bytes())?;
+    stdout.write_all(b"\n")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Resizes an image to fit within specified dimensions while preserving aspect ratio.
+///
+/// This function only scales down images that exceed the specified dimensions - it will never
+/// scale up a smaller image. If the image is already smaller than or equal to the maximum
+/// dimensions, it returns a clone of the original image unchanged.
+///
+/// # Arguments
+///
+/// * `img` - The source image to resize
+/// * `max_width` - Maximum width constraint in pixels
+/// * `max_height` - Maximum height constraint in pixels
+///
+/// # Returns
+///
+/// A new `RgbaImage` that fits within the specified dimensions while maintaining the original
+/// aspect ratio. If no resizing is needed, returns the input image.
+pub(crate) fn resize_to_fit(img: RgbaImage, max_width: u32, max_height: u32) -> RgbaImage {
+    let (w, h) = img.dimensions();
+    let scale_w = max_width as f32 / w as f32;
+    let scale_h = max_height as f32 / h as f32;
+    let scale = scale_w.min(scale_h).min(1.0);
+
+    if scale >= 1.0 {
+        img
+    } else {
+        let new_w = (w as f32 * scale).round().max(1.0) as u32;
+        let new_h = (h as f32 * scale).round().max(1.0) as u32;
+        imageops::resize(&img, new_w, new_h, FilterType::Lanczos3)
+    }
+}
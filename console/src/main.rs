@@ -16,30 +16,41 @@ use core::fmt;
 use std::{
     env,
     fmt::Display,
-    io::{self, Write},
+    path::PathBuf,
     process,
     time::{Duration, Instant, SystemTime},
 };
 
 use better_term::Style;
 use crossterm::terminal::{Clear, ClearType};
-use icy_sixel::{EncodeOptions, QuantizeMethod, sixel_encode};
-use image::{
-    imageops::{self, FilterType},
-    load_from_memory,
-};
+use image::load_from_memory;
 use inquire::ui::{Attributes, Color, RenderConfig, StyleSheet, Styled};
 use inquire::{InquireError, Select};
 use mimalloc::MiMalloc;
 
-use tokio::sync::mpsc;
+use tokio::{sync::mpsc, task::JoinHandle};
 
+#[cfg(unix)]
+use crate::data_storage::daemon;
 use crate::{
     data_storage::surrealdb_layer::data_layer_commands::{
-        self, CopyDestinationBehavior, SurrealAuthConfig, data_storage_start_and_run,
+        self, CopyDestinationBehavior, DataLayerCommands, SurrealAuthConfig,
+        SurrealDbConnectionConfig, data_storage_start_and_run,
     },
-    menu::inquire::do_now_list_menu::{
-        ShouldResumeCurrentlyWorkingOn, present_normal_do_now_list_menu,
+    display::display_sixel::{print_sixel, resize_to_fit},
+    menu::{
+        command_line::{
+            QueryOutput,
+            graph::{GraphFormat, GraphOutput, print_graph},
+            outline::{OutlineCommand, run_outline_command},
+            print_items_matching_query,
+            standup::print_standup,
+            status::{DEFAULT_STATUS_FORMAT, StatusOutput, print_status},
+        },
+        http_api::serve_http_api,
+        inquire::do_now_list_menu::{
+            ShouldResumeCurrentlyWorkingOn, present_normal_do_now_list_menu,
+        },
     },
 };
 
@@ -72,20 +83,39 @@ struct CliSurrealConfig {
     initialize_from_database: Option<String>,
     initialize_from_copy_behavior: CopyDestinationBehavior,
     initialize_from_ask: bool,
+    /// One-shot: print the items matching this query instead of starting the menus
+    query_output: Option<(String, QueryOutput)>,
+    /// One-shot: print what is being worked on for a status bar instead of starting the menus
+    status_output: Option<StatusOutput>,
+    /// One-shot: print a stand-up report, limited to the items under these motivations when any are given
+    standup_under: Option<Vec<String>>,
+    /// One-shot: print the item hierarchy and dependencies as DOT or Mermaid
+    graph_output: Option<GraphOutput>,
+    /// One-shot: print a subtree as a markdown outline, or import a markdown outline under an item
+    outline_command: Option<OutlineCommand>,
+    /// Serve the HTTP JSON API on this port instead of starting the menus
+    serve_port: Option<u16>,
+    /// Own the database and share it with other front ends over a Unix domain socket
+    daemon: bool,
+    /// Where the daemon listens, and where front ends look for a running daemon
+    socket_path: Option<String>,
 }
 
+const DEFAULT_SERVE_PORT: u16 = 7878;
+
 fn print_help_and_exit() -> ! {
     eprintln!(
         r#"Task On Purpose
 
 Usage:
-  taskonpurpose [inmemorydb]
+  taskonpurpose [inmemorydb] [serve [--port <port>] | daemon | status [--format <template> | --json] | standup [--under <motivation>]... | graph [--mermaid] [--root <item>] | outline --root <item> [--import <file>]] [--socket <path>]
     [--surreal-endpoint <endpoint>]
     [--namespace <ns>]
     [--username <user>]
     [--initialize-from-database <db> [--initialize-from-endpoint <endpoint>] [--initialize-from-namespace <ns>]]
     [--ask]
     [--surreal-auth-username <user> --surreal-auth-password <pass> [--surreal-auth-level <root|ns|db>]]
+    [--list <query> | --export <query>]
 
 Options:
   --surreal-endpoint, -e        SurrealDB connection string/endpoint (e.g. mem://, file://..., ws://...)
@@ -99,11 +129,29 @@ Options:
   --surreal-auth-username       SurrealDB login username (optional; used for remote auth)
   --surreal-auth-password       SurrealDB login password (optional; used for remote auth)
   --surreal-auth-level          SurrealDB auth level: root | ns | db (default: root)
+  --list <query>                One-shot: print the items matching the query, for example --list "type:action ready"
+  --export <query>              One-shot: print the items matching the query as CSV, use "" for all active items
+  serve                         Serve a JSON API on 127.0.0.1 for other front ends instead of starting the menus, see /api/openapi.json. Requests need the token printed at startup
+  --port <port>                 Use with serve: the port to listen on (default: 7878)
+  daemon                        Own the database and share it with other front ends over a Unix domain socket until Ctrl-C is pressed
+  --socket <path>               The daemon's socket (default: $XDG_RUNTIME_DIR or the temp directory, then taskonpurpose-<username>-<hash of the endpoint and namespace>.sock). When others can use its directory, like /tmp, it goes in a private taskonpurpose-<uid> directory next to it
+  status                        One-shot: print what is being worked on, or the top of the Do Now list, with how many items are urgent and what is scheduled next. Quick enough for tmux, polybar, or a shell prompt, and shares a running daemon's database
+  --format <template>           Use with status: placeholders are {current} {elapsed} {urgent} {next} {next_time} {next_summary} {mode} (default: "{current} {elapsed} ({urgent} urgent) {next}")
+  --json                        Use with status: print JSON instead of one line
+  standup                       One-shot: print yesterday, today, and blockers as markdown for a stand-up. Yesterday is the previous weekday, today is today's plan or the top of the Do Now list
+  --under <motivation>          Use with standup: only include items under this motivation, can be given more than once
+  graph                         One-shot: print the active items, their parent/child links, and their after item and after event dependencies as Graphviz DOT, for example `taskonpurpose graph | dot -Tsvg > items.svg`
+  --mermaid                     Use with graph: print a Mermaid flowchart instead of DOT
+  --root <item>                 Use with graph: only the subtree under this item, given by its summary or its id such as item:abc. Use with outline: the item to print or import under
+  outline                       One-shot: print the item given with --root and everything under it as a markdown outline, with [x] for finished items
+  --import <file>               Use with outline: add the items of a markdown bullet list under the --root item instead, "-" reads stdin. Nesting follows the indentation, and words at the end of a line like type:action, urgency:definitely, or #tag set the type, urgency, and tags
   --help, -h                    Show this help
 
 Notes:
   - The SurrealDB database name is derived from the provided username (this replaces the previous hardcoded \"Russ\").
   - On startup, if namespace \"TaskOnPurpose\" is empty but legacy namespace \"OnPurpose\" has data, the data is copied into \"TaskOnPurpose\".
+  - When a daemon is running for the same endpoint, namespace, and username every other front end, including serve, --list, and --export, uses it automatically rather than opening the database. inmemorydb never uses a daemon.
+  - Queries are the same as "Filter with a query" in the search menu, e.g. "urgency>=definitely", "under:\"Health\"", "created<30d", "finished:last-week", "text:~invoice".
   - If connecting to a remote SurrealDB with IAM enabled, you likely need to pass `--surreal-auth-username/--surreal-auth-password`.
 "#
     );
@@ -131,6 +179,21 @@ fn parse_cli(args: &[String]) -> Result<CliSurrealConfig, String> {
     let mut initialize_from_database: Option<String> = None;
     let mut initialize_from_copy_behavior = CopyDestinationBehavior::ErrorIfNotEmpty;
     let mut initialize_from_ask = false;
+    let mut query_output: Option<(String, QueryOutput)> = None;
+    let mut serve = false;
+    let mut daemon = false;
+    let mut socket_path: Option<String> = None;
+    let mut port: Option<u16> = None;
+    let mut status = false;
+    let mut status_format: Option<String> = None;
+    let mut status_json = false;
+    let mut standup = false;
+    let mut standup_under: Vec<String> = Vec::default();
+    let mut graph = false;
+    let mut graph_mermaid = false;
+    let mut root: Option<String> = None;
+    let mut outline = false;
+    let mut outline_import: Option<String> = None;
 
     let mut i = 1usize;
     while i < args.len() {
@@ -139,6 +202,36 @@ fn parse_cli(args: &[String]) -> Result<CliSurrealConfig, String> {
             i += 1;
             continue;
         }
+        if args[i] == "serve" {
+            serve = true;
+            i += 1;
+            continue;
+        }
+        if args[i] == "daemon" {
+            daemon = true;
+            i += 1;
+            continue;
+        }
+        if args[i] == "status" {
+            status = true;
+            i += 1;
+            continue;
+        }
+        if args[i] == "standup" {
+            standup = true;
+            i += 1;
+            continue;
+        }
+        if args[i] == "graph" {
+            graph = true;
+            i += 1;
+            continue;
+        }
+        if args[i] == "outline" {
+            outline = true;
+            i += 1;
+            continue;
+        }
         match args[i].as_str() {
             "--help" | "-h" => print_help_and_exit(),
             "-username" | "-user" => {
@@ -222,6 +315,79 @@ fn parse_cli(args: &[String]) -> Result<CliSurrealConfig, String> {
             "--ask" => {
                 initialize_from_ask = true;
             }
+            "--socket" => {
+                i += 1;
+                socket_path = Some(
+                    args.get(i)
+                        .ok_or_else(|| format!("Missing value for {}", args[i - 1]))?
+                        .to_string(),
+                );
+            }
+            "--port" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| format!("Missing value for {}", args[i - 1]))?;
+                port = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("'{}' is not a valid port", value))?,
+                );
+            }
+            "--format" => {
+                i += 1;
+                status_format = Some(
+                    args.get(i)
+                        .ok_or_else(|| format!("Missing value for {}", args[i - 1]))?
+                        .to_string(),
+                );
+            }
+            "--json" => {
+                status_json = true;
+            }
+            "--under" => {
+                i += 1;
+                standup_under.push(
+                    args.get(i)
+                        .ok_or_else(|| format!("Missing value for {}", args[i - 1]))?
+                        .to_string(),
+                );
+            }
+            "--mermaid" => {
+                graph_mermaid = true;
+            }
+            "--root" => {
+                i += 1;
+                root = Some(
+                    args.get(i)
+                        .ok_or_else(|| format!("Missing value for {}", args[i - 1]))?
+                        .to_string(),
+                );
+            }
+            "--import" => {
+                i += 1;
+                outline_import = Some(
+                    args.get(i)
+                        .ok_or_else(|| format!("Missing value for {}", args[i - 1]))?
+                        .to_string(),
+                );
+            }
+            "--list" | "--export" => {
+                if query_output.is_some() {
+                    return Err("Only one of --list or --export can be given".to_string());
+                }
+                let output = if args[i] == "--list" {
+                    QueryOutput::List
+                } else {
+                    QueryOutput::Csv
+                };
+                i += 1;
+                let query = args
+                    .get(i)
+                    .ok_or_else(|| format!("Missing query for {}", args[i - 1]))?
+                    .to_string();
+                query_output = Some((query, output));
+            }
             _ => {
                 return Err(format!("Unknown argument '{}'. Try --help.", args[i]));
             }
@@ -251,6 +417,111 @@ fn parse_cli(args: &[String]) -> Result<CliSurrealConfig, String> {
         return Err("--ask cannot be combined with --force (choose one)".to_string());
     }
 
+    if initialize_from_database.is_some() && query_output.is_some() {
+        return Err(
+            "--list/--export cannot be combined with --initialize-from-database".to_string(),
+        );
+    }
+
+    if port.is_some() && !serve {
+        return Err("--port requires serve".to_string());
+    }
+
+    if serve && (initialize_from_database.is_some() || query_output.is_some()) {
+        return Err(
+            "serve cannot be combined with --initialize-from-database, --list, or --export"
+                .to_string(),
+        );
+    }
+
+    if daemon && (serve || initialize_from_database.is_some() || query_output.is_some()) {
+        return Err(
+            "daemon cannot be combined with serve, --initialize-from-database, --list, or --export"
+                .to_string(),
+        );
+    }
+
+    if (status_format.is_some() || status_json) && !status {
+        return Err("--format and --json require status".to_string());
+    }
+
+    if status_format.is_some() && status_json {
+        return Err("--format cannot be combined with --json (choose one)".to_string());
+    }
+
+    if status && (serve || daemon || initialize_from_database.is_some() || query_output.is_some()) {
+        return Err(
+            "status cannot be combined with serve, daemon, --initialize-from-database, --list, or --export"
+                .to_string(),
+        );
+    }
+
+    if !standup_under.is_empty() && !standup {
+        return Err("--under requires standup".to_string());
+    }
+
+    if standup
+        && (serve
+            || daemon
+            || status
+            || initialize_from_database.is_some()
+            || query_output.is_some())
+    {
+        return Err(
+            "standup cannot be combined with serve, daemon, status, --initialize-from-database, --list, or --export"
+                .to_string(),
+        );
+    }
+
+    if (graph_mermaid && !graph) || (root.is_some() && !graph && !outline) {
+        return Err(
+            "--mermaid and --root require graph, --root can also be used with outline".to_string(),
+        );
+    }
+
+    if graph
+        && (serve
+            || daemon
+            || status
+            || standup
+            || initialize_from_database.is_some()
+            || query_output.is_some())
+    {
+        return Err(
+            "graph cannot be combined with serve, daemon, status, standup, --initialize-from-database, --list, or --export"
+                .to_string(),
+        );
+    }
+
+    if outline_import.is_some() && !outline {
+        return Err("--import requires outline".to_string());
+    }
+
+    if outline && root.is_none() {
+        return Err("outline requires --root <item>".to_string());
+    }
+
+    if outline
+        && (serve
+            || daemon
+            || status
+            || standup
+            || graph
+            || initialize_from_database.is_some()
+            || query_output.is_some())
+    {
+        return Err(
+            "outline cannot be combined with serve, daemon, status, standup, graph, --initialize-from-database, --list, or --export"
+                .to_string(),
+        );
+    }
+
+    if daemon && cfg!(not(unix)) {
+        return Err(
+            "daemon needs Unix domain sockets, which this platform does not have".to_string(),
+        );
+    }
+
     Ok(CliSurrealConfig {
         endpoint,
         namespace,
@@ -263,6 +534,32 @@ fn parse_cli(args: &[String]) -> Result<CliSurrealConfig, String> {
         initialize_from_database,
         initialize_from_copy_behavior,
         initialize_from_ask,
+        query_output,
+        status_output: status.then(|| {
+            if status_json {
+                StatusOutput::Json
+            } else {
+                StatusOutput::Format(
+                    status_format.unwrap_or_else(|| DEFAULT_STATUS_FORMAT.to_string()),
+                )
+            }
+        }),
+        standup_under: standup.then_some(standup_under),
+        graph_output: graph.then(|| GraphOutput {
+            format: if graph_mermaid {
+                GraphFormat::Mermaid
+            } else {
+                GraphFormat::Dot
+            },
+            root: root.clone(),
+        }),
+        outline_command: outline.then(|| OutlineCommand {
+            root: root.unwrap_or_default(),
+            import_from: outline_import,
+        }),
+        serve_port: serve.then(|| port.unwrap_or(DEFAULT_SERVE_PORT)),
+        daemon,
+        socket_path,
     })
 }
 
@@ -288,28 +585,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ));
     inquire::set_global_render_config(render_config);
 
-    println!("{}", Clear(ClearType::All));
-    print_hourglass_logo().unwrap_or_else(|err| eprintln!("Unable to display logo (sixel): {err}"));
-
-    const CARGO_PKG_VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
-
-    println!(
-        "{}Welcome to 🕜 Task On Purpose 🕜{}",
-        Style::default().bold(),
-        Style::default()
-    );
-    println!(
-        "{}Version {}{}",
-        Style::default().italic(),
-        CARGO_PKG_VERSION.unwrap_or("UNKNOWN"),
-        Style::default()
-    );
-    println!();
-
-    let commands_in_flight_limit = 20;
-    let (send_to_data_storage_layer_tx, have_data_storage_layer_use_to_receive_rx) =
-        mpsc::channel(commands_in_flight_limit);
-
     let args: Vec<String> = env::args().collect();
     let surreal_cli = match parse_cli(&args) {
         Ok(cfg) => cfg,
@@ -319,6 +594,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    //Printing the items matching a query, the status, a stand-up, the graph, or an outline and serving the API are meant for other programs so they skip the welcome
+    if surreal_cli.query_output.is_none()
+        && surreal_cli.status_output.is_none()
+        && surreal_cli.standup_under.is_none()
+        && surreal_cli.graph_output.is_none()
+        && surreal_cli.outline_command.is_none()
+        && surreal_cli.serve_port.is_none()
+    {
+        print_welcome();
+    }
+
+    let commands_in_flight_limit = 20;
+    let (send_to_data_storage_layer_tx, have_data_storage_layer_use_to_receive_rx) =
+        mpsc::channel(commands_in_flight_limit);
+
     let auth = match (
         surreal_cli.auth_username.clone(),
         surreal_cli.auth_password.clone(),
@@ -434,18 +724,142 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
-    let data_storage_join_handle = tokio::spawn(async move {
-        data_storage_start_and_run(
-            have_data_storage_layer_use_to_receive_rx,
-            crate::data_storage::surrealdb_layer::data_layer_commands::SurrealDbConnectionConfig {
-                endpoint: surreal_cli.endpoint,
-                namespace: surreal_cli.namespace,
-                database: surreal_cli.username,
-                auth,
-            },
-        )
-        .await
-    });
+    let query_output = surreal_cli.query_output.clone();
+    let status_output = surreal_cli.status_output.clone();
+    let standup_under = surreal_cli.standup_under.clone();
+    let graph_output = surreal_cli.graph_output.clone();
+    let outline_command = surreal_cli.outline_command.clone();
+    let serve_port = surreal_cli.serve_port;
+
+    #[cfg(unix)]
+    let socket_path = surreal_cli
+        .socket_path
+        .clone()
+        .map(|x| daemon::socket_path_in_private_directory(&PathBuf::from(x)))
+        .unwrap_or_else(|| {
+            daemon::default_socket_path(
+                &surreal_cli.endpoint,
+                &surreal_cli.namespace,
+                &surreal_cli.username,
+            )
+        });
+    //Take the socket before opening the database so a second daemon stops without touching it
+    #[cfg(unix)]
+    let daemon_listener = if surreal_cli.daemon {
+        match daemon::bind_daemon_socket(&socket_path).await {
+            Ok(listener) => Some(listener),
+            Err(err) => {
+                eprintln!("Unable to start the daemon: {err}");
+                process::exit(2);
+            }
+        }
+    } else {
+        None
+    };
+    #[cfg(unix)]
+    let look_for_daemon_at = daemon_listener.is_none().then(|| socket_path.clone());
+    #[cfg(not(unix))]
+    let look_for_daemon_at = None;
+
+    let data_storage_join_handle = spawn_data_storage_layer(
+        have_data_storage_layer_use_to_receive_rx,
+        SurrealDbConnectionConfig {
+            endpoint: surreal_cli.endpoint,
+            namespace: surreal_cli.namespace,
+            database: surreal_cli.username,
+            auth,
+        },
+        look_for_daemon_at,
+    )
+    .await;
+
+    #[cfg(unix)]
+    if let Some(listener) = daemon_listener {
+        eprintln!(
+            "Daemon listening on {}, press Ctrl-C to stop.",
+            socket_path.display()
+        );
+        let result = daemon::run_daemon(listener, send_to_data_storage_layer_tx.clone(), async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await;
+        std::fs::remove_file(&socket_path).ok();
+        drop(send_to_data_storage_layer_tx);
+        data_storage_join_handle.await.unwrap();
+        if let Err(err) = result {
+            eprintln!("The daemon stopped: {err}");
+            process::exit(2);
+        }
+        return Ok(());
+    }
+
+    if let Some((query, output)) = query_output {
+        let result =
+            print_items_matching_query(&query, output, &send_to_data_storage_layer_tx).await;
+        drop(send_to_data_storage_layer_tx);
+        data_storage_join_handle.await.unwrap();
+        if let Err(err) = result {
+            eprintln!("{err}");
+            process::exit(2);
+        }
+        return Ok(());
+    }
+
+    if let Some(status_output) = status_output {
+        let result = print_status(&status_output, &send_to_data_storage_layer_tx).await;
+        drop(send_to_data_storage_layer_tx);
+        data_storage_join_handle.await.unwrap();
+        if let Err(err) = result {
+            eprintln!("{err}");
+            process::exit(2);
+        }
+        return Ok(());
+    }
+
+    if let Some(under) = standup_under {
+        let result = print_standup(&under, &send_to_data_storage_layer_tx).await;
+        drop(send_to_data_storage_layer_tx);
+        data_storage_join_handle.await.unwrap();
+        if let Err(err) = result {
+            eprintln!("{err}");
+            process::exit(2);
+        }
+        return Ok(());
+    }
+
+    if let Some(graph_output) = graph_output {
+        let result = print_graph(&graph_output, &send_to_data_storage_layer_tx).await;
+        drop(send_to_data_storage_layer_tx);
+        data_storage_join_handle.await.unwrap();
+        if let Err(err) = result {
+            eprintln!("{err}");
+            process::exit(2);
+        }
+        return Ok(());
+    }
+
+    if let Some(outline_command) = outline_command {
+        let result = run_outline_command(&outline_command, &send_to_data_storage_layer_tx).await;
+        drop(send_to_data_storage_layer_tx);
+        data_storage_join_handle.await.unwrap();
+        if let Err(err) = result {
+            eprintln!("{err}");
+            process::exit(2);
+        }
+        return Ok(());
+    }
+
+    if let Some(port) = serve_port {
+        let result = serve_http_api(port, send_to_data_storage_layer_tx.clone()).await;
+        drop(send_to_data_storage_layer_tx);
+        data_storage_join_handle.await.unwrap();
+        if let Err(err) = result {
+            eprintln!("Unable to serve the API on port {port}: {err}");
+            process::exit(2);
+        }
+        return Ok(());
+    }
+
     //If the current executable is more than 3 months old print a message that there is probably a newer version available
     let exe_path = env::current_exe().unwrap();
     let exe_metadata = exe_path.metadata().unwrap();
@@ -483,6 +897,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Runs the data storage layer in this process, unless a daemon is already listening on the socket
+/// in which case every command is forwarded to the daemon instead. An in-memory database is a
+/// throwaway session so it never uses a daemon, even one for the same database name.
+#[cfg_attr(not(unix), allow(unused_variables))]
+async fn spawn_data_storage_layer(
+    have_data_storage_layer_use_to_receive_rx: mpsc::Receiver<DataLayerCommands>,
+    config: SurrealDbConnectionConfig,
+    look_for_daemon_at: Option<PathBuf>,
+) -> JoinHandle<()> {
+    #[cfg(unix)]
+    if !config.endpoint.starts_with("mem://")
+        && let Some(socket_path) = look_for_daemon_at
+        && let Some(stream) = daemon::connect_to_daemon(&socket_path).await
+    {
+        eprintln!("Using the daemon at {}", socket_path.display());
+        return tokio::spawn(daemon::forward_to_daemon(
+            have_data_storage_layer_use_to_receive_rx,
+            stream,
+        ));
+    }
+    tokio::spawn(data_storage_start_and_run(
+        have_data_storage_layer_use_to_receive_rx,
+        config,
+    ))
+}
+
+fn print_welcome() {
+    println!("{}", Clear(ClearType::All));
+    print_hourglass_logo().unwrap_or_else(|err| eprintln!("Unable to display logo (sixel): {err}"));
+
+    const CARGO_PKG_VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
+
+    println!(
+        "{}Welcome to 🕜 Task On Purpose 🕜{}",
+        Style::default().bold(),
+        Style::default()
+    );
+    println!(
+        "{}Version {}{}",
+        Style::default().italic(),
+        CARGO_PKG_VERSION.unwrap_or("UNKNOWN"),
+        Style::default()
+    );
+    println!();
+}
+
 /// Prints the OnPurpose hourglass logo to stdout as a sixel-encoded image.
 ///
 /// This function loads the embedded PNG logo, resizes it to fit within terminal dimensions,
@@ -500,60 +960,20 @@ fn print_hourglass_logo() -> Result<(), Box<dyn std::error::Error>> {
     let canvas = load_from_memory(include_bytes!("logo/hourglass_logo.png"))?.to_rgba8();
 
     // Keep the original aspect; only scale down if needed.
-    let resized = resize_to_fit(canvas, 130, 260);
-
-    let encode_opts = EncodeOptions {
-        max_colors: 256,
-        diffusion: 0.5, //Reduced dithering, less noise, good for graphics Higher values produce smoother gradients but may introduce noise. Lower values preserve sharp edges but may show color banding. Values are clamped to the range 0.0-1.0.
-        quantize_method: QuantizeMethod::Wu,
-    };
-    let sixel = sixel_encode(
-        resized.as_raw(),
-        resized.width() as usize,
-        resized.height() as usize,
-        &encode_opts,
-    )?;
-    let mut stdout = io::stdout().lock();
-    stdout.write_all(sixel.as_bytes())?;
-    stdout.write_all(b"\n")?;
-    stdout.flush()?;
-    Ok(())
-}
-
-/// Resizes an image to fit within specified dimensions while preserving aspect ratio.
-///
-/// This function only scales down images that exceed the specified dimensions - it will never
-/// scale up a smaller image. If the image is already smaller than or equal to the maximum
-/// dimensions, it returns a clone of the original image unchanged.
-///
-/// # Arguments
-///
-/// * `img` - The source image to resize
-/// * `max_width` - Maximum width constraint in pixels
-/// * `max_height` - Maximum height constraint in pixels
-///
-/// # Returns
-///
-/// A new `RgbaImage` that fits within the specified dimensions while maintaining the original
-/// aspect ratio. If no resizing is needed, returns the input image.
-fn resize_to_fit(img: image::RgbaImage, max_width: u32, max_height: u32) -> image::RgbaImage {
-    let (w, h) = img.dimensions();
-    let scale_w = max_width as f32 / w as f32;
-    let scale_h = max_height as f32 / h as f32;
-    let scale = scale_w.min(scale_h).min(1.0);
-
-    if scale >= 1.0 {
-        img
-    } else {
-        let new_w = (w as f32 * scale).round().max(1.0) as u32;
-        let new_h = (h as f32 * scale).round().max(1.0) as u32;
-        imageops::resize(&img, new_w, new_h, FilterType::Lanczos3)
-    }
+    print_sixel(&resize_to_fit(canvas, 130, 260))
 }
 
 #[cfg(test)]
 mod tests {
     use super::parse_cli;
+    #[cfg(unix)]
+    use super::spawn_data_storage_layer;
+    use crate::menu::command_line::{
+        QueryOutput,
+        graph::{GraphFormat, GraphOutput},
+        outline::OutlineCommand,
+        status::{DEFAULT_STATUS_FORMAT, StatusOutput},
+    };
 
     #[test]
     fn parse_cli_rejects_single_dash_username_flag() {
@@ -594,4 +1014,299 @@ mod tests {
         let err = parse_cli(&args).expect_err("Should fail when --ask and --force are combined");
         assert!(err.contains("--ask cannot be combined with --force"));
     }
+
+    #[test]
+    fn parse_cli_reads_list_and_export_queries() {
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "--list".to_string(),
+            "type:action ready".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.query_output,
+            Some(("type:action ready".to_string(), QueryOutput::List))
+        );
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "--export".to_string(),
+            "".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(cfg.query_output, Some((String::new(), QueryOutput::Csv)));
+    }
+
+    #[test]
+    fn parse_cli_errors_when_list_and_export_combined() {
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "--list".to_string(),
+            "ready".to_string(),
+            "--export".to_string(),
+            "ready".to_string(),
+        ];
+        let err = parse_cli(&args).expect_err("Should fail when --list and --export are combined");
+        assert!(err.contains("Only one of --list or --export"));
+
+        let args = vec!["taskonpurpose".to_string(), "--list".to_string()];
+        let err = parse_cli(&args).expect_err("Should fail without a query");
+        assert!(err.contains("Missing query for --list"));
+    }
+
+    #[test]
+    fn parse_cli_reads_serve_and_port() {
+        let args = vec!["taskonpurpose".to_string(), "serve".to_string()];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(cfg.serve_port, Some(super::DEFAULT_SERVE_PORT));
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "inmemorydb".to_string(),
+            "serve".to_string(),
+            "--port".to_string(),
+            "9000".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(cfg.serve_port, Some(9000));
+        assert_eq!(cfg.endpoint, "mem://");
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "--port".to_string(),
+            "9000".to_string(),
+        ];
+        let err = parse_cli(&args).expect_err("Should fail when --port is used without serve");
+        assert!(err.contains("--port requires serve"));
+    }
+
+    #[test]
+    fn parse_cli_reads_daemon_and_socket() {
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "daemon".to_string(),
+            "--socket".to_string(),
+            "/tmp/tasks.sock".to_string(),
+        ];
+        if cfg!(unix) {
+            let cfg = parse_cli(&args).unwrap();
+            assert!(cfg.daemon);
+            assert_eq!(cfg.socket_path.as_deref(), Some("/tmp/tasks.sock"));
+        } else {
+            assert!(parse_cli(&args).is_err());
+        }
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "daemon".to_string(),
+            "serve".to_string(),
+        ];
+        let err = parse_cli(&args).expect_err("Should fail when daemon and serve are combined");
+        assert!(err.contains("daemon cannot be combined with serve"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn an_in_memory_front_end_does_not_use_a_running_daemon() {
+        use chrono::Utc;
+        use tokio::sync::{mpsc, oneshot};
+
+        use crate::{
+            data_storage::{
+                daemon,
+                surrealdb_layer::{
+                    data_layer_commands::{
+                        DataLayerCommands, SurrealDbConnectionConfig, data_storage_start_and_run,
+                    },
+                    surreal_tables::SurrealTables,
+                },
+            },
+            new_item::NewItem,
+        };
+
+        let config = SurrealDbConnectionConfig {
+            endpoint: "mem://".to_string(),
+            namespace: "TaskOnPurpose".to_string(),
+            database: "in_memory_test".to_string(),
+            auth: None,
+        };
+        let (daemon_sender, daemon_receiver) = mpsc::channel(1);
+        let daemon_data_storage_join_handle =
+            tokio::spawn(data_storage_start_and_run(daemon_receiver, config.clone()));
+        daemon_sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "In the daemon".to_string(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+
+        let socket_directory = std::env::temp_dir().join(format!(
+            "taskonpurpose-in-memory-test-{}",
+            std::process::id()
+        ));
+        let socket_path = socket_directory.join("daemon.sock");
+        let listener = daemon::bind_daemon_socket(&socket_path).await.unwrap();
+        let (stop_daemon, stopped) = oneshot::channel::<()>();
+        let daemon_join_handle = tokio::spawn(daemon::run_daemon(listener, daemon_sender, async {
+            stopped.await.ok();
+        }));
+
+        let (sender, receiver) = mpsc::channel(1);
+        let front_end_join_handle =
+            spawn_data_storage_layer(receiver, config, Some(socket_path)).await;
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert!(surreal_tables.surreal_items.is_empty());
+
+        drop(sender);
+        front_end_join_handle.await.unwrap();
+        stop_daemon.send(()).unwrap();
+        daemon_join_handle.await.unwrap().unwrap();
+        std::fs::remove_dir_all(&socket_directory).unwrap();
+        daemon_data_storage_join_handle.await.unwrap();
+    }
+
+    #[test]
+    fn parse_cli_reads_status_format_and_json() {
+        let args = vec!["taskonpurpose".to_string(), "status".to_string()];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.status_output,
+            Some(StatusOutput::Format(DEFAULT_STATUS_FORMAT.to_string()))
+        );
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "status".to_string(),
+            "--format".to_string(),
+            "{current} {elapsed}".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.status_output,
+            Some(StatusOutput::Format("{current} {elapsed}".to_string()))
+        );
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "status".to_string(),
+            "--json".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(cfg.status_output, Some(StatusOutput::Json));
+
+        let args = vec!["taskonpurpose".to_string(), "--json".to_string()];
+        let err = parse_cli(&args).expect_err("Should fail when --json is used without status");
+        assert!(err.contains("--format and --json require status"));
+    }
+
+    #[test]
+    fn parse_cli_reads_standup_and_its_motivations() {
+        let args = vec!["taskonpurpose".to_string(), "standup".to_string()];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(cfg.standup_under, Some(Vec::new()));
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "standup".to_string(),
+            "--under".to_string(),
+            "Work".to_string(),
+            "--under".to_string(),
+            "Side project".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.standup_under,
+            Some(vec!["Work".to_string(), "Side project".to_string()])
+        );
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "--under".to_string(),
+            "Work".to_string(),
+        ];
+        let err = parse_cli(&args).expect_err("Should fail when --under is used without standup");
+        assert!(err.contains("--under requires standup"));
+    }
+
+    #[test]
+    fn parse_cli_reads_graph_format_and_root() {
+        let args = vec!["taskonpurpose".to_string(), "graph".to_string()];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.graph_output,
+            Some(GraphOutput {
+                format: GraphFormat::Dot,
+                root: None,
+            })
+        );
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "graph".to_string(),
+            "--mermaid".to_string(),
+            "--root".to_string(),
+            "Launch".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.graph_output,
+            Some(GraphOutput {
+                format: GraphFormat::Mermaid,
+                root: Some("Launch".to_string()),
+            })
+        );
+
+        let args = vec!["taskonpurpose".to_string(), "--mermaid".to_string()];
+        let err = parse_cli(&args).expect_err("Should fail when --mermaid is used without graph");
+        assert!(err.contains("--mermaid and --root require graph"));
+    }
+
+    #[test]
+    fn parse_cli_reads_outline_root_and_import() {
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "outline".to_string(),
+            "--root".to_string(),
+            "Launch".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.outline_command,
+            Some(OutlineCommand {
+                root: "Launch".to_string(),
+                import_from: None,
+            })
+        );
+        assert_eq!(cfg.graph_output, None);
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "outline".to_string(),
+            "--import".to_string(),
+            "plan.md".to_string(),
+            "--root".to_string(),
+            "item:abc".to_string(),
+        ];
+        let cfg = parse_cli(&args).unwrap();
+        assert_eq!(
+            cfg.outline_command,
+            Some(OutlineCommand {
+                root: "item:abc".to_string(),
+                import_from: Some("plan.md".to_string()),
+            })
+        );
+
+        let args = vec!["taskonpurpose".to_string(), "outline".to_string()];
+        let err = parse_cli(&args).expect_err("Should fail without --root");
+        assert!(err.contains("outline requires --root"));
+
+        let args = vec![
+            "taskonpurpose".to_string(),
+            "--import".to_string(),
+            "plan.md".to_string(),
+        ];
+        let err = parse_cli(&args).expect_err("Should fail when --import is used without outline");
+        assert!(err.contains("--import requires outline"));
+    }
 }
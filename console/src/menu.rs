@@ -1 +1,3 @@
-pub(crate) mod inquire;
+pub(crate) mod command_line;
+pub(crate) mod http_api;
+pub(crate) mod inquire;
@@ -0,0 +1,180 @@
+pub(crate) mod graph;
+pub(crate) mod outline;
+pub(crate) mod standup;
+pub(crate) mod status;
+
+use chrono::Utc;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands,
+        surreal_smart_list::{SurrealSmartListItemType, SurrealSmartListUrgency},
+        surreal_tables::SurrealTables,
+    },
+    display::{
+        display_item_node::DisplayFormat,
+        display_item_status_with_urgency::DisplayItemStatusWithUrgency,
+    },
+    node::{Filter, item_status::ItemStatus},
+    systems::item_query::{
+        find_items_matching_query, item_type_query_name, parse_item_query, urgency_query_name,
+    },
+};
+
+/// What to do with the items matching a query given on the command line with `--list` or `--export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QueryOutput {
+    /// One line per item, the same as the search menu shows them
+    List,
+    /// Comma separated values with a header row
+    Csv,
+}
+
+/// Quotes the value when needed so commas, quotes, and new lines inside a summary survive
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+const CSV_HEADER: &str = "id,summary,type,urgency,ready,tags,under,created,finished";
+
+fn csv_row<'s>(item_status: &'s ItemStatus<'s>) -> String {
+    let item = item_status.get_item();
+    let urgency = match item_status.get_urgency_now() {
+        Some(urgency) => SurrealSmartListUrgency::from(urgency),
+        None => SurrealSmartListUrgency::InTheModeByImportance,
+    };
+    let ready = item_status.is_active() && !item_status.has_dependencies(Filter::Active);
+    let under = item_status
+        .get_parents(Filter::All)
+        .map(|x| x.get_summary())
+        .collect::<Vec<_>>()
+        .join("; ");
+    [
+        item_status.get_surreal_record_id().to_string(),
+        item.get_summary().to_string(),
+        item_type_query_name(&SurrealSmartListItemType::from(item.get_item_type())).to_string(),
+        urgency_query_name(&urgency).to_string(),
+        ready.to_string(),
+        item.get_tags().join(" "),
+        under,
+        item.get_created().to_rfc3339(),
+        item_status
+            .get_finished_at()
+            .as_ref()
+            .map(|x| x.to_rfc3339())
+            .unwrap_or_default(),
+    ]
+    .iter()
+    .map(|x| csv_field(x))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+/// Prints the items matching the query to stdout
+pub(crate) async fn print_items_matching_query(
+    query: &str,
+    output: QueryOutput,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), String> {
+    let query = parse_item_query(query)?;
+
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .map_err(|err| format!("Unable to load data: {}", err))?;
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let items = find_items_matching_query(&query, calculated_data.get_items_status());
+
+    match output {
+        QueryOutput::List => {
+            for item in items {
+                println!(
+                    "{}",
+                    DisplayItemStatusWithUrgency::new(item, Filter::All, DisplayFormat::SingleLine)
+                );
+            }
+        }
+        QueryOutput::Csv => {
+            println!("{}", CSV_HEADER);
+            for item in items {
+                println!("{}", csv_row(item));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use surrealdb::RecordId;
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{SurrealItemBuilder, SurrealItemType, SurrealOrderedSubItem},
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{csv_field, csv_row};
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("Call mom"), "Call mom");
+        assert_eq!(csv_field("Milk, eggs"), "\"Milk, eggs\"");
+        assert_eq!(
+            csv_field("Read \"Deep Work\""),
+            "\"Read \"\"Deep Work\"\"\""
+        );
+    }
+
+    #[test]
+    fn csv_row_has_a_column_for_each_header() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "parent").into()))
+                    .summary("Groceries")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .smaller_items_in_priority_order(vec![SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: ("item", "child").into(),
+                    }])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "child").into()))
+                    .summary("Buy milk, eggs")
+                    .item_type(SurrealItemType::Action)
+                    .tags(Some(vec!["errand".to_string()]))
+                    .created(now.into())
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let calculated_data = CalculatedData::new_from_base_data(
+            BaseData::new_from_surreal_tables(surreal_tables, now),
+        );
+        let items_status = calculated_data.get_items_status();
+        let child_id: RecordId = ("item", "child").into();
+        let child = items_status.get(&&child_id).unwrap();
+
+        assert_eq!(
+            csv_row(child),
+            format!(
+                "item:child,\"Buy milk, eggs\",action,importance,true,errand,Groceries,{},",
+                now.to_rfc3339()
+            )
+        );
+    }
+}
@@ -0,0 +1,529 @@
+use std::fmt::Write;
+
+use ahash::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use surrealdb::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{BaseData, event::Event},
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands,
+        surreal_item::{SurrealDependency, SurrealItemType, SurrealUrgency},
+        surreal_tables::SurrealTables,
+    },
+    node::item_status::ItemStatus,
+};
+
+/// The text format `graph` prints the item hierarchy and dependencies in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GraphFormat {
+    /// Graphviz, for example `taskonpurpose graph | dot -Tsvg > items.svg`
+    Dot,
+    /// A Mermaid flowchart, which GitHub and many markdown editors draw
+    Mermaid,
+}
+
+/// What `graph` prints, the whole active graph or only the subtree under `root`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GraphOutput {
+    pub(crate) format: GraphFormat,
+    /// An item id such as "item:abc" or an item summary, ignoring case
+    pub(crate) root: Option<String>,
+}
+
+enum NodeKind<'s> {
+    Item(&'s ItemStatus<'s>),
+    Event(&'s Event<'s>),
+}
+
+struct Node<'s> {
+    record_id: &'s RecordId,
+    kind: NodeKind<'s>,
+}
+
+impl Node<'_> {
+    fn get_label(&self) -> &str {
+        match self.kind {
+            NodeKind::Item(item_status) => item_status.get_summary(),
+            NodeKind::Event(event) => event.get_summary(),
+        }
+    }
+
+    fn get_fill(&self) -> Option<&'static str> {
+        match self.kind {
+            NodeKind::Item(item_status) if item_status.is_finished() => Some("#dee2e6"),
+            NodeKind::Item(item_status) => match item_status.get_urgency_now() {
+                Some(
+                    SurrealUrgency::MoreUrgentThanAnythingIncludingScheduled
+                    | SurrealUrgency::ScheduledAnyMode(..),
+                ) => Some("#ff6b6b"),
+                Some(SurrealUrgency::MoreUrgentThanMode) => Some("#ffa94d"),
+                Some(
+                    SurrealUrgency::InTheModeScheduled(..)
+                    | SurrealUrgency::InTheModeDefinitelyUrgent,
+                ) => Some("#ffd43b"),
+                Some(SurrealUrgency::InTheModeMaybeUrgent) => Some("#fff3bf"),
+                Some(SurrealUrgency::InTheModeByImportance) | None => None,
+            },
+            NodeKind::Event(event) if event.is_triggered() => Some("#dee2e6"),
+            NodeKind::Event(..) => Some("#e5dbff"),
+        }
+    }
+
+    /// Items that are waiting on something get a dashed border, events never have one
+    fn is_ready(&self) -> bool {
+        match self.kind {
+            NodeKind::Item(item_status) => item_status.is_ready_to_be_worked_on(),
+            NodeKind::Event(..) => true,
+        }
+    }
+
+    fn get_dot_shape(&self) -> &'static str {
+        match self.kind {
+            NodeKind::Item(item_status) => match item_status.get_item().get_item_type() {
+                SurrealItemType::Motivation(..) => "house",
+                SurrealItemType::Goal(..) => "ellipse",
+                SurrealItemType::Action => "box",
+                SurrealItemType::IdeaOrThought => "note",
+                SurrealItemType::PersonOrGroup => "egg",
+                SurrealItemType::Undeclared => "tab",
+            },
+            NodeKind::Event(..) => "diamond",
+        }
+    }
+
+    fn get_mermaid_brackets(&self) -> (&'static str, &'static str) {
+        match self.kind {
+            NodeKind::Item(item_status) => match item_status.get_item().get_item_type() {
+                SurrealItemType::Motivation(..) => ("{{", "}}"),
+                SurrealItemType::Goal(..) => ("([", "])"),
+                SurrealItemType::Action => ("[", "]"),
+                SurrealItemType::IdeaOrThought => (">", "]"),
+                SurrealItemType::PersonOrGroup => ("((", "))"),
+                SurrealItemType::Undeclared => ("(", ")"),
+            },
+            NodeKind::Event(..) => ("{", "}"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum EdgeKind {
+    ParentChild,
+    AfterItem,
+    AfterEvent,
+}
+
+/// `from` comes first, so a parent before its child and a dependency before what waits on it
+struct Edge<'s> {
+    from: &'s RecordId,
+    to: &'s RecordId,
+    kind: EdgeKind,
+}
+
+struct ItemGraph<'s> {
+    nodes: Vec<Node<'s>>,
+    edges: Vec<Edge<'s>>,
+}
+
+/// The item with this id, or else the one item with this summary ignoring case
+pub(crate) fn find_root<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    root: &str,
+) -> Result<&'s ItemStatus<'s>, String> {
+    if let Some(item_status) = items_status
+        .values()
+        .find(|x| x.get_surreal_record_id().to_string() == root)
+    {
+        return Ok(item_status);
+    }
+    let matches = items_status
+        .values()
+        .filter(|x| x.get_summary().eq_ignore_ascii_case(root))
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [item_status] => Ok(*item_status),
+        [] => Err(format!("There is no item called \"{}\"", root)),
+        _ => Err(format!(
+            "{} items are called \"{}\", give the id of the one to start from instead",
+            matches.len(),
+            root
+        )),
+    }
+}
+
+/// The root plus its active descendants, or when there is no root every active item. Items and
+/// events that something in scope waits on are added so every dependency has both ends.
+fn build_graph<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    events: &'s HashMap<&'s RecordId, Event<'s>>,
+    root: Option<&'s ItemStatus<'s>>,
+) -> ItemGraph<'s> {
+    let in_scope: Vec<&ItemStatus> = match root {
+        Some(root) => {
+            let mut in_scope = vec![root];
+            let mut seen = HashSet::from_iter([root.get_surreal_record_id()]);
+            let mut i = 0;
+            while let Some(item_status) = in_scope.get(i).copied() {
+                let children = item_status
+                    .get_item()
+                    .get_children()
+                    .filter_map(|x| items_status.get(x))
+                    .filter(|x| x.is_active() && seen.insert(x.get_surreal_record_id()))
+                    .collect::<Vec<_>>();
+                in_scope.extend(children);
+                i += 1;
+            }
+            in_scope
+        }
+        None => items_status.values().filter(|x| x.is_active()).collect(),
+    };
+    let in_scope_ids = in_scope
+        .iter()
+        .map(|x| x.get_surreal_record_id())
+        .collect::<HashSet<_>>();
+
+    let mut nodes = HashMap::default();
+    let mut edges = Vec::default();
+    for item_status in in_scope.iter().copied() {
+        let record_id = item_status.get_surreal_record_id();
+        nodes.insert(record_id, NodeKind::Item(item_status));
+        for child in item_status.get_item().get_children() {
+            if let Some(child) = in_scope_ids.get(child) {
+                edges.push(Edge {
+                    from: record_id,
+                    to: *child,
+                    kind: EdgeKind::ParentChild,
+                });
+            }
+        }
+        for dependency in item_status.get_item().get_surreal_dependencies() {
+            match dependency {
+                SurrealDependency::AfterItem(after) => {
+                    if let Some((after, after_status)) = items_status.get_key_value(after) {
+                        nodes.insert(*after, NodeKind::Item(after_status));
+                        edges.push(Edge {
+                            from: *after,
+                            to: record_id,
+                            kind: EdgeKind::AfterItem,
+                        });
+                    }
+                }
+                SurrealDependency::AfterEvent(after) => {
+                    if let Some((after, event)) = events.get_key_value(after) {
+                        nodes.insert(*after, NodeKind::Event(event));
+                        edges.push(Edge {
+                            from: *after,
+                            to: record_id,
+                            kind: EdgeKind::AfterEvent,
+                        });
+                    }
+                }
+                SurrealDependency::AfterDateTime(..) | SurrealDependency::DuringItem(..) => {}
+            }
+        }
+    }
+
+    let mut nodes = nodes
+        .into_iter()
+        .map(|(record_id, kind)| Node { record_id, kind })
+        .collect::<Vec<_>>();
+    nodes.sort_by(|a, b| {
+        a.get_label()
+            .cmp(b.get_label())
+            .then_with(|| a.record_id.to_string().cmp(&b.record_id.to_string()))
+    });
+    edges.sort_by(|a, b| {
+        (a.from.to_string(), a.to.to_string(), &a.kind).cmp(&(
+            b.from.to_string(),
+            b.to.to_string(),
+            &b.kind,
+        ))
+    });
+    ItemGraph { nodes, edges }
+}
+
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn to_dot(graph: &ItemGraph<'_>) -> String {
+    let mut dot = String::new();
+    writeln!(dot, "digraph items {{").unwrap();
+    writeln!(dot, "  rankdir=LR;").unwrap();
+    for node in graph.nodes.iter() {
+        let mut style = vec![if node.is_ready() { "bold" } else { "dashed" }];
+        let mut attributes = vec![
+            format!("label={}", dot_string(node.get_label())),
+            format!("shape={}", node.get_dot_shape()),
+        ];
+        if let Some(fill) = node.get_fill() {
+            style.push("filled");
+            attributes.push(format!("fillcolor=\"{}\"", fill));
+        }
+        attributes.push(format!("style=\"{}\"", style.join(",")));
+        writeln!(
+            dot,
+            "  {} [{}];",
+            dot_string(&node.record_id.to_string()),
+            attributes.join(", ")
+        )
+        .unwrap();
+    }
+    for edge in graph.edges.iter() {
+        let attributes = match edge.kind {
+            EdgeKind::ParentChild => "",
+            EdgeKind::AfterItem => " [style=dashed, color=\"#1c7ed6\", label=\"after\"]",
+            EdgeKind::AfterEvent => " [style=dotted, color=\"#ae3ec9\", label=\"after event\"]",
+        };
+        writeln!(
+            dot,
+            "  {} -> {}{};",
+            dot_string(&edge.from.to_string()),
+            dot_string(&edge.to.to_string()),
+            attributes
+        )
+        .unwrap();
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+/// Mermaid labels are quoted so only the quote itself needs replacing
+fn mermaid_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "#quot;"))
+}
+
+fn to_mermaid(graph: &ItemGraph<'_>) -> String {
+    //Record ids are not valid Mermaid ids so each node gets a short one
+    let ids = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.record_id, format!("n{}", i)))
+        .collect::<HashMap<_, _>>();
+    let mut mermaid = String::new();
+    writeln!(mermaid, "flowchart LR").unwrap();
+    for node in graph.nodes.iter() {
+        let id = &ids[node.record_id];
+        let (open, close) = node.get_mermaid_brackets();
+        writeln!(
+            mermaid,
+            "  {}{}{}{}",
+            id,
+            open,
+            mermaid_string(node.get_label()),
+            close
+        )
+        .unwrap();
+        let mut style = Vec::default();
+        if let Some(fill) = node.get_fill() {
+            style.push(format!("fill:{}", fill));
+        }
+        if node.is_ready() {
+            style.push("stroke-width:3px".to_string());
+        } else {
+            style.push("stroke-dasharray:5 5".to_string());
+        }
+        writeln!(mermaid, "  style {} {}", id, style.join(",")).unwrap();
+    }
+    for edge in graph.edges.iter() {
+        let arrow = match edge.kind {
+            EdgeKind::ParentChild => "-->",
+            EdgeKind::AfterItem => "==>|after|",
+            EdgeKind::AfterEvent => "-.->|after event|",
+        };
+        writeln!(mermaid, "  {} {} {}", ids[edge.from], arrow, ids[edge.to]).unwrap();
+    }
+    mermaid
+}
+
+/// The item hierarchy and the after item and after event dependencies as DOT or Mermaid. Node shape
+/// shows the item type, fill the urgency, and a dashed border that the item is waiting on something.
+/// Parent to child edges are solid, after item edges dashed or thick, and after event edges dotted.
+pub(crate) fn graph_export(
+    surreal_tables: SurrealTables,
+    now: DateTime<Utc>,
+    graph_output: &GraphOutput,
+) -> Result<String, String> {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let items_status = calculated_data.get_items_status();
+    let root = graph_output
+        .root
+        .as_deref()
+        .map(|root| find_root(items_status, root))
+        .transpose()?;
+    let graph = build_graph(
+        items_status,
+        calculated_data.get_base_data().get_events(),
+        root,
+    );
+    Ok(match graph_output.format {
+        GraphFormat::Dot => to_dot(&graph),
+        GraphFormat::Mermaid => to_mermaid(&graph),
+    })
+}
+
+/// Prints the graph to stdout
+pub(crate) async fn print_graph(
+    graph_output: &GraphOutput,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), String> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .map_err(|err| format!("Unable to load data: {}", err))?;
+    print!(
+        "{}",
+        graph_export(surreal_tables, Utc::now(), graph_output)?
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::data_storage::surrealdb_layer::{
+        surreal_event::SurrealEvent,
+        surreal_item::{
+            SurrealDependency, SurrealItemBuilder, SurrealItemType, SurrealMotivationKind,
+            SurrealOrderedSubItem,
+        },
+        surreal_tables::{SurrealTables, SurrealTablesBuilder},
+    };
+
+    use super::{GraphFormat, GraphOutput, graph_export};
+
+    fn surreal_tables() -> SurrealTables {
+        let long_ago = Utc::now() - Duration::days(30);
+        SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "motivation").into()))
+                    .summary("Grow the business")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::CoreWork))
+                    .created(long_ago)
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "build").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "deploy").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "build").into()))
+                    .summary("Build the \"beta\"")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "deploy").into()))
+                    .summary("Deploy")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .dependencies(vec![
+                        SurrealDependency::AfterItem(("item", "build").into()),
+                        SurrealDependency::AfterEvent(("event", "approved").into()),
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "orphan").into()))
+                    .summary("Orphaned idea")
+                    .item_type(SurrealItemType::IdeaOrThought)
+                    .created(long_ago)
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_events(vec![SurrealEvent {
+                id: Some(("event", "approved").into()),
+                version: 0,
+                last_updated: long_ago.into(),
+                triggered: false,
+                summary: "Release approved".to_string(),
+            }])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn dot_styles_nodes_by_type_and_readiness_and_edges_by_kind() {
+        let dot = graph_export(
+            surreal_tables(),
+            Utc::now(),
+            &GraphOutput {
+                format: GraphFormat::Dot,
+                root: None,
+            },
+        )
+        .unwrap();
+
+        assert!(dot.starts_with("digraph items {\n"));
+        assert!(dot.contains("label=\"Build the \\\"beta\\\"\", shape=box, style=\"bold\""));
+        assert!(dot.contains("label=\"Deploy\", shape=box, style=\"dashed\""));
+        assert!(dot.contains("label=\"Grow the business\", shape=house"));
+        assert!(dot.contains("label=\"Orphaned idea\", shape=note"));
+        assert!(dot.contains("label=\"Release approved\", shape=diamond"));
+        assert!(dot.contains("\"item:motivation\" -> \"item:deploy\";"));
+        assert!(dot.contains(
+            "\"item:build\" -> \"item:deploy\" [style=dashed, color=\"#1c7ed6\", label=\"after\"];"
+        ));
+        assert!(dot.contains(
+            "\"event:approved\" -> \"item:deploy\" [style=dotted, color=\"#ae3ec9\", label=\"after event\"];"
+        ));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn mermaid_of_a_subtree_leaves_out_everything_else() {
+        let mermaid = graph_export(
+            surreal_tables(),
+            Utc::now(),
+            &GraphOutput {
+                format: GraphFormat::Mermaid,
+                root: Some("grow the BUSINESS".to_string()),
+            },
+        )
+        .unwrap();
+
+        //Nodes are sorted by summary so the ids are stable
+        assert_eq!(
+            mermaid,
+            [
+                "flowchart LR",
+                "  n0[\"Build the #quot;beta#quot;\"]",
+                "  style n0 stroke-width:3px",
+                "  n1[\"Deploy\"]",
+                "  style n1 stroke-dasharray:5 5",
+                "  n2{{\"Grow the business\"}}",
+                "  style n2 stroke-dasharray:5 5",
+                "  n3{\"Release approved\"}",
+                "  style n3 fill:#e5dbff,stroke-width:3px",
+                "  n3 -.->|after event| n1",
+                "  n0 ==>|after| n1",
+                "  n2 --> n0",
+                "  n2 --> n1",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let err = graph_export(
+            surreal_tables(),
+            Utc::now(),
+            &GraphOutput {
+                format: GraphFormat::Mermaid,
+                root: Some("Nothing".to_string()),
+            },
+        )
+        .expect_err("There is no such item");
+        assert_eq!(err, "There is no item called \"Nothing\"");
+    }
+}
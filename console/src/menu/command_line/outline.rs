@@ -0,0 +1,83 @@
+use std::io::Read;
+
+use chrono::Utc;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_tables::SurrealTables,
+    },
+    new_item::NewItemTree,
+    systems::outline::{item_outline, parse_outline},
+};
+
+use super::graph::find_root;
+
+/// What `outline` does, print the subtree under `root` or add the items of a markdown outline under it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OutlineCommand {
+    /// An item id such as "item:abc" or an item summary, ignoring case
+    pub(crate) root: String,
+    /// The markdown file to import, "-" for stdin. None prints the outline instead.
+    pub(crate) import_from: Option<String>,
+}
+
+fn count_items(trees: &[NewItemTree]) -> usize {
+    trees
+        .iter()
+        .map(|x| 1 + count_items(&x.smaller_items))
+        .sum()
+}
+
+fn read_outline(import_from: &str) -> Result<String, String> {
+    if import_from == "-" {
+        let mut text = String::default();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|err| format!("Unable to read the outline from stdin: {}", err))?;
+        Ok(text)
+    } else {
+        std::fs::read_to_string(import_from)
+            .map_err(|err| format!("Unable to read the outline from {}: {}", import_from, err))
+    }
+}
+
+/// Prints the outline to stdout or imports it
+pub(crate) async fn run_outline_command(
+    outline_command: &OutlineCommand,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), String> {
+    //Read and check the outline before loading anything so a mistake is reported quickly
+    let now = Utc::now();
+    let import = outline_command
+        .import_from
+        .as_deref()
+        .map(|import_from| parse_outline(&read_outline(import_from)?, now))
+        .transpose()?;
+
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .map_err(|err| format!("Unable to load data: {}", err))?;
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let items_status = calculated_data.get_items_status();
+    let root = find_root(items_status, &outline_command.root)?;
+
+    match import {
+        None => print!("{}", item_outline(items_status, root)),
+        Some(children) => {
+            let count = count_items(&children);
+            send_to_data_storage_layer
+                .send(DataLayerCommands::ParentItemWithNewChildTrees {
+                    children,
+                    parent: root.get_surreal_record_id().clone(),
+                })
+                .await
+                .map_err(|err| format!("Unable to save the outline: {}", err))?;
+            println!("Added {} items under \"{}\"", count, root.get_summary());
+        }
+    }
+    Ok(())
+}
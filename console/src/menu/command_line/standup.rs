@@ -0,0 +1,367 @@
+use std::fmt::Write;
+
+use ahash::HashMap;
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeDelta, Utc, Weekday};
+use surrealdb::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_tables::SurrealTables,
+    },
+    display::display_duration::DisplayDuration,
+    menu::inquire::do_now_list_menu::compute_time_spent_in_window,
+    node::{
+        Filter,
+        item_status::{DependencyWithItemNode, ItemStatus},
+        urgency_level_item_with_item_status::UrgencyLevelItemWithItemStatus,
+    },
+    systems::do_now_list::DoNowList,
+};
+
+/// How many items from the top of the Do Now list stand in for today's plan when there is none
+const TOP_DO_NOW_ITEMS: usize = 3;
+
+/// The weekday before the given day, so on a Monday it is the Friday before
+fn previous_working_day(day: NaiveDate) -> NaiveDate {
+    let mut previous = day.pred_opt().expect("Not the first day there is");
+    while matches!(previous.weekday(), Weekday::Sat | Weekday::Sun) {
+        previous = previous.pred_opt().expect("Not the first day there is");
+    }
+    previous
+}
+
+fn start_of_day(day: NaiveDate) -> DateTime<Utc> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    midnight
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|x| x.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// An empty list of motivations lets everything through
+fn is_under(item_status: &ItemStatus<'_>, motivations: &[&RecordId]) -> bool {
+    motivations.is_empty()
+        || item_status
+            .get_self_and_parents_flattened(Filter::All)
+            .iter()
+            .any(|x| motivations.contains(&x.get_surreal_record_id()))
+}
+
+/// The motivations with these summaries, ignoring case. The error names the one that is not found.
+fn find_motivations<'a>(
+    do_now_list: &'a DoNowList,
+    summaries: &[String],
+) -> Result<Vec<&'a RecordId>, String> {
+    summaries
+        .iter()
+        .map(|summary| {
+            do_now_list
+                .get_all_items_status()
+                .values()
+                .find(|x| x.is_type_motivation() && x.get_summary().eq_ignore_ascii_case(summary))
+                .map(|x| x.get_surreal_record_id())
+                .ok_or_else(|| format!("There is no motivation called \"{}\"", summary))
+        })
+        .collect()
+}
+
+fn display_time(time: &TimeDelta) -> String {
+    DisplayDuration::new(&time.to_std().unwrap_or_default()).to_string()
+}
+
+fn write_yesterday(
+    markdown: &mut String,
+    do_now_list: &DoNowList,
+    day: NaiveDate,
+    motivations: &[&RecordId],
+) {
+    let items_status = do_now_list.get_all_items_status();
+    let start = start_of_day(day);
+    let end = start_of_day(day.succ_opt().expect("Not the last day there is"));
+
+    let mut worked_on: HashMap<&RecordId, TimeDelta> = HashMap::default();
+    for time_spent in do_now_list.get_time_spent_log().iter() {
+        let time = compute_time_spent_in_window(start, end, &[time_spent]);
+        if time.is_zero() {
+            continue;
+        }
+        if let Some(item) = time_spent.get_worked_on() {
+            *worked_on.entry(item).or_default() += time;
+        }
+    }
+
+    let mut lines = items_status
+        .values()
+        .filter(|x| is_under(x, motivations))
+        .filter_map(|item_status| {
+            let finished = item_status
+                .get_finished_at()
+                .is_some_and(|x| start <= x && x < end);
+            let time = worked_on.get(item_status.get_surreal_record_id());
+            let line = match (finished, time) {
+                (true, Some(time)) => format!(
+                    "Finished {} ({})",
+                    item_status.get_summary(),
+                    display_time(time)
+                ),
+                (true, None) => format!("Finished {}", item_status.get_summary()),
+                (false, Some(time)) => {
+                    format!("{} ({})", item_status.get_summary(), display_time(time))
+                }
+                (false, None) => return None,
+            };
+            Some((finished, time.copied().unwrap_or_default(), line))
+        })
+        .collect::<Vec<_>>();
+    //Finished first then the most time spent
+    lines.sort_by(
+        |(a_finished, a_time, a_line), (b_finished, b_time, b_line)| {
+            b_finished
+                .cmp(a_finished)
+                .then_with(|| b_time.cmp(a_time))
+                .then_with(|| a_line.cmp(b_line))
+        },
+    );
+
+    writeln!(markdown, "## Yesterday ({})", day.format("%a %d %b")).unwrap();
+    writeln!(markdown).unwrap();
+    if lines.is_empty() {
+        writeln!(markdown, "- Nothing logged").unwrap();
+    }
+    for (_, _, line) in lines {
+        writeln!(markdown, "- {}", line).unwrap();
+    }
+}
+
+fn write_today(markdown: &mut String, do_now_list: &DoNowList, motivations: &[&RecordId]) {
+    let items_status = do_now_list.get_all_items_status();
+    let today = do_now_list.get_now().with_timezone(&Local).date_naive();
+    let planned = do_now_list
+        .get_base_data()
+        .get_daily_plan(&today)
+        .map(|daily_plan| {
+            daily_plan
+                .commitments
+                .iter()
+                .filter_map(|x| items_status.get(x))
+                .filter(|x| is_under(x, motivations))
+                .collect::<Vec<_>>()
+        })
+        .filter(|x| !x.is_empty());
+    let today_items = match planned {
+        Some(planned) => planned,
+        None => {
+            let mut top = Vec::default();
+            for item_status in do_now_list
+                .get_ordered_do_now_list()
+                .iter()
+                .flat_map(|slot| match slot {
+                    UrgencyLevelItemWithItemStatus::SingleItem(entry) => {
+                        std::slice::from_ref(entry)
+                    }
+                    UrgencyLevelItemWithItemStatus::MultipleItems(entries) => entries.as_slice(),
+                })
+                .filter_map(|x| items_status.get(x.get_surreal_record_id()))
+                .filter(|x| is_under(x, motivations))
+            {
+                if top.len() == TOP_DO_NOW_ITEMS {
+                    break;
+                }
+                if !top.iter().any(|x: &&ItemStatus<'_>| {
+                    x.get_surreal_record_id() == item_status.get_surreal_record_id()
+                }) {
+                    top.push(item_status);
+                }
+            }
+            top
+        }
+    };
+
+    writeln!(markdown, "## Today").unwrap();
+    writeln!(markdown).unwrap();
+    if today_items.is_empty() {
+        writeln!(markdown, "- Nothing planned").unwrap();
+    }
+    for item_status in today_items {
+        if item_status.is_finished() {
+            writeln!(markdown, "- {} (done)", item_status.get_summary()).unwrap();
+        } else {
+            writeln!(markdown, "- {}", item_status.get_summary()).unwrap();
+        }
+    }
+}
+
+fn write_blockers(markdown: &mut String, do_now_list: &DoNowList, motivations: &[&RecordId]) {
+    let mut lines = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| x.is_active() && is_under(x, motivations))
+        .filter_map(|item_status| {
+            let waiting_on = item_status
+                .get_dependencies(Filter::Active)
+                .filter_map(|dependency| match dependency {
+                    DependencyWithItemNode::AfterEvent(event) => {
+                        Some(format!("event \"{}\"", event.get_summary()))
+                    }
+                    DependencyWithItemNode::AfterItem(item_node) => Some(format!(
+                        "\"{}\" to be finished",
+                        item_node.get_item().get_summary()
+                    )),
+                    DependencyWithItemNode::AfterDateTime { .. }
+                    | DependencyWithItemNode::UntilScheduled { .. }
+                    | DependencyWithItemNode::AfterChildItem(..)
+                    | DependencyWithItemNode::DuringItem(..)
+                    | DependencyWithItemNode::WaitingToBeInterrupted => None,
+                })
+                .collect::<Vec<_>>();
+            if waiting_on.is_empty() {
+                None
+            } else {
+                Some(format!(
+                    "{} is waiting on {}",
+                    item_status.get_summary(),
+                    waiting_on.join(" and ")
+                ))
+            }
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+
+    writeln!(markdown, "## Blockers").unwrap();
+    writeln!(markdown).unwrap();
+    if lines.is_empty() {
+        writeln!(markdown, "- None").unwrap();
+    }
+    for line in lines {
+        writeln!(markdown, "- {}", line).unwrap();
+    }
+}
+
+/// Yesterday, today, and blockers as markdown. Yesterday is the previous weekday, today is today's
+/// plan or else the top of the Do Now list, and blockers are unfinished items waiting on an event
+/// or on another item. When motivations are given only the items under them are included.
+pub(crate) fn standup_markdown(
+    surreal_tables: SurrealTables,
+    now: DateTime<Utc>,
+    under: &[String],
+) -> Result<String, String> {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let do_now_list = DoNowList::new_do_now_list(calculated_data, &now);
+    let motivations = find_motivations(&do_now_list, under)?;
+    let yesterday = previous_working_day(now.with_timezone(&Local).date_naive());
+
+    let mut markdown = String::new();
+    write_yesterday(&mut markdown, &do_now_list, yesterday, &motivations);
+    writeln!(markdown).unwrap();
+    write_today(&mut markdown, &do_now_list, &motivations);
+    writeln!(markdown).unwrap();
+    write_blockers(&mut markdown, &do_now_list, &motivations);
+    Ok(markdown)
+}
+
+/// Prints the stand-up report to stdout
+pub(crate) async fn print_standup(
+    under: &[String],
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), String> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .map_err(|err| format!("Unable to load data: {}", err))?;
+    print!("{}", standup_markdown(surreal_tables, Utc::now(), under)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, Utc};
+
+    use crate::data_storage::surrealdb_layer::{
+        surreal_event::SurrealEvent,
+        surreal_item::{
+            SurrealDependency, SurrealItemBuilder, SurrealItemType, SurrealMotivationKind,
+            SurrealOrderedSubItem,
+        },
+        surreal_tables::SurrealTablesBuilder,
+    };
+
+    use super::{previous_working_day, standup_markdown};
+
+    #[test]
+    fn the_previous_working_day_skips_the_weekend() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 6, d).unwrap();
+        //Monday 3 June 2024 goes back to Friday 31 May
+        assert_eq!(
+            previous_working_day(day(3)),
+            NaiveDate::from_ymd_opt(2024, 5, 31).unwrap()
+        );
+        assert_eq!(previous_working_day(day(5)), day(4));
+    }
+
+    #[test]
+    fn blockers_are_only_from_the_chosen_motivation() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "work").into()))
+                    .summary("Work")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::CoreWork))
+                    .smaller_items_in_priority_order(vec![SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: ("item", "deploy").into(),
+                    }])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "deploy").into()))
+                    .summary("Deploy release")
+                    .item_type(SurrealItemType::Action)
+                    .dependencies(vec![SurrealDependency::AfterEvent(
+                        ("event", "approval").into(),
+                    )])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "paint").into()))
+                    .summary("Paint the fence")
+                    .item_type(SurrealItemType::Action)
+                    .dependencies(vec![SurrealDependency::AfterItem(
+                        ("item", "buy_paint").into(),
+                    )])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "buy_paint").into()))
+                    .summary("Buy paint")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_events(vec![SurrealEvent {
+                id: Some(("event", "approval").into()),
+                version: 0,
+                last_updated: (now - Duration::days(1)).into(),
+                triggered: false,
+                summary: "Release approved".to_string(),
+            }])
+            .build()
+            .unwrap();
+
+        let markdown = standup_markdown(surreal_tables, now, &["work".to_string()]).unwrap();
+        let blockers = markdown
+            .split("## Blockers")
+            .nth(1)
+            .unwrap()
+            .trim()
+            .lines()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            blockers,
+            vec!["- Deploy release is waiting on event \"Release approved\""]
+        );
+    }
+}
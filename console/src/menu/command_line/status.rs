@@ -0,0 +1,365 @@
+use ahash::HashSet;
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_item::SurrealUrgency,
+        surreal_tables::SurrealTables,
+    },
+    menu::http_api::model::ItemRefJson,
+    node::urgency_level_item_with_item_status::UrgencyLevelItemWithItemStatus,
+    systems::do_now_list::DoNowList,
+};
+
+/// Used when `status` is given without `--format`
+pub(crate) const DEFAULT_STATUS_FORMAT: &str = "{current} {elapsed} ({urgent} urgent) {next}";
+
+/// How `status` prints what is being worked on, meant to be run every few seconds by a status bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StatusOutput {
+    /// A single line from a template, see `render_status` for the placeholders
+    Format(String),
+    Json,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct WorkingOnJson {
+    pub(crate) item: ItemRefJson,
+    pub(crate) started: DateTime<Utc>,
+    pub(crate) elapsed_seconds: i64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct NextScheduledJson {
+    pub(crate) start: DateTime<Utc>,
+    pub(crate) item: ItemRefJson,
+}
+
+/// What is being worked on, or what the Do Now list says to work on when nothing is
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct StatusJson {
+    pub(crate) mode: String,
+    pub(crate) working_on: Option<WorkingOnJson>,
+    pub(crate) top_do_now: Option<ItemRefJson>,
+    /// Items on the Do Now list that are definitely urgent or more, each counted once
+    pub(crate) urgent: usize,
+    pub(crate) next_scheduled: Option<NextScheduledJson>,
+}
+
+fn is_urgent(urgency: &SurrealUrgency) -> bool {
+    !matches!(
+        urgency,
+        SurrealUrgency::InTheModeMaybeUrgent | SurrealUrgency::InTheModeByImportance
+    )
+}
+
+pub(crate) fn status_json(surreal_tables: SurrealTables, now: DateTime<Utc>) -> StatusJson {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let do_now_list = DoNowList::new_do_now_list(calculated_data, &now);
+    let items_status = do_now_list.get_all_items_status();
+
+    //A working on record for an item that has since been finished is left over and not shown
+    let working_on = do_now_list
+        .get_base_data()
+        .get_surreal_working_on()
+        .and_then(|working_on| {
+            let item_status = items_status.get(&working_on.item)?;
+            if !item_status.is_active() {
+                return None;
+            }
+            let started: DateTime<Utc> = working_on.when_started.clone().into();
+            Some(WorkingOnJson {
+                item: ItemRefJson::from(item_status.get_item()),
+                started,
+                elapsed_seconds: (now - started).num_seconds().max(0),
+            })
+        });
+
+    let entries = do_now_list
+        .get_ordered_do_now_list()
+        .iter()
+        .flat_map(|slot| match slot {
+            UrgencyLevelItemWithItemStatus::SingleItem(entry) => std::slice::from_ref(entry),
+            UrgencyLevelItemWithItemStatus::MultipleItems(entries) => entries.as_slice(),
+        })
+        .collect::<Vec<_>>();
+    let top_do_now = entries
+        .first()
+        .map(|entry| ItemRefJson::from(entry.get_item_node().get_item()));
+    let urgent = entries
+        .iter()
+        .filter(|entry| is_urgent(&entry.get_urgency_now()))
+        .map(|entry| entry.get_surreal_record_id())
+        .collect::<HashSet<_>>()
+        .len();
+
+    let next_scheduled = do_now_list
+        .get_upcoming()
+        .get_ordered_scheduled_items()
+        .iter()
+        .flatten()
+        .filter(|x| *x.get_scheduled_start() >= now)
+        .min_by_key(|x| *x.get_scheduled_start())
+        .map(|x| NextScheduledJson {
+            start: *x.get_scheduled_start(),
+            item: ItemRefJson::from(x.get_item_status().get_item()),
+        });
+
+    StatusJson {
+        mode: do_now_list.get_current_mode().get_name().to_string(),
+        working_on,
+        top_do_now,
+        urgent,
+        next_scheduled,
+    }
+}
+
+/// Short enough for a status bar, for example "25m" or "1h05m"
+fn compact_elapsed(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+/// Just the time when it is today, otherwise the day as well
+fn compact_start(start: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    let start = start.with_timezone(&Local);
+    if start.date_naive() == now.with_timezone(&Local).date_naive() {
+        start.format("%H:%M").to_string()
+    } else {
+        start.format("%a %H:%M").to_string()
+    }
+}
+
+/// Fills in the template, a placeholder that is not known or that is missing its `}` is an error.
+///
+/// Placeholders:
+/// - `{current}` what is being worked on, or the top of the Do Now list when nothing is
+/// - `{elapsed}` how long it has been worked on, empty when nothing is
+/// - `{urgent}` how many urgent items are on the Do Now list
+/// - `{next}` when the next scheduled item starts and its summary, empty when nothing is scheduled
+/// - `{next_time}` and `{next_summary}` the two halves of `{next}`
+/// - `{mode}` the current mode
+///
+/// `{{` and `}}` print a brace. Whitespace on either side of a placeholder that is empty is
+/// collapsed into what was before it, the rest of the template is kept as it is.
+pub(crate) fn render_status(
+    template: &str,
+    status: &StatusJson,
+    now: &DateTime<Utc>,
+) -> Result<String, String> {
+    let current = status
+        .working_on
+        .as_ref()
+        .map(|x| &x.item)
+        .or(status.top_do_now.as_ref())
+        .map(|x| x.summary.clone())
+        .unwrap_or_default();
+    let next_time = status
+        .next_scheduled
+        .as_ref()
+        .map(|x| compact_start(&x.start, now))
+        .unwrap_or_default();
+    let next_summary = status
+        .next_scheduled
+        .as_ref()
+        .map(|x| x.item.summary.clone())
+        .unwrap_or_default();
+
+    let mut rendered = String::new();
+    //The whitespace taken off before an empty placeholder, put back if anything follows it
+    let mut after_empty: Option<String> = None;
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        let text = match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                "{".to_string()
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                "}".to_string()
+            }
+            '{' => {
+                let mut name = String::new();
+                let mut closed = false;
+                for x in chars.by_ref() {
+                    if x == '}' {
+                        closed = true;
+                        break;
+                    }
+                    name.push(x);
+                }
+                if !closed {
+                    return Err(format!(
+                        "The placeholder {{{} in the format is missing its }}",
+                        name
+                    ));
+                }
+                match name.as_str() {
+                    "current" => current.clone(),
+                    "elapsed" => status
+                        .working_on
+                        .as_ref()
+                        .map(|x| compact_elapsed(x.elapsed_seconds))
+                        .unwrap_or_default(),
+                    "urgent" => status.urgent.to_string(),
+                    "next" if next_time.is_empty() => String::new(),
+                    "next" => format!("{} {}", next_time, next_summary),
+                    "next_time" => next_time.clone(),
+                    "next_summary" => next_summary.clone(),
+                    "mode" => status.mode.clone(),
+                    _ => return Err(format!("Unknown placeholder {{{}}} in the format", name)),
+                }
+            }
+            c if c.is_whitespace() && after_empty.is_some() => continue,
+            c => c.to_string(),
+        };
+        if text.is_empty() {
+            let removed = rendered.split_off(rendered.trim_end().len());
+            after_empty = match after_empty {
+                Some(before) if removed.is_empty() => Some(before),
+                _ => Some(removed),
+            };
+        } else {
+            if let Some(before) = after_empty.take()
+                && !rendered.is_empty()
+            {
+                rendered.push_str(&before);
+            }
+            rendered.push_str(&text);
+        }
+    }
+    Ok(rendered)
+}
+
+/// Prints one line, or JSON, to stdout
+pub(crate) async fn print_status(
+    output: &StatusOutput,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), String> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .map_err(|err| format!("Unable to load data: {}", err))?;
+    let now = Utc::now();
+    let status = status_json(surreal_tables, now);
+    match output {
+        StatusOutput::Format(template) => println!("{}", render_status(template, &status, &now)?),
+        StatusOutput::Json => println!(
+            "{}",
+            serde_json::to_string(&status).map_err(|err| err.to_string())?
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::{
+        data_storage::surrealdb_layer::{
+            surreal_item::{SurrealItemBuilder, SurrealItemType},
+            surreal_tables::SurrealTablesBuilder,
+            surreal_working_on::SurrealWorkingOn,
+        },
+        menu::http_api::model::ItemRefJson,
+    };
+
+    use super::{DEFAULT_STATUS_FORMAT, StatusJson, WorkingOnJson, render_status, status_json};
+
+    fn status(working_on: Option<WorkingOnJson>) -> StatusJson {
+        StatusJson {
+            mode: "Work".to_string(),
+            working_on,
+            top_do_now: Some(ItemRefJson {
+                id: "item:top".to_string(),
+                summary: "Answer email".to_string(),
+            }),
+            urgent: 2,
+            next_scheduled: None,
+        }
+    }
+
+    #[test]
+    fn render_status_fills_in_placeholders_and_collapses_empty_ones() {
+        let now = Utc::now();
+        assert_eq!(
+            render_status(DEFAULT_STATUS_FORMAT, &status(None), &now).unwrap(),
+            "Answer email (2 urgent)"
+        );
+
+        let working_on = WorkingOnJson {
+            item: ItemRefJson {
+                id: "item:report".to_string(),
+                summary: "Write report".to_string(),
+            },
+            started: now - Duration::minutes(65),
+            elapsed_seconds: 65 * 60,
+        };
+        assert_eq!(
+            render_status(
+                "{{{mode}}} {current} {elapsed}",
+                &status(Some(working_on)),
+                &now
+            )
+            .unwrap(),
+            "{Work} Write report 1h05m"
+        );
+
+        assert_eq!(
+            render_status("[{mode}]  {elapsed} | {urgent}  ", &status(None), &now).unwrap(),
+            "[Work]  | 2  "
+        );
+        assert_eq!(
+            render_status("{elapsed}\t{mode}:  {current}", &status(None), &now).unwrap(),
+            "Work:  Answer email"
+        );
+
+        assert_eq!(
+            render_status("{nope}", &status(None), &now).err(),
+            Some("Unknown placeholder {nope} in the format".to_string())
+        );
+        assert_eq!(
+            render_status("{current} {mode", &status(None), &now).err(),
+            Some("The placeholder {mode in the format is missing its }".to_string())
+        );
+    }
+
+    #[test]
+    fn status_shows_what_is_being_worked_on() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "report").into()))
+                    .summary("Write report")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_working_on(vec![SurrealWorkingOn {
+                id: None,
+                version: 0,
+                item: ("item", "report").into(),
+                when_started: (now - Duration::minutes(25)).into(),
+            }])
+            .build()
+            .unwrap();
+
+        let status = status_json(surreal_tables, now);
+        let working_on = status.working_on.unwrap();
+        assert_eq!(working_on.item.summary, "Write report");
+        assert_eq!(working_on.elapsed_seconds, 25 * 60);
+        assert_eq!(status.urgent, 0);
+        assert_eq!(status.next_scheduled, None);
+    }
+}
@@ -0,0 +1,373 @@
+pub(crate) mod commands;
+pub(crate) mod model;
+
+use std::net::{Ipv4Addr, SocketAddr};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use tokio::{net::TcpListener, sync::mpsc::Sender};
+
+use crate::data_storage::surrealdb_layer::{
+    data_layer_commands::{CommandRejection, DataLayerCommands},
+    surreal_tables::SurrealTables,
+};
+
+use commands::{ApiCommand, parse_id};
+use model::{
+    DoNowListJson, ItemDetailJson, ItemJson, UpcomingJson, do_now_list_json,
+    item_detail_json_by_id, query_json, search_json, upcoming_json,
+};
+
+/// Describes every route, kept by hand next to the handlers so it is reviewed along with them
+const OPENAPI: &str = include_str!("http_api/openapi.json");
+
+type ApiError = (StatusCode, String);
+
+/// Binding to 127.0.0.1 keeps other computers out, but a web page can still reach the API by
+/// pointing a name it controls at 127.0.0.1. So the Host header has to be this computer and every
+/// request has to carry the token that is printed when serving starts.
+#[derive(Clone)]
+struct ApiAccess {
+    port: u16,
+    token: String,
+}
+
+impl ApiAccess {
+    fn new(port: u16) -> Self {
+        Self {
+            port,
+            token: format!("{:032x}", rand::random::<u128>()),
+        }
+    }
+
+    fn check(&self, headers: &HeaderMap) -> Result<(), ApiError> {
+        let allowed_hosts = [
+            format!("127.0.0.1:{}", self.port),
+            format!("localhost:{}", self.port),
+        ];
+        let host = headers.get(header::HOST).and_then(|x| x.to_str().ok());
+        if !host.is_some_and(|host| allowed_hosts.iter().any(|x| x == host)) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                format!(
+                    "The Host header must be one of {}",
+                    allowed_hosts.join(", ")
+                ),
+            ));
+        }
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.strip_prefix("Bearer "));
+        if token != Some(self.token.as_str()) {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                "Send the token that serve printed when it started as \"Authorization: Bearer <token>\""
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+async fn check_access(
+    State(access): State<ApiAccess>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    access.check(request.headers())?;
+    Ok(next.run(request).await)
+}
+
+async fn load_surreal_tables(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<SurrealTables, ApiError> {
+    SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .map_err(|err| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Unable to load data: {}", err),
+            )
+        })
+}
+
+async fn get_openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI)
+}
+
+async fn get_do_now_list(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+) -> Result<Json<DoNowListJson>, ApiError> {
+    let surreal_tables = load_surreal_tables(&send_to_data_storage_layer).await?;
+    Ok(Json(do_now_list_json(surreal_tables, Utc::now())))
+}
+
+async fn get_upcoming(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+) -> Result<Json<UpcomingJson>, ApiError> {
+    let surreal_tables = load_surreal_tables(&send_to_data_storage_layer).await?;
+    Ok(Json(upcoming_json(surreal_tables, Utc::now())))
+}
+
+#[derive(Deserialize)]
+struct ItemsParameters {
+    /// Same as `--list`, all active items when not given
+    #[serde(default)]
+    query: String,
+}
+
+async fn get_items(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Query(parameters): Query<ItemsParameters>,
+) -> Result<Json<Vec<ItemJson>>, ApiError> {
+    let surreal_tables = load_surreal_tables(&send_to_data_storage_layer).await?;
+    query_json(surreal_tables, Utc::now(), &parameters.query)
+        .map(Json)
+        .map_err(|err| (StatusCode::BAD_REQUEST, err))
+}
+
+async fn get_item(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Path(id): Path<String>,
+) -> Result<Json<ItemDetailJson>, ApiError> {
+    let id = parse_id(&id).map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+    let surreal_tables = load_surreal_tables(&send_to_data_storage_layer).await?;
+    item_detail_json_by_id(surreal_tables, Utc::now(), &id)
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("No item with the id {}", id)))
+}
+
+#[derive(Deserialize)]
+struct SearchParameters {
+    text: String,
+}
+
+async fn get_search(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Query(parameters): Query<SearchParameters>,
+) -> Result<Json<Vec<ItemJson>>, ApiError> {
+    let surreal_tables = load_surreal_tables(&send_to_data_storage_layer).await?;
+    Ok(Json(search_json(
+        surreal_tables,
+        Utc::now(),
+        &parameters.text,
+    )))
+}
+
+/// Commands are run in the order they are received, so anything read after this returns already
+/// sees the change
+async fn post_command(
+    State(send_to_data_storage_layer): State<Sender<DataLayerCommands>>,
+    Json(command): Json<ApiCommand>,
+) -> Result<StatusCode, ApiError> {
+    let command = command
+        .into_data_layer_command(Utc::now())
+        .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
+    command
+        .check_and_run(&send_to_data_storage_layer)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Data storage layer closed".to_string(),
+            )
+        })?
+        .map_err(|rejection| match rejection {
+            CommandRejection::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            CommandRejection::Invalid(message) => (StatusCode::BAD_REQUEST, message),
+            CommandRejection::Conflict(message) => (StatusCode::CONFLICT, message),
+        })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn router(send_to_data_storage_layer: Sender<DataLayerCommands>, access: ApiAccess) -> Router {
+    Router::new()
+        .route("/api/openapi.json", get(get_openapi))
+        .route("/api/do-now", get(get_do_now_list))
+        .route("/api/upcoming", get(get_upcoming))
+        .route("/api/items", get(get_items))
+        .route("/api/items/{id}", get(get_item))
+        .route("/api/search", get(get_search))
+        .route("/api/commands", post(post_command))
+        .with_state(send_to_data_storage_layer)
+        .layer(middleware::from_fn_with_state(access, check_access))
+}
+
+/// Serves the JSON API on 127.0.0.1 only, so it is reachable from front ends on this computer and
+/// nowhere else, until Ctrl-C is pressed. The token front ends need is new each time.
+pub(crate) async fn serve_http_api(
+    port: u16,
+    send_to_data_storage_layer: Sender<DataLayerCommands>,
+) -> std::io::Result<()> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let listener = TcpListener::bind(address).await?;
+    let address = listener.local_addr()?;
+    let access = ApiAccess::new(address.port());
+    eprintln!(
+        "Serving the API on http://{}/api, the OpenAPI description is at http://{}/api/openapi.json. Press Ctrl-C to stop.",
+        address, address
+    );
+    eprintln!(
+        "Send \"Authorization: Bearer {}\" with every request, the token changes each time serve starts.",
+        access.token
+    );
+    axum::serve(listener, router(send_to_data_storage_layer, access))
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json,
+        extract::State,
+        http::{HeaderMap, StatusCode, header},
+    };
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        data_storage::surrealdb_layer::{
+            data_layer_commands::{
+                DataLayerCommands, SurrealDbConnectionConfig, data_storage_start_and_run,
+            },
+            surreal_tables::SurrealTables,
+        },
+        new_item::NewItem,
+    };
+
+    use super::{ApiAccess, OPENAPI, commands::ApiCommand, post_command};
+
+    fn command(json: &str) -> Json<ApiCommand> {
+        Json(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn openapi_describes_every_route() {
+        let openapi: serde_json::Value = serde_json::from_str(OPENAPI).unwrap();
+        let paths = openapi["paths"].as_object().unwrap();
+        for path in [
+            "/api/openapi.json",
+            "/api/do-now",
+            "/api/upcoming",
+            "/api/items",
+            "/api/items/{id}",
+            "/api/search",
+            "/api/commands",
+        ] {
+            assert!(paths.contains_key(path), "{} is not described", path);
+        }
+    }
+
+    #[test]
+    fn requests_need_this_computer_as_the_host_and_the_token() {
+        let access = ApiAccess {
+            port: 7878,
+            token: "secret".to_string(),
+        };
+        let mut headers = HeaderMap::new();
+        assert_eq!(StatusCode::FORBIDDEN, access.check(&headers).unwrap_err().0);
+
+        headers.insert(header::HOST, "127.0.0.1:7878".parse().unwrap());
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            access.check(&headers).unwrap_err().0
+        );
+        headers.insert(header::AUTHORIZATION, "Bearer wrong".parse().unwrap());
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            access.check(&headers).unwrap_err().0
+        );
+        headers.insert(header::AUTHORIZATION, "Bearer secret".parse().unwrap());
+        assert_eq!(Ok(()), access.check(&headers));
+
+        headers.insert(header::HOST, "localhost:7878".parse().unwrap());
+        assert_eq!(Ok(()), access.check(&headers));
+        //What a web page gets when it points its own name at 127.0.0.1
+        headers.insert(header::HOST, "attacker.example:7878".parse().unwrap());
+        assert_eq!(StatusCode::FORBIDDEN, access.check(&headers).unwrap_err().0);
+        headers.insert(header::HOST, "localhost:8080".parse().unwrap());
+        assert_eq!(StatusCode::FORBIDDEN, access.check(&headers).unwrap_err().0);
+    }
+
+    #[tokio::test]
+    async fn commands_for_items_that_do_not_exist_are_refused_and_the_data_layer_keeps_running() {
+        let (sender, receiver) = mpsc::channel(1);
+        let data_storage_join_handle = tokio::spawn(data_storage_start_and_run(
+            receiver,
+            SurrealDbConnectionConfig {
+                endpoint: "mem://".to_string(),
+                namespace: "TaskOnPurpose".to_string(),
+                database: "http_api_test".to_string(),
+                auth: None,
+            },
+        ));
+
+        let (status, message) = post_command(
+            State(sender.clone()),
+            command(r#"{"command": "finish_item", "item": "item:nope"}"#),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(message, "No item with the id item:nope");
+
+        sender
+            .send(DataLayerCommands::NewItem(NewItem::new(
+                "Parent".to_string(),
+                Utc::now(),
+            )))
+            .await
+            .unwrap();
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        let parent = surreal_tables.surreal_items[0].id.clone().unwrap();
+        let (status, _) = post_command(
+            State(sender.clone()),
+            command(&format!(
+                r#"{{"command": "parent_item_with_existing_item", "child": "{}", "parent": "{}", "higher_importance_than_this": "{}"}}"#,
+                parent, parent, parent
+            )),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let status = post_command(
+            State(sender.clone()),
+            command(&format!(
+                r#"{{"command": "capture", "summary": "Child", "parent": "{}"}}"#,
+                parent
+            )),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = post_command(
+            State(sender.clone()),
+            command(&format!(
+                r#"{{"command": "delete_item", "item": "{}"}}"#,
+                parent
+            )),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let surreal_tables = SurrealTables::new(&sender).await.unwrap();
+        assert_eq!(surreal_tables.surreal_items.len(), 2);
+
+        drop(sender);
+        data_storage_join_handle.await.unwrap();
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use surrealdb::RecordId;
+
+use crate::{
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_in_the_moment_priority::SurrealAction,
+        surreal_item::SurrealItem, surreal_time_spent::SurrealWhyInScope,
+    },
+    new_item::NewItem,
+    new_time_spent::NewTimeSpent,
+};
+
+/// The changes a front end can make through `POST /api/commands`. Ids are given the way they are
+/// returned, for example `item:abc123`, and times are RFC 3339 with now being the default.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "command", rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum ApiCommand {
+    /// A new item, under `parent` when it is given or in the inbox otherwise
+    Capture {
+        summary: String,
+        #[serde(default)]
+        tags: Vec<String>,
+        parent: Option<String>,
+    },
+    FinishItem {
+        item: String,
+        when_finished: Option<DateTime<Utc>>,
+    },
+    ReactivateItem {
+        item: String,
+    },
+    /// Refused with 409 Conflict when the item has time spent on it or has smaller items
+    DeleteItem {
+        item: String,
+    },
+    UpdateSummary {
+        item: String,
+        summary: String,
+    },
+    UpdateTags {
+        item: String,
+        tags: Vec<String>,
+    },
+    ParentItemWithExistingItem {
+        child: String,
+        parent: String,
+        higher_importance_than_this: Option<String>,
+    },
+    ParentItemRemoveParent {
+        child: String,
+        parent_to_remove: String,
+    },
+    SnoozeItem {
+        item: String,
+        until: DateTime<Utc>,
+    },
+    UnsnoozeItem {
+        item: String,
+    },
+    SetWorkingOn {
+        item: String,
+        when_started: Option<DateTime<Utc>>,
+    },
+    ClearWorkingOn,
+    /// Time spent making progress on the items, logged as coming from menu navigation
+    RecordTimeSpent {
+        working_on: Vec<String>,
+        when_started: DateTime<Utc>,
+        when_stopped: DateTime<Utc>,
+    },
+}
+
+/// Only item ids are accepted, an id from another table would otherwise be treated as an item
+pub(super) fn parse_id(id: &str) -> Result<RecordId, String> {
+    id.parse::<RecordId>()
+        .ok()
+        .filter(|x| x.table() == SurrealItem::TABLE_NAME)
+        .ok_or_else(|| format!("\"{}\" is not an item id, ids look like item:abc123", id))
+}
+
+impl ApiCommand {
+    pub(crate) fn into_data_layer_command(
+        self,
+        now: DateTime<Utc>,
+    ) -> Result<DataLayerCommands, String> {
+        let command = match self {
+            ApiCommand::Capture {
+                summary,
+                tags,
+                parent,
+            } => {
+                if summary.trim().is_empty() {
+                    return Err("summary cannot be empty".to_string());
+                }
+                let mut new_item = NewItem::new(summary.trim().to_string(), now);
+                new_item.tags = tags;
+                match parent {
+                    Some(parent) => DataLayerCommands::ParentItemWithANewChildItem {
+                        child: new_item,
+                        parent: parse_id(&parent)?,
+                        higher_importance_than_this: None,
+                    },
+                    None => DataLayerCommands::NewItem(new_item),
+                }
+            }
+            ApiCommand::FinishItem {
+                item,
+                when_finished,
+            } => DataLayerCommands::FinishItem {
+                item: parse_id(&item)?,
+                when_finished: when_finished.unwrap_or(now).into(),
+            },
+            ApiCommand::ReactivateItem { item } => DataLayerCommands::ReactivateItem {
+                item: parse_id(&item)?,
+            },
+            ApiCommand::DeleteItem { item } => DataLayerCommands::DeleteItem(parse_id(&item)?),
+            ApiCommand::UpdateSummary { item, summary } => {
+                if summary.trim().is_empty() {
+                    return Err("summary cannot be empty".to_string());
+                }
+                DataLayerCommands::UpdateSummary(parse_id(&item)?, summary.trim().to_string())
+            }
+            ApiCommand::UpdateTags { item, tags } => {
+                DataLayerCommands::UpdateTags(parse_id(&item)?, tags)
+            }
+            ApiCommand::ParentItemWithExistingItem {
+                child,
+                parent,
+                higher_importance_than_this,
+            } => DataLayerCommands::ParentItemWithExistingItem {
+                child: parse_id(&child)?,
+                parent: parse_id(&parent)?,
+                higher_importance_than_this: higher_importance_than_this
+                    .map(|x| parse_id(&x))
+                    .transpose()?,
+            },
+            ApiCommand::ParentItemRemoveParent {
+                child,
+                parent_to_remove,
+            } => DataLayerCommands::ParentItemRemoveParent {
+                child: parse_id(&child)?,
+                parent_to_remove: parse_id(&parent_to_remove)?,
+            },
+            ApiCommand::SnoozeItem { item, until } => DataLayerCommands::SnoozeItem {
+                item: parse_id(&item)?,
+                until: until.into(),
+            },
+            ApiCommand::UnsnoozeItem { item } => DataLayerCommands::UnsnoozeItem(parse_id(&item)?),
+            ApiCommand::SetWorkingOn { item, when_started } => DataLayerCommands::SetWorkingOn {
+                item: parse_id(&item)?,
+                when_started: when_started.unwrap_or(now).into(),
+            },
+            ApiCommand::ClearWorkingOn => DataLayerCommands::ClearWorkingOn,
+            ApiCommand::RecordTimeSpent {
+                working_on,
+                when_started,
+                when_stopped,
+            } => {
+                if when_stopped < when_started {
+                    return Err("when_stopped cannot be before when_started".to_string());
+                }
+                DataLayerCommands::RecordTimeSpent(NewTimeSpent {
+                    working_on: working_on
+                        .iter()
+                        .map(|x| parse_id(x).map(SurrealAction::MakeProgress))
+                        .collect::<Result<_, _>>()?,
+                    urgency: None,
+                    why_in_scope: vec![SurrealWhyInScope::MenuNavigation],
+                    when_started,
+                    when_stopped,
+                })
+            }
+        };
+        Ok(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use surrealdb::RecordId;
+
+    use crate::data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands;
+
+    use super::ApiCommand;
+
+    #[test]
+    fn capture_with_a_parent_becomes_a_new_child_item() {
+        let command: ApiCommand = serde_json::from_str(
+            r#"{"command": "capture", "summary": " Buy milk ", "tags": ["errand"], "parent": "item:groceries"}"#,
+        )
+        .unwrap();
+
+        let parent: RecordId = ("item", "groceries").into();
+        match command.into_data_layer_command(Utc::now()) {
+            Ok(DataLayerCommands::ParentItemWithANewChildItem {
+                child,
+                parent: actual_parent,
+                higher_importance_than_this: None,
+            }) => {
+                assert_eq!(child.summary, "Buy milk");
+                assert_eq!(child.tags, vec!["errand".to_string()]);
+                assert_eq!(actual_parent, parent);
+            }
+            _ => panic!("Expected a new child item"),
+        }
+    }
+
+    #[test]
+    fn commands_without_fields_and_with_defaults_parse() {
+        let command: ApiCommand =
+            serde_json::from_str(r#"{"command": "clear_working_on"}"#).unwrap();
+        assert_eq!(command, ApiCommand::ClearWorkingOn);
+
+        let now = Utc::now();
+        let command: ApiCommand =
+            serde_json::from_str(r#"{"command": "finish_item", "item": "item:done"}"#).unwrap();
+        match command.into_data_layer_command(now) {
+            Ok(DataLayerCommands::FinishItem {
+                item,
+                when_finished,
+            }) => {
+                assert_eq!(item, ("item", "done").into());
+                assert_eq!(when_finished, now.into());
+            }
+            _ => panic!("Expected finish item"),
+        }
+    }
+
+    #[test]
+    fn invalid_commands_are_explained() {
+        assert!(serde_json::from_str::<ApiCommand>(r#"{"command": "launch_rocket"}"#).is_err());
+
+        let command: ApiCommand = serde_json::from_str(
+            r#"{"command": "update_summary", "item": "item:x", "summary": " "}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            command.into_data_layer_command(Utc::now()).err(),
+            Some("summary cannot be empty".to_string())
+        );
+
+        let command: ApiCommand =
+            serde_json::from_str(r#"{"command": "unsnooze_item", "item": "not an id"}"#).unwrap();
+        assert_eq!(
+            command.into_data_layer_command(Utc::now()).err(),
+            Some("\"not an id\" is not an item id, ids look like item:abc123".to_string())
+        );
+
+        let command: ApiCommand =
+            serde_json::from_str(r#"{"command": "finish_item", "item": "time_spent:abc"}"#)
+                .unwrap();
+        assert_eq!(
+            command.into_data_layer_command(Utc::now()).err(),
+            Some("\"time_spent:abc\" is not an item id, ids look like item:abc123".to_string())
+        );
+    }
+}
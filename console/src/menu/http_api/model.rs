@@ -0,0 +1,458 @@
+use ahash::HashMap;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use surrealdb::RecordId;
+
+use crate::{
+    base_data::{BaseData, item::Item},
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        surreal_item::NotesLocation,
+        surreal_smart_list::{SurrealSmartListItemType, SurrealSmartListUrgency},
+        surreal_tables::SurrealTables,
+    },
+    node::{
+        Filter,
+        action_with_item_status::ActionWithItemStatus,
+        item_status::{DependencyWithItemNode, ItemStatus},
+        urgency_level_item_with_item_status::UrgencyLevelItemWithItemStatus,
+        why_in_scope_and_action_with_item_status::{WhyInScope, WhyInScopeAndActionWithItemStatus},
+    },
+    systems::{
+        do_now_list::DoNowList,
+        item_query::{
+            find_items_matching_query, item_type_query_name, parse_item_query, urgency_query_name,
+        },
+    },
+};
+
+/// Just enough to identify an item and show it to someone
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct ItemRefJson {
+    pub(crate) id: String,
+    pub(crate) summary: String,
+}
+
+impl From<&Item<'_>> for ItemRefJson {
+    fn from(item: &Item<'_>) -> Self {
+        ItemRefJson {
+            id: item.get_surreal_record_id().to_string(),
+            summary: item.get_summary().to_string(),
+        }
+    }
+}
+
+/// An item with the same columns as `--export`, type and urgency use the names from the query
+/// language
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct ItemJson {
+    pub(crate) id: String,
+    pub(crate) summary: String,
+    pub(crate) item_type: &'static str,
+    pub(crate) urgency: &'static str,
+    pub(crate) ready: bool,
+    pub(crate) tags: Vec<String>,
+    pub(crate) created: DateTime<Utc>,
+    pub(crate) finished: Option<DateTime<Utc>>,
+}
+
+impl From<&ItemStatus<'_>> for ItemJson {
+    fn from(item_status: &ItemStatus<'_>) -> Self {
+        let item = item_status.get_item();
+        let urgency = match item_status.get_urgency_now() {
+            Some(urgency) => SurrealSmartListUrgency::from(urgency),
+            None => SurrealSmartListUrgency::InTheModeByImportance,
+        };
+        ItemJson {
+            id: item_status.get_surreal_record_id().to_string(),
+            summary: item.get_summary().to_string(),
+            item_type: item_type_query_name(&SurrealSmartListItemType::from(item.get_item_type())),
+            urgency: urgency_query_name(&urgency),
+            ready: item_status.is_active() && !item_status.has_dependencies(Filter::Active),
+            tags: item.get_tags().to_vec(),
+            created: *item.get_created(),
+            finished: *item.get_finished_at(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct DoNowEntryJson {
+    /// What the Do Now list is asking to be done with the item, for example `make_progress`
+    pub(crate) action: &'static str,
+    pub(crate) why_in_scope: Vec<&'static str>,
+    pub(crate) urgency: &'static str,
+    pub(crate) item: ItemJson,
+}
+
+impl From<&WhyInScopeAndActionWithItemStatus<'_>> for DoNowEntryJson {
+    fn from(entry: &WhyInScopeAndActionWithItemStatus<'_>) -> Self {
+        let (action, item_status) = match entry.get_action() {
+            ActionWithItemStatus::SetReadyAndUrgency(x) => ("set_ready_and_urgency", x),
+            ActionWithItemStatus::ParentBackToAMotivation(x) => ("parent_back_to_a_motivation", x),
+            ActionWithItemStatus::ItemNeedsAClassification(x) => ("item_needs_a_classification", x),
+            ActionWithItemStatus::ReviewItem(x) => ("review_item", x),
+            ActionWithItemStatus::PickItemReviewFrequency(x) => ("pick_item_review_frequency", x),
+            ActionWithItemStatus::MakeProgress(x) => ("make_progress", x),
+        };
+        let mut why_in_scope = entry
+            .get_why_in_scope()
+            .iter()
+            .map(|x| match x {
+                WhyInScope::Importance => "importance",
+                WhyInScope::Urgency => "urgency",
+                WhyInScope::MenuNavigation => "menu_navigation",
+            })
+            .collect::<Vec<_>>();
+        why_in_scope.sort();
+        DoNowEntryJson {
+            action,
+            why_in_scope,
+            urgency: urgency_query_name(&SurrealSmartListUrgency::from(&entry.get_urgency_now())),
+            item: ItemJson::from(*item_status),
+        }
+    }
+}
+
+/// The Do Now list in order, each slot holds one entry or several entries to pick between
+#[derive(Debug, Serialize)]
+pub(crate) struct DoNowListJson {
+    pub(crate) mode: String,
+    pub(crate) slots: Vec<Vec<DoNowEntryJson>>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ScheduledItemJson {
+    pub(crate) start: DateTime<Utc>,
+    pub(crate) end: DateTime<Utc>,
+    pub(crate) item: ItemRefJson,
+}
+
+/// Scheduled items in the order they are planned or, when they do not fit, the items in conflict
+#[derive(Debug, Serialize)]
+pub(crate) struct UpcomingJson {
+    pub(crate) scheduled: Vec<ScheduledItemJson>,
+    pub(crate) conflicts: Vec<ItemRefJson>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct ItemDetailJson {
+    pub(crate) item: ItemJson,
+    pub(crate) notes: Option<String>,
+    /// Every path from the item up to something with no parent, the closest parent first
+    pub(crate) parent_chains: Vec<Vec<ItemRefJson>>,
+    pub(crate) children: Vec<ItemRefJson>,
+    pub(crate) waiting_on: Vec<ItemRefJson>,
+}
+
+fn notes_json(notes_location: &NotesLocation) -> Option<String> {
+    match notes_location {
+        NotesLocation::None => None,
+        NotesLocation::OneNoteLink(x) | NotesLocation::WebLink(x) | NotesLocation::Markdown(x) => {
+            Some(x.clone())
+        }
+    }
+}
+
+fn parent_chains(
+    item_status: &ItemStatus<'_>,
+    items_status: &HashMap<&RecordId, ItemStatus<'_>>,
+) -> Vec<Vec<ItemRefJson>> {
+    item_status
+        .get_parents(Filter::Active)
+        .flat_map(|parent| {
+            let parent_status = items_status
+                .get(parent.get_surreal_record_id())
+                .expect("Every item has a status");
+            let above = parent_chains(parent_status, items_status);
+            if above.is_empty() {
+                vec![vec![ItemRefJson::from(parent.get_item())]]
+            } else {
+                above
+                    .into_iter()
+                    .map(|chain| {
+                        std::iter::once(ItemRefJson::from(parent.get_item()))
+                            .chain(chain)
+                            .collect()
+                    })
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+fn item_detail_json(
+    item_status: &ItemStatus<'_>,
+    items_status: &HashMap<&RecordId, ItemStatus<'_>>,
+) -> ItemDetailJson {
+    let mut children = item_status
+        .get_children(Filter::Active)
+        .map(|x| ItemRefJson::from(x.get_item()))
+        .collect::<Vec<_>>();
+    children.sort_by(|a, b| a.summary.cmp(&b.summary));
+    let waiting_on = item_status
+        .get_dependencies(Filter::Active)
+        .filter_map(|x| match x {
+            DependencyWithItemNode::AfterItem(item_node) => {
+                Some(ItemRefJson::from(item_node.get_item()))
+            }
+            _ => None,
+        })
+        .collect();
+    ItemDetailJson {
+        item: ItemJson::from(item_status),
+        notes: notes_json(item_status.get_item().get_notes_location()),
+        parent_chains: parent_chains(item_status, items_status),
+        children,
+        waiting_on,
+    }
+}
+
+pub(crate) fn do_now_list_json(surreal_tables: SurrealTables, now: DateTime<Utc>) -> DoNowListJson {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let do_now_list = DoNowList::new_do_now_list(calculated_data, &now);
+    let slots = do_now_list
+        .get_ordered_do_now_list()
+        .iter()
+        .map(|slot| match slot {
+            UrgencyLevelItemWithItemStatus::SingleItem(entry) => vec![DoNowEntryJson::from(entry)],
+            UrgencyLevelItemWithItemStatus::MultipleItems(entries) => {
+                entries.iter().map(DoNowEntryJson::from).collect()
+            }
+        })
+        .collect();
+    DoNowListJson {
+        mode: do_now_list.get_current_mode().get_name().to_string(),
+        slots,
+    }
+}
+
+pub(crate) fn upcoming_json(surreal_tables: SurrealTables, now: DateTime<Utc>) -> UpcomingJson {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let do_now_list = DoNowList::new_do_now_list(calculated_data, &now);
+    let upcoming = do_now_list.get_upcoming();
+    UpcomingJson {
+        scheduled: upcoming
+            .get_ordered_scheduled_items()
+            .iter()
+            .flatten()
+            .map(|x| ScheduledItemJson {
+                start: *x.get_scheduled_start(),
+                end: *x.get_scheduled_end(),
+                item: ItemRefJson::from(x.get_item_status().get_item()),
+            })
+            .collect(),
+        conflicts: upcoming
+            .get_conflicts()
+            .iter()
+            .map(|x| ItemRefJson::from(*x))
+            .collect(),
+    }
+}
+
+/// None when there is no item with that id
+pub(crate) fn item_detail_json_by_id(
+    surreal_tables: SurrealTables,
+    now: DateTime<Utc>,
+    id: &RecordId,
+) -> Option<ItemDetailJson> {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let items_status = calculated_data.get_items_status();
+    items_status
+        .get(id)
+        .map(|item_status| item_detail_json(item_status, items_status))
+}
+
+/// Fuzzy search over summaries and notes, best matches first
+pub(crate) fn search_json(
+    surreal_tables: SurrealTables,
+    now: DateTime<Utc>,
+    search_for: &str,
+) -> Vec<ItemJson> {
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    let items_status = calculated_data.get_items_status();
+    calculated_data
+        .get_base_data()
+        .get_search_index()
+        .search(search_for)
+        .iter()
+        .map(|x| {
+            let item_status = items_status
+                .get(x.get_item().get_surreal_record_id())
+                .expect("Every item has a status");
+            ItemJson::from(item_status)
+        })
+        .collect()
+}
+
+/// The items matching a query from the query language
+pub(crate) fn query_json(
+    surreal_tables: SurrealTables,
+    now: DateTime<Utc>,
+    query: &str,
+) -> Result<Vec<ItemJson>, String> {
+    let query = parse_item_query(query)?;
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+    let calculated_data = CalculatedData::new_from_base_data(base_data);
+    Ok(
+        find_items_matching_query(&query, calculated_data.get_items_status())
+            .into_iter()
+            .map(ItemJson::from)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use surrealdb::RecordId;
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{
+                NotesLocation, SurrealDependency, SurrealItemBuilder, SurrealItemType,
+                SurrealOrderedSubItem,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{ItemJson, ItemRefJson, item_detail_json};
+
+    fn item_ref(id: &str, summary: &str) -> ItemRefJson {
+        ItemRefJson {
+            id: format!("item:{}", id),
+            summary: summary.to_string(),
+        }
+    }
+
+    #[test]
+    fn item_detail_has_every_parent_chain_up_to_the_top() {
+        let now = Utc::now();
+        let sub_item = |id: &str| SurrealOrderedSubItem::SubItem {
+            surreal_item_id: ("item", id).into(),
+        };
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "health").into()))
+                    .summary("Health")
+                    .item_type(SurrealItemType::Motivation(Default::default()))
+                    .smaller_items_in_priority_order(vec![sub_item("run")])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "race").into()))
+                    .summary("Finish a 10k")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .smaller_items_in_priority_order(vec![sub_item("run")])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "fun").into()))
+                    .summary("Have fun")
+                    .item_type(SurrealItemType::Motivation(Default::default()))
+                    .smaller_items_in_priority_order(vec![sub_item("race")])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "run").into()))
+                    .summary("Go for a run")
+                    .item_type(SurrealItemType::Action)
+                    .notes_location(NotesLocation::Markdown("Bring water".to_string()))
+                    .smaller_items_in_priority_order(vec![sub_item("shoes")])
+                    .dependencies(vec![SurrealDependency::AfterItem(
+                        ("item", "weather").into(),
+                    )])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "shoes").into()))
+                    .summary("Find running shoes")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "weather").into()))
+                    .summary("Check the weather")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let calculated_data = CalculatedData::new_from_base_data(
+            BaseData::new_from_surreal_tables(surreal_tables, now),
+        );
+        let items_status = calculated_data.get_items_status();
+        let run: RecordId = ("item", "run").into();
+
+        let mut detail = item_detail_json(items_status.get(&run).unwrap(), items_status);
+        detail
+            .parent_chains
+            .sort_by(|a, b| a[0].summary.cmp(&b[0].summary));
+
+        assert_eq!(detail.item.summary, "Go for a run");
+        assert_eq!(detail.notes.as_deref(), Some("Bring water"));
+        assert_eq!(
+            detail.parent_chains,
+            vec![
+                vec![
+                    item_ref("race", "Finish a 10k"),
+                    item_ref("fun", "Have fun")
+                ],
+                vec![item_ref("health", "Health")],
+            ]
+        );
+        assert_eq!(
+            detail.children,
+            vec![item_ref("shoes", "Find running shoes")]
+        );
+        assert_eq!(
+            detail.waiting_on,
+            vec![item_ref("weather", "Check the weather")]
+        );
+    }
+
+    #[test]
+    fn item_json_uses_the_query_language_names() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "milk").into()))
+                    .summary("Buy milk")
+                    .item_type(SurrealItemType::Action)
+                    .tags(Some(vec!["errand".to_string()]))
+                    .created(now.into())
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let calculated_data = CalculatedData::new_from_base_data(
+            BaseData::new_from_surreal_tables(surreal_tables, now),
+        );
+        let milk: RecordId = ("item", "milk").into();
+
+        assert_eq!(
+            ItemJson::from(calculated_data.get_items_status().get(&milk).unwrap()),
+            ItemJson {
+                id: "item:milk".to_string(),
+                summary: "Buy milk".to_string(),
+                item_type: "action",
+                urgency: "importance",
+                ready: true,
+                tags: vec!["errand".to_string()],
+                created: now,
+                finished: None,
+            }
+        );
+    }
+}
@@ -6,11 +6,16 @@ use crossterm::terminal;
 use regex::{Regex, RegexBuilder};
 
 pub(crate) mod back_menu;
+pub(crate) mod daily_plan;
 pub(crate) mod do_now_list_menu;
 pub(crate) mod item_children_summary;
+pub(crate) mod item_query;
 pub(crate) mod select_higher_importance_than_this;
+pub(crate) mod snooze_item;
+pub(crate) mod tags;
 pub(crate) mod time_spent_summary;
 pub(crate) mod update_item_summary;
+pub(crate) mod waiting_for;
 
 /// Default page size for `inquire::Select` and `inquire::MultiSelect`.
 ///
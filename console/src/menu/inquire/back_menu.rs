@@ -1,6 +1,17 @@
+pub(crate) mod celebrate;
 pub(crate) mod configure_modes;
 pub(crate) mod configure_settings;
+pub(crate) mod end_of_day;
 pub(crate) mod finished_items;
+pub(crate) mod interruption_report;
+pub(crate) mod learned_priorities;
+pub(crate) mod meeting_with;
+pub(crate) mod neglected_items;
+pub(crate) mod process_inbox;
+pub(crate) mod standup_report;
+pub(crate) mod time_charts;
+pub(crate) mod waiting_for_report;
+pub(crate) mod weekly_review;
 
 use std::{cmp::Ordering, fmt::Display, vec};
 
@@ -26,6 +37,7 @@ use crate::{
     menu::inquire::{
         back_menu::configure_modes::configure_modes, default_select_page_size,
         parse_exact_or_relative_datetime, parse_exact_or_relative_datetime_help_string,
+        tags::split_tags_from_summary,
     },
     new_item::NewItem,
     node::{
@@ -36,15 +48,26 @@ use crate::{
 };
 
 use super::{
+    daily_plan::present_daily_planning,
     do_now_list_menu::{ShouldResumeCurrentlyWorkingOn, present_normal_do_now_list_menu},
     update_item_summary::update_item_summary,
 };
 
 enum TopMenuSelection {
+    PlanToday,
+    EndOfDay,
+    WeeklyReview,
+    StandupReport,
     Reflection,
+    Celebrate,
+    ProcessInbox,
+    WaitingFor,
+    MeetingWith,
+    NeglectedItems,
     FinishedItems,
     ViewDoNowList,
     ViewImportancePriorities,
+    LearnedPriorities,
     ClearInTheMomentPriorities,
     ConfigureModes,
     ConfigureSettings,
@@ -54,14 +77,26 @@ enum TopMenuSelection {
 impl Display for TopMenuSelection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            TopMenuSelection::PlanToday => write!(f, "🌅  Plan today"),
+            TopMenuSelection::EndOfDay => write!(f, "🌙  End of day shutdown"),
+            TopMenuSelection::WeeklyReview => write!(f, "🗓️  Weekly review"),
+            TopMenuSelection::StandupReport => write!(f, "📋  Stand-up report"),
             TopMenuSelection::Reflection => write!(f, "🤔  Reflection, what I did"),
+            TopMenuSelection::Celebrate => write!(f, "🎉  Celebrate"),
             TopMenuSelection::FinishedItems => write!(f, "✅  Finished items"),
+            TopMenuSelection::ProcessInbox => write!(f, "📥  Process Inbox"),
+            TopMenuSelection::WaitingFor => write!(f, "⏳  Waiting For"),
+            TopMenuSelection::MeetingWith => write!(f, "👥  Meeting with…"),
+            TopMenuSelection::NeglectedItems => write!(f, "🕸️  Neglected items"),
             TopMenuSelection::ViewDoNowList => {
                 write!(f, "🔙  Return to Do Now List")
             }
             TopMenuSelection::ViewImportancePriorities => {
                 write!(f, "⚖️  View Importance Priorities")
             }
+            TopMenuSelection::LearnedPriorities => {
+                write!(f, "📈  Learned Priority Suggestions")
+            }
             TopMenuSelection::DebugViewAllItems => {
                 write!(f, "🔍  Debug View All Items")
             }
@@ -78,9 +113,19 @@ impl TopMenuSelection {
     fn make_list() -> Vec<TopMenuSelection> {
         vec![
             Self::ViewImportancePriorities,
+            Self::LearnedPriorities,
             Self::ClearInTheMomentPriorities,
+            Self::PlanToday,
+            Self::EndOfDay,
+            Self::WeeklyReview,
+            Self::StandupReport,
             Self::Reflection,
+            Self::Celebrate,
             Self::FinishedItems,
+            Self::ProcessInbox,
+            Self::WaitingFor,
+            Self::MeetingWith,
+            Self::NeglectedItems,
             Self::ConfigureModes,
             Self::ConfigureSettings,
             Self::ViewDoNowList,
@@ -99,10 +144,37 @@ pub(crate) async fn present_back_menu(
         .with_page_size(default_select_page_size())
         .prompt();
     match selection {
-        Ok(TopMenuSelection::Reflection) => present_reflection(send_to_data_storage_layer).await,
+        Ok(TopMenuSelection::PlanToday) => present_daily_planning(send_to_data_storage_layer).await,
+        Ok(TopMenuSelection::EndOfDay) => {
+            end_of_day::present_end_of_day(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::WeeklyReview) => {
+            weekly_review::present_weekly_review(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::StandupReport) => {
+            standup_report::present_standup_report(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::Reflection) => {
+            present_reflection_menu(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::Celebrate) => {
+            celebrate::present_celebration(send_to_data_storage_layer).await
+        }
         Ok(TopMenuSelection::FinishedItems) => {
             finished_items::present_finished_items_menu(send_to_data_storage_layer).await
         }
+        Ok(TopMenuSelection::ProcessInbox) => {
+            process_inbox::present_process_inbox_menu(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::WaitingFor) => {
+            waiting_for_report::present_waiting_for_report(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::MeetingWith) => {
+            meeting_with::present_meeting_with_menu(send_to_data_storage_layer).await
+        }
+        Ok(TopMenuSelection::NeglectedItems) => {
+            neglected_items::present_neglected_items_menu(send_to_data_storage_layer).await
+        }
         Ok(TopMenuSelection::ViewDoNowList) => {
             present_normal_do_now_list_menu(
                 send_to_data_storage_layer,
@@ -113,6 +185,9 @@ pub(crate) async fn present_back_menu(
         Ok(TopMenuSelection::ViewImportancePriorities) => {
             view_priorities(send_to_data_storage_layer).await
         }
+        Ok(TopMenuSelection::LearnedPriorities) => {
+            learned_priorities::present_learned_priorities_menu(send_to_data_storage_layer).await
+        }
         Ok(TopMenuSelection::ClearInTheMomentPriorities) => {
             clear_in_the_moment_priorities(send_to_data_storage_layer).await
         }
@@ -130,11 +205,15 @@ pub(crate) async fn present_back_menu(
 pub(crate) async fn capture(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
 ) -> Result<(), ()> {
-    let new_item_summary = Text::new("Enter New Item ⍠").prompt();
+    let new_item_summary = Text::new("Enter New Item ⍠")
+        .with_help_message("Add tags with #, for example #phone #errand")
+        .prompt();
 
     match new_item_summary {
         Ok(new_item_summary) => {
-            let new_item = NewItem::new(new_item_summary, Utc::now());
+            let (new_item_summary, tags) = split_tags_from_summary(&new_item_summary);
+            let mut new_item = NewItem::new(new_item_summary, Utc::now());
+            new_item.tags = tags;
             send_to_data_storage_layer
                 .send(DataLayerCommands::NewItem(new_item))
                 .await
@@ -404,6 +483,41 @@ async fn view_priorities_single_item_no_children(
     }
 }
 
+enum ReflectionChoice {
+    TimeSpent,
+    DailySummaries,
+}
+
+impl Display for ReflectionChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReflectionChoice::TimeSpent => write!(f, "🕜 Time spent between two times"),
+            ReflectionChoice::DailySummaries => write!(f, "📔 Daily summaries"),
+        }
+    }
+}
+
+async fn present_reflection_menu(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let choices = vec![
+        ReflectionChoice::TimeSpent,
+        ReflectionChoice::DailySummaries,
+    ];
+    let selection = Select::new("Reflect on...", choices)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(ReflectionChoice::TimeSpent) => present_reflection(send_to_data_storage_layer).await,
+        Ok(ReflectionChoice::DailySummaries) => {
+            end_of_day::present_daily_summaries(send_to_data_storage_layer).await
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
 #[allow(clippy::mutable_key_type)]
 async fn present_reflection(
     send_to_data_storage_layer: &Sender<DataLayerCommands>,
@@ -680,6 +794,51 @@ async fn present_reflection(
         );
     }
 
+    if total_time_num_seconds != 0 {
+        println!();
+        time_charts::print_time_charts(items_status, &logs_in_range, &start_utc, &end_utc);
+    }
+
+    //Breaks come from the work timer and are kept out of the time spent log so they never count as work
+    let break_time = surreal_tables
+        .get_surreal_breaks()
+        .iter()
+        .map(|x| -> (DateTime<Utc>, DateTime<Utc>) {
+            (x.when_started.clone().into(), x.when_stopped.clone().into())
+        })
+        .filter(|(when_started, when_stopped)| {
+            *when_started >= start_utc && *when_stopped <= end_utc
+        })
+        .fold(
+            (chrono::Duration::default(), 0),
+            |(sum_duration, count), (when_started, when_stopped)| {
+                (sum_duration + (when_stopped - when_started), count + 1)
+            },
+        );
+    if break_time.1 != 0 {
+        println!();
+        println!("☕ Rest");
+        println!(
+            "\t{} breaks for {}",
+            break_time.1,
+            DisplayDuration::new(&break_time.0.to_std().expect("valid"))
+        );
+        let work_and_rest_num_seconds = total_time_num_seconds + break_time.0.num_seconds();
+        if work_and_rest_num_seconds != 0 {
+            println!(
+                "Work vs rest: {}% work, {}% rest",
+                total_time_num_seconds * 100 / work_and_rest_num_seconds,
+                break_time.0.num_seconds() * 100 / work_and_rest_num_seconds
+            );
+        }
+    }
+
+    interruption_report::print_interruptions(
+        surreal_tables.get_surreal_interruptions(),
+        &start_utc,
+        &end_utc,
+    );
+
     println!();
     match Text::new("Press Enter to continue...").prompt() {
         Ok(_) | Err(InquireError::OperationCanceled) => Ok(()),
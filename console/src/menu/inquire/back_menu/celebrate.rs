@@ -0,0 +1,212 @@
+use better_term::Style;
+use chrono::{Local, TimeDelta};
+use image::{Rgba, RgbaImage};
+use inquire::{InquireError, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands,
+    display::{display_item::DisplayItem, display_sixel::print_sixel},
+    menu::inquire::do_now_list_menu::load_do_now_list_from_db,
+    systems::{
+        celebration::{
+            GOALS_FINISHED_MILESTONES, HABIT_TAG, HOURS_INVESTED_MILESTONES, current_streak,
+            days_worked_towards, finished_recap, habit_streaks, longest_streak, milestones,
+            time_invested, time_invested_by_top_motivation,
+        },
+        do_now_list::DoNowList,
+    },
+};
+
+/// How far back the recap looks and how recently a milestone has to have been passed to be new
+const RECAP_DAYS: i64 = 7;
+
+/// How many of the biggest finished items are shown in the recap
+const RECAP_SIZE: usize = 5;
+
+fn hours(time: TimeDelta) -> f64 {
+    time.num_minutes() as f64 / 60.0
+}
+
+fn whole_hours(time: TimeDelta) -> u64 {
+    time.num_hours().max(0) as u64
+}
+
+/// A gold medal, drawn rather than loaded so there is no extra image to embed
+fn milestone_badge() -> RgbaImage {
+    const SIZE: u32 = 96;
+    let radius = (SIZE as f32 - 1.0) / 2.0;
+    RgbaImage::from_fn(SIZE, SIZE, |x, y| {
+        let distance = ((x as f32 - radius).powi(2) + (y as f32 - radius).powi(2)).sqrt();
+        if distance > radius {
+            Rgba([0, 0, 0, 0])
+        } else if distance > radius - 6.0 {
+            Rgba([184, 134, 11, 255])
+        } else if distance > radius - 10.0 {
+            Rgba([255, 236, 139, 255])
+        } else {
+            Rgba([255, 193, 37, 255])
+        }
+    })
+}
+
+/// Prints the milestones and returns true if any of them was passed within the recap window
+fn print_milestones(do_now_list: &DoNowList) -> bool {
+    let items_status = do_now_list.get_all_items_status();
+    let now = *do_now_list.get_now();
+    let recap_start = now - TimeDelta::days(RECAP_DAYS);
+    let mut passed_a_milestone = false;
+
+    println!("{}Milestones{}", Style::new().bold(), Style::new());
+    let goals_finished = items_status
+        .values()
+        .map(|x| x.get_item())
+        .filter(|x| x.is_type_goal())
+        .filter_map(|x| *x.get_finished_at())
+        .collect::<Vec<_>>();
+    let finished_recently = goals_finished.iter().filter(|x| **x >= recap_start).count();
+    let total = goals_finished.len() as u64;
+    println!(
+        "  🏁 {} goals finished, {} of them in the last {} days",
+        total, finished_recently, RECAP_DAYS
+    );
+    let (reached, next) = milestones(total, &GOALS_FINISHED_MILESTONES);
+    let (reached_before, _) =
+        milestones(total - finished_recently as u64, &GOALS_FINISHED_MILESTONES);
+    if reached != reached_before
+        && let Some(reached) = reached
+    {
+        passed_a_milestone = true;
+        println!("     🏅 Passed {} goals finished!", reached);
+    } else if let Some(next) = next {
+        println!("     Next milestone is {} goals finished", next);
+    }
+
+    let invested = time_invested(items_status, do_now_list.get_time_spent_log(), &now);
+    let invested_before =
+        time_invested(items_status, do_now_list.get_time_spent_log(), &recap_start);
+    let by_motivation = time_invested_by_top_motivation(items_status, &invested);
+    if by_motivation.is_empty() {
+        println!("  ⏳ No time has been logged towards a motivation yet");
+    } else {
+        println!("  ⏳ Time invested");
+    }
+    for (motivation, time) in by_motivation {
+        let (reached, next) = milestones(whole_hours(time), &HOURS_INVESTED_MILESTONES);
+        let before = invested_before
+            .get(motivation.get_surreal_record_id())
+            .copied()
+            .unwrap_or_default();
+        let (reached_before, _) = milestones(whole_hours(before), &HOURS_INVESTED_MILESTONES);
+        print!(
+            "     {}: {:.1} hours",
+            motivation.get_summary(),
+            hours(time)
+        );
+        if reached != reached_before
+            && let Some(reached) = reached
+        {
+            passed_a_milestone = true;
+            println!(", 🏅 passed {} hours!", reached);
+        } else if let Some(next) = next {
+            println!(", next milestone is {} hours", next);
+        } else {
+            println!();
+        }
+    }
+    passed_a_milestone
+}
+
+fn print_streaks(do_now_list: &DoNowList) {
+    let items_status = do_now_list.get_all_items_status();
+    let time_spent_log = do_now_list.get_time_spent_log();
+    let today = do_now_list.get_now().with_timezone(&Local).date_naive();
+
+    println!("{}Streaks{}", Style::new().bold(), Style::new());
+    let core_days = days_worked_towards(items_status, time_spent_log, |x| {
+        x.is_type_motivation_kind_core()
+    });
+    println!(
+        "  🔥 Core work logged {} days in a row, longest streak {} days",
+        current_streak(&core_days, today),
+        longest_streak(&core_days)
+    );
+
+    let habits = habit_streaks(items_status, time_spent_log, today);
+    if habits.is_empty() {
+        println!(
+            "  Tag an item with \"{}\" to keep a streak of the days it is worked on",
+            HABIT_TAG
+        );
+    }
+    for habit in habits {
+        println!(
+            "  🌱 {}: {} days in a row, longest streak {} days",
+            habit.get_habit().get_summary(),
+            habit.get_current(),
+            habit.get_longest()
+        );
+    }
+}
+
+fn print_recap(do_now_list: &DoNowList) {
+    let items_status = do_now_list.get_all_items_status();
+    let now = *do_now_list.get_now();
+    let invested = time_invested(items_status, do_now_list.get_time_spent_log(), &now);
+    let recap = finished_recap(
+        items_status,
+        &invested,
+        &(now - TimeDelta::days(RECAP_DAYS)),
+    );
+
+    println!(
+        "{}Biggest finishes of the last {} days{}",
+        Style::new().bold(),
+        RECAP_DAYS,
+        Style::new()
+    );
+    if recap.is_empty() {
+        println!("  Nothing was finished, the next week is a fresh start");
+        return;
+    }
+    for (i, recap_item) in recap.iter().take(RECAP_SIZE).enumerate() {
+        println!(
+            "  {}. ✅ {} ({} items, {:.1} hours)",
+            i + 1,
+            DisplayItem::new(recap_item.get_item()),
+            recap_item.get_subtree_size(),
+            hours(recap_item.get_time_invested())
+        );
+    }
+    if recap.len() > RECAP_SIZE {
+        println!("  …and {} more", recap.len() - RECAP_SIZE);
+    }
+}
+
+/// Milestones, streaks and a recap of the week's biggest finishes. When a milestone was just passed
+/// a medal is shown on terminals that support sixel.
+pub(crate) async fn present_celebration(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+
+    println!();
+    println!("{}🎉 Celebrate{}", Style::new().bold(), Style::new());
+    println!();
+    let passed_a_milestone = print_milestones(&do_now_list);
+    println!();
+    print_streaks(&do_now_list);
+    println!();
+    print_recap(&do_now_list);
+    println!();
+    if passed_a_milestone {
+        print_sixel(&milestone_badge())
+            .unwrap_or_else(|err| eprintln!("Unable to display badge (sixel): {err}"));
+    }
+
+    match Text::new("Press Enter to continue...").prompt() {
+        Ok(_) | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
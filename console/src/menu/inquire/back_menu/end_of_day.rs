@@ -0,0 +1,471 @@
+use std::fmt::Display;
+
+use better_term::Style;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use inquire::{InquireError, Select, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::time_spent::TimeSpent,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_daily_summary::SurrealDailySummary,
+        surreal_item::SurrealScheduled, surreal_tables::SurrealTables,
+    },
+    display::{
+        display_daily_summary::DisplayDailySummary, display_duration::DisplayDuration,
+        display_item::DisplayItem,
+    },
+    menu::inquire::{
+        daily_plan::{Progress, commitments_progress, print_commitments_progress, today},
+        default_select_page_size,
+        do_now_list_menu::{
+            compute_time_spent_in_window,
+            do_now_list_single_item::{
+                resume_note::ask_for_resume_note,
+                urgency_plan::present_set_ready_and_urgency_plan_menu,
+            },
+            load_do_now_list_from_db, start_of_today,
+        },
+    },
+    node::item_status::ItemStatus,
+    systems::do_now_list::DoNowList,
+};
+
+/// Time between entries shorter than this is moving from one thing to the next, not a gap
+const SMALLEST_GAP: chrono::Duration = chrono::Duration::minutes(15);
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+/// Stretches of at least `smallest_gap` that are not covered by any of the intervals, which can be
+/// in any order and can overlap. Only the time between the first start and the last stop is checked.
+fn find_gaps(mut intervals: Vec<Interval>, smallest_gap: chrono::Duration) -> Vec<Interval> {
+    intervals.sort_by_key(|(start, _)| *start);
+    let mut gaps = Vec::default();
+    let mut covered_until: Option<DateTime<Utc>> = None;
+    for (start, stop) in intervals {
+        if let Some(covered_until) = covered_until
+            && start - covered_until >= smallest_gap
+        {
+            gaps.push((covered_until, start));
+        }
+        covered_until = Some(covered_until.map_or(stop, |x| x.max(stop)));
+    }
+    gaps
+}
+
+fn format_interval((start, stop): &Interval) -> String {
+    format!(
+        "{}-{} {}",
+        start.with_timezone(&Local).format("%I:%M%P"),
+        stop.with_timezone(&Local).format("%I:%M%P"),
+        DisplayDuration::new(&(*stop - *start).to_std().unwrap_or_default())
+    )
+}
+
+fn overlaps_today(interval: &Interval, today: &Interval) -> bool {
+    interval.0 < today.1 && interval.1 > today.0
+}
+
+/// Everything logged today in order, with a warning for each stretch that nothing accounts for.
+/// Breaks and interruptions account for time even though they are not work.
+fn reconcile_time_log(do_now_list: &DoNowList, today: &Interval) {
+    let items_status = do_now_list.get_all_items_status();
+    let base_data = do_now_list.get_base_data();
+
+    let mut lines: Vec<(Interval, String)> = do_now_list
+        .get_time_spent_log()
+        .iter()
+        .map(|x| ((*x.get_started_at(), *x.get_stopped_at()), x))
+        .filter(|(interval, _)| overlaps_today(interval, today))
+        .map(|(interval, time_spent)| {
+            let summary = time_spent
+                .get_worked_on()
+                .and_then(|x| items_status.get(x))
+                .map(|x| x.get_summary().to_string())
+                .unwrap_or_else(|| "(item no longer exists)".to_string());
+            (interval, summary)
+        })
+        .collect();
+    lines.extend(
+        base_data
+            .get_surreal_breaks()
+            .iter()
+            .map(|x| -> Interval { (x.when_started.clone().into(), x.when_stopped.clone().into()) })
+            .filter(|interval| overlaps_today(interval, today))
+            .map(|interval| (interval, "☕ Break".to_string())),
+    );
+    lines.extend(
+        base_data
+            .get_surreal_interruptions()
+            .iter()
+            .map(|x| -> (Interval, String) {
+                (
+                    (x.when_started.clone().into(), x.when_stopped.clone().into()),
+                    format!("⚡ Interrupted by {}", x.source),
+                )
+            })
+            .filter(|(interval, _)| overlaps_today(interval, today)),
+    );
+
+    if lines.is_empty() {
+        println!("Nothing was logged today");
+        return;
+    }
+
+    let gaps = find_gaps(
+        lines.iter().map(|(interval, _)| *interval).collect(),
+        SMALLEST_GAP,
+    );
+    let gap_count = gaps.len();
+    let warning = Style::new().bold();
+    let normal = Style::new();
+    lines.extend(
+        gaps.into_iter()
+            .map(|gap| (gap, format!("{}⚠️  Nothing logged{}", warning, normal))),
+    );
+    lines.sort_by_key(|(interval, _)| interval.0);
+    for (interval, text) in lines.iter() {
+        println!("  {} {}", format_interval(interval), text);
+    }
+    if gap_count != 0 {
+        println!(
+            "{} gaps, use \"I worked on this\" on an item to log time that was missed",
+            gap_count
+        );
+    }
+}
+
+/// A scheduled item whose time has come and gone without it being finished
+fn is_missed(item_status: &ItemStatus<'_>, now: &DateTime<Utc>) -> bool {
+    let latest_end: DateTime<Utc> = match item_status.get_scheduled_now() {
+        Some(SurrealScheduled::Exact { start, duration }) => {
+            let start: DateTime<Utc> = start.clone().into();
+            let duration: std::time::Duration = (*duration).into();
+            start + duration
+        }
+        Some(SurrealScheduled::Range {
+            start_range: (_, latest_start),
+            duration,
+        }) => {
+            let latest_start: DateTime<Utc> = latest_start.clone().into();
+            let duration: std::time::Duration = (*duration).into();
+            latest_start + duration
+        }
+        None => return false,
+    };
+    item_status.is_active() && latest_end < *now
+}
+
+enum MissedChoice {
+    Reschedule,
+    LeaveItAsItIs,
+}
+
+impl Display for MissedChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissedChoice::Reschedule => write!(f, "📅 Reschedule"),
+            MissedChoice::LeaveItAsItIs => write!(f, "Leave it as it is"),
+        }
+    }
+}
+
+async fn reschedule_missed(
+    do_now_list: &DoNowList,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let now = do_now_list.get_now();
+    let mut missed = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| is_missed(x, now))
+        .collect::<Vec<_>>();
+    if missed.is_empty() {
+        println!("Nothing scheduled was missed");
+        return Ok(());
+    }
+    missed.sort_by(|a, b| a.get_summary().cmp(b.get_summary()));
+    for item_status in missed {
+        println!("{}", DisplayItem::new(item_status.get_item()));
+        match Select::new(
+            "This was scheduled and its time has passed|",
+            vec![MissedChoice::Reschedule, MissedChoice::LeaveItAsItIs],
+        )
+        .prompt()
+        {
+            Ok(MissedChoice::Reschedule) => {
+                present_set_ready_and_urgency_plan_menu(
+                    item_status,
+                    do_now_list.get_base_data(),
+                    send_to_data_storage_layer,
+                )
+                .await?
+            }
+            Ok(MissedChoice::LeaveItAsItIs) | Err(InquireError::OperationCanceled) => {}
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+pub(crate) struct TimeByMotivation {
+    pub(crate) core: chrono::Duration,
+    pub(crate) non_core: chrono::Duration,
+    pub(crate) neither: chrono::Duration,
+}
+
+/// Time logged within the window split by the kind of motivation it went towards. Like the
+/// reflection report, time that is not under a core or a neither motivation counts as non-core.
+pub(crate) fn time_by_motivation(do_now_list: &DoNowList, window: &Interval) -> TimeByMotivation {
+    let items_status = do_now_list.get_all_items_status();
+    do_now_list.get_time_spent_log().iter().fold(
+        TimeByMotivation::default(),
+        |mut acc, time_spent| {
+            let time = compute_time_spent_in_window(window.0, window.1, &[time_spent]);
+            let worked_towards = time_spent
+                .worked_towards()
+                .iter()
+                .filter_map(|x| items_status.get(x))
+                .collect::<Vec<_>>();
+            if worked_towards
+                .iter()
+                .any(|x| x.is_type_motivation_kind_core())
+            {
+                acc.core += time;
+            } else if worked_towards
+                .iter()
+                .any(|x| x.is_type_motivation_kind_neither())
+            {
+                acc.neither += time;
+            } else {
+                acc.non_core += time;
+            }
+            acc
+        },
+    )
+}
+
+fn plan_vs_actual(do_now_list: &DoNowList, today: &Interval) -> (u32, u32) {
+    let commitments = commitments_progress(do_now_list, &today.0);
+    if commitments.is_empty() {
+        println!("Today was not planned");
+        return (0, 0);
+    }
+    print_commitments_progress(&commitments);
+
+    let on_commitments = do_now_list
+        .get_time_spent_log()
+        .iter()
+        .filter(|time_spent| {
+            commitments.iter().any(|(item_status, _)| {
+                time_spent
+                    .worked_towards()
+                    .contains(item_status.get_surreal_record_id())
+            })
+        })
+        .collect::<Vec<&TimeSpent<'_>>>();
+    let on_commitments = compute_time_spent_in_window(today.0, today.1, &on_commitments);
+    let all = do_now_list.get_time_spent_log().iter().collect::<Vec<_>>();
+    let all = compute_time_spent_in_window(today.0, today.1, &all);
+    println!(
+        "{} of the {} logged today went towards the commitments",
+        DisplayDuration::new(&on_commitments.to_std().unwrap_or_default()),
+        DisplayDuration::new(&all.to_std().unwrap_or_default())
+    );
+
+    let done = commitments
+        .iter()
+        .filter(|(_, progress)| *progress == Progress::Done)
+        .count();
+    (commitments.len() as u32, done as u32)
+}
+
+fn finished_on<'a>(do_now_list: &'a DoNowList, day: &NaiveDate) -> Vec<&'a ItemStatus<'a>> {
+    let mut finished = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| {
+            x.get_finished_at()
+                .is_some_and(|x| x.with_timezone(&Local).date_naive() == *day)
+        })
+        .collect::<Vec<_>>();
+    finished.sort_by_key(|x| *x.get_finished_at());
+    finished
+}
+
+fn print_step(step: &str) {
+    println!();
+    println!("{}{}{}", Style::new().bold(), step, Style::new());
+}
+
+/// Closes out the day: checks today's time log for gaps, leaves a resume note on what is still being
+/// worked on, reschedules what was missed, compares the plan with what happened, and writes a
+/// summary of the day that can be read again from Reflection.
+pub(crate) async fn present_end_of_day(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+    let day = today();
+    let today: Interval = (start_of_today().with_timezone(&Utc), *do_now_list.get_now());
+
+    println!();
+    println!("🌙 Shutting down for {}", day.format("%A %d %B"));
+
+    print_step("1. Today's time log");
+    reconcile_time_log(&do_now_list, &today);
+
+    print_step("2. Still in progress");
+    let still_working_on = do_now_list
+        .get_base_data()
+        .get_surreal_working_on()
+        .and_then(|x| do_now_list.get_all_items_status().get(&x.item))
+        .filter(|x| x.is_active());
+    match still_working_on {
+        Some(item_status) => {
+            println!("{}", DisplayItem::new(item_status.get_item()));
+            ask_for_resume_note(item_status.get_item(), send_to_data_storage_layer).await?;
+        }
+        None => println!("Nothing is being worked on"),
+    }
+
+    print_step("3. Missed scheduled items");
+    reschedule_missed(&do_now_list, send_to_data_storage_layer).await?;
+
+    print_step("4. Plan vs actual");
+    let (commitments_made, commitments_done) = plan_vs_actual(&do_now_list, &today);
+
+    print_step("5. Summary");
+    let notes = match Text::new("Any notes about today?")
+        .with_help_message("Optional, Esc to skip")
+        .prompt()
+    {
+        Ok(notes) => notes.trim().to_string(),
+        Err(InquireError::OperationCanceled) => String::new(),
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    };
+    let time_by_motivation = time_by_motivation(&do_now_list, &today);
+    let daily_summary = SurrealDailySummary {
+        id: Some(SurrealDailySummary::id_for(&day)),
+        version: 0,
+        day: SurrealDailySummary::day_key(&day),
+        written: Utc::now().into(),
+        finished: finished_on(&do_now_list, &day)
+            .into_iter()
+            .map(|x| x.get_summary().to_string())
+            .collect(),
+        commitments_made,
+        commitments_done,
+        core_work: time_by_motivation.core.to_std().unwrap_or_default().into(),
+        non_core_work: time_by_motivation
+            .non_core
+            .to_std()
+            .unwrap_or_default()
+            .into(),
+        other_work: time_by_motivation
+            .neither
+            .to_std()
+            .unwrap_or_default()
+            .into(),
+        notes,
+    };
+    println!();
+    println!("{}", DisplayDailySummary::new(&daily_summary));
+    send_to_data_storage_layer
+        .send(DataLayerCommands::WriteDailySummary(daily_summary))
+        .await
+        .unwrap();
+
+    println!();
+    match Text::new("The day is closed out, press Enter to continue...").prompt() {
+        Ok(_) | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+struct DailySummaryChoice<'e>(&'e SurrealDailySummary);
+
+impl Display for DailySummaryChoice<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let daily_summary = self.0;
+        write!(f, "{}", daily_summary.day)?;
+        if let Ok(day) = NaiveDate::parse_from_str(&daily_summary.day, "%Y-%m-%d") {
+            write!(f, " {}", day.format("%a"))?;
+        }
+        write!(f, " ({} finished", daily_summary.finished.len())?;
+        if daily_summary.commitments_made != 0 {
+            write!(
+                f,
+                ", {} of {} commitments",
+                daily_summary.commitments_done, daily_summary.commitments_made
+            )?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Lets the summaries written at the end of each day be read again, newest first
+pub(crate) async fn present_daily_summaries(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let mut daily_summaries = surreal_tables
+        .get_surreal_daily_summaries()
+        .iter()
+        .collect::<Vec<_>>();
+    if daily_summaries.is_empty() {
+        println!("No daily summaries yet, they are written by the end of day shutdown");
+        return Ok(());
+    }
+    daily_summaries.sort_by(|a, b| b.day.cmp(&a.day));
+
+    loop {
+        let list = daily_summaries
+            .iter()
+            .copied()
+            .map(DailySummaryChoice)
+            .collect::<Vec<_>>();
+        match Select::new("Select a day to read its summary|", list)
+            .with_page_size(default_select_page_size())
+            .prompt()
+        {
+            Ok(DailySummaryChoice(daily_summary)) => {
+                println!();
+                println!("{}", DisplayDailySummary::new(daily_summary));
+                println!();
+            }
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::find_gaps;
+
+    #[test]
+    fn gaps_are_only_flagged_when_nothing_covers_them() {
+        let start = Utc::now();
+        let at = |minutes: i64| start + Duration::minutes(minutes);
+        let gaps = find_gaps(
+            vec![
+                (at(60), at(90)),
+                (at(0), at(30)),
+                //Overlaps the first entry and ends within a few minutes of the next one
+                (at(20), at(50)),
+                (at(120), at(130)),
+            ],
+            Duration::minutes(15),
+        );
+        assert_eq!(gaps, vec![(at(90), at(120))]);
+    }
+}
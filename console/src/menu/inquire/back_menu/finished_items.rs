@@ -11,7 +11,10 @@ use crate::{
     data_storage::surrealdb_layer::{
         data_layer_commands::DataLayerCommands, surreal_tables::SurrealTables,
     },
-    display::display_item_node::{DisplayFormat, DisplayItemNode},
+    display::{
+        display_item_history::DisplayItemHistory,
+        display_item_node::{DisplayFormat, DisplayItemNode},
+    },
     menu::inquire::{default_select_page_size, item_children_summary, time_spent_summary},
     node::{Filter, item_status::ItemStatus},
     systems::do_now_list::DoNowList,
@@ -155,6 +158,12 @@ async fn present_finished_item_detail(
     item_children_summary::print_completed_children(menu_for);
     item_children_summary::print_in_progress_children(menu_for, do_now_list.get_all_items_status());
 
+    let base_data = do_now_list.get_base_data();
+    let history = base_data.get_item_history(menu_for.get_surreal_record_id());
+    println!();
+    println!("{}History:{}", underline, normal_style);
+    println!("{}", DisplayItemHistory::new(&history, base_data));
+
     println!();
 
     let choices = vec![
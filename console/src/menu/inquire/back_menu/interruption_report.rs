@@ -0,0 +1,153 @@
+use ahash::HashMap;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use crate::{
+    data_storage::surrealdb_layer::surreal_interruption::SurrealInterruption,
+    display::display_duration::DisplayDuration,
+};
+
+#[derive(Debug, PartialEq)]
+struct InterruptionTotal<K> {
+    key: K,
+    count: usize,
+    time_lost: chrono::Duration,
+}
+
+fn add_to_totals<K: Eq + std::hash::Hash>(
+    totals: &mut HashMap<K, (usize, chrono::Duration)>,
+    key: K,
+    time_lost: chrono::Duration,
+) {
+    let (count, sum) = totals.entry(key).or_default();
+    *count += 1;
+    *sum += time_lost;
+}
+
+/// Interruptions that started within the range, totalled by the local day they happened on, oldest
+/// day first, and by source, the most time lost first
+fn total_interruptions<'a>(
+    interruptions: &'a [SurrealInterruption],
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> (
+    Vec<InterruptionTotal<NaiveDate>>,
+    Vec<InterruptionTotal<&'a str>>,
+) {
+    let mut by_day = HashMap::default();
+    let mut by_source = HashMap::default();
+    for interruption in interruptions.iter() {
+        let when_started: DateTime<Utc> = interruption.when_started.clone().into();
+        let when_stopped: DateTime<Utc> = interruption.when_stopped.clone().into();
+        if when_started < *start || when_started > *end {
+            continue;
+        }
+        let time_lost = when_stopped - when_started;
+        add_to_totals(
+            &mut by_day,
+            when_started.with_timezone(&Local).date_naive(),
+            time_lost,
+        );
+        add_to_totals(&mut by_source, interruption.source.as_str(), time_lost);
+    }
+
+    let mut by_day = by_day
+        .into_iter()
+        .map(|(key, (count, time_lost))| InterruptionTotal {
+            key,
+            count,
+            time_lost,
+        })
+        .collect::<Vec<_>>();
+    by_day.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut by_source = by_source
+        .into_iter()
+        .map(|(key, (count, time_lost))| InterruptionTotal {
+            key,
+            count,
+            time_lost,
+        })
+        .collect::<Vec<_>>();
+    by_source.sort_by(|a, b| b.time_lost.cmp(&a.time_lost).then(a.key.cmp(b.key)));
+
+    (by_day, by_source)
+}
+
+fn display_time_lost(time_lost: &chrono::Duration) -> String {
+    DisplayDuration::new(&time_lost.to_std().unwrap_or_default()).to_string()
+}
+
+/// Prints nothing when there were no interruptions in the range
+pub(crate) fn print_interruptions(
+    interruptions: &[SurrealInterruption],
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) {
+    let (by_day, by_source) = total_interruptions(interruptions, start, end);
+    if by_day.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("⚡ Interruptions");
+    println!("By day");
+    for total in by_day.iter() {
+        println!(
+            "\t{}: {} times, {} lost",
+            total.key.format("%a %d %b"),
+            total.count,
+            display_time_lost(&total.time_lost)
+        );
+    }
+    println!("By source");
+    for total in by_source.iter() {
+        println!(
+            "\t{}: {} times, {} lost",
+            total.key,
+            total.count,
+            display_time_lost(&total.time_lost)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::data_storage::surrealdb_layer::surreal_interruption::SurrealInterruption;
+
+    use super::total_interruptions;
+
+    #[test]
+    fn interruptions_are_totalled_by_source_with_the_most_time_lost_first() {
+        let now = Utc::now();
+        let interruption = |source: &str, minutes_ago: i64, minutes: i64| {
+            let when_started = now - Duration::minutes(minutes_ago);
+            SurrealInterruption::new(
+                None,
+                source.to_string(),
+                when_started.into(),
+                (when_started + Duration::minutes(minutes)).into(),
+            )
+        };
+        let interruptions = vec![
+            interruption("Slack", 60, 2),
+            interruption("Phone call", 50, 15),
+            interruption("Slack", 40, 3),
+            //Before the range so it is left out
+            interruption("Coworker", 60 * 24 * 7, 30),
+        ];
+
+        let (by_day, by_source) =
+            total_interruptions(&interruptions, &(now - Duration::hours(2)), &now);
+
+        assert_eq!(by_day.iter().map(|x| x.count).sum::<usize>(), 3);
+        assert_eq!(
+            by_source
+                .iter()
+                .map(|x| (x.key, x.count, x.time_lost.num_minutes()))
+                .collect::<Vec<_>>(),
+            vec![("Phone call", 1, 15), ("Slack", 2, 5)]
+        );
+    }
+}
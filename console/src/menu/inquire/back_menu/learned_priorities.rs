@@ -0,0 +1,123 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::Utc;
+use inquire::{InquireError, Select};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{BaseData, item::Item},
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_tables::SurrealTables,
+    },
+    display::display_item::DisplayItem,
+    menu::inquire::default_select_page_size,
+    systems::priority_ratings::{PriorityRatings, ReorderSuggestion},
+};
+
+struct ReorderSuggestionChoice<'e> {
+    suggestion: ReorderSuggestion<'e>,
+    parent: &'e Item<'e>,
+    move_this: &'e Item<'e>,
+    above_this: &'e Item<'e>,
+}
+
+impl Display for ReorderSuggestionChoice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Under {}: move {} above {} (learned rating +{:.0})",
+            DisplayItem::new(self.parent),
+            DisplayItem::new(self.move_this),
+            DisplayItem::new(self.above_this),
+            self.suggestion.rating_difference
+        )
+    }
+}
+
+pub(crate) async fn present_learned_priorities_menu(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let current_mode = base_data
+        .get_surreal_current_modes()
+        .first()
+        .and_then(|x| x.current_mode.as_ref());
+
+    let ratings = PriorityRatings::new(base_data.get_surreal_priority_comparisons(), current_mode);
+    if ratings.is_empty() {
+        let mut other_modes = base_data
+            .get_modes()
+            .iter()
+            .filter(|mode| {
+                Some(mode.get_surreal_id()) != current_mode
+                    && base_data
+                        .get_surreal_priority_comparisons()
+                        .iter()
+                        .any(|x| x.for_mode.as_ref() == Some(mode.get_surreal_id()))
+            })
+            .map(|mode| mode.get_name())
+            .collect::<Vec<_>>();
+        other_modes.sort();
+        println!();
+        if other_modes.is_empty() {
+            println!(
+                "No priority comparisons have been recorded yet. Answers from the priority wizard are used to learn priorities."
+            );
+        } else {
+            println!(
+                "No priority comparisons have been recorded for the current mode yet. Change to one of these modes to see what was learned there: {}",
+                other_modes.join(", ")
+            );
+        }
+        println!();
+        return Ok(());
+    }
+
+    let items = base_data.get_items();
+    let list = ratings
+        .find_reorder_suggestions(items)
+        .into_iter()
+        .map(|suggestion| ReorderSuggestionChoice {
+            parent: items.get(suggestion.parent).expect("Comes from this list"),
+            move_this: items
+                .get(suggestion.move_this)
+                .expect("Comes from this list"),
+            above_this: items
+                .get(suggestion.above_this)
+                .expect("Comes from this list"),
+            suggestion,
+        })
+        .collect::<Vec<_>>();
+    if list.is_empty() {
+        println!();
+        println!("The learned priorities agree with how items are currently ordered.");
+        println!();
+        return Ok(());
+    }
+
+    let selection = Select::new(
+        "The learned priorities disagree with the current order. Select a change to apply|",
+        list,
+    )
+    .with_page_size(default_select_page_size())
+    .prompt();
+    match selection {
+        Ok(ReorderSuggestionChoice { suggestion, .. }) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UpdateRelativeImportance {
+                    parent: suggestion.parent.clone(),
+                    update_this_child: suggestion.move_this.clone(),
+                    higher_importance_than_this_child: Some(suggestion.above_this.clone()),
+                })
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::Utc;
+use inquire::{InquireError, Select, Text};
+use surrealdb::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{BaseData, item::Item},
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands,
+        surreal_in_the_moment_priority::SurrealAction,
+        surreal_item::{SurrealDependency, SurrealItemType},
+        surreal_tables::SurrealTables,
+        surreal_time_spent::SurrealWhyInScope,
+    },
+    display::{display_duration::DisplayDuration, display_item::DisplayItem},
+    menu::inquire::{
+        default_select_page_size, tags::split_tags_from_summary,
+        waiting_for::present_waiting_for_menu,
+    },
+    new_item::NewItem,
+    new_time_spent::NewTimeSpent,
+    node::item_status::ItemStatus,
+    systems::person_agenda::{AgendaItem, AgendaReason, find_person_agenda},
+};
+
+struct PersonChoice<'e>(&'e Item<'e>);
+
+impl Display for PersonChoice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "👤 {}", self.0.get_summary())
+    }
+}
+
+fn pick_person(base_data: &BaseData) -> Result<Option<RecordId>, ()> {
+    let mut people = base_data
+        .get_items()
+        .values()
+        .filter(|x| x.is_active() && x.is_person_or_group())
+        .collect::<Vec<_>>();
+    if people.is_empty() {
+        println!();
+        println!("There are no people or groups to meet with yet.");
+        println!();
+        return Ok(None);
+    }
+    people.sort_by(|a, b| a.get_summary().cmp(b.get_summary()));
+    let list = people.into_iter().map(PersonChoice).collect::<Vec<_>>();
+
+    let selection = Select::new("Who are you meeting with?|", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(PersonChoice(person)) => Ok(Some(person.get_surreal_record_id().clone())),
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+enum AgendaChoice<'e> {
+    Item(&'e AgendaItem<'e>),
+    Capture,
+    EndMeeting,
+}
+
+impl Display for AgendaChoice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AgendaChoice::Item(agenda_item) => {
+                let reason = match agenda_item.get_reason() {
+                    AgendaReason::DelegatedToThem => "📤 Delegated",
+                    AgendaReason::ToDiscuss => "💬 To discuss",
+                    AgendaReason::NeedsThem => "⏳ Waiting on them",
+                };
+                write!(
+                    f,
+                    "[{}] {}",
+                    reason,
+                    DisplayItem::new(agenda_item.get_item_status().get_item())
+                )
+            }
+            AgendaChoice::Capture => write!(f, "➕ Capture a new item to discuss"),
+            AgendaChoice::EndMeeting => write!(f, "🏁 End meeting"),
+        }
+    }
+}
+
+enum AgendaItemChoice {
+    Discussed,
+    Finished,
+}
+
+impl Display for AgendaItemChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AgendaItemChoice::Discussed => write!(f, "💬 Discussed, take it off the agenda"),
+            AgendaItemChoice::Finished => write!(f, "✅ Finished"),
+        }
+    }
+}
+
+/// Takes the item off of the agenda for the person without finishing it
+async fn mark_discussed(
+    item_status: &ItemStatus<'_>,
+    reason: AgendaReason,
+    person: &RecordId,
+    base_data: &BaseData,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let item = item_status.get_surreal_record_id().clone();
+    match reason {
+        AgendaReason::DelegatedToThem => {
+            present_waiting_for_menu(item_status, base_data, send_to_data_storage_layer).await?
+        }
+        AgendaReason::ToDiscuss => send_to_data_storage_layer
+            .send(DataLayerCommands::ParentItemRemoveParent {
+                child: item,
+                parent_to_remove: person.clone(),
+            })
+            .await
+            .unwrap(),
+        AgendaReason::NeedsThem => {
+            let after_person = SurrealDependency::AfterItem(person.clone());
+            if item_status
+                .get_item()
+                .get_surreal_dependencies()
+                .contains(&after_person)
+            {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::RemoveItemDependency(
+                        item.clone(),
+                        after_person,
+                    ))
+                    .await
+                    .unwrap();
+            }
+            send_to_data_storage_layer
+                .send(DataLayerCommands::ParentItemRemoveParent {
+                    child: person.clone(),
+                    parent_to_remove: item,
+                })
+                .await
+                .unwrap();
+        }
+    }
+    Ok(())
+}
+
+async fn present_agenda_item_menu(
+    agenda_item: &AgendaItem<'_>,
+    person: &RecordId,
+    base_data: &BaseData,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let item_status = agenda_item.get_item_status();
+    let list = vec![AgendaItemChoice::Discussed, AgendaItemChoice::Finished];
+    let selection = Select::new("Select from the below list|", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(AgendaItemChoice::Discussed) => {
+            mark_discussed(
+                item_status,
+                agenda_item.get_reason(),
+                person,
+                base_data,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Ok(AgendaItemChoice::Finished) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::FinishItem {
+                    item: item_status.get_surreal_record_id().clone(),
+                    when_finished: Utc::now().into(),
+                })
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+/// Captures something that came up in the meeting as a smaller item of the person so it stays on
+/// their agenda
+async fn capture_for_person(
+    person: &RecordId,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let summary = Text::new("Enter New Item ⍠")
+        .with_help_message("Add tags with #, for example #phone #errand")
+        .prompt();
+    match summary {
+        Ok(summary) if summary.trim().is_empty() => Ok(()),
+        Ok(summary) => {
+            let (summary, tags) = split_tags_from_summary(&summary);
+            let mut new_item = NewItem::new(summary, Utc::now());
+            new_item.tags = tags;
+            new_item.item_type = SurrealItemType::Action;
+            send_to_data_storage_layer
+                .send(DataLayerCommands::ParentItemWithANewChildItem {
+                    child: new_item,
+                    parent: person.clone(),
+                    higher_importance_than_this: None,
+                })
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+/// Walks through everything to bring up with a person or group: what is stated under them, what is
+/// waiting on them, and what was delegated to them. The time from picking the person until the
+/// meeting ends is logged against the person and anything that was touched along the way.
+pub(crate) async fn present_meeting_with_menu(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let base_data = BaseData::new_from_surreal_tables(surreal_tables, Utc::now());
+    let Some(person) = pick_person(&base_data)? else {
+        return Ok(());
+    };
+    let person_summary = base_data
+        .get_items()
+        .get(&person)
+        .expect("Picked from the list of items")
+        .get_summary()
+        .to_string();
+    drop(base_data);
+
+    let when_started = Utc::now();
+    let mut touched: Vec<RecordId> = Vec::default();
+    let result = loop {
+        let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let agenda = find_person_agenda(&person, calculated_data.get_items_status());
+        let list = agenda
+            .iter()
+            .map(AgendaChoice::Item)
+            .chain([AgendaChoice::Capture, AgendaChoice::EndMeeting])
+            .collect::<Vec<_>>();
+
+        println!();
+        if agenda.is_empty() {
+            println!(
+                "👥 Meeting with {}, nothing is on the agenda",
+                person_summary
+            );
+        } else {
+            println!(
+                "👥 Meeting with {}, {} item(s) on the agenda",
+                person_summary,
+                agenda.len()
+            );
+        }
+        let selection = Select::new("Select from the below list|", list)
+            .with_page_size(default_select_page_size())
+            .prompt();
+        match selection {
+            Ok(AgendaChoice::Item(agenda_item)) => {
+                let record_id = agenda_item.get_item_status().get_surreal_record_id();
+                if !touched.contains(record_id) {
+                    touched.push(record_id.clone());
+                }
+                if let Err(()) = present_agenda_item_menu(
+                    agenda_item,
+                    &person,
+                    calculated_data.get_base_data(),
+                    send_to_data_storage_layer,
+                )
+                .await
+                {
+                    break Err(());
+                }
+            }
+            Ok(AgendaChoice::Capture) => {
+                if let Err(()) = capture_for_person(&person, send_to_data_storage_layer).await {
+                    break Err(());
+                }
+            }
+            Ok(AgendaChoice::EndMeeting) | Err(InquireError::OperationCanceled) => break Ok(()),
+            Err(InquireError::OperationInterrupted) => break Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    };
+
+    let when_stopped = Utc::now();
+    let working_on = std::iter::once(SurrealAction::MakeProgress(person))
+        .chain(touched.into_iter().map(SurrealAction::MakeProgress))
+        .collect();
+    send_to_data_storage_layer
+        .send(DataLayerCommands::RecordTimeSpent(NewTimeSpent {
+            working_on,
+            urgency: None,
+            why_in_scope: vec![SurrealWhyInScope::MenuNavigation],
+            when_started,
+            when_stopped,
+        }))
+        .await
+        .unwrap();
+    if result.is_ok() {
+        let duration = (when_stopped - when_started).to_std().unwrap_or_default();
+        println!();
+        println!(
+            "Meeting with {} logged, {}",
+            person_summary,
+            DisplayDuration::new(&duration)
+        );
+        println!();
+    }
+    result
+}
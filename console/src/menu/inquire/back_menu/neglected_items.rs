@@ -0,0 +1,185 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::{Local, TimeDelta, Utc};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal,
+};
+use inquire::{InquireError, Select};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_item::Responsibility,
+        surreal_tables::SurrealTables,
+    },
+    display::{
+        display_item::DisplayItem,
+        display_item_node::{DisplayFormat, DisplayItemNode},
+    },
+    menu::inquire::{
+        default_select_page_size, do_now_list_menu::review_item::present_review_item_menu,
+        snooze_item::snooze_item,
+    },
+    node::Filter,
+    systems::neglected::{NeglectedItem, find_neglected_items},
+};
+
+/// How long an item has to go without being worked on or reviewed before it is shown as neglected.
+const NEGLECTED_AFTER_DAYS: i64 = 30;
+
+struct NeglectedItemChoice<'e> {
+    neglected: NeglectedItem<'e>,
+    days: i64,
+}
+
+impl Display for NeglectedItemChoice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let motivation = match self.neglected.get_top_motivation() {
+            Some(motivation) => motivation.get_summary(),
+            None => "No motivation",
+        };
+        let last_touched = self
+            .neglected
+            .get_last_touched()
+            .with_timezone(&Local)
+            .format("%a %d %b %Y");
+        write!(
+            f,
+            "[{}] {} days (since {}) {}",
+            motivation,
+            self.days,
+            last_touched,
+            DisplayItem::new(self.neglected.get_item_status().get_item())
+        )
+    }
+}
+
+enum NeglectedItemAction {
+    Finish,
+    Snooze,
+    MakeReactive,
+    ReviewNow,
+    Back,
+}
+
+fn read_neglected_item_action() -> Result<NeglectedItemAction, ()> {
+    println!(
+        "Press: (f) finish, (s) snooze, (m) make reactive, only act when it comes up, (r) review now, any other key to go back"
+    );
+    terminal::enable_raw_mode().unwrap();
+    let key = loop {
+        match event::read() {
+            Ok(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => break key_event,
+            Ok(_) => continue,
+            Err(err) => {
+                terminal::disable_raw_mode().unwrap();
+                panic!("Unexpected error, try restarting the terminal: {}", err)
+            }
+        }
+    };
+    terminal::disable_raw_mode().unwrap();
+
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Err(()),
+        KeyCode::Char('f') | KeyCode::Char('F') => Ok(NeglectedItemAction::Finish),
+        KeyCode::Char('s') | KeyCode::Char('S') => Ok(NeglectedItemAction::Snooze),
+        KeyCode::Char('m') | KeyCode::Char('M') => Ok(NeglectedItemAction::MakeReactive),
+        KeyCode::Char('r') | KeyCode::Char('R') => Ok(NeglectedItemAction::ReviewNow),
+        _ => Ok(NeglectedItemAction::Back),
+    }
+}
+
+pub(crate) async fn present_neglected_items_menu(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    loop {
+        let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let list = find_neglected_items(
+            calculated_data.get_items_status(),
+            calculated_data.get_base_data().get_time_spent_log(),
+            &now,
+            TimeDelta::days(NEGLECTED_AFTER_DAYS),
+        )
+        .into_iter()
+        .map(|neglected| NeglectedItemChoice {
+            days: neglected.get_days_since_last_touched(&now),
+            neglected,
+        })
+        .collect::<Vec<_>>();
+
+        if list.is_empty() {
+            println!();
+            println!(
+                "Nothing is neglected, every active goal and action has been touched in the last {} days.",
+                NEGLECTED_AFTER_DAYS
+            );
+            println!();
+            return Ok(());
+        }
+
+        println!();
+        let selection = Select::new(
+            "Neglected items, by days since last worked on or reviewed|",
+            list,
+        )
+        .with_page_size(default_select_page_size())
+        .prompt();
+        let selected = match selection {
+            Ok(selected) => selected,
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        };
+
+        let item_status = selected.neglected.get_item_status();
+        println!();
+        println!(
+            "{}",
+            DisplayItemNode::new(
+                item_status.get_item_node(),
+                Filter::Active,
+                DisplayFormat::MultiLineTree
+            )
+        );
+        let record_id = item_status.get_surreal_record_id().clone();
+        match read_neglected_item_action()? {
+            NeglectedItemAction::Finish => {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::FinishItem {
+                        item: record_id,
+                        when_finished: Utc::now().into(),
+                    })
+                    .await
+                    .unwrap();
+            }
+            NeglectedItemAction::Snooze => {
+                snooze_item(record_id, send_to_data_storage_layer).await?;
+            }
+            NeglectedItemAction::MakeReactive => {
+                //Reactive items wait for something to come up so they stay on the books without showing up
+                //in the Do Now list
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::UpdateResponsibilityAndItemType(
+                        record_id,
+                        Responsibility::ReactiveBeAvailableToAct,
+                        item_status.get_item().get_item_type().clone(),
+                    ))
+                    .await
+                    .unwrap();
+            }
+            NeglectedItemAction::ReviewNow => {
+                present_review_item_menu(item_status, send_to_data_storage_layer).await?;
+            }
+            NeglectedItemAction::Back => {}
+        }
+    }
+}
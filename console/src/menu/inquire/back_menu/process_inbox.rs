@@ -0,0 +1,226 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::Utc;
+use inquire::{InquireError, Select};
+use surrealdb::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands,
+        surreal_item::{Responsibility, SurrealItemType},
+        surreal_tables::SurrealTables,
+    },
+    display::display_item_node::{DisplayFormat, DisplayItemNode},
+    menu::inquire::{
+        default_select_page_size,
+        do_now_list_menu::{
+            do_now_list_single_item::{
+                declare_item_type, give_this_item_a_parent::give_this_item_a_parent,
+                urgency_plan::present_set_ready_and_urgency_plan_menu,
+            },
+            pick_item_review_frequency::present_pick_item_review_frequency_menu,
+        },
+    },
+    node::{Filter, item_status::ItemStatus},
+    systems::inbox::find_inbox_items,
+};
+
+enum InboxItemChoice {
+    Classify,
+    PickAParent,
+    SetReadinessAndUrgency,
+    SetReviewFrequency,
+    MarkAsAnIdea,
+    Delete,
+    Skip,
+    Stop,
+}
+
+impl Display for InboxItemChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InboxItemChoice::Classify => write!(f, "🏷️  Classify, what type of item is this?"),
+            InboxItemChoice::PickAParent => write!(f, "⭱  Pick a parent, why do this?"),
+            InboxItemChoice::SetReadinessAndUrgency => write!(f, "🚦  Set readiness and urgency"),
+            InboxItemChoice::SetReviewFrequency => write!(f, "🔁  Set review frequency"),
+            InboxItemChoice::MarkAsAnIdea => write!(f, "💡  Mark as an idea"),
+            InboxItemChoice::Delete => write!(f, "🗑️  Delete"),
+            InboxItemChoice::Skip => write!(f, "⏭️  Skip for now"),
+            InboxItemChoice::Stop => write!(f, "🔙  Stop processing the inbox"),
+        }
+    }
+}
+
+impl InboxItemChoice {
+    fn make_list(can_delete: bool) -> Vec<InboxItemChoice> {
+        let mut list = vec![
+            InboxItemChoice::Classify,
+            InboxItemChoice::PickAParent,
+            InboxItemChoice::SetReadinessAndUrgency,
+            InboxItemChoice::SetReviewFrequency,
+            InboxItemChoice::MarkAsAnIdea,
+        ];
+        if can_delete {
+            list.push(InboxItemChoice::Delete);
+        }
+        list.push(InboxItemChoice::Skip);
+        list.push(InboxItemChoice::Stop);
+        list
+    }
+}
+
+enum YesOrNo {
+    Yes,
+    No,
+}
+
+impl Display for YesOrNo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            YesOrNo::Yes => write!(f, "Yes"),
+            YesOrNo::No => write!(f, "No"),
+        }
+    }
+}
+
+fn what_is_needed(item_status: &ItemStatus<'_>) -> &'static str {
+    match item_status.get_item().get_item_type() {
+        SurrealItemType::Undeclared if item_status.has_parents(Filter::Active) => "Needs a type",
+        SurrealItemType::Undeclared => "Needs a type and a parent",
+        SurrealItemType::Action
+        | SurrealItemType::Goal(_)
+        | SurrealItemType::IdeaOrThought
+        | SurrealItemType::Motivation(_)
+        | SurrealItemType::PersonOrGroup => "Needs a parent",
+    }
+}
+
+/// Walks through every undeclared or parentless item one at a time, oldest capture first, so the
+/// inbox can be taken to zero in one sitting rather than as they come up in the Do Now list. An
+/// item stays on screen until it is no longer in the inbox so several things can be set on it.
+pub(crate) async fn present_process_inbox_menu(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let mut skipped: Vec<RecordId> = Vec::default();
+    let mut shown: Vec<RecordId> = Vec::default();
+    loop {
+        let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let remaining = find_inbox_items(calculated_data.get_items_status())
+            .into_iter()
+            .filter(|x| !skipped.contains(x.get_surreal_record_id()))
+            .collect::<Vec<_>>();
+        let Some(item_status) = remaining.first() else {
+            println!();
+            if skipped.is_empty() {
+                println!("📭 Inbox zero, every item has a type and a reason to do it.");
+            } else {
+                println!(
+                    "📭 Done for now, {} skipped item(s) are still in the inbox.",
+                    skipped.len()
+                );
+            }
+            println!();
+            return Ok(());
+        };
+        let record_id = item_status.get_surreal_record_id().clone();
+        if !shown.contains(&record_id) {
+            shown.push(record_id.clone());
+        }
+        let handled = shown
+            .iter()
+            .filter(|x| !remaining.iter().any(|y| y.get_surreal_record_id() == *x))
+            .count();
+
+        println!();
+        println!(
+            "📥 Inbox item {} of {}, {}",
+            handled + 1,
+            handled + remaining.len(),
+            what_is_needed(item_status)
+        );
+        println!(
+            "{}",
+            DisplayItemNode::new(
+                item_status.get_item_node(),
+                Filter::Active,
+                DisplayFormat::MultiLineTree
+            )
+        );
+
+        //Once time is logged against an item it is part of the record of what was done so it
+        //should be finished rather than deleted. Smaller items would be left without a parent.
+        let has_time_spent = calculated_data
+            .get_base_data()
+            .get_time_spent_log()
+            .iter()
+            .any(|x| x.worked_towards().contains(&record_id));
+        let can_delete = !has_time_spent && !item_status.has_children(Filter::All);
+        let selection = Select::new(
+            "Select from the below list|",
+            InboxItemChoice::make_list(can_delete),
+        )
+        .with_page_size(default_select_page_size())
+        .prompt();
+        match selection {
+            Ok(InboxItemChoice::Classify) => {
+                declare_item_type(item_status.get_item(), send_to_data_storage_layer).await?
+            }
+            Ok(InboxItemChoice::PickAParent) => {
+                give_this_item_a_parent(item_status.get_item(), false, send_to_data_storage_layer)
+                    .await?
+            }
+            Ok(InboxItemChoice::SetReadinessAndUrgency) => {
+                present_set_ready_and_urgency_plan_menu(
+                    item_status,
+                    calculated_data.get_base_data(),
+                    send_to_data_storage_layer,
+                )
+                .await?
+            }
+            Ok(InboxItemChoice::SetReviewFrequency) => {
+                present_pick_item_review_frequency_menu(item_status, send_to_data_storage_layer)
+                    .await?
+            }
+            Ok(InboxItemChoice::MarkAsAnIdea) => send_to_data_storage_layer
+                .send(DataLayerCommands::UpdateResponsibilityAndItemType(
+                    record_id,
+                    Responsibility::ProactiveActionToTake,
+                    SurrealItemType::IdeaOrThought,
+                ))
+                .await
+                .unwrap(),
+            Ok(InboxItemChoice::Delete) => {
+                let confirm = Select::new(
+                    &format!(
+                        "Delete \"{}\"? This cannot be undone.",
+                        item_status.get_summary()
+                    ),
+                    vec![YesOrNo::No, YesOrNo::Yes],
+                )
+                .prompt();
+                match confirm {
+                    Ok(YesOrNo::Yes) => send_to_data_storage_layer
+                        .send(DataLayerCommands::DeleteItem(record_id))
+                        .await
+                        .unwrap(),
+                    Ok(YesOrNo::No) | Err(InquireError::OperationCanceled) => {}
+                    Err(InquireError::OperationInterrupted) => return Err(()),
+                    Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+                }
+            }
+            Ok(InboxItemChoice::Skip) => skipped.push(record_id),
+            Ok(InboxItemChoice::Stop) | Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::Utc;
+use inquire::{InquireError, MultiSelect, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_item::SurrealItemType,
+        surreal_tables::SurrealTables,
+    },
+    menu::{command_line::standup::standup_markdown, inquire::default_select_page_size},
+};
+
+/// Asks which motivations to limit the report to, none selected means everything, then prints the
+/// same markdown that `taskonpurpose standup` prints so it can be copied into the team's chat.
+pub(crate) async fn present_standup_report(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    let mut motivations = surreal_tables
+        .surreal_items
+        .iter()
+        .filter(|x| matches!(x.item_type, SurrealItemType::Motivation(..)) && x.finished.is_none())
+        .map(|x| x.summary.clone())
+        .collect::<Vec<_>>();
+    motivations.sort();
+    motivations.dedup();
+
+    let under = if motivations.is_empty() {
+        Vec::default()
+    } else {
+        match MultiSelect::new("Only include the items under…", motivations)
+            .with_help_message("Select none to include everything")
+            .with_page_size(default_select_page_size())
+            .prompt()
+        {
+            Ok(under) => under,
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    };
+
+    println!();
+    match standup_markdown(surreal_tables, Utc::now(), &under) {
+        Ok(markdown) => print!("{}", markdown),
+        Err(err) => println!("{}", err),
+    }
+    println!();
+    match Text::new("Press Enter to continue...").prompt() {
+        Ok(_) | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
@@ -0,0 +1,226 @@
+use ahash::HashMap;
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, Utc};
+use image::Rgba;
+use surrealdb::RecordId;
+
+use crate::{
+    base_data::time_spent::TimeSpent,
+    display::display_chart::{ChartSeries, DisplayDonutChart, DisplayStackedBarChart, print_chart},
+    node::item_status::ItemStatus,
+};
+
+const MOTIVATION_SERIES: [ChartSeries; 3] = [
+    ChartSeries {
+        name: "🏢 Core",
+        symbol: '█',
+        color: Rgba([66, 133, 244, 255]),
+    },
+    ChartSeries {
+        name: "🧹 Non-core",
+        symbol: '▓',
+        color: Rgba([244, 160, 0, 255]),
+    },
+    ChartSeries {
+        name: "🚫 Neither",
+        symbol: '░',
+        color: Rgba([158, 158, 158, 255]),
+    },
+];
+
+const URGENCY_SERIES: [ChartSeries; 4] = [
+    ChartSeries {
+        name: "Urgent",
+        symbol: '█',
+        color: Rgba([219, 68, 55, 255]),
+    },
+    ChartSeries {
+        name: "Important",
+        symbol: '▓',
+        color: Rgba([15, 157, 88, 255]),
+    },
+    ChartSeries {
+        name: "Urgent and important",
+        symbol: '▒',
+        color: Rgba([171, 71, 188, 255]),
+    },
+    ChartSeries {
+        name: "Neither",
+        symbol: '░',
+        color: Rgba([158, 158, 158, 255]),
+    },
+];
+
+fn hours(time: TimeDelta) -> f64 {
+    time.num_seconds() as f64 / 3600.0
+}
+
+/// Index into `MOTIVATION_SERIES`. Like the rest of the reflection report, time that is not under a
+/// core or a neither motivation counts as non-core.
+fn motivation_series(
+    items_status: &HashMap<&RecordId, ItemStatus<'_>>,
+    time_spent: &TimeSpent<'_>,
+) -> usize {
+    let worked_towards = time_spent
+        .worked_towards()
+        .iter()
+        .filter_map(|x| items_status.get(x))
+        .collect::<Vec<_>>();
+    if worked_towards
+        .iter()
+        .any(|x| x.is_type_motivation_kind_core())
+    {
+        0
+    } else if worked_towards
+        .iter()
+        .any(|x| x.is_type_motivation_kind_neither())
+    {
+        2
+    } else {
+        1
+    }
+}
+
+/// Hours of core, non-core, and neither work for every local day from `start` to `end`, days with
+/// nothing logged included. Time counts towards the day it was started on.
+fn hours_per_day(
+    items_status: &HashMap<&RecordId, ItemStatus<'_>>,
+    time_spent_log: &[TimeSpent<'_>],
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> Vec<(NaiveDate, [f64; 3])> {
+    let first_day = start.with_timezone(&Local).date_naive();
+    let last_day = end.with_timezone(&Local).date_naive();
+    let mut result = first_day
+        .iter_days()
+        .take_while(|x| *x <= last_day)
+        .map(|x| (x, [0.0; 3]))
+        .collect::<Vec<_>>();
+    for time_spent in time_spent_log.iter() {
+        let day = time_spent
+            .get_started_at()
+            .with_timezone(&Local)
+            .date_naive();
+        let Some((_, hours_by_series)) = result.iter_mut().find(|(x, _)| *x == day) else {
+            continue;
+        };
+        hours_by_series[motivation_series(items_status, time_spent)] +=
+            hours(time_spent.get_time_delta());
+    }
+    result
+}
+
+/// Hours that were urgent, important, both, or neither, in the order of `URGENCY_SERIES`
+fn hours_by_urgency(time_spent_log: &[TimeSpent<'_>]) -> Vec<f64> {
+    time_spent_log
+        .iter()
+        .fold(vec![0.0; URGENCY_SERIES.len()], |mut acc, time_spent| {
+            let series = match (time_spent.is_urgent(), time_spent.is_important()) {
+                (true, false) => 0,
+                (false, true) => 1,
+                (true, true) => 2,
+                (false, false) => 3,
+            };
+            acc[series] += hours(time_spent.get_time_delta());
+            acc
+        })
+}
+
+pub(crate) fn print_time_charts(
+    items_status: &HashMap<&RecordId, ItemStatus<'_>>,
+    time_spent_log: &[TimeSpent<'_>],
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) {
+    let bars = hours_per_day(items_status, time_spent_log, start, end)
+        .into_iter()
+        .map(|(day, hours)| (day.format("%a %d %b").to_string(), hours.to_vec()))
+        .collect();
+    print_chart(&DisplayStackedBarChart::new(
+        "Hours per day".to_string(),
+        "h",
+        &MOTIVATION_SERIES,
+        bars,
+    ));
+    println!();
+    print_chart(&DisplayDonutChart::new(
+        "Urgency vs importance".to_string(),
+        "h",
+        &URGENCY_SERIES,
+        hours_by_urgency(time_spent_log),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_in_the_moment_priority::SurrealAction,
+            surreal_item::{SurrealItemBuilder, SurrealItemType, SurrealMotivationKind},
+            surreal_tables::SurrealTablesBuilder,
+            surreal_time_spent::SurrealTimeSpent,
+        },
+    };
+
+    use super::hours_per_day;
+
+    #[test]
+    fn every_day_in_the_range_gets_a_bar_split_by_motivation() {
+        let now = Utc::now();
+        let yesterday_noon = (Local::now().date_naive() - TimeDelta::days(1))
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc);
+        let time_spent = |item: &str, minutes: i64| SurrealTimeSpent {
+            id: None,
+            version: 1,
+            working_on: vec![SurrealAction::MakeProgress(("item", item).into())],
+            why_in_scope: vec![],
+            urgency: None,
+            when_started: yesterday_noon.into(),
+            when_stopped: (yesterday_noon + TimeDelta::minutes(minutes)).into(),
+            dedication: None,
+        };
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "core").into()))
+                    .summary("Ship the release")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::CoreWork))
+                    .created(now - TimeDelta::days(10))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "chore").into()))
+                    .summary("Do the dishes")
+                    .item_type(SurrealItemType::Action)
+                    .created(now - TimeDelta::days(10))
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_time_spent_log(vec![time_spent("core", 90), time_spent("chore", 30)])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let days = hours_per_day(
+            calculated_data.get_items_status(),
+            calculated_data.get_base_data().get_time_spent_log(),
+            &(yesterday_noon - TimeDelta::days(1)),
+            &now,
+        );
+
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].1, [0.0, 0.0, 0.0]);
+        assert_eq!(days[1].0, yesterday_noon.with_timezone(&Local).date_naive());
+        assert_eq!(days[1].1, [1.5, 0.5, 0.0]);
+        assert_eq!(days[2].1, [0.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::{DateTime, Local, Utc};
+use inquire::{InquireError, Select};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    calculated_data::CalculatedData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_tables::SurrealTables,
+    },
+    display::display_item::DisplayItem,
+    menu::inquire::{default_select_page_size, waiting_for::present_waiting_for_menu},
+    node::item_status::ItemStatus,
+    systems::waiting_for::find_waiting_for_by_person,
+};
+
+struct WaitingForChoice<'e> {
+    person: &'e str,
+    item_status: &'e ItemStatus<'e>,
+}
+
+impl Display for WaitingForChoice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let item = self.item_status.get_item();
+        let waiting_for = item
+            .get_waiting_for()
+            .expect("Only waiting for items are listed");
+        let follow_up: DateTime<Utc> = waiting_for.follow_up.clone().into();
+        let follow_up: DateTime<Local> = follow_up.into();
+        let due = if item.is_follow_up_due() { "📞 " } else { "" };
+        write!(
+            f,
+            "[{}] {}follow up {} {}",
+            self.person,
+            due,
+            follow_up.format("%a %d %b %Y"),
+            DisplayItem::new(item)
+        )
+    }
+}
+
+/// Everything that is waiting on someone else grouped by who it is waiting on, with the follow ups
+/// that are due marked. Selecting an item is for recording a follow up or that the wait is over.
+pub(crate) async fn present_waiting_for_report(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    loop {
+        let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let by_person = find_waiting_for_by_person(calculated_data.get_items_status());
+        if by_person.is_empty() {
+            println!();
+            println!("Nothing is waiting on anyone else.");
+            println!();
+            return Ok(());
+        }
+
+        let list = by_person
+            .iter()
+            .flat_map(|waiting_for_person| {
+                let person = match waiting_for_person.get_person() {
+                    Some(person) => person.get_summary(),
+                    None => "Person no longer exists",
+                };
+                waiting_for_person
+                    .get_waiting()
+                    .iter()
+                    .copied()
+                    .map(move |item_status| WaitingForChoice {
+                        person,
+                        item_status,
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        println!();
+        let selection = Select::new("Waiting for, grouped by person|", list)
+            .with_page_size(default_select_page_size())
+            .prompt();
+        match selection {
+            Ok(selected) => {
+                present_waiting_for_menu(
+                    selected.item_status,
+                    calculated_data.get_base_data(),
+                    send_to_data_storage_layer,
+                )
+                .await?
+            }
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+}
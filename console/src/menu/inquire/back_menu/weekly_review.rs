@@ -0,0 +1,512 @@
+use std::fmt::Display;
+
+use better_term::Style;
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use inquire::{InquireError, Select, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands,
+        surreal_tables::SurrealTables,
+        surreal_weekly_review::{SurrealWeeklyReview, SurrealWeeklyReviewStep},
+    },
+    display::{
+        display_duration::DisplayDuration, display_item::DisplayItem,
+        display_scheduled_item::DisplayScheduledItem,
+    },
+    menu::inquire::{
+        daily_plan::today,
+        default_select_page_size,
+        do_now_list_menu::{load_do_now_list_from_db, review_item::present_review_item_menu},
+    },
+    node::item_status::ItemStatus,
+    systems::{do_now_list::DoNowList, neglected::find_top_motivation},
+};
+
+use super::{
+    end_of_day::time_by_motivation, interruption_report::print_interruptions,
+    process_inbox::present_process_inbox_menu, waiting_for_report::present_waiting_for_report,
+};
+
+/// How far ahead the look ahead step looks
+const LOOK_AHEAD_DAYS: i64 = 14;
+
+/// How far back the time balance and celebration steps look
+const LOOK_BACK_DAYS: i64 = 7;
+
+fn step_title(step: &SurrealWeeklyReviewStep) -> &'static str {
+    match step {
+        SurrealWeeklyReviewStep::EmptyTheInbox => "📥 Empty the inbox",
+        SurrealWeeklyReviewStep::ReviewsDue => "🔁 Reviews that are due",
+        SurrealWeeklyReviewStep::WaitingForAndEvents => "⏳ Waiting for and pending events",
+        SurrealWeeklyReviewStep::LookAhead => "📅 The next two weeks",
+        SurrealWeeklyReviewStep::TimeBalance => "⚖️  Time balance for the past week",
+        SurrealWeeklyReviewStep::Celebrate => "🎉 Celebrate what was finished",
+    }
+}
+
+/// The most recently started review that still has steps left
+fn review_to_resume(weekly_reviews: &[SurrealWeeklyReview]) -> Option<&SurrealWeeklyReview> {
+    weekly_reviews
+        .iter()
+        .filter(|x| !x.is_finished())
+        .max_by(|a, b| a.started.cmp(&b.started))
+}
+
+fn last_finished(weekly_reviews: &[SurrealWeeklyReview]) -> Option<DateTime<Utc>> {
+    weekly_reviews
+        .iter()
+        .filter_map(|x| x.finished.clone())
+        .max()
+        .map(|x| x.into())
+}
+
+enum StartChoice<'e> {
+    Resume(&'e SurrealWeeklyReview),
+    StartOver,
+}
+
+impl Display for StartChoice<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StartChoice::Resume(weekly_review) => {
+                let started: DateTime<Utc> = weekly_review.started.clone().into();
+                let next_step = weekly_review
+                    .next_step
+                    .expect("Only unfinished reviews are resumed");
+                write!(
+                    f,
+                    "▶️  Resume the review started {} at step {}, {}",
+                    started.with_timezone(&Local).format("%a %d %b"),
+                    next_step.number(),
+                    step_title(&next_step)
+                )
+            }
+            StartChoice::StartOver => write!(f, "🆕 Start a new review"),
+        }
+    }
+}
+
+/// The review left behind when starting over, closed without being finished so it is not offered
+/// again
+fn abandoned(weekly_review: &SurrealWeeklyReview) -> SurrealWeeklyReview {
+    let mut abandoned = weekly_review.clone();
+    abandoned.next_step = None;
+    abandoned
+}
+
+/// The review to do and, when starting over, the abandoned review it replaces. None when canceled.
+fn choose_review(
+    weekly_reviews: &[SurrealWeeklyReview],
+) -> Result<Option<(SurrealWeeklyReview, Option<SurrealWeeklyReview>)>, ()> {
+    let new_review = || SurrealWeeklyReview::new(today(), Utc::now().into());
+    if let Some(last_finished) = last_finished(weekly_reviews) {
+        println!(
+            "Last weekly review was finished {}",
+            last_finished.with_timezone(&Local).format("%a %d %b %Y")
+        );
+    }
+    let Some(to_resume) = review_to_resume(weekly_reviews) else {
+        return Ok(Some((new_review(), None)));
+    };
+
+    match Select::new(
+        "A weekly review was stopped part way through|",
+        vec![StartChoice::Resume(to_resume), StartChoice::StartOver],
+    )
+    .prompt()
+    {
+        Ok(StartChoice::Resume(weekly_review)) => Ok(Some((weekly_review.clone(), None))),
+        Ok(StartChoice::StartOver) => Ok(Some((new_review(), Some(abandoned(to_resume))))),
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+enum ReviewDueChoice<'e> {
+    Item {
+        motivation: Option<&'e str>,
+        item_status: &'e ItemStatus<'e>,
+    },
+    NextStep,
+}
+
+impl Display for ReviewDueChoice<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewDueChoice::Item {
+                motivation,
+                item_status,
+            } => write!(
+                f,
+                "[{}] {}",
+                motivation.unwrap_or("No motivation"),
+                DisplayItem::new(item_status.get_item())
+            ),
+            ReviewDueChoice::NextStep => write!(f, "⏭️  On to the next step"),
+        }
+    }
+}
+
+/// Items whose review frequency says a review is due, grouped by the motivation they are under with
+/// the items that are not under a motivation last
+fn make_reviews_due_list(do_now_list: &DoNowList) -> Vec<ReviewDueChoice<'_>> {
+    let items_status = do_now_list.get_all_items_status();
+    let mut list = items_status
+        .values()
+        .filter(|x| x.is_active() && x.get_item().is_a_review_due())
+        .map(|item_status| ReviewDueChoice::Item {
+            motivation: find_top_motivation(item_status, items_status).map(|x| x.get_summary()),
+            item_status,
+        })
+        .collect::<Vec<_>>();
+    list.sort_by(|a, b| match (a, b) {
+        (
+            ReviewDueChoice::Item {
+                motivation: a_motivation,
+                item_status: a_item_status,
+            },
+            ReviewDueChoice::Item {
+                motivation: b_motivation,
+                item_status: b_item_status,
+            },
+        ) => b_motivation
+            .is_some()
+            .cmp(&a_motivation.is_some())
+            .then_with(|| a_motivation.cmp(b_motivation))
+            .then_with(|| a_item_status.get_summary().cmp(b_item_status.get_summary())),
+        _ => std::cmp::Ordering::Equal,
+    });
+    list
+}
+
+async fn present_reviews_due(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    loop {
+        let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+        let mut list = make_reviews_due_list(&do_now_list);
+        if list.is_empty() {
+            println!("No reviews are due");
+            return Ok(());
+        }
+        println!("{} reviews are due", list.len());
+        list.push(ReviewDueChoice::NextStep);
+
+        match Select::new("Select an item to review|", list)
+            .with_page_size(default_select_page_size())
+            .prompt()
+        {
+            Ok(ReviewDueChoice::Item { item_status, .. }) => {
+                present_review_item_menu(item_status, send_to_data_storage_layer).await?
+            }
+            Ok(ReviewDueChoice::NextStep) | Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+}
+
+fn print_pending_events(do_now_list: &DoNowList) {
+    let mut pending = do_now_list
+        .get_event_nodes()
+        .values()
+        .filter(|x| x.is_active())
+        .collect::<Vec<_>>();
+    if pending.is_empty() {
+        println!("No events are pending");
+        return;
+    }
+    pending.sort_by(|a, b| b.get_last_updated().cmp(a.get_last_updated()));
+    println!("⚡ Events still pending");
+    for event_node in pending {
+        println!(
+            "\t{} (since {}, {} waiting on it)",
+            event_node.get_summary(),
+            event_node
+                .get_last_updated()
+                .with_timezone(&Local)
+                .format("%a %d %b"),
+            event_node.get_waiting_on_this().len()
+        );
+    }
+}
+
+fn print_look_ahead(do_now_list: &DoNowList) {
+    let upcoming = do_now_list.get_upcoming();
+    let until = *do_now_list.get_now() + TimeDelta::days(LOOK_AHEAD_DAYS);
+    let mut scheduled = upcoming
+        .get_ordered_scheduled_items()
+        .iter()
+        .flatten()
+        .filter(|x| *x.get_scheduled_start() < until)
+        .collect::<Vec<_>>();
+    scheduled.sort_by_key(|x| *x.get_scheduled_start());
+    if scheduled.is_empty() {
+        println!("Nothing is scheduled in the next {} days", LOOK_AHEAD_DAYS);
+    }
+    for scheduled_item in scheduled {
+        println!("{}", DisplayScheduledItem::new(scheduled_item));
+    }
+    if upcoming.has_conflicts() {
+        println!("Scheduled items don't fit, at least one of these needs to be adjusted:");
+        for conflict in upcoming.get_conflicts() {
+            println!("{}", DisplayItem::new(conflict));
+        }
+    }
+}
+
+fn percent_of(part: &chrono::Duration, total: &chrono::Duration) -> i64 {
+    if total.is_zero() {
+        0
+    } else {
+        part.num_seconds() * 100 / total.num_seconds()
+    }
+}
+
+fn print_time_balance(do_now_list: &DoNowList) {
+    let now = *do_now_list.get_now();
+    let week_ago = now - TimeDelta::days(LOOK_BACK_DAYS);
+    let time_by_motivation = time_by_motivation(do_now_list, &(week_ago, now));
+    let total = time_by_motivation.core + time_by_motivation.non_core + time_by_motivation.neither;
+    if total.is_zero() {
+        println!("No time was logged in the past {} days", LOOK_BACK_DAYS);
+    } else {
+        for (name, time) in [
+            ("🏢 Core work", &time_by_motivation.core),
+            ("🧹 Non-core work", &time_by_motivation.non_core),
+            ("🚫 Neither", &time_by_motivation.neither),
+        ] {
+            println!(
+                "{}: {} ({}%)",
+                name,
+                DisplayDuration::new(&time.to_std().unwrap_or_default()),
+                percent_of(time, &total)
+            );
+        }
+    }
+
+    let base_data = do_now_list.get_base_data();
+    let break_time = base_data
+        .get_surreal_breaks()
+        .iter()
+        .map(|x| -> (DateTime<Utc>, DateTime<Utc>) {
+            (x.when_started.clone().into(), x.when_stopped.clone().into())
+        })
+        .filter(|(when_started, _)| *when_started >= week_ago)
+        .map(|(when_started, when_stopped)| when_stopped - when_started)
+        .sum::<chrono::Duration>();
+    if !break_time.is_zero() {
+        println!(
+            "☕ Breaks: {}",
+            DisplayDuration::new(&break_time.to_std().unwrap_or_default())
+        );
+    }
+    print_interruptions(base_data.get_surreal_interruptions(), &week_ago, &now);
+}
+
+fn print_finished_this_week(do_now_list: &DoNowList) {
+    let week_ago = *do_now_list.get_now() - TimeDelta::days(LOOK_BACK_DAYS);
+    let mut finished = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| x.get_finished_at().is_some_and(|x| x >= week_ago))
+        .collect::<Vec<_>>();
+    if finished.is_empty() {
+        println!("Nothing was finished this week, the next one is a fresh start");
+        return;
+    }
+    finished.sort_by_key(|x| *x.get_finished_at());
+    println!(
+        "{}🎉 {} things finished this week{}",
+        Style::new().bold(),
+        finished.len(),
+        Style::new()
+    );
+    for item_status in finished {
+        println!("\t✅ {}", DisplayItem::new(item_status.get_item()));
+    }
+}
+
+fn press_enter_to_continue() -> Result<(), ()> {
+    match Text::new("Press Enter to continue...").prompt() {
+        Ok(_) | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+async fn present_step(
+    step: &SurrealWeeklyReviewStep,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    match step {
+        SurrealWeeklyReviewStep::EmptyTheInbox => {
+            present_process_inbox_menu(send_to_data_storage_layer).await
+        }
+        SurrealWeeklyReviewStep::ReviewsDue => {
+            present_reviews_due(send_to_data_storage_layer).await
+        }
+        SurrealWeeklyReviewStep::WaitingForAndEvents => {
+            present_waiting_for_report(send_to_data_storage_layer).await?;
+            let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+            print_pending_events(&do_now_list);
+            press_enter_to_continue()
+        }
+        SurrealWeeklyReviewStep::LookAhead => {
+            let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+            print_look_ahead(&do_now_list);
+            press_enter_to_continue()
+        }
+        SurrealWeeklyReviewStep::TimeBalance => {
+            let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+            print_time_balance(&do_now_list);
+            press_enter_to_continue()
+        }
+        SurrealWeeklyReviewStep::Celebrate => {
+            let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+            print_finished_this_week(&do_now_list);
+            press_enter_to_continue()
+        }
+    }
+}
+
+enum BetweenStepsChoice {
+    Continue,
+    StopForNow,
+}
+
+impl Display for BetweenStepsChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BetweenStepsChoice::Continue => write!(f, "▶️  Continue"),
+            BetweenStepsChoice::StopForNow => write!(f, "⏸️  Stop for now, resume later"),
+        }
+    }
+}
+
+/// A GTD style weekly review done as one step after another. Progress is saved after each step so
+/// a review that is stopped, or interrupted, picks up again at the step it was on.
+pub(crate) async fn present_weekly_review(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
+        .await
+        .unwrap();
+    println!();
+    let Some((mut weekly_review, replaced)) =
+        choose_review(surreal_tables.get_surreal_weekly_reviews())?
+    else {
+        return Ok(());
+    };
+    if let Some(replaced) = replaced {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::SaveWeeklyReview(replaced))
+            .await
+            .unwrap();
+    }
+    send_to_data_storage_layer
+        .send(DataLayerCommands::SaveWeeklyReview(weekly_review.clone()))
+        .await
+        .unwrap();
+
+    while let Some(step) = weekly_review.next_step {
+        println!();
+        println!(
+            "{}🗓️  Weekly review step {} of {}: {}{}",
+            Style::new().bold(),
+            step.number(),
+            SurrealWeeklyReviewStep::ALL.len(),
+            step_title(&step),
+            Style::new()
+        );
+        present_step(&step, send_to_data_storage_layer).await?;
+
+        weekly_review.next_step = step.next();
+        if weekly_review.is_finished() {
+            weekly_review.finished = Some(Utc::now().into());
+        }
+        send_to_data_storage_layer
+            .send(DataLayerCommands::SaveWeeklyReview(weekly_review.clone()))
+            .await
+            .unwrap();
+
+        if let Some(next_step) = weekly_review.next_step {
+            let selection = Select::new(
+                &format!("Next up: {}", step_title(&next_step)),
+                vec![BetweenStepsChoice::Continue, BetweenStepsChoice::StopForNow],
+            )
+            .prompt();
+            match selection {
+                Ok(BetweenStepsChoice::Continue) => {}
+                Ok(BetweenStepsChoice::StopForNow) | Err(InquireError::OperationCanceled) => {
+                    println!("Progress saved, the review will resume at this step");
+                    return Ok(());
+                }
+                Err(InquireError::OperationInterrupted) => return Err(()),
+                Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+            }
+        }
+    }
+
+    println!();
+    println!("✅ Weekly review done");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeDelta, Utc};
+
+    use crate::data_storage::surrealdb_layer::surreal_weekly_review::{
+        SurrealWeeklyReview, SurrealWeeklyReviewStep,
+    };
+
+    use super::{abandoned, last_finished, review_to_resume};
+
+    #[test]
+    fn the_latest_unfinished_review_is_resumed() {
+        let now = Utc::now();
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+
+        let mut finished = SurrealWeeklyReview::new(day(24), (now - TimeDelta::days(7)).into());
+        finished.next_step = None;
+        finished.finished = Some((now - TimeDelta::days(6)).into());
+        let mut left_part_way =
+            SurrealWeeklyReview::new(day(17), (now - TimeDelta::days(14)).into());
+        left_part_way.next_step = Some(SurrealWeeklyReviewStep::TimeBalance);
+        let mut stopped = SurrealWeeklyReview::new(day(31), now.into());
+        stopped.next_step = Some(SurrealWeeklyReviewStep::LookAhead);
+
+        let weekly_reviews = vec![left_part_way.clone(), stopped.clone(), finished.clone()];
+        assert_eq!(review_to_resume(&weekly_reviews), Some(&stopped));
+        assert_eq!(
+            last_finished(&weekly_reviews),
+            Some(now - TimeDelta::days(6))
+        );
+
+        let weekly_reviews = vec![abandoned(&left_part_way), abandoned(&stopped), finished];
+        assert_eq!(review_to_resume(&weekly_reviews), None);
+        assert_eq!(
+            last_finished(&weekly_reviews),
+            Some(now - TimeDelta::days(6))
+        );
+    }
+
+    #[test]
+    fn steps_go_in_order_and_stop_after_the_last() {
+        let mut steps = Vec::default();
+        let mut step = SurrealWeeklyReview::new(
+            NaiveDate::from_ymd_opt(2024, 5, 31).unwrap(),
+            Utc::now().into(),
+        )
+        .next_step;
+        while let Some(current) = step {
+            steps.push(current);
+            step = current.next();
+        }
+        assert_eq!(steps, SurrealWeeklyReviewStep::ALL.to_vec());
+    }
+}
@@ -0,0 +1,397 @@
+use std::fmt::Display;
+
+use ahash::HashSet;
+use better_term::Style;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use inquire::{InquireError, MultiSelect, Text};
+use surrealdb::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::time_spent::TimeSpent,
+    data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands,
+    display::{
+        display_duration::DisplayDuration, display_item::DisplayItem,
+        display_item_node::DisplayFormat, display_item_status::DisplayItemStatus,
+        display_scheduled_item::DisplayScheduledItem,
+    },
+    menu::inquire::{
+        default_select_page_size,
+        do_now_list_menu::{
+            compute_time_spent_in_window,
+            do_now_list_single_item::resume_note::print_latest_resume_note,
+            load_do_now_list_from_db, start_of_today,
+        },
+    },
+    node::{
+        Filter, action_with_item_status::ActionWithItemStatus, item_status::ItemStatus,
+        urgency_level_item_with_item_status::UrgencyLevelItemWithItemStatus,
+    },
+    systems::do_now_list::DoNowList,
+};
+
+/// How many commitments can be made for one day, few enough that they can all really get done
+const MAX_COMMITMENTS: usize = 3;
+
+pub(crate) fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+pub(crate) fn has_a_plan_for_today(do_now_list: &DoNowList) -> bool {
+    do_now_list
+        .get_base_data()
+        .get_daily_plan(&today())
+        .is_some()
+}
+
+/// The item that was left unfinished before today, either what is still marked as being worked on
+/// or else the item with the newest resume note
+fn left_unfinished_before_today<'a>(
+    do_now_list: &'a DoNowList,
+    start_of_today: &DateTime<Utc>,
+) -> Option<&'a ItemStatus<'a>> {
+    let base_data = do_now_list.get_base_data();
+    let items_status = do_now_list.get_all_items_status();
+    let still_working_on = base_data
+        .get_surreal_working_on()
+        .and_then(|x| items_status.get(&x.item))
+        .filter(|x| x.is_active());
+    still_working_on.or_else(|| {
+        base_data
+            .get_surreal_resume_notes()
+            .iter()
+            .filter(|x| DateTime::<Utc>::from(x.created.clone()) < *start_of_today)
+            .max_by(|a, b| a.created.cmp(&b.created))
+            .and_then(|x| items_status.get(&x.item))
+            .filter(|x| x.is_active())
+    })
+}
+
+/// Items on the Do Now list because of their importance, in the order they are listed there
+fn importance_list<'a>(do_now_list: &'a DoNowList) -> Vec<&'a RecordId> {
+    let mut seen = HashSet::default();
+    do_now_list
+        .get_ordered_do_now_list()
+        .iter()
+        .flat_map(|x| match x {
+            UrgencyLevelItemWithItemStatus::SingleItem(item) => std::slice::from_ref(item),
+            UrgencyLevelItemWithItemStatus::MultipleItems(items) => items.as_slice(),
+        })
+        .filter(|x| x.is_in_scope_for_importance())
+        .filter(|x| matches!(x.get_action(), ActionWithItemStatus::MakeProgress(_)))
+        .map(|x| x.get_surreal_record_id())
+        .filter(|x| seen.insert(*x))
+        .collect()
+}
+
+struct Commitment<'e>(&'e ItemStatus<'e>);
+
+impl Display for Commitment<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display = DisplayItemStatus::new(self.0, Filter::Active, DisplayFormat::SingleLine);
+        write!(f, "{}", display)
+    }
+}
+
+fn print_where_things_stand(do_now_list: &DoNowList, start_of_today: &DateTime<Utc>) {
+    let bold = Style::new().bold();
+    let normal = Style::new();
+
+    if let Some(unfinished) = left_unfinished_before_today(do_now_list, start_of_today) {
+        println!();
+        println!("{}Left unfinished:{}", bold, normal);
+        println!("{}", DisplayItem::new(unfinished.get_item()));
+        print_latest_resume_note(unfinished.get_item(), do_now_list.get_base_data());
+    }
+
+    let today = today();
+    let scheduled_today = do_now_list
+        .get_upcoming()
+        .get_ordered_scheduled_items()
+        .iter()
+        .flatten()
+        .filter(|x| x.get_scheduled_start().with_timezone(&Local).date_naive() == today)
+        .collect::<Vec<_>>();
+    if !scheduled_today.is_empty() {
+        println!();
+        println!("{}Scheduled today:{}", bold, normal);
+        for scheduled_item in scheduled_today {
+            println!("{}", DisplayScheduledItem::new(scheduled_item));
+        }
+    }
+
+    let mut reviews_due = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| x.is_active() && x.get_item().is_a_review_due())
+        .map(|x| x.get_item())
+        .collect::<Vec<_>>();
+    if !reviews_due.is_empty() {
+        reviews_due.sort_by(|a, b| a.get_summary().cmp(b.get_summary()));
+        println!();
+        println!("{}Reviews due:{}", bold, normal);
+        for item in reviews_due {
+            println!("{}", DisplayItem::new(item));
+        }
+    }
+}
+
+/// Walks through what was left unfinished, what is scheduled today, and the reviews that are due,
+/// then asks for up to three commitments for today. Planning again replaces today's commitments.
+pub(crate) async fn present_daily_planning(
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let do_now_list = load_do_now_list_from_db(send_to_data_storage_layer).await;
+    let start_of_today = start_of_today().with_timezone(&Utc);
+
+    println!();
+    println!("🌅 Planning {}", Local::now().format("%A %d %B"));
+    print_where_things_stand(&do_now_list, &start_of_today);
+
+    let items_status = do_now_list.get_all_items_status();
+    let already_committed = do_now_list
+        .get_base_data()
+        .get_daily_plan(&today())
+        .map(|x| x.commitments.clone())
+        .unwrap_or_default();
+    let mut candidates = already_committed
+        .iter()
+        .filter_map(|x| items_status.get(x))
+        .filter(|x| x.is_active())
+        .collect::<Vec<_>>();
+    let already_committed_count = candidates.len();
+    candidates.extend(
+        importance_list(&do_now_list)
+            .into_iter()
+            .filter(|x| !already_committed.contains(x))
+            .filter_map(|x| items_status.get(x)),
+    );
+    if candidates.is_empty() {
+        println!();
+        println!("Nothing on the importance list to commit to");
+        return Ok(());
+    }
+
+    println!();
+    let commitments = loop {
+        let list = candidates
+            .iter()
+            .copied()
+            .map(Commitment)
+            .collect::<Vec<_>>();
+        let selected = MultiSelect::new(
+            &format!(
+                "Pick up to {} things to get done today (Space: toggle, Enter: done)|",
+                MAX_COMMITMENTS
+            ),
+            list,
+        )
+        .with_default(&(0..already_committed_count).collect::<Vec<_>>())
+        .with_page_size(default_select_page_size())
+        .prompt();
+        match selected {
+            Ok(selected) if selected.len() > MAX_COMMITMENTS => {
+                println!(
+                    "{} were picked, please pick no more than {}",
+                    selected.len(),
+                    MAX_COMMITMENTS
+                );
+                continue;
+            }
+            Ok(selected) => {
+                break selected
+                    .into_iter()
+                    .map(|x| x.0.get_surreal_record_id().clone())
+                    .collect::<Vec<_>>();
+            }
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    };
+
+    send_to_data_storage_layer
+        .send(DataLayerCommands::SetDailyPlan {
+            day: today(),
+            commitments,
+        })
+        .await
+        .unwrap();
+
+    println!();
+    match Text::new("Today is planned, press Enter to continue...").prompt() {
+        Ok(_) | Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Progress {
+    Done,
+    WorkedOn(chrono::Duration),
+    NotStarted,
+}
+
+/// Today's commitments in the order they were picked, along with how far along each one is
+pub(crate) fn commitments_progress<'a>(
+    do_now_list: &'a DoNowList,
+    start_of_today: &DateTime<Utc>,
+) -> Vec<(&'a ItemStatus<'a>, Progress)> {
+    let Some(plan) = do_now_list.get_base_data().get_daily_plan(&today()) else {
+        return Vec::default();
+    };
+    let items_status = do_now_list.get_all_items_status();
+    plan.commitments
+        .iter()
+        .filter_map(|x| items_status.get(x))
+        .map(|item_status| {
+            let progress = if item_status.is_finished() {
+                Progress::Done
+            } else {
+                let worked_on = do_now_list
+                    .get_time_spent_log()
+                    .iter()
+                    .filter(|x| {
+                        x.worked_towards()
+                            .contains(item_status.get_surreal_record_id())
+                    })
+                    .collect::<Vec<&TimeSpent<'_>>>();
+                let time_spent = compute_time_spent_in_window(
+                    *start_of_today,
+                    *do_now_list.get_now(),
+                    &worked_on,
+                );
+                if time_spent.is_zero() {
+                    Progress::NotStarted
+                } else {
+                    Progress::WorkedOn(time_spent)
+                }
+            };
+            (item_status, progress)
+        })
+        .collect()
+}
+
+/// Pinned above the Do Now list. Prints nothing when today has not been planned, the "Plan today"
+/// choice in the Do Now list is reminder enough.
+pub(crate) fn print_todays_commitments(do_now_list: &DoNowList) {
+    if !has_a_plan_for_today(do_now_list) {
+        return;
+    }
+
+    let start_of_today = start_of_today().with_timezone(&Utc);
+    let commitments = commitments_progress(do_now_list, &start_of_today);
+    print_commitments_progress(&commitments);
+}
+
+/// Prints nothing when there are no commitments
+pub(crate) fn print_commitments_progress(commitments: &[(&ItemStatus<'_>, Progress)]) {
+    if commitments.is_empty() {
+        return;
+    }
+    let bold = Style::new().bold();
+    let normal = Style::new();
+    let done = commitments
+        .iter()
+        .filter(|(_, progress)| *progress == Progress::Done)
+        .count();
+    println!();
+    println!(
+        "{}📌 Today's commitments ({} of {} done){}",
+        bold,
+        done,
+        commitments.len(),
+        normal
+    );
+    for (item_status, progress) in commitments.iter() {
+        let display = DisplayItem::new(item_status.get_item());
+        match progress {
+            Progress::Done => println!("  ✅ {}", display),
+            Progress::WorkedOn(time_spent) => println!(
+                "  ▶️  {} ({} today)",
+                display,
+                DisplayDuration::new(&time_spent.to_std().unwrap_or_default())
+            ),
+            Progress::NotStarted => println!("  ⬜ {}", display),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_daily_plan::SurrealDailyPlan,
+            surreal_in_the_moment_priority::SurrealAction,
+            surreal_item::{SurrealItemBuilder, SurrealItemType},
+            surreal_tables::SurrealTablesBuilder,
+            surreal_time_spent::SurrealTimeSpent,
+        },
+        systems::do_now_list::DoNowList,
+    };
+
+    use super::{Progress, commitments_progress, today};
+
+    #[test]
+    fn commitments_show_what_is_done_and_what_was_worked_on_today() {
+        let now = Utc::now();
+        let item = |id: &str, finished: bool| {
+            SurrealItemBuilder::default()
+                .id(Some(("item", id).into()))
+                .summary(id)
+                .item_type(SurrealItemType::Action)
+                .finished(if finished { Some(now.into()) } else { None })
+                .build()
+                .unwrap()
+        };
+        let start_of_today = now - Duration::hours(1);
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                item("done", true),
+                item("started", false),
+                item("untouched", false),
+            ])
+            .surreal_time_spent_log(vec![SurrealTimeSpent {
+                id: None,
+                version: 0,
+                working_on: vec![SurrealAction::MakeProgress(("item", "started").into())],
+                urgency: None,
+                why_in_scope: Vec::default(),
+                when_started: (now - Duration::minutes(20)).into(),
+                when_stopped: (now - Duration::minutes(5)).into(),
+                dedication: None,
+            }])
+            .surreal_daily_plans(vec![SurrealDailyPlan::new(
+                today(),
+                now.into(),
+                vec![
+                    ("item", "untouched").into(),
+                    ("item", "done").into(),
+                    ("item", "started").into(),
+                ],
+            )])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+        let do_now_list = DoNowList::new_do_now_list(calculated_data, &now);
+
+        let progress = commitments_progress(&do_now_list, &start_of_today)
+            .into_iter()
+            .map(|(item_status, progress)| (item_status.get_summary(), progress))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            progress,
+            vec![
+                ("untouched", Progress::NotStarted),
+                ("done", Progress::Done),
+                ("started", Progress::WorkedOn(Duration::minutes(15))),
+            ]
+        );
+    }
+}
@@ -10,7 +10,7 @@ pub(crate) mod search;
 use std::{fmt::Display, iter::once};
 
 use crate::{
-    menu::inquire::default_select_page_size,
+    menu::inquire::{default_select_page_size, snooze_item::snooze_item},
     systems::do_now_list::current_mode_node::CurrentModeNode,
 };
 use ahash::{HashMap, HashSet};
@@ -43,7 +43,10 @@ use crate::{
         display_urgency_level_item_with_item_status::DisplayUrgencyLevelItemWithItemStatus,
         display_why_in_scope_and_action_with_item_status::DisplayWhyInScopeAndActionWithItemStatus,
     },
-    menu::inquire::back_menu::present_back_menu,
+    menu::inquire::{
+        back_menu::present_back_menu,
+        daily_plan::{has_a_plan_for_today, present_daily_planning, print_todays_commitments},
+    },
     node::{
         Filter,
         action_with_item_status::ActionWithItemStatus,
@@ -67,9 +70,11 @@ pub(crate) enum InquireDoNowListItem<'e> {
     Search,
     ChangeMode(&'e CurrentModeNode<'e>),
     ExcludeFromThisMode,
+    SnoozeOrUnsnooze,
     DeclareEvent { waiting_on: Vec<&'e EventNode<'e>> },
     DoNowListSingleItem(&'e UrgencyLevelItemWithItemStatus<'e>),
     RefreshList(DateTime<Local>),
+    PlanToday,
     BackMenu,
     Help,
 }
@@ -80,6 +85,7 @@ impl Display for InquireDoNowListItem<'_> {
             Self::CaptureNewItem => write!(f, "🗬   Capture New Item"),
             Self::Search => write!(f, "🔍  Search"),
             Self::ExcludeFromThisMode => write!(f, "🚫  Exclude Items From This Mode"),
+            Self::SnoozeOrUnsnooze => write!(f, "💤  Snooze or Unsnooze Items"),
             Self::DoNowListSingleItem(item) => {
                 let display = DisplayUrgencyLevelItemWithItemStatus::new(
                     item,
@@ -115,6 +121,7 @@ impl Display for InquireDoNowListItem<'_> {
                     write!(f, "⚡  Waiting on: {} events", waiting_on.len())
                 }
             }
+            Self::PlanToday => write!(f, "🌅  Plan today"),
             Self::BackMenu => write!(f, "🏠  Back Menu"),
             Self::Help => write!(f, "❓  Help"),
         }
@@ -127,6 +134,7 @@ impl<'a> InquireDoNowListItem<'a> {
         event_nodes: &'a HashMap<&'a RecordId, EventNode<'a>>,
         do_now_list_created: DateTime<Utc>,
         current_mode: &'a CurrentModeNode<'a>,
+        has_a_plan_for_today: bool,
     ) -> Vec<InquireDoNowListItem<'a>> {
         let waiting_on = event_nodes
             .values()
@@ -150,7 +158,9 @@ impl<'a> InquireDoNowListItem<'a> {
             item_action
                 .iter()
                 .map(InquireDoNowListItem::DoNowListSingleItem),
+            (!has_a_plan_for_today).then_some(InquireDoNowListItem::PlanToday),
             once(InquireDoNowListItem::ExcludeFromThisMode),
+            once(InquireDoNowListItem::SnoozeOrUnsnooze),
             once(InquireDoNowListItem::BackMenu),
             once(InquireDoNowListItem::Help),
         )
@@ -215,6 +225,7 @@ pub(crate) async fn present_normal_do_now_list_menu(
 
     present_upcoming(&do_now_list);
     present_time_spent_today_summary(&do_now_list);
+    print_todays_commitments(&do_now_list);
     present_do_now_list_menu(do_now_list, send_to_data_storage_layer).await
 }
 
@@ -248,20 +259,23 @@ pub(crate) fn compute_time_spent_in_window(
         .fold(chrono::Duration::zero(), |acc, d| acc + d)
 }
 
-pub(crate) fn present_time_spent_today_summary(do_now_list: &DoNowList) {
+/// Local midnight at the start of today
+pub(crate) fn start_of_today() -> DateTime<Local> {
     let now_local = Local::now();
     let today_midnight = now_local.with_time(NaiveTime::MIN);
 
-    let start_local = match today_midnight {
+    match today_midnight {
         chrono::LocalResult::Single(dt) => dt,
         // If it is ambiguous (rare for midnight), pick the earliest.
         chrono::LocalResult::Ambiguous(earliest, _) => earliest,
         // If it doesn't exist (very unlikely at midnight), fall back to "now_local".
         chrono::LocalResult::None => now_local,
-    };
+    }
+}
 
-    let start_utc = start_local.with_timezone(&Utc);
-    let end_utc = now_local.with_timezone(&Utc);
+pub(crate) fn present_time_spent_today_summary(do_now_list: &DoNowList) {
+    let start_utc = start_of_today().with_timezone(&Utc);
+    let end_utc = Local::now().with_timezone(&Utc);
 
     let logs: Vec<&TimeSpent<'_>> = do_now_list.get_time_spent_log().iter().collect();
     let total_time = compute_time_spent_in_window(start_utc, end_utc, &logs);
@@ -396,6 +410,7 @@ pub(crate) async fn present_do_now_list_menu(
         event_nodes,
         *do_now_list.get_now(),
         do_now_list.get_current_mode_node(),
+        has_a_plan_for_today(&do_now_list),
     );
 
     println!();
@@ -421,6 +436,9 @@ pub(crate) async fn present_do_now_list_menu(
 
     match selected {
         Ok(InquireDoNowListItem::Help) => present_do_now_help(),
+        Ok(InquireDoNowListItem::PlanToday) => {
+            present_daily_planning(send_to_data_storage_layer).await
+        }
         Ok(InquireDoNowListItem::CaptureNewItem) => capture(send_to_data_storage_layer).await,
         Ok(InquireDoNowListItem::Search) => {
             present_search_menu(&do_now_list, send_to_data_storage_layer).await
@@ -428,6 +446,9 @@ pub(crate) async fn present_do_now_list_menu(
         Ok(InquireDoNowListItem::ExcludeFromThisMode) => {
             present_exclude_from_this_mode_menu(&do_now_list, send_to_data_storage_layer).await
         }
+        Ok(InquireDoNowListItem::SnoozeOrUnsnooze) => {
+            present_snooze_or_unsnooze_menu(&do_now_list, send_to_data_storage_layer).await
+        }
         Ok(InquireDoNowListItem::ChangeMode(current_mode)) => {
             present_change_mode_menu(current_mode, send_to_data_storage_layer).await
         }
@@ -717,6 +738,99 @@ async fn present_exclude_from_this_mode_menu(
     }
 }
 
+enum SnoozeSelection<'e> {
+    Snooze(DisplayWhyInScopeAndActionWithItemStatus<'e>),
+    Unsnooze {
+        item_status: &'e ItemStatus<'e>,
+        snoozed_until: &'e DateTime<Utc>,
+    },
+}
+
+impl Display for SnoozeSelection<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnoozeSelection::Snooze(item) => write!(f, "💤 Snooze: {}", item),
+            SnoozeSelection::Unsnooze {
+                item_status,
+                snoozed_until,
+            } => {
+                let snoozed_until: DateTime<Local> = (**snoozed_until).into();
+                write!(
+                    f,
+                    "⏰ Unsnooze: {} (Snoozed until: {})",
+                    DisplayItem::new(item_status.get_item()),
+                    snoozed_until.format("%a %d %b %Y %I:%M%P")
+                )
+            }
+        }
+    }
+}
+
+/// Snoozed items are not in the Do Now list so to unsnooze them they are listed after the items that
+/// are currently shown, the ones that come back the soonest first.
+async fn present_snooze_or_unsnooze_menu(
+    do_now_list: &DoNowList,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let mut snoozed = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| x.is_active())
+        .filter_map(|item_status| {
+            item_status
+                .get_item()
+                .get_snoozed_until()
+                .map(|snoozed_until| (item_status, snoozed_until))
+        })
+        .collect::<Vec<_>>();
+    snoozed.sort_by_key(|(_, snoozed_until)| *snoozed_until);
+    let list = chain!(
+        collect_all_currently_shown_do_now_items(do_now_list)
+            .into_iter()
+            .map(SnoozeSelection::Snooze),
+        snoozed
+            .into_iter()
+            .map(|(item_status, snoozed_until)| SnoozeSelection::Unsnooze {
+                item_status,
+                snoozed_until,
+            })
+    )
+    .collect::<Vec<_>>();
+
+    let selected = Select::new("Select an item to snooze or unsnooze|", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+
+    match selected {
+        Ok(SnoozeSelection::Snooze(item)) => {
+            let record_id = item.get_surreal_record_id().clone();
+            if do_now_list
+                .get_base_data()
+                .get_surreal_working_on()
+                .is_some_and(|working_on| working_on.item == record_id)
+            {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::ClearWorkingOn)
+                    .await
+                    .unwrap();
+            }
+            snooze_item(record_id, send_to_data_storage_layer).await
+        }
+        Ok(SnoozeSelection::Unsnooze { item_status, .. }) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UnsnoozeItem(
+                    item_status.get_surreal_record_id().clone(),
+                ))
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
 enum DoNowHelpChoices {
     GettingStarted,
     HowWorkIsScheduled,
@@ -849,6 +963,7 @@ mod tests {
             event_nodes,
             do_now_list_created,
             current_mode_node,
+            true,
         );
 
         assert!(
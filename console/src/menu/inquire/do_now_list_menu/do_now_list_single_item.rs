@@ -1,7 +1,11 @@
 pub(crate) mod give_this_item_a_parent;
+pub(crate) mod interrupted_by;
+pub(crate) mod item_notes;
 pub(crate) mod log_worked_on_this;
+pub(crate) mod resume_note;
 mod something_else_should_be_done_first;
 pub(crate) mod state_a_smaller_action;
+pub(crate) mod timebox;
 pub(crate) mod urgency_plan;
 
 use std::{fmt::Display, iter::chain};
@@ -26,8 +30,9 @@ use crate::{
         surreal_working_on::SurrealWorkingOn,
     },
     display::{
-        DisplayStyle, display_item::DisplayItem, display_item_node::DisplayItemNode,
-        display_item_type::DisplayItemType, display_urgency_plan::DisplayUrgency,
+        DisplayStyle, display_item::DisplayItem, display_item_history::DisplayItemHistory,
+        display_item_node::DisplayItemNode, display_item_type::DisplayItemType,
+        display_urgency_plan::DisplayUrgency,
     },
     menu::inquire::{
         back_menu::capture,
@@ -43,8 +48,11 @@ use crate::{
         },
         item_children_summary,
         select_higher_importance_than_this::select_higher_importance_than_this,
+        snooze_item::snooze_item,
+        tags::edit_item_tags,
         time_spent_summary,
         update_item_summary::update_item_summary,
+        waiting_for::{present_waiting_for_menu, print_waiting_for},
     },
     new_item,
     node::{
@@ -62,25 +70,47 @@ enum DoNowListSingleItemSelection<'e> {
     },
     CaptureNewItem,
     StartWorkingOnThis,
+    WorkInATimebox,
     GiveThisItemAParent,
     ChangeReadyAndUrgencyPlan,
     UnableToDoThisRightNow {
         started: Option<&'e SurrealWorkingOn>,
     },
+    Snooze {
+        started: Option<&'e SurrealWorkingOn>,
+    },
+    Unsnooze {
+        snoozed_until: &'e DateTime<Utc>,
+    },
+    WaitingFor {
+        waiting_on: Option<&'e Item<'e>>,
+        is_waiting: bool,
+    },
     SomethingElseShouldBeDoneFirst,
     ReviewItem,
     StateASmallerAction,
     WorkedOnThis {
         started: Option<&'e SurrealWorkingOn>,
     },
+    InterruptedBy {
+        working_on: &'e SurrealWorkingOn,
+    },
     Finished,
     ReturnToDoNowList,
     UpdateSummary,
+    Notes {
+        has_notes: bool,
+    },
+    EditTags,
     SwitchToParentItem(DisplayItem<'e>, &'e ItemStatus<'e>),
     ParentToItem,
     RemoveParent(DisplayItem<'e>, &'e ItemStatus<'e>),
     SwitchToChildItem(DisplayItem<'e>, &'e ItemStatus<'e>),
     RemoveChild(DisplayItem<'e>, &'e ItemStatus<'e>),
+    ShowHistory,
+    ResumeNoteHistory {
+        count: usize,
+    },
     DebugPrintItem,
 }
 
@@ -89,7 +119,16 @@ impl Display for DoNowListSingleItemSelection<'_> {
         match self {
             Self::CaptureNewItem => write!(f, "Capture New Item"),
             Self::StartWorkingOnThis => write!(f, "Start working on this"),
+            Self::WorkInATimebox => write!(f, "⏱️ Work on this in a timebox"),
             Self::UpdateSummary => write!(f, "Update Summary"),
+            Self::Notes { has_notes } => {
+                if *has_notes {
+                    write!(f, "📝 Notes")
+                } else {
+                    write!(f, "📝 Add Notes")
+                }
+            }
+            Self::EditTags => write!(f, "🏷️ Edit Tags"),
             Self::SwitchToParentItem(parent_item, _) => {
                 write!(f, "⇄ Select larger Reason: {}", parent_item)
             }
@@ -108,6 +147,8 @@ impl Display for DoNowListSingleItemSelection<'_> {
             }
             Self::RemoveChild(child_item, _) => write!(f, "🚫 Remove action: {}", child_item),
             Self::RemoveParent(parent_item, _) => write!(f, "🚫 Remove reason: {}", parent_item),
+            Self::ShowHistory => write!(f, "📜 Show History"),
+            Self::ResumeNoteHistory { count } => write!(f, "📌 Past resume notes ({})", count),
             Self::DebugPrintItem => write!(f, "Debug Print Item"),
             Self::SomethingElseShouldBeDoneFirst => {
                 write!(
@@ -123,6 +164,23 @@ impl Display for DoNowListSingleItemSelection<'_> {
             Self::UnableToDoThisRightNow { started: _ } => {
                 write!(f, "I am unable to do this right now")
             }
+            Self::Snooze { started: _ } => write!(f, "💤 Snooze"),
+            Self::Unsnooze { snoozed_until } => {
+                let snoozed_until: DateTime<Local> = (**snoozed_until).into();
+                write!(
+                    f,
+                    "⏰ Unsnooze (Snoozed until: {})",
+                    snoozed_until.format("%a %d %b %Y %I:%M%P")
+                )
+            }
+            Self::WaitingFor {
+                waiting_on: Some(waiting_on),
+                is_waiting: true,
+            } => write!(f, "⏳ Waiting for {}, follow up", waiting_on.get_summary()),
+            Self::WaitingFor {
+                is_waiting: true, ..
+            } => write!(f, "⏳ Waiting for someone, follow up"),
+            Self::WaitingFor { .. } => write!(f, "⏳ Waiting on someone else for this"),
             Self::WorkedOnThis { started } => {
                 if let Some(started) = started {
                     let started = started.when_started.0;
@@ -139,6 +197,7 @@ impl Display for DoNowListSingleItemSelection<'_> {
                     write!(f, "I worked on this")
                 }
             }
+            Self::InterruptedBy { .. } => write!(f, "⚡ Interrupted by…"),
             Self::Finished => write!(f, "I finished"),
             Self::ReturnToDoNowList => write!(f, "Return to the Do Now Menu"),
             Self::ChangeReadyAndUrgencyPlan => write!(f, "Change Ready & Urgency Plan"),
@@ -151,6 +210,7 @@ impl<'e> DoNowListSingleItemSelection<'e> {
         item_node: &'e ItemNode<'e>,
         all_items_status: &'e HashMap<&'e RecordId, ItemStatus<'e>>,
         currently_working_on_item: Option<&'e SurrealWorkingOn>,
+        resume_note_count: usize,
     ) -> (Vec<Self>, usize) {
         let mut default_item = 0;
         //if the currently_working_on_item is not this item then blank it out that we are not working on it
@@ -175,16 +235,37 @@ impl<'e> DoNowListSingleItemSelection<'e> {
             default_item = 1;
         }
 
+        if !has_no_parent {
+            list.push(Self::WorkInATimebox);
+        }
+
         list.push(Self::WorkedOnThis {
             started: currently_working_on_item,
         });
 
+        if let Some(working_on) = currently_working_on_item {
+            list.push(Self::InterruptedBy { working_on });
+        }
+
         list.push(Self::Finished);
 
         list.push(Self::UnableToDoThisRightNow {
             started: currently_working_on_item,
         });
 
+        list.push(Self::Snooze {
+            started: currently_working_on_item,
+        });
+
+        if let Some(snoozed_until) = item_node.get_item().get_snoozed_until() {
+            list.push(Self::Unsnooze { snoozed_until });
+        }
+
+        list.push(Self::WaitingFor {
+            waiting_on: item_node.get_waiting_on(),
+            is_waiting: item_node.get_item().get_waiting_for().is_some(),
+        });
+
         list.push(Self::StateASmallerAction);
 
         list.push(Self::SomethingElseShouldBeDoneFirst);
@@ -236,9 +317,18 @@ impl<'e> DoNowListSingleItemSelection<'e> {
 
         list.extend(vec![
             Self::UpdateSummary,
-            Self::DebugPrintItem,
-            Self::ReturnToDoNowList,
+            Self::Notes {
+                has_notes: item_node.get_item().has_notes(),
+            },
+            Self::EditTags,
+            Self::ShowHistory,
         ]);
+        if resume_note_count > 1 {
+            list.push(Self::ResumeNoteHistory {
+                count: resume_note_count,
+            });
+        }
+        list.extend(vec![Self::DebugPrintItem, Self::ReturnToDoNowList]);
 
         (list, default_item)
     }
@@ -275,16 +365,24 @@ pub(crate) async fn present_do_now_list_item_selected(
         println!();
         println!("{}Urgency:{} {}", underline, normal_style, display_urgency);
     }
+    resume_note::print_latest_resume_note(menu_for.get_item(), do_now_list.get_base_data());
     item_children_summary::print_completed_children(menu_for);
     item_children_summary::print_in_progress_children(menu_for, do_now_list.get_all_items_status());
+    print_waiting_for(menu_for);
+    item_notes::print_notes(menu_for.get_item());
     println!();
 
     let all_items_lap_highest_count = do_now_list.get_all_items_status();
     let currently_working_on_item = do_now_list.get_base_data().get_surreal_working_on();
+    let resume_note_count = do_now_list
+        .get_base_data()
+        .get_resume_notes(menu_for.get_surreal_record_id())
+        .len();
     let (list, default_item) = DoNowListSingleItemSelection::create_list(
         menu_for.get_item_node(),
         all_items_lap_highest_count,
         currently_working_on_item,
+        resume_note_count,
     );
 
     let selection = Select::new("Select from the below list|", list)
@@ -319,6 +417,16 @@ pub(crate) async fn present_do_now_list_item_selected(
             //Fall back to the main list because now this is the item we are working on it will be auto selected from the main list
             Ok(())
         }
+        Ok(DoNowListSingleItemSelection::WorkInATimebox) => {
+            let base_data = do_now_list.get_base_data();
+            timebox::work_in_a_timebox(
+                menu_for,
+                why_in_scope,
+                base_data,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
         Ok(DoNowListSingleItemSelection::StateASmallerAction) => {
             state_a_smaller_action(menu_for.get_item_node(), send_to_data_storage_layer).await?;
             let surreal_tables = SurrealTables::new(send_to_data_storage_layer)
@@ -379,6 +487,34 @@ pub(crate) async fn present_do_now_list_item_selected(
             present_set_ready_and_urgency_plan_menu(menu_for, base_data, send_to_data_storage_layer)
                 .await
         }
+        Ok(DoNowListSingleItemSelection::Snooze { started }) => {
+            if let Some(working_on) = started
+                && &working_on.item == menu_for.get_surreal_record_id()
+            {
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::ClearWorkingOn)
+                    .await
+                    .unwrap();
+            }
+            snooze_item(
+                menu_for.get_surreal_record_id().clone(),
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Ok(DoNowListSingleItemSelection::Unsnooze { .. }) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UnsnoozeItem(
+                    menu_for.get_surreal_record_id().clone(),
+                ))
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Ok(DoNowListSingleItemSelection::WaitingFor { .. }) => {
+            let base_data = do_now_list.get_base_data();
+            present_waiting_for_menu(menu_for, base_data, send_to_data_storage_layer).await
+        }
         Ok(DoNowListSingleItemSelection::SomethingElseShouldBeDoneFirst) => {
             something_else_should_be_done_first(menu_for.get_item(), send_to_data_storage_layer)
                 .await
@@ -409,6 +545,18 @@ pub(crate) async fn present_do_now_list_item_selected(
                 base_data,
                 send_to_data_storage_layer,
             )
+            .await?;
+            resume_note::ask_for_resume_note(menu_for.get_item(), send_to_data_storage_layer).await
+        }
+        Ok(DoNowListSingleItemSelection::InterruptedBy { working_on }) => {
+            let base_data = do_now_list.get_base_data();
+            interrupted_by::interrupted_by(
+                menu_for,
+                working_on,
+                why_in_scope,
+                base_data,
+                send_to_data_storage_layer,
+            )
             .await
         }
         Ok(DoNowListSingleItemSelection::Finished) => {
@@ -461,6 +609,42 @@ pub(crate) async fn present_do_now_list_item_selected(
         Ok(DoNowListSingleItemSelection::ParentToItem) => {
             parent_to_item(menu_for.get_item(), send_to_data_storage_layer).await
         }
+        Ok(DoNowListSingleItemSelection::Notes { .. }) => {
+            item_notes::present_item_notes_menu(menu_for.get_item(), send_to_data_storage_layer)
+                .await
+        }
+        Ok(DoNowListSingleItemSelection::EditTags) => {
+            edit_item_tags(menu_for.get_item(), send_to_data_storage_layer).await
+        }
+        Ok(DoNowListSingleItemSelection::ShowHistory) => {
+            let base_data = do_now_list.get_base_data();
+            let history = base_data.get_item_history(menu_for.get_surreal_record_id());
+            println!();
+            println!("{}History:{}", underline, normal_style);
+            println!("{}", DisplayItemHistory::new(&history, base_data));
+            Box::pin(present_do_now_list_item_selected(
+                menu_for,
+                why_in_scope,
+                when_selected,
+                do_now_list,
+                send_to_data_storage_layer,
+            ))
+            .await
+        }
+        Ok(DoNowListSingleItemSelection::ResumeNoteHistory { .. }) => {
+            resume_note::print_resume_note_history(
+                menu_for.get_item(),
+                do_now_list.get_base_data(),
+            );
+            Box::pin(present_do_now_list_item_selected(
+                menu_for,
+                why_in_scope,
+                when_selected,
+                do_now_list,
+                send_to_data_storage_layer,
+            ))
+            .await
+        }
         Ok(DoNowListSingleItemSelection::DebugPrintItem) => {
             println!("{:?}", menu_for);
             Ok(())
@@ -0,0 +1,239 @@
+use std::fmt::Display;
+
+use ahash::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use inquire::{InquireError, Select, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::BaseData,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_interruption::SurrealInterruption,
+        surreal_working_on::SurrealWorkingOn,
+    },
+    display::display_duration::DisplayDuration,
+    menu::inquire::{default_select_page_size, tags::split_tags_from_summary},
+    new_item::NewItem,
+    new_time_spent::NewTimeSpent,
+    node::{item_status::ItemStatus, why_in_scope_and_action_with_item_status::ToSurreal},
+};
+
+use super::{WhyInScope, log_worked_on_this::create_working_on_list};
+
+enum SourceChoice<'e> {
+    Previous(&'e str),
+    New,
+}
+
+impl Display for SourceChoice<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceChoice::Previous(source) => write!(f, "{}", source),
+            SourceChoice::New => write!(f, "✏️  Someone or something new"),
+        }
+    }
+}
+
+enum CaptureChoice {
+    JustGetBackToWork,
+    CaptureAsANewItem,
+}
+
+impl Display for CaptureChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaptureChoice::JustGetBackToWork => write!(f, "No, nothing to follow up on"),
+            CaptureChoice::CaptureAsANewItem => write!(f, "📥 Capture it as a new item"),
+        }
+    }
+}
+
+/// Sources that have interrupted before, the most frequent first so the usual suspects are quick to
+/// pick
+fn previous_sources(interruptions: &[SurrealInterruption]) -> Vec<&str> {
+    let mut counts: HashMap<&str, usize> = HashMap::default();
+    for interruption in interruptions.iter() {
+        *counts.entry(interruption.source.as_str()).or_default() += 1;
+    }
+    let mut sources = counts.into_iter().collect::<Vec<_>>();
+    sources.sort_by(|(a_source, a_count), (b_source, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_source.cmp(b_source))
+    });
+    sources.into_iter().map(|(source, _)| source).collect()
+}
+
+/// None when canceled
+fn ask_for_source(base_data: &BaseData) -> Result<Option<String>, ()> {
+    let previous = previous_sources(base_data.get_surreal_interruptions());
+    if !previous.is_empty() {
+        let list = previous
+            .into_iter()
+            .map(SourceChoice::Previous)
+            .chain(std::iter::once(SourceChoice::New))
+            .collect::<Vec<_>>();
+        match Select::new("Interrupted by…", list)
+            .with_page_size(default_select_page_size())
+            .prompt()
+        {
+            Ok(SourceChoice::Previous(source)) => return Ok(Some(source.to_string())),
+            Ok(SourceChoice::New) => {}
+            Err(InquireError::OperationCanceled) => return Ok(None),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+
+    match Text::new("Interrupted by…")
+        .with_help_message("Who or what, for example \"Phone call\" or \"Slack\"")
+        .prompt()
+    {
+        Ok(source) if source.trim().is_empty() => Ok(None),
+        Ok(source) => Ok(Some(source.trim().to_string())),
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+async fn capture_as_a_new_item(
+    source: &str,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let selection = Select::new(
+        "Is there something to follow up on?",
+        vec![
+            CaptureChoice::JustGetBackToWork,
+            CaptureChoice::CaptureAsANewItem,
+        ],
+    )
+    .prompt();
+    match selection {
+        Ok(CaptureChoice::JustGetBackToWork) | Err(InquireError::OperationCanceled) => Ok(()),
+        Ok(CaptureChoice::CaptureAsANewItem) => {
+            match Text::new("Enter New Item ⍠")
+                .with_initial_value(source)
+                .with_help_message("Add tags with #, for example #phone #errand")
+                .prompt()
+            {
+                Ok(summary) => {
+                    let (summary, tags) = split_tags_from_summary(&summary);
+                    let mut new_item = NewItem::new(summary, Utc::now());
+                    new_item.tags = tags;
+                    send_to_data_storage_layer
+                        .send(DataLayerCommands::NewItem(new_item))
+                        .await
+                        .unwrap();
+                    Ok(())
+                }
+                Err(InquireError::OperationCanceled) => Ok(()),
+                Err(InquireError::OperationInterrupted) => Err(()),
+                Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+            }
+        }
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+/// Logs the time worked on the item up to the interruption, waits for the interruption to be over,
+/// records it, and then starts working on the item again so the time log is split around it.
+pub(crate) async fn interrupted_by(
+    menu_for: &ItemStatus<'_>,
+    working_on: &SurrealWorkingOn,
+    why_in_scope: &HashSet<WhyInScope>,
+    base_data: &BaseData,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let when_interrupted = Utc::now();
+    let Some(source) = ask_for_source(base_data)? else {
+        return Ok(());
+    };
+
+    let when_started: DateTime<Utc> = working_on.when_started.clone().into();
+    send_to_data_storage_layer
+        .send(DataLayerCommands::RecordTimeSpent(NewTimeSpent {
+            why_in_scope: why_in_scope.to_surreal(),
+            working_on: create_working_on_list(menu_for),
+            urgency: menu_for.get_urgency_now().cloned(),
+            when_started,
+            when_stopped: when_interrupted,
+        }))
+        .await
+        .unwrap();
+    send_to_data_storage_layer
+        .send(DataLayerCommands::ClearWorkingOn)
+        .await
+        .unwrap();
+
+    //Esc still records the interruption but does not go back to working on the item. Ctrl-C also
+    //records it, up to now, because the time log has already been split and should not be left
+    //half done, and then exits.
+    let go_back_to_work = match capture_as_a_new_item(&source, send_to_data_storage_layer).await {
+        Ok(()) => match Text::new(&format!(
+            "Press Enter when you are back to \"{}\" (Esc to stop working on it)",
+            menu_for.get_item().get_summary()
+        ))
+        .prompt()
+        {
+            Ok(_) => Ok(true),
+            Err(InquireError::OperationCanceled) => Ok(false),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        },
+        Err(()) => Err(()),
+    };
+
+    let when_back = Utc::now();
+    send_to_data_storage_layer
+        .send(DataLayerCommands::RecordInterruption {
+            interrupted: Some(menu_for.get_surreal_record_id().clone()),
+            source,
+            when_started: when_interrupted.into(),
+            when_stopped: when_back.into(),
+        })
+        .await
+        .unwrap();
+    let time_lost = (when_back - when_interrupted).to_std().unwrap_or_default();
+    println!("Interrupted for {}", DisplayDuration::new(&time_lost));
+
+    if go_back_to_work? {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::SetWorkingOn {
+                item: menu_for.get_surreal_record_id().clone(),
+                when_started: when_back.into(),
+            })
+            .await
+            .unwrap();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use crate::data_storage::surrealdb_layer::surreal_interruption::SurrealInterruption;
+
+    use super::previous_sources;
+
+    #[test]
+    fn previous_sources_are_most_frequent_first() {
+        let now = Utc::now();
+        let interruptions = [
+            "Slack",
+            "Phone call",
+            "Slack",
+            "Coworker",
+            "Phone call",
+            "Slack",
+        ]
+        .into_iter()
+        .map(|source| SurrealInterruption::new(None, source.to_string(), now.into(), now.into()))
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            previous_sources(&interruptions),
+            vec!["Slack", "Phone call", "Coworker"]
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Display;
+
+use better_term::Style;
+use inquire::{Editor, InquireError, Select, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::item::Item,
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_item::NotesLocation,
+    },
+    display::display_hyperlink::DisplayHyperlink,
+    menu::inquire::default_select_page_size,
+};
+
+enum NotesSelection {
+    EditMarkdownNotes { has_markdown_notes: bool },
+    SetWebLink,
+    SetOneNoteLink,
+    RemoveNotes,
+    ReturnToItem,
+}
+
+impl Display for NotesSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EditMarkdownNotes { has_markdown_notes } => {
+                if *has_markdown_notes {
+                    write!(f, "Edit notes in your editor")
+                } else {
+                    write!(f, "Write notes in your editor (markdown)")
+                }
+            }
+            Self::SetWebLink => write!(f, "Link to notes on the web"),
+            Self::SetOneNoteLink => write!(f, "Link to notes in OneNote"),
+            Self::RemoveNotes => write!(f, "🚫 Remove notes"),
+            Self::ReturnToItem => write!(f, "Return to the item"),
+        }
+    }
+}
+
+/// Prints the notes for an item, links are printed so they can be clicked on in terminals that
+/// support it. Nothing is printed if the item does not have notes.
+pub(crate) fn print_notes(item: &Item<'_>) {
+    let underline = Style::default().underline();
+    let normal_style = Style::default();
+    match item.get_notes_location() {
+        NotesLocation::None => {}
+        NotesLocation::Markdown(notes) => {
+            println!("{}Notes:{}", underline, normal_style);
+            println!("{}", notes.trim_end());
+        }
+        NotesLocation::WebLink(url) => {
+            println!(
+                "{}Notes:{} {}",
+                underline,
+                normal_style,
+                DisplayHyperlink::new(url, url)
+            );
+        }
+        NotesLocation::OneNoteLink(url) => {
+            println!(
+                "{}Notes:{} {}",
+                underline,
+                normal_style,
+                DisplayHyperlink::new(url, "Open in OneNote")
+            );
+        }
+    }
+}
+
+pub(crate) async fn present_item_notes_menu(
+    item: &Item<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    println!();
+    if item.has_notes() {
+        print_notes(item);
+    } else {
+        println!("This item does not have any notes yet.");
+    }
+    println!();
+
+    let markdown_notes = match item.get_notes_location() {
+        NotesLocation::Markdown(notes) => Some(notes.as_str()),
+        _ => None,
+    };
+    let mut list = vec![
+        NotesSelection::EditMarkdownNotes {
+            has_markdown_notes: markdown_notes.is_some(),
+        },
+        NotesSelection::SetWebLink,
+        NotesSelection::SetOneNoteLink,
+    ];
+    if item.has_notes() {
+        list.push(NotesSelection::RemoveNotes);
+    }
+    list.push(NotesSelection::ReturnToItem);
+
+    let selection = Select::new("Select from the below list|", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    let notes_location = match selection {
+        Ok(NotesSelection::EditMarkdownNotes { .. }) => {
+            let editor = Editor::new("Notes").with_file_extension(".md");
+            let editor = match markdown_notes {
+                Some(notes) => editor.with_predefined_text(notes),
+                None => editor,
+            };
+            match editor.prompt() {
+                Ok(notes) if notes.trim().is_empty() => NotesLocation::None,
+                Ok(notes) => NotesLocation::Markdown(notes),
+                Err(InquireError::OperationCanceled) => return Ok(()),
+                Err(InquireError::OperationInterrupted) => return Err(()),
+                Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+            }
+        }
+        Ok(NotesSelection::SetWebLink) => match prompt_for_link("Web link to the notes")? {
+            Some(url) => NotesLocation::WebLink(url),
+            None => return Ok(()),
+        },
+        Ok(NotesSelection::SetOneNoteLink) => match prompt_for_link("OneNote link to the notes")? {
+            Some(url) => NotesLocation::OneNoteLink(url),
+            None => return Ok(()),
+        },
+        Ok(NotesSelection::RemoveNotes) => NotesLocation::None,
+        Ok(NotesSelection::ReturnToItem) | Err(InquireError::OperationCanceled) => return Ok(()),
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    };
+
+    send_to_data_storage_layer
+        .send(DataLayerCommands::UpdateNotesLocation(
+            item.get_surreal_record_id().clone(),
+            notes_location,
+        ))
+        .await
+        .unwrap();
+    Ok(())
+}
+
+fn prompt_for_link(message: &str) -> Result<Option<String>, ()> {
+    match Text::new(message).prompt() {
+        Ok(url) if url.trim().is_empty() => Ok(None),
+        Ok(url) => Ok(Some(url.trim().to_string())),
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
@@ -63,7 +63,7 @@ pub(crate) async fn log_worked_on_this(
     Ok(())
 }
 
-fn create_working_on_list(selected: &ItemStatus<'_>) -> Vec<SurrealAction> {
+pub(super) fn create_working_on_list(selected: &ItemStatus<'_>) -> Vec<SurrealAction> {
     selected
         .get_self_and_parents_flattened(Filter::Active)
         .iter()
@@ -0,0 +1,86 @@
+use better_term::{Color, Style};
+use inquire::{InquireError, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{BaseData, item::Item},
+    data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands,
+    display::display_resume_note::DisplayResumeNote,
+};
+
+/// Prints where the item was left off, nothing is printed if it has no resume notes
+pub(crate) fn print_latest_resume_note(item: &Item<'_>, base_data: &BaseData) {
+    let resume_notes = base_data.get_resume_notes(item.get_surreal_record_id());
+    if let Some(latest) = resume_notes.first() {
+        let highlight = Style::default().fg(Color::BrightYellow).bold();
+        let normal_style = Style::default();
+        println!();
+        println!(
+            "{}📌 Where you left off:{} {}",
+            highlight,
+            normal_style,
+            DisplayResumeNote::new(latest)
+        );
+    }
+}
+
+/// Every resume note for the item, newest first
+pub(crate) fn print_resume_note_history(item: &Item<'_>, base_data: &BaseData) {
+    let underline = Style::default().underline();
+    let normal_style = Style::default();
+    println!();
+    println!("{}Resume notes:{}", underline, normal_style);
+    for resume_note in base_data.get_resume_notes(item.get_surreal_record_id()) {
+        println!("{}", DisplayResumeNote::new(resume_note));
+    }
+}
+
+/// None when the rest of the note should be skipped
+fn ask_for_part(message: &str, help_message: &str) -> Result<Option<String>, ()> {
+    match Text::new(message).with_help_message(help_message).prompt() {
+        Ok(part) => Ok(Some(part.trim().to_string())),
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+/// Optionally leaves a note on the item for picking it back up later. Leaving the first question
+/// blank, or pressing Esc on any of them, skips the note.
+pub(crate) async fn ask_for_resume_note(
+    item: &Item<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    println!();
+    let Some(doing) = ask_for_part(
+        "📌 Where did you leave off? What were you doing?",
+        "Leave blank to skip the resume note",
+    )?
+    else {
+        return Ok(());
+    };
+    if doing.is_empty() {
+        return Ok(());
+    }
+    let Some(next) = ask_for_part("What's next?", "Can be left blank")? else {
+        return Ok(());
+    };
+    let Some(location) = ask_for_part(
+        "Where are the files or links?",
+        "A path or a web link, can be left blank",
+    )?
+    else {
+        return Ok(());
+    };
+
+    send_to_data_storage_layer
+        .send(DataLayerCommands::NewResumeNote {
+            item: item.get_surreal_record_id().clone(),
+            doing,
+            next,
+            location,
+        })
+        .await
+        .unwrap();
+    Ok(())
+}
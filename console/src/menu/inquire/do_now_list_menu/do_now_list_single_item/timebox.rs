@@ -0,0 +1,313 @@
+use std::{
+    fmt::Display,
+    io::{Write, stdout},
+    time::Duration,
+};
+
+use ahash::HashSet;
+use chrono::{DateTime, Utc};
+use fundu::{CustomDurationParser, CustomTimeUnit, SaturatingInto, TimeUnit};
+use inquire::{InquireError, Select, Text};
+use lazy_static::lazy_static;
+use tokio::{sync::mpsc::Sender, time::Instant};
+
+use crate::{
+    base_data::BaseData,
+    data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands,
+    display::display_duration::DisplayDuration,
+    menu::inquire::default_select_page_size,
+    new_time_spent::NewTimeSpent,
+    node::{item_status::ItemStatus, why_in_scope_and_action_with_item_status::ToSurreal},
+};
+
+use super::{
+    WhyInScope, log_worked_on_this::create_working_on_list, resume_note::ask_for_resume_note,
+};
+
+const POMODORO: Duration = Duration::from_secs(25 * 60);
+const LONG_TIMEBOX: Duration = Duration::from_secs(50 * 60);
+const SHORT_BREAK: Duration = Duration::from_secs(5 * 60);
+const LONG_BREAK: Duration = Duration::from_secs(15 * 60);
+
+enum LengthChoice {
+    Preset(&'static str, Duration),
+    EnterALength,
+}
+
+impl Display for LengthChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LengthChoice::Preset(name, length) => {
+                write!(f, "{} {}", name, DisplayDuration::new(length))
+            }
+            LengthChoice::EnterALength => write!(f, "Enter a length"),
+        }
+    }
+}
+
+enum TimeboxEndChoice {
+    LogAndTakeABreak,
+    KeepGoing,
+    LogAndStop,
+}
+
+impl Display for TimeboxEndChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeboxEndChoice::LogAndTakeABreak => write!(f, "☕ Log the time and take a break"),
+            TimeboxEndChoice::KeepGoing => write!(f, "▶️  Keep going for another timebox"),
+            TimeboxEndChoice::LogAndStop => write!(f, "📝 Log the time and stop"),
+        }
+    }
+}
+
+enum CountDown {
+    Finished,
+    StoppedEarly,
+}
+
+/// "mm:ss", or "h:mm:ss" once there is an hour or more left
+fn format_countdown(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    if seconds >= 60 * 60 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / (60 * 60),
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    } else {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+fn ring_the_bell() {
+    print!("\x07");
+    stdout().flush().ok();
+}
+
+/// Redraws the time left on one line every second until it runs out or Ctrl-C is pressed
+async fn count_down(length: Duration, label: &str) -> CountDown {
+    let ends = Instant::now() + length;
+    let mut tick = tokio::time::interval(Duration::from_secs(1));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {
+                let remaining = ends.saturating_duration_since(Instant::now());
+                print!(
+                    "\r{} {} left (Ctrl-C to stop early) ",
+                    label,
+                    format_countdown(remaining)
+                );
+                stdout().flush().ok();
+                if remaining.is_zero() {
+                    println!();
+                    return CountDown::Finished;
+                }
+            }
+            _ = &mut ctrl_c => {
+                println!();
+                return CountDown::StoppedEarly;
+            }
+        }
+    }
+}
+
+/// None when canceled
+fn ask_for_length(message: &str, presets: Vec<LengthChoice>) -> Result<Option<Duration>, ()> {
+    lazy_static! {
+        static ref length_parser: CustomDurationParser<'static> = CustomDurationParser::builder()
+            .allow_time_unit_delimiter()
+            .number_is_optional()
+            .time_units(&[
+                CustomTimeUnit::with_default(
+                    TimeUnit::Minute,
+                    &["m", "min", "mins", "minute", "minutes"]
+                ),
+                CustomTimeUnit::with_default(TimeUnit::Hour, &["h", "hour", "hours"]),
+            ])
+            .build();
+    }
+
+    let selection = Select::new(message, presets)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(LengthChoice::Preset(_, length)) => Ok(Some(length)),
+        Ok(LengthChoice::EnterALength) => loop {
+            match Text::new("How long? (Examples: \"30m\", \"1h\", \"1h 30m\")").prompt() {
+                Ok(length) => match length_parser.parse(&length) {
+                    Ok(length) if length.is_positive() => break Ok(Some(length.saturating_into())),
+                    _ => {
+                        println!("Invalid length, please try again");
+                        println!();
+                        continue;
+                    }
+                },
+                Err(InquireError::OperationCanceled) => break Ok(None),
+                Err(InquireError::OperationInterrupted) => break Err(()),
+                Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+            }
+        },
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+async fn log_timebox(
+    worked_on: &ItemStatus<'_>,
+    why_in_scope: &HashSet<WhyInScope>,
+    when_started: DateTime<Utc>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) {
+    let when_stopped = Utc::now();
+    send_to_data_storage_layer
+        .send(DataLayerCommands::RecordTimeSpent(NewTimeSpent {
+            why_in_scope: why_in_scope.to_surreal(),
+            working_on: create_working_on_list(worked_on),
+            urgency: worked_on.get_urgency_now().cloned(),
+            when_started,
+            when_stopped,
+        }))
+        .await
+        .unwrap();
+    send_to_data_storage_layer
+        .send(DataLayerCommands::ClearWorkingOn)
+        .await
+        .unwrap();
+    let time_spent = (when_stopped - when_started).to_std().unwrap_or_default();
+    println!("Logged {}", DisplayDuration::new(&time_spent));
+}
+
+async fn take_a_break(send_to_data_storage_layer: &Sender<DataLayerCommands>) -> Result<(), ()> {
+    let Some(length) = ask_for_length(
+        "How long of a break?",
+        vec![
+            LengthChoice::Preset("☕", SHORT_BREAK),
+            LengthChoice::Preset("🛋️", LONG_BREAK),
+            LengthChoice::EnterALength,
+        ],
+    )?
+    else {
+        return Ok(());
+    };
+
+    let when_started = Utc::now();
+    count_down(length, "☕ Break,").await;
+    ring_the_bell();
+    send_to_data_storage_layer
+        .send(DataLayerCommands::RecordBreak {
+            when_started: when_started.into(),
+            when_stopped: Utc::now().into(),
+        })
+        .await
+        .unwrap();
+    println!("Break is over");
+    Ok(())
+}
+
+/// Works on the item for a set length of time with a countdown, then rings the terminal bell and
+/// asks whether to log the time, keep going, or take a break. When this item was already being
+/// worked on the logged time starts from when that was started.
+pub(crate) async fn work_in_a_timebox(
+    menu_for: &ItemStatus<'_>,
+    why_in_scope: &HashSet<WhyInScope>,
+    base_data: &BaseData,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let Some(length) = ask_for_length(
+        "How long is this timebox?",
+        vec![
+            LengthChoice::Preset("🍅", POMODORO),
+            LengthChoice::Preset("⏳", LONG_TIMEBOX),
+            LengthChoice::EnterALength,
+        ],
+    )?
+    else {
+        return Ok(());
+    };
+
+    let when_started: DateTime<Utc> = match base_data
+        .get_surreal_working_on()
+        .filter(|x| &x.item == menu_for.get_surreal_record_id())
+    {
+        Some(working_on) => working_on.when_started.clone().into(),
+        None => {
+            let now = Utc::now();
+            send_to_data_storage_layer
+                .send(DataLayerCommands::SetWorkingOn {
+                    item: menu_for.get_surreal_record_id().clone(),
+                    when_started: now.into(),
+                })
+                .await
+                .unwrap();
+            now
+        }
+    };
+
+    let label = format!("⏱️  {},", menu_for.get_item().get_summary());
+    loop {
+        if let CountDown::Finished = count_down(length, &label).await {
+            ring_the_bell();
+        }
+
+        let selection = Select::new(
+            "Timebox is over|",
+            vec![
+                TimeboxEndChoice::LogAndTakeABreak,
+                TimeboxEndChoice::KeepGoing,
+                TimeboxEndChoice::LogAndStop,
+            ],
+        )
+        .with_page_size(default_select_page_size())
+        .prompt();
+        match selection {
+            Ok(TimeboxEndChoice::LogAndTakeABreak) => {
+                log_timebox(
+                    menu_for,
+                    why_in_scope,
+                    when_started,
+                    send_to_data_storage_layer,
+                )
+                .await;
+                ask_for_resume_note(menu_for.get_item(), send_to_data_storage_layer).await?;
+                return take_a_break(send_to_data_storage_layer).await;
+            }
+            Ok(TimeboxEndChoice::KeepGoing) => continue,
+            Ok(TimeboxEndChoice::LogAndStop) => {
+                log_timebox(
+                    menu_for,
+                    why_in_scope,
+                    when_started,
+                    send_to_data_storage_layer,
+                )
+                .await;
+                return ask_for_resume_note(menu_for.get_item(), send_to_data_storage_layer).await;
+            }
+            //Still working on it, the time can be logged later with "Stop working on"
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_countdown;
+
+    #[test]
+    fn countdown_shows_hours_only_when_needed() {
+        assert_eq!(format_countdown(Duration::from_secs(25 * 60)), "25:00");
+        assert_eq!(format_countdown(Duration::from_secs(9)), "00:09");
+        assert_eq!(
+            format_countdown(Duration::from_secs(60 * 60 + 5 * 60 + 3)),
+            "1:05:03"
+        );
+    }
+}
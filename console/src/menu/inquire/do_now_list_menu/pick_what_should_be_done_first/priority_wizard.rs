@@ -22,7 +22,7 @@ use crate::{
         do_now_list_menu::do_now_list_single_item::urgency_plan::prompt_for_triggers,
     },
     node::Filter,
-    systems::do_now_list::DoNowList,
+    systems::{do_now_list::DoNowList, priority_ratings::PriorityRatings},
 };
 
 use super::WhyInScopeAndActionWithItemStatus;
@@ -67,6 +67,7 @@ fn prompt_for_priority_scope(do_now_list: &DoNowList) -> Result<Option<RecordId>
 }
 
 enum FinalPriorityWizardChoice {
+    PickHighestLearnedPriority,
     PickRandom,
     RepeatProcess,
 }
@@ -74,6 +75,9 @@ enum FinalPriorityWizardChoice {
 impl Display for FinalPriorityWizardChoice {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
+            FinalPriorityWizardChoice::PickHighestLearnedPriority => {
+                write!(f, "Pick the one with the highest learned priority")
+            }
             FinalPriorityWizardChoice::PickRandom => write!(f, "Pick one at random"),
             FinalPriorityWizardChoice::RepeatProcess => write!(f, "Repeat the process"),
         }
@@ -160,6 +164,7 @@ pub(crate) async fn priority_wizard_loop<'a>(
             let final_choice = Select::new(
                 "All items have been compared. What would you like to do?",
                 vec![
+                    FinalPriorityWizardChoice::PickHighestLearnedPriority,
                     FinalPriorityWizardChoice::PickRandom,
                     FinalPriorityWizardChoice::RepeatProcess,
                 ],
@@ -168,6 +173,25 @@ pub(crate) async fn priority_wizard_loop<'a>(
             .prompt();
 
             match final_choice {
+                Ok(FinalPriorityWizardChoice::PickHighestLearnedPriority) => {
+                    let ratings = PriorityRatings::new(
+                        do_now_list
+                            .get_base_data()
+                            .get_surreal_priority_comparisons(),
+                        do_now_list.get_current_mode().get_mode_id(),
+                    );
+                    let mut by_learned_priority = unselected_items.clone();
+                    ratings.sort_by_rating(&mut by_learned_priority, |x| x.get_surreal_record_id());
+                    super::handle_item_selection(
+                        by_learned_priority.first().expect("is not empty"),
+                        do_now_list,
+                        send_to_data_storage_layer,
+                    )
+                    .await?;
+
+                    // After returning from the menu, return Ok to refresh the main loop
+                    return Ok(());
+                }
                 Ok(FinalPriorityWizardChoice::PickRandom) => {
                     // Pick a random item from all choices and set it as higher priority for 1 minute
                     let random_idx = rand::random_range(0..unselected_items.len());
@@ -347,6 +371,14 @@ pub(crate) async fn priority_wizard_loop<'a>(
                     })
                     .await
                     .unwrap();
+                send_to_data_storage_layer
+                    .send(DataLayerCommands::RecordPriorityComparison {
+                        winner: selected_at_random.get_surreal_record_id().clone(),
+                        loser: lower_priority_item.get_surreal_record_id().clone(),
+                        for_mode: for_mode.clone(),
+                    })
+                    .await
+                    .unwrap();
 
                 selected_items.insert(lower_priority_item.get_surreal_record_id().clone());
             }
@@ -415,6 +447,16 @@ pub(crate) async fn priority_wizard_loop<'a>(
                     })
                     .await
                     .unwrap();
+                for higher_priority_item in &lower_priority_than {
+                    send_to_data_storage_layer
+                        .send(DataLayerCommands::RecordPriorityComparison {
+                            winner: higher_priority_item.get_surreal_record_id().clone(),
+                            loser: selected_at_random.get_surreal_record_id().clone(),
+                            for_mode: for_mode.clone(),
+                        })
+                        .await
+                        .unwrap();
+                }
             } else {
                 // User didn't select any higher-or-lower comparisons - ask what they want to do
                 let no_selection_choice = Select::new(
@@ -31,6 +31,7 @@ use crate::{
             pick_item_review_frequency::present_pick_item_review_frequency_menu,
         },
         select_higher_importance_than_this::select_higher_importance_than_this,
+        tags::edit_item_tags,
     },
     node::{
         Filter,
@@ -48,6 +49,7 @@ enum ReviewItemMenuChoices<'e> {
     UpdateDependencies { current_item: &'e ItemStatus<'e> },
     UpdateUrgencyPlan { current_item: &'e ItemStatus<'e> },
     UpdateReviewFrequency { current_item: &'e ItemStatus<'e> },
+    UpdateTags { current_item: &'e ItemStatus<'e> },
     FinishThisItem,
     AddNewParent,
     AddNewChild,
@@ -118,6 +120,18 @@ impl Display for ReviewItemMenuChoices<'_> {
                     write!(f, "Update review frequency, current setting: not set")
                 }
             }
+            ReviewItemMenuChoices::UpdateTags { current_item } => {
+                let tags = current_item.get_item().get_tags();
+                if tags.is_empty() {
+                    write!(f, "Update tags, current setting: none")
+                } else {
+                    write!(f, "Update tags, current setting:")?;
+                    for tag in tags {
+                        write!(f, " #{}", tag)?;
+                    }
+                    Ok(())
+                }
+            }
             ReviewItemMenuChoices::FinishThisItem { .. } => write!(f, "Finish this item"),
             ReviewItemMenuChoices::AddNewParent => write!(f, "Add new parent"),
             ReviewItemMenuChoices::AddNewChild => write!(f, "Add new child"),
@@ -187,6 +201,7 @@ impl ReviewItemMenuChoices<'_> {
         list.push(ReviewItemMenuChoices::UpdateUrgencyPlan { current_item });
         list.push(ReviewItemMenuChoices::UpdateReviewFrequency { current_item });
         list.push(ReviewItemMenuChoices::UpdateDependencies { current_item });
+        list.push(ReviewItemMenuChoices::UpdateTags { current_item });
         list.push(ReviewItemMenuChoices::FinishThisItem);
         list.push(ReviewItemMenuChoices::AddNewParent);
 
@@ -401,6 +416,13 @@ pub(crate) async fn present_review_item_menu(
 
                 continue;
             }
+            ReviewItemMenuChoices::UpdateTags { current_item } => {
+                edit_item_tags(current_item.get_item(), send_to_data_storage_layer)
+                    .await
+                    .unwrap();
+
+                continue;
+            }
             ReviewItemMenuChoices::UpdateUrgencyPlan { current_item } => {
                 let now = Utc::now();
                 let current_urgency = current_item.get_item_node().get_urgency_plan().as_ref();
@@ -1,12 +1,17 @@
+mod find;
+mod query;
+mod smart_lists;
+
 use std::fmt::{self, Display, Formatter};
 
 use chrono::Utc;
-use inquire::{InquireError, Select};
+use inquire::{InquireError, Select, Text};
 use tokio::sync::mpsc::Sender;
 
 use crate::{
     data_storage::surrealdb_layer::{
         data_layer_commands::DataLayerCommands, surreal_item::SurrealUrgency,
+        surreal_smart_list::SurrealSmartList,
     },
     display::{
         display_item_node::DisplayFormat,
@@ -18,7 +23,7 @@ use crate::{
         item_status::{ItemStatus, UrgencyPlanWithItemNode},
         why_in_scope_and_action_with_item_status::WhyInScope,
     },
-    systems::do_now_list::DoNowList,
+    systems::{do_now_list::DoNowList, smart_lists::find_smart_list_items},
 };
 
 use crate::menu::inquire::default_select_page_size;
@@ -63,9 +68,17 @@ enum SearchMenuUrgencyItem<'e> {
         when_ready_will_be_highest_importance: Vec<&'e ItemStatus<'e>>,
         nothing_is_ready: Vec<&'e ItemStatus<'e>>,
     },
+    SmartList {
+        smart_list: &'e SurrealSmartList,
+        items: Vec<&'e ItemStatus<'e>>,
+    },
+    NewSmartList,
     Item {
         item: &'e ItemStatus<'e>,
     },
+    SearchNotes,
+    Find,
+    Query,
 }
 
 impl Display for SearchMenuUrgencyItem<'_> {
@@ -169,6 +182,13 @@ impl Display for SearchMenuUrgencyItem<'_> {
                     nothing_is_ready.len()
                 )
             }
+            SearchMenuUrgencyItem::SmartList { smart_list, items } => {
+                write!(f, "🔖 {} ({} items)", smart_list.name, items.len())
+            }
+            SearchMenuUrgencyItem::NewSmartList => write!(f, "➕ New smart list"),
+            SearchMenuUrgencyItem::SearchNotes => write!(f, "📝 Search notes"),
+            SearchMenuUrgencyItem::Find => write!(f, "🔍 Find items about..."),
+            SearchMenuUrgencyItem::Query => write!(f, "🔎 Filter with a query"),
             SearchMenuUrgencyItem::Item { item } => {
                 let display_item_status = DisplayItemStatusWithUrgency::new(
                     item,
@@ -194,7 +214,13 @@ impl<'e> SearchMenuUrgencyItem<'e> {
                 ready_highest_importance: ready,
                 ..
             } => ready.push(to_push),
-            SearchMenuUrgencyItem::Item { .. } | SearchMenuUrgencyItem::AllMotivations { .. } => {
+            SearchMenuUrgencyItem::Item { .. }
+            | SearchMenuUrgencyItem::AllMotivations { .. }
+            | SearchMenuUrgencyItem::SmartList { .. }
+            | SearchMenuUrgencyItem::NewSmartList
+            | SearchMenuUrgencyItem::SearchNotes
+            | SearchMenuUrgencyItem::Find
+            | SearchMenuUrgencyItem::Query => {
                 panic!("Programming error. Can't push onto {:#?}", self)
             }
         }
@@ -214,7 +240,13 @@ impl<'e> SearchMenuUrgencyItem<'e> {
                 when_ready_will_be_highest_importance: not_ready,
                 ..
             } => not_ready.push(to_push),
-            SearchMenuUrgencyItem::Item { .. } | SearchMenuUrgencyItem::AllMotivations { .. } => {
+            SearchMenuUrgencyItem::Item { .. }
+            | SearchMenuUrgencyItem::AllMotivations { .. }
+            | SearchMenuUrgencyItem::SmartList { .. }
+            | SearchMenuUrgencyItem::NewSmartList
+            | SearchMenuUrgencyItem::SearchNotes
+            | SearchMenuUrgencyItem::Find
+            | SearchMenuUrgencyItem::Query => {
                 panic!("Programming error. Can't push onto {:#?}", self)
             }
         }
@@ -236,7 +268,13 @@ impl<'e> SearchMenuUrgencyItem<'e> {
             SearchMenuUrgencyItem::HighestImportance {
                 nothing_is_ready, ..
             } => nothing_is_ready.push(to_push),
-            SearchMenuUrgencyItem::Item { .. } | SearchMenuUrgencyItem::AllMotivations { .. } => {
+            SearchMenuUrgencyItem::Item { .. }
+            | SearchMenuUrgencyItem::AllMotivations { .. }
+            | SearchMenuUrgencyItem::SmartList { .. }
+            | SearchMenuUrgencyItem::NewSmartList
+            | SearchMenuUrgencyItem::SearchNotes
+            | SearchMenuUrgencyItem::Find
+            | SearchMenuUrgencyItem::Query => {
                 panic!("Programming error. Can't push onto {:#?}", self)
             }
         }
@@ -246,6 +284,9 @@ impl<'e> SearchMenuUrgencyItem<'e> {
         match self {
             SearchMenuUrgencyItem::AllMotivations { motivations } => motivations.push(to_push),
             SearchMenuUrgencyItem::Item { .. }
+            | SearchMenuUrgencyItem::SearchNotes
+            | SearchMenuUrgencyItem::SmartList { .. }
+            | SearchMenuUrgencyItem::NewSmartList
             | SearchMenuUrgencyItem::MoreUrgentThanAnythingIncludingScheduled { .. }
             | SearchMenuUrgencyItem::ScheduledAnyMode { .. }
             | SearchMenuUrgencyItem::MoreUrgentThanMode { .. }
@@ -296,7 +337,13 @@ impl<'e> SearchMenuUrgencyItem<'e> {
                 nothing_is_ready: coming_later,
             } => ready.is_empty() && not_ready.is_empty() && coming_later.is_empty(),
             SearchMenuUrgencyItem::AllMotivations { motivations } => motivations.is_empty(),
-            SearchMenuUrgencyItem::Item { item: _item } => false,
+            //A smart list is shown even when nothing is in it so it can still be deleted
+            SearchMenuUrgencyItem::Item { item: _item }
+            | SearchMenuUrgencyItem::SmartList { .. }
+            | SearchMenuUrgencyItem::NewSmartList
+            | SearchMenuUrgencyItem::SearchNotes
+            | SearchMenuUrgencyItem::Find
+            | SearchMenuUrgencyItem::Query => false,
         }
     }
 }
@@ -605,7 +652,13 @@ pub(crate) async fn present_search_menu(
         }
     }
 
-    let mut list = Vec::default();
+    let mut list = vec![SearchMenuUrgencyItem::Find, SearchMenuUrgencyItem::Query];
+    if items
+        .values()
+        .any(|x| x.is_active() && x.get_item().has_notes())
+    {
+        list.push(SearchMenuUrgencyItem::SearchNotes);
+    }
     if !all_motivations.is_empty() {
         list.push(all_motivations);
     }
@@ -630,6 +683,13 @@ pub(crate) async fn present_search_menu(
     if !highest_importance.is_empty() {
         list.push(highest_importance);
     }
+    for smart_list in do_now_list.get_base_data().get_surreal_smart_lists() {
+        list.push(SearchMenuUrgencyItem::SmartList {
+            smart_list,
+            items: find_smart_list_items(&smart_list.filter, items),
+        });
+    }
+    list.push(SearchMenuUrgencyItem::NewSmartList);
 
     for (_, item) in items.iter().filter(|(_, x)| x.is_active()) {
         list.push(SearchMenuUrgencyItem::Item { item });
@@ -822,6 +882,27 @@ pub(crate) async fn present_search_menu(
                 _ => panic!("Programming error. Expected item"),
             }
         }
+        Ok(SearchMenuUrgencyItem::SearchNotes) => {
+            present_search_notes_menu(do_now_list, send_to_data_storage_layer).await
+        }
+        Ok(SearchMenuUrgencyItem::Find) => {
+            find::present_find_menu(do_now_list, send_to_data_storage_layer).await
+        }
+        Ok(SearchMenuUrgencyItem::Query) => {
+            query::present_query_menu("", do_now_list, send_to_data_storage_layer).await
+        }
+        Ok(SearchMenuUrgencyItem::SmartList { smart_list, items }) => {
+            smart_lists::present_smart_list_menu(
+                smart_list,
+                items,
+                do_now_list,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Ok(SearchMenuUrgencyItem::NewSmartList) => {
+            smart_lists::present_new_smart_list_menu(do_now_list, send_to_data_storage_layer).await
+        }
         Err(InquireError::OperationCanceled) => Ok(()),
         Err(InquireError::OperationInterrupted) => Err(()),
         Err(InquireError::InvalidConfiguration(_)) => {
@@ -833,3 +914,61 @@ pub(crate) async fn present_search_menu(
         Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
     }
 }
+
+async fn present_search_notes_menu(
+    do_now_list: &DoNowList,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let search_for = match Text::new("Search notes for").prompt() {
+        Ok(search_for) => search_for,
+        Err(InquireError::OperationCanceled) => {
+            return Box::pin(present_search_menu(do_now_list, send_to_data_storage_layer)).await;
+        }
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    };
+
+    let list = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| x.is_active() && x.get_item().notes_contain(search_for.trim()))
+        .map(|item| SearchMenuUrgencyItem::Item { item })
+        .collect::<Vec<_>>();
+    if list.is_empty() {
+        println!();
+        println!("No notes contain \"{}\"", search_for.trim());
+        println!();
+        return Box::pin(present_search_notes_menu(
+            do_now_list,
+            send_to_data_storage_layer,
+        ))
+        .await;
+    }
+
+    let selection = Select::new("Select an item to view", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(SearchMenuUrgencyItem::Item { item }) => {
+            let why_in_scope = WhyInScope::new_menu_navigation();
+            present_do_now_list_item_selected(
+                item,
+                &why_in_scope,
+                Utc::now(),
+                do_now_list,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Err(InquireError::OperationCanceled) => {
+            Box::pin(present_search_notes_menu(
+                do_now_list,
+                send_to_data_storage_layer,
+            ))
+            .await
+        }
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Ok(_) => panic!("Programming error. Expected item"),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
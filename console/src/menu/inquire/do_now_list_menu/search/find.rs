@@ -0,0 +1,84 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::Utc;
+use inquire::{InquireError, Select, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::search_index::SearchMatch,
+    data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands,
+    display::display_item::DisplayItem,
+    menu::inquire::{
+        default_select_page_size,
+        do_now_list_menu::do_now_list_single_item::present_do_now_list_item_selected,
+    },
+    node::why_in_scope_and_action_with_item_status::WhyInScope,
+    systems::do_now_list::DoNowList,
+};
+
+struct SearchMatchSelection<'e>(SearchMatch<'e>);
+
+impl Display for SearchMatchSelection<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let item = self.0.get_item();
+        if item.is_finished() {
+            write!(f, "✅ (finished) ")?;
+        }
+        write!(f, "{}", DisplayItem::new(item))
+    }
+}
+
+pub(super) async fn present_find_menu(
+    do_now_list: &DoNowList,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let search_for = match Text::new("Find items about")
+        .with_help_message("Searches summaries and notes, typos are ok")
+        .prompt()
+    {
+        Ok(search_for) => search_for,
+        Err(InquireError::OperationCanceled) => return Ok(()),
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    };
+
+    let list = do_now_list
+        .get_base_data()
+        .get_search_index()
+        .search(&search_for)
+        .into_iter()
+        .map(SearchMatchSelection)
+        .collect::<Vec<_>>();
+    if list.is_empty() {
+        println!();
+        println!("Nothing found for \"{}\"", search_for.trim());
+        println!();
+        return Box::pin(present_find_menu(do_now_list, send_to_data_storage_layer)).await;
+    }
+
+    let selection = Select::new("Best matches first|", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(SearchMatchSelection(search_match)) => {
+            let item_status = do_now_list
+                .get_all_items_status()
+                .get(search_match.get_item().get_surreal_record_id())
+                .expect("Every item has a status");
+            let why_in_scope = WhyInScope::new_menu_navigation();
+            present_do_now_list_item_selected(
+                item_status,
+                &why_in_scope,
+                Utc::now(),
+                do_now_list,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Err(InquireError::OperationCanceled) => {
+            Box::pin(present_find_menu(do_now_list, send_to_data_storage_layer)).await
+        }
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::Utc;
+use inquire::{InquireError, Select, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands, surreal_smart_list::SurrealSmartListFilter,
+    },
+    display::{
+        display_item_node::DisplayFormat,
+        display_item_status_with_urgency::DisplayItemStatusWithUrgency,
+    },
+    menu::inquire::{
+        default_select_page_size,
+        do_now_list_menu::do_now_list_single_item::present_do_now_list_item_selected,
+        item_query::prompt_for_item_query,
+    },
+    node::{Filter, item_status::ItemStatus, why_in_scope_and_action_with_item_status::WhyInScope},
+    systems::{do_now_list::DoNowList, item_query::find_items_matching_query},
+};
+
+enum QueryResultSelection<'e> {
+    Item(&'e ItemStatus<'e>),
+    ChangeQuery,
+    SaveAsSmartList,
+}
+
+impl Display for QueryResultSelection<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryResultSelection::Item(item) => {
+                let display_item_status =
+                    DisplayItemStatusWithUrgency::new(item, Filter::All, DisplayFormat::SingleLine);
+                write!(f, "{}", display_item_status)
+            }
+            QueryResultSelection::ChangeQuery => write!(f, "✏️ Change the query"),
+            QueryResultSelection::SaveAsSmartList => write!(f, "💾 Save as a smart list"),
+        }
+    }
+}
+
+pub(super) async fn present_query_menu(
+    initial_query: &str,
+    do_now_list: &DoNowList,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let Some((query_text, query)) = prompt_for_item_query("Filter with a query", initial_query)?
+    else {
+        return Ok(());
+    };
+
+    let items = find_items_matching_query(&query, do_now_list.get_all_items_status());
+    if items.is_empty() {
+        println!();
+        println!("Nothing matches \"{}\"", query_text);
+        println!();
+        return Box::pin(present_query_menu(
+            &query_text,
+            do_now_list,
+            send_to_data_storage_layer,
+        ))
+        .await;
+    }
+
+    let mut list = items
+        .into_iter()
+        .map(QueryResultSelection::Item)
+        .collect::<Vec<_>>();
+    list.push(QueryResultSelection::ChangeQuery);
+    list.push(QueryResultSelection::SaveAsSmartList);
+
+    let selection = Select::new(&format!("{}|", query_text), list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(QueryResultSelection::Item(item)) => {
+            let why_in_scope = WhyInScope::new_menu_navigation();
+            present_do_now_list_item_selected(
+                item,
+                &why_in_scope,
+                Utc::now(),
+                do_now_list,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Ok(QueryResultSelection::ChangeQuery) => {
+            Box::pin(present_query_menu(
+                &query_text,
+                do_now_list,
+                send_to_data_storage_layer,
+            ))
+            .await
+        }
+        Ok(QueryResultSelection::SaveAsSmartList) => {
+            let name = match Text::new("Name of the smart list").prompt() {
+                Ok(name) => name.trim().to_string(),
+                Err(InquireError::OperationCanceled) => return Ok(()),
+                Err(InquireError::OperationInterrupted) => return Err(()),
+                Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+            };
+            if name.is_empty() {
+                return Ok(());
+            }
+            send_to_data_storage_layer
+                .send(DataLayerCommands::NewSmartList {
+                    name: name.clone(),
+                    filter: SurrealSmartListFilter {
+                        query: Some(query_text),
+                        ..Default::default()
+                    },
+                })
+                .await
+                .unwrap();
+            println!(
+                "Saved smart list \"{}\", it is now in the search menu",
+                name
+            );
+            Ok(())
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
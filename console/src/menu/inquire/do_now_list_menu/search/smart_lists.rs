@@ -0,0 +1,334 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::Utc;
+use inquire::{InquireError, MultiSelect, Select, Text};
+use surrealdb::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands,
+        surreal_smart_list::{
+            SurrealSmartList, SurrealSmartListFilter, SurrealSmartListItemType,
+            SurrealSmartListReadiness, SurrealSmartListUrgency,
+        },
+    },
+    display::{
+        display_item::DisplayItem, display_item_node::DisplayFormat,
+        display_item_status_with_urgency::DisplayItemStatusWithUrgency,
+    },
+    menu::inquire::{
+        default_select_page_size,
+        do_now_list_menu::do_now_list_single_item::present_do_now_list_item_selected,
+        item_query::prompt_for_item_query, tags::parse_tags,
+    },
+    node::{Filter, item_status::ItemStatus, why_in_scope_and_action_with_item_status::WhyInScope},
+    systems::do_now_list::DoNowList,
+};
+
+enum SmartListSelection<'e> {
+    Item(&'e ItemStatus<'e>),
+    DeleteSmartList,
+}
+
+impl Display for SmartListSelection<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SmartListSelection::Item(item) => {
+                let display_item_status = DisplayItemStatusWithUrgency::new(
+                    item,
+                    Filter::Active,
+                    DisplayFormat::SingleLine,
+                );
+                write!(f, "{}", display_item_status)
+            }
+            SmartListSelection::DeleteSmartList => write!(f, "🗑️ Delete this smart list"),
+        }
+    }
+}
+
+pub(super) async fn present_smart_list_menu<'e>(
+    smart_list: &SurrealSmartList,
+    items: Vec<&'e ItemStatus<'e>>,
+    do_now_list: &DoNowList,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let mut list = items
+        .into_iter()
+        .map(SmartListSelection::Item)
+        .collect::<Vec<_>>();
+    list.push(SmartListSelection::DeleteSmartList);
+
+    let selection = Select::new(&format!("{}|", smart_list.name), list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(SmartListSelection::Item(item)) => {
+            let why_in_scope = WhyInScope::new_menu_navigation();
+            present_do_now_list_item_selected(
+                item,
+                &why_in_scope,
+                Utc::now(),
+                do_now_list,
+                send_to_data_storage_layer,
+            )
+            .await
+        }
+        Ok(SmartListSelection::DeleteSmartList) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::DeleteSmartList(
+                    smart_list.id.clone().expect("In DB"),
+                ))
+                .await
+                .unwrap();
+            println!("Deleted smart list \"{}\"", smart_list.name);
+            Ok(())
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+struct ItemTypeChoice(SurrealSmartListItemType);
+
+impl Display for ItemTypeChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            SurrealSmartListItemType::Undeclared => write!(f, "Undeclared"),
+            SurrealSmartListItemType::Action => write!(f, "Action"),
+            SurrealSmartListItemType::Goal => write!(f, "Goal or Project"),
+            SurrealSmartListItemType::IdeaOrThought => write!(f, "Idea or Thought"),
+            SurrealSmartListItemType::Motivation => write!(f, "Motivational Purpose"),
+            SurrealSmartListItemType::PersonOrGroup => write!(f, "Person or Group"),
+        }
+    }
+}
+
+enum UrgencyChoice {
+    Any,
+    Urgency(SurrealSmartListUrgency),
+}
+
+impl Display for UrgencyChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            UrgencyChoice::Any => write!(f, "Any urgency"),
+            UrgencyChoice::Urgency(urgency) => match urgency {
+                SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled => {
+                    write!(f, "More urgent than anything including scheduled")
+                }
+                SurrealSmartListUrgency::ScheduledAnyMode => write!(f, "Scheduled any mode"),
+                SurrealSmartListUrgency::MoreUrgentThanMode => write!(f, "More urgent than mode"),
+                SurrealSmartListUrgency::InTheModeScheduled => write!(f, "In the mode, scheduled"),
+                SurrealSmartListUrgency::InTheModeDefinitelyUrgent => {
+                    write!(f, "In the mode, definitely urgent")
+                }
+                SurrealSmartListUrgency::InTheModeMaybeUrgent => {
+                    write!(f, "In the mode, maybe urgent")
+                }
+                SurrealSmartListUrgency::InTheModeByImportance => {
+                    write!(f, "In the mode, by importance")
+                }
+            },
+        }
+    }
+}
+
+enum ReadinessChoice {
+    Any,
+    Readiness(SurrealSmartListReadiness),
+}
+
+impl Display for ReadinessChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadinessChoice::Any => write!(f, "Ready or not ready"),
+            ReadinessChoice::Readiness(SurrealSmartListReadiness::Ready) => write!(f, "Ready"),
+            ReadinessChoice::Readiness(SurrealSmartListReadiness::NotReady) => {
+                write!(f, "Not ready")
+            }
+        }
+    }
+}
+
+enum MotivationChoice<'e> {
+    Any,
+    Under(&'e ItemStatus<'e>),
+}
+
+impl Display for MotivationChoice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MotivationChoice::Any => write!(f, "Under any motivation or none"),
+            MotivationChoice::Under(motivation) => {
+                write!(f, "Under {}", DisplayItem::new(motivation.get_item()))
+            }
+        }
+    }
+}
+
+fn handle_prompt_error<T>(result: Result<T, InquireError>) -> Result<Option<T>, ()> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+/// Either takes a query or walks through each part of the filter, anything left empty or set to
+/// "any" is not used.
+pub(super) async fn present_new_smart_list_menu(
+    do_now_list: &DoNowList,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let Some(name) = handle_prompt_error(Text::new("Name of the smart list").prompt())? else {
+        return Ok(());
+    };
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Ok(());
+    }
+
+    let Some((query, _)) = prompt_for_item_query(
+        "Query for the smart list, leave empty to pick the filter step by step",
+        "",
+    )?
+    else {
+        return Ok(());
+    };
+    if !query.is_empty() {
+        send_to_data_storage_layer
+            .send(DataLayerCommands::NewSmartList {
+                name: name.clone(),
+                filter: SurrealSmartListFilter {
+                    query: Some(query),
+                    ..Default::default()
+                },
+            })
+            .await
+            .unwrap();
+        println!(
+            "Saved smart list \"{}\", it is now in the search menu",
+            name
+        );
+        return Ok(());
+    }
+
+    let Some(tags) = handle_prompt_error(
+        Text::new("Tags that items must have, separated by spaces (e.g. #phone #errand)")
+            .with_help_message("Leave empty to not filter by tags")
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let tags = parse_tags(&tags);
+
+    let item_type_choices = [
+        SurrealSmartListItemType::Action,
+        SurrealSmartListItemType::Goal,
+        SurrealSmartListItemType::Motivation,
+        SurrealSmartListItemType::IdeaOrThought,
+        SurrealSmartListItemType::PersonOrGroup,
+        SurrealSmartListItemType::Undeclared,
+    ]
+    .into_iter()
+    .map(ItemTypeChoice)
+    .collect::<Vec<_>>();
+    let Some(item_types) = handle_prompt_error(
+        MultiSelect::new(
+            "Item types to include (Space: toggle, Enter: done, select none for any type)|",
+            item_type_choices,
+        )
+        .with_page_size(default_select_page_size())
+        .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let item_types = item_types.into_iter().map(|x| x.0).collect::<Vec<_>>();
+
+    let urgency_choices = vec![
+        UrgencyChoice::Any,
+        UrgencyChoice::Urgency(SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled),
+        UrgencyChoice::Urgency(SurrealSmartListUrgency::ScheduledAnyMode),
+        UrgencyChoice::Urgency(SurrealSmartListUrgency::MoreUrgentThanMode),
+        UrgencyChoice::Urgency(SurrealSmartListUrgency::InTheModeScheduled),
+        UrgencyChoice::Urgency(SurrealSmartListUrgency::InTheModeDefinitelyUrgent),
+        UrgencyChoice::Urgency(SurrealSmartListUrgency::InTheModeMaybeUrgent),
+        UrgencyChoice::Urgency(SurrealSmartListUrgency::InTheModeByImportance),
+    ];
+    let Some(urgency) = handle_prompt_error(
+        Select::new("Urgency|", urgency_choices)
+            .with_page_size(default_select_page_size())
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let urgency = match urgency {
+        UrgencyChoice::Any => None,
+        UrgencyChoice::Urgency(urgency) => Some(urgency),
+    };
+
+    let readiness_choices = vec![
+        ReadinessChoice::Any,
+        ReadinessChoice::Readiness(SurrealSmartListReadiness::Ready),
+        ReadinessChoice::Readiness(SurrealSmartListReadiness::NotReady),
+    ];
+    let Some(readiness) = handle_prompt_error(
+        Select::new("Readiness|", readiness_choices)
+            .with_page_size(default_select_page_size())
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let readiness = match readiness {
+        ReadinessChoice::Any => None,
+        ReadinessChoice::Readiness(readiness) => Some(readiness),
+    };
+
+    let mut motivations = do_now_list
+        .get_all_items_status()
+        .values()
+        .filter(|x| x.is_active() && x.is_type_motivation())
+        .collect::<Vec<_>>();
+    motivations.sort_by(|a, b| a.get_summary().cmp(b.get_summary()));
+    let mut motivation_choices = vec![MotivationChoice::Any];
+    motivation_choices.extend(motivations.into_iter().map(MotivationChoice::Under));
+    let Some(under_motivation) = handle_prompt_error(
+        Select::new("Motivation|", motivation_choices)
+            .with_page_size(default_select_page_size())
+            .prompt(),
+    )?
+    else {
+        return Ok(());
+    };
+    let under_motivation: Option<RecordId> = match under_motivation {
+        MotivationChoice::Any => None,
+        MotivationChoice::Under(motivation) => Some(motivation.get_surreal_record_id().clone()),
+    };
+
+    send_to_data_storage_layer
+        .send(DataLayerCommands::NewSmartList {
+            name: name.clone(),
+            filter: SurrealSmartListFilter {
+                tags,
+                item_types,
+                urgency,
+                readiness,
+                under_motivation,
+                query: None,
+            },
+        })
+        .await
+        .unwrap();
+    println!(
+        "Saved smart list \"{}\", it is now in the search menu",
+        name
+    );
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use inquire::{InquireError, Text};
+
+use crate::systems::item_query::{ItemQuery, parse_item_query};
+
+#[must_use]
+pub(crate) fn item_query_help_string() -> &'static str {
+    concat!(
+        "Terms next to each other must all match, \"or\" matches either side, \"not\" or \"-\" negates,\n",
+        "and parentheses group terms. Examples:\n",
+        "\"type:action ready\" ready actions (types: action, goal, idea, motivation, person, undeclared)\n",
+        "\"urgency>=definitely\" (urgency: importance, maybe, definitely, scheduled, more-than-mode,\n",
+        "  scheduled-any-mode, top, compare with : = > >= < <=)\n",
+        "\"under:\\\"Health\\\"\" anything under an item whose summary contains Health\n",
+        "\"tag:phone\" or \"#phone\" items tagged phone\n",
+        "\"created<30d\" created within the last 30 days, or \"created>=2025-01-31\"\n",
+        "\"finished:last-week\" (also today, yesterday, this-week, this-month, last-month, or \"finished<2d\")\n",
+        "\"text:~\\\"invoice\\\"\" summary or notes contain invoice, \"text:\\\"Call mom\\\"\" the exact summary\n",
+        "Any other word is searched for in the summary. Finished items are only included when the query\n",
+        "mentions finished.\n"
+    )
+}
+
+/// Asks until a valid query is typed, None if the user cancels.
+pub(crate) fn prompt_for_item_query(
+    message: &str,
+    initial_value: &str,
+) -> Result<Option<(String, ItemQuery)>, ()> {
+    let mut initial_value = initial_value.to_string();
+    loop {
+        match Text::new(message)
+            .with_initial_value(&initial_value)
+            .with_help_message("\"?\" for help")
+            .prompt()
+        {
+            Ok(input) if input.trim() == "?" => {
+                println!("{}", item_query_help_string());
+            }
+            Ok(input) => match parse_item_query(&input) {
+                Ok(query) => return Ok(Some((input.trim().to_string(), query))),
+                Err(err) => {
+                    println!("{}. Please try again.", err);
+                    println!();
+                    initial_value = input;
+                }
+            },
+            Err(InquireError::OperationCanceled) => return Ok(None),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+}
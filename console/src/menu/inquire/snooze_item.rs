@@ -0,0 +1,50 @@
+use chrono::{DateTime, Local, Utc};
+use inquire::{InquireError, Text};
+use surrealdb::RecordId;
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands,
+    menu::inquire::{
+        parse_exact_or_relative_datetime, parse_exact_or_relative_datetime_help_string,
+    },
+};
+
+/// Prompts for when the item should come back, for example "2h", "tomorrow 9am", or "next monday",
+/// and replaces any existing AfterDateTime dependency with it.
+pub(crate) async fn snooze_item(
+    item_to_snooze: RecordId,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let snooze_until = loop {
+        match Text::new("Snooze until (\"?\" for help)").prompt() {
+            Ok(snooze_until) => match parse_exact_or_relative_datetime(&snooze_until) {
+                Some(snooze_until) => break snooze_until,
+                None => {
+                    println!("Invalid input. Please try again.");
+                    println!();
+                    println!("{}", parse_exact_or_relative_datetime_help_string());
+                    continue;
+                }
+            },
+            Err(InquireError::OperationCanceled) => return Ok(()),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    };
+
+    let snooze_until: DateTime<Utc> = snooze_until.with_timezone(&Utc);
+    send_to_data_storage_layer
+        .send(DataLayerCommands::SnoozeItem {
+            item: item_to_snooze,
+            until: snooze_until.into(),
+        })
+        .await
+        .unwrap();
+    let snooze_until: DateTime<Local> = snooze_until.into();
+    println!(
+        "Snoozed until {}",
+        snooze_until.format("%a %d %b %Y %I:%M%P")
+    );
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use inquire::{InquireError, Text};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::item::Item, data_storage::surrealdb_layer::data_layer_commands::DataLayerCommands,
+};
+
+/// Tags are stored lowercase and without the leading '#' so "#Phone" and "phone" are the same tag.
+pub(crate) fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+    if tag.is_empty() { None } else { Some(tag) }
+}
+
+/// Parses a list of tags separated by spaces or commas, for example "#phone, errand". Duplicates are
+/// removed and the order they were typed in is kept.
+pub(crate) fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags = Vec::default();
+    for tag in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(normalize_tag)
+    {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Pulls tags typed at capture time out of the summary, for example "Call the plumber #phone #errand"
+/// becomes the summary "Call the plumber" with the tags phone and errand. Only words that start with
+/// '#' followed by a letter are tags so something like "#1 priority" is left alone.
+pub(crate) fn split_tags_from_summary(input: &str) -> (String, Vec<String>) {
+    let is_tag = |word: &&str| {
+        word.strip_prefix('#')
+            .and_then(|x| x.chars().next())
+            .is_some_and(|x| x.is_alphabetic())
+    };
+    if !input.split_whitespace().any(|word| is_tag(&word)) {
+        return (input.to_string(), Vec::default());
+    }
+
+    let summary = input
+        .split_whitespace()
+        .filter(|word| !is_tag(word))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let tags = parse_tags(
+        &input
+            .split_whitespace()
+            .filter(is_tag)
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    (summary, tags)
+}
+
+fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|x| format!("#{}", x))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) async fn edit_item_tags(
+    item: &Item<'_>,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let current_tags = format_tags(item.get_tags());
+    let tags = Text::new("Tags, separated by spaces (e.g. #phone #errand #low-energy)")
+        .with_initial_value(&current_tags)
+        .prompt();
+    let tags = match tags {
+        Ok(tags) => parse_tags(&tags),
+        Err(InquireError::OperationCanceled) => return Ok(()),
+        Err(InquireError::OperationInterrupted) => return Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    };
+
+    send_to_data_storage_layer
+        .send(DataLayerCommands::UpdateTags(
+            item.get_surreal_record_id().clone(),
+            tags,
+        ))
+        .await
+        .unwrap();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_tag, parse_tags, split_tags_from_summary};
+
+    #[test]
+    fn tags_are_lowercase_without_the_hash() {
+        assert_eq!(normalize_tag("#Low-Energy"), Some("low-energy".to_string()));
+        assert_eq!(normalize_tag(" phone "), Some("phone".to_string()));
+        assert_eq!(normalize_tag("#"), None);
+    }
+
+    #[test]
+    fn tags_can_be_separated_by_spaces_or_commas_and_duplicates_are_removed() {
+        assert_eq!(
+            parse_tags("#phone, errand #Phone,,low-energy"),
+            vec![
+                "phone".to_string(),
+                "errand".to_string(),
+                "low-energy".to_string()
+            ]
+        );
+        assert!(parse_tags("  ").is_empty());
+    }
+
+    #[test]
+    fn tags_are_split_out_of_the_summary() {
+        assert_eq!(
+            split_tags_from_summary("Call the plumber #phone #Errand"),
+            (
+                "Call the plumber".to_string(),
+                vec!["phone".to_string(), "errand".to_string()]
+            )
+        );
+        assert_eq!(
+            split_tags_from_summary("#phone mom about   the weekend"),
+            (
+                "mom about the weekend".to_string(),
+                vec!["phone".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn summary_without_tags_is_left_alone() {
+        assert_eq!(
+            split_tags_from_summary("Make #1 priority  list in C#"),
+            ("Make #1 priority  list in C#".to_string(), Vec::default())
+        );
+    }
+}
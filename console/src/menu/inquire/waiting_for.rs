@@ -0,0 +1,244 @@
+use std::fmt::{self, Display, Formatter};
+
+use better_term::Style;
+use chrono::{DateTime, Local, Utc};
+use inquire::{InquireError, Select, Text};
+use surrealdb::{RecordId, sql::Datetime};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    base_data::{BaseData, item::Item},
+    data_storage::surrealdb_layer::{
+        data_layer_commands::DataLayerCommands,
+        surreal_item::{SurrealItemType, SurrealWaitingFor},
+    },
+    menu::inquire::{
+        default_select_page_size, parse_exact_or_relative_datetime,
+        parse_exact_or_relative_datetime_help_string,
+    },
+    new_item::NewItemBuilder,
+    node::item_status::ItemStatus,
+};
+
+fn format_date(when: &Datetime) -> String {
+    let when: DateTime<Utc> = when.clone().into();
+    let when: DateTime<Local> = when.into();
+    when.format("%a %d %b %Y %I:%M%P").to_string()
+}
+
+/// Shows who the item is waiting on, when it was asked for, and when to follow up
+pub(crate) fn print_waiting_for(item_status: &ItemStatus<'_>) {
+    let Some(waiting_for) = item_status.get_item().get_waiting_for() else {
+        return;
+    };
+    let underline = Style::default().underline();
+    let normal_style = Style::default();
+    let person = match item_status.get_waiting_on() {
+        Some(person) => person.get_summary(),
+        None => "(person no longer exists)",
+    };
+    println!();
+    println!(
+        "{}Waiting for:{} {}, requested {}, follow up {}{}",
+        underline,
+        normal_style,
+        person,
+        format_date(&waiting_for.requested),
+        format_date(&waiting_for.follow_up),
+        if item_status.get_item().is_follow_up_due() {
+            " (due now)"
+        } else {
+            ""
+        }
+    );
+}
+
+enum PersonChoice<'e> {
+    Existing(&'e Item<'e>),
+    SomeoneNew,
+}
+
+impl Display for PersonChoice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PersonChoice::Existing(person) => write!(f, "👤 {}", person.get_summary()),
+            PersonChoice::SomeoneNew => write!(f, "➕ Someone new"),
+        }
+    }
+}
+
+enum Person {
+    Existing(RecordId),
+    New(String),
+}
+
+fn pick_person(base_data: &BaseData) -> Result<Option<Person>, ()> {
+    let mut people = base_data
+        .get_items()
+        .values()
+        .filter(|x| x.is_active() && x.is_person_or_group())
+        .collect::<Vec<_>>();
+    people.sort_by(|a, b| a.get_summary().cmp(b.get_summary()));
+    let list = people
+        .into_iter()
+        .map(PersonChoice::Existing)
+        .chain(std::iter::once(PersonChoice::SomeoneNew))
+        .collect::<Vec<_>>();
+
+    let selection = Select::new("Who is this waiting on?|", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(PersonChoice::Existing(person)) => Ok(Some(Person::Existing(
+            person.get_surreal_record_id().clone(),
+        ))),
+        Ok(PersonChoice::SomeoneNew) => match Text::new("Name of the person or group").prompt() {
+            Ok(name) if name.trim().is_empty() => Ok(None),
+            Ok(name) => Ok(Some(Person::New(name.trim().to_string()))),
+            Err(InquireError::OperationCanceled) => Ok(None),
+            Err(InquireError::OperationInterrupted) => Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        },
+        Err(InquireError::OperationCanceled) => Ok(None),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
+
+fn prompt_for_follow_up() -> Result<Option<Datetime>, ()> {
+    loop {
+        match Text::new("When should you follow up? (\"?\" for help)").prompt() {
+            Ok(follow_up) => match parse_exact_or_relative_datetime(&follow_up) {
+                Some(follow_up) => {
+                    let follow_up: DateTime<Utc> = follow_up.with_timezone(&Utc);
+                    return Ok(Some(follow_up.into()));
+                }
+                None => {
+                    println!("Invalid input. Please try again.");
+                    println!();
+                    println!("{}", parse_exact_or_relative_datetime_help_string());
+                }
+            },
+            Err(InquireError::OperationCanceled) => return Ok(None),
+            Err(InquireError::OperationInterrupted) => return Err(()),
+            Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+        }
+    }
+}
+
+/// Hands the item off to someone, recording who and when to follow up with them
+async fn start_waiting_for(
+    item: RecordId,
+    base_data: &BaseData,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let Some(person) = pick_person(base_data)? else {
+        return Ok(());
+    };
+    let Some(follow_up) = prompt_for_follow_up()? else {
+        return Ok(());
+    };
+    let requested: Datetime = Utc::now().into();
+    let command = match person {
+        Person::Existing(person) => DataLayerCommands::UpdateWaitingFor(
+            item,
+            Some(SurrealWaitingFor::new(person, requested, follow_up)),
+        ),
+        Person::New(name) => DataLayerCommands::WaitingForNewPerson {
+            item,
+            person: NewItemBuilder::default()
+                .summary(name)
+                .item_type(SurrealItemType::PersonOrGroup)
+                .build()
+                .expect("Filled out required fields"),
+            requested,
+            follow_up,
+        },
+    };
+    send_to_data_storage_layer.send(command).await.unwrap();
+    Ok(())
+}
+
+enum WaitingForChoice {
+    FollowedUp,
+    ChangePerson,
+    Received,
+    NoLongerWaiting,
+}
+
+impl Display for WaitingForChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitingForChoice::FollowedUp => write!(f, "📞 I followed up, check again later"),
+            WaitingForChoice::ChangePerson => write!(f, "👤 Now waiting on someone else"),
+            WaitingForChoice::Received => write!(f, "📬 Received it, this is finished"),
+            WaitingForChoice::NoLongerWaiting => {
+                write!(f, "✋ No longer waiting, I will take this back")
+            }
+        }
+    }
+}
+
+/// For an item that is not waiting on anyone this asks who it is waiting on, otherwise it is for
+/// recording a follow up or that the wait is over.
+pub(crate) async fn present_waiting_for_menu(
+    item_status: &ItemStatus<'_>,
+    base_data: &BaseData,
+    send_to_data_storage_layer: &Sender<DataLayerCommands>,
+) -> Result<(), ()> {
+    let record_id = item_status.get_surreal_record_id().clone();
+    let Some(waiting_for) = item_status.get_item().get_waiting_for() else {
+        return start_waiting_for(record_id, base_data, send_to_data_storage_layer).await;
+    };
+
+    let list = vec![
+        WaitingForChoice::FollowedUp,
+        WaitingForChoice::ChangePerson,
+        WaitingForChoice::Received,
+        WaitingForChoice::NoLongerWaiting,
+    ];
+    let selection = Select::new("Select from the below list|", list)
+        .with_page_size(default_select_page_size())
+        .prompt();
+    match selection {
+        Ok(WaitingForChoice::FollowedUp) => {
+            let Some(follow_up) = prompt_for_follow_up()? else {
+                return Ok(());
+            };
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UpdateWaitingFor(
+                    record_id,
+                    Some(SurrealWaitingFor {
+                        follow_up,
+                        ..waiting_for.clone()
+                    }),
+                ))
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Ok(WaitingForChoice::ChangePerson) => {
+            start_waiting_for(record_id, base_data, send_to_data_storage_layer).await
+        }
+        Ok(WaitingForChoice::Received) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::FinishItem {
+                    item: record_id,
+                    when_finished: Utc::now().into(),
+                })
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Ok(WaitingForChoice::NoLongerWaiting) => {
+            send_to_data_storage_layer
+                .send(DataLayerCommands::UpdateWaitingFor(record_id, None))
+                .await
+                .unwrap();
+            Ok(())
+        }
+        Err(InquireError::OperationCanceled) => Ok(()),
+        Err(InquireError::OperationInterrupted) => Err(()),
+        Err(err) => panic!("Unexpected error, try restarting the terminal: {}", err),
+    }
+}
@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
 use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
 
-#[derive(Builder, Clone, Debug)]
+#[derive(Builder, Clone, Debug, Serialize, Deserialize)]
 #[builder(setter(into))]
 pub(crate) struct NewEvent {
     pub(crate) summary: String,
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
 use surrealdb::sql::Datetime;
 
 use crate::{
@@ -10,7 +11,7 @@ use crate::{
     new_event::NewEvent,
 };
 
-#[derive(Builder, Clone, Debug)]
+#[derive(Builder, Clone, Debug, Serialize, Deserialize)]
 #[builder(setter(into))]
 pub(crate) struct NewItem {
     pub(crate) summary: String,
@@ -44,10 +45,13 @@ pub(crate) struct NewItem {
 
     #[builder(default)]
     pub(crate) review_guidance: Option<SurrealReviewGuidance>,
+
+    #[builder(default)]
+    pub(crate) tags: Vec<String>,
 }
 
 /// This type exists because it is possible to add a new event to a new item meaning that both need to be created at the same time.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum NewDependency {
     /// Dependency already exists in the database.
     Existing(SurrealDependency),
@@ -69,6 +73,15 @@ impl NewItem {
             last_reviewed: None,
             review_frequency: None,
             review_guidance: None,
+            tags: Vec::default(),
         }
     }
 }
+
+/// A new item together with the new items under it, in priority order, so a whole tree can be created
+/// at once, for example from an imported outline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct NewItemTree {
+    pub(crate) item: NewItem,
+    pub(crate) smaller_items: Vec<NewItemTree>,
+}
@@ -1,7 +1,8 @@
 use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
 use surrealdb::RecordId;
 
-#[derive(Builder)]
+#[derive(Builder, Serialize, Deserialize)]
 #[builder(setter(into))]
 pub(crate) struct NewMode {
     pub(crate) name: String,
@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use crate::data_storage::surrealdb_layer::{
     surreal_in_the_moment_priority::SurrealAction, surreal_item::SurrealUrgency,
     surreal_time_spent::SurrealWhyInScope,
 };
 
+#[derive(Serialize, Deserialize)]
 pub(crate) struct NewTimeSpent {
     pub(crate) working_on: Vec<SurrealAction>,
     pub(crate) urgency: Option<SurrealUrgency>,
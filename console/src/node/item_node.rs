@@ -26,6 +26,7 @@ pub(crate) struct ItemNode<'s> {
     dependencies: Vec<DependencyWithItem<'s>>,
     urgency_plan: Option<UrgencyPlanWithItem<'s>>,
     urgent_action_items: Vec<ActionWithItem<'s>>,
+    waiting_on: Option<&'s Item<'s>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -281,6 +282,9 @@ impl<'s> ItemNode<'s> {
             //Perf Improvement: Finished items should not have any urgent action items
             Vec::default()
         };
+        let waiting_on = item
+            .get_waiting_for()
+            .and_then(|x| all_items.get(&x.person));
         ItemNode {
             item,
             parents,
@@ -288,6 +292,7 @@ impl<'s> ItemNode<'s> {
             dependencies,
             urgency_plan,
             urgent_action_items,
+            waiting_on,
         }
     }
 
@@ -435,6 +440,11 @@ impl<'s> ItemNode<'s> {
     pub(crate) fn get_urgent_action_items(&'s self) -> &'s Vec<ActionWithItem<'s>> {
         &self.urgent_action_items
     }
+
+    /// The person or group this item is waiting on
+    pub(crate) fn get_waiting_on(&self) -> Option<&'s Item<'s>> {
+        self.waiting_on
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
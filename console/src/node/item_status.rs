@@ -365,6 +365,10 @@ impl<'s> ItemStatus<'s> {
         self.item_node.get_summary()
     }
 
+    pub(crate) fn get_waiting_on(&self) -> Option<&'s Item<'s>> {
+        self.item_node.get_waiting_on()
+    }
+
     pub(crate) fn has_children(&self, filter: Filter) -> bool {
         self.item_node.has_children(filter)
     }
@@ -6,5 +6,14 @@
 //! * Reflection
 //! * Recall
 
+pub(crate) mod celebration;
 pub(crate) mod do_now_list;
+pub(crate) mod inbox;
+pub(crate) mod item_query;
+pub(crate) mod neglected;
+pub(crate) mod outline;
+pub(crate) mod person_agenda;
+pub(crate) mod priority_ratings;
+pub(crate) mod smart_lists;
 pub(crate) mod upcoming;
+pub(crate) mod waiting_for;
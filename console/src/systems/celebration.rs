@@ -0,0 +1,444 @@
+use ahash::{HashMap, HashSet};
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, Utc};
+use surrealdb::RecordId;
+
+use crate::{
+    base_data::{item::Item, time_spent::TimeSpent},
+    node::{Filter, item_status::ItemStatus},
+};
+
+/// Items tagged with this are habits and each one gets its own streak
+pub(crate) const HABIT_TAG: &str = "habit";
+
+pub(crate) const GOALS_FINISHED_MILESTONES: [u64; 9] = [1, 5, 10, 25, 50, 100, 250, 500, 1000];
+
+pub(crate) const HOURS_INVESTED_MILESTONES: [u64; 11] =
+    [1, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// The biggest milestone that `value` has reached, if any, and the next one to aim for
+pub(crate) fn milestones(value: u64, milestones: &[u64]) -> (Option<u64>, Option<u64>) {
+    let reached = milestones.iter().rev().find(|x| **x <= value).copied();
+    let next = milestones.iter().find(|x| **x > value).copied();
+    (reached, next)
+}
+
+/// Every item the time spent counts towards. Like for neglected items working on an item counts
+/// towards that item and everything above it in the current tree.
+fn credited_items<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_spent: &TimeSpent<'_>,
+) -> HashSet<&'s RecordId> {
+    time_spent
+        .worked_towards()
+        .iter()
+        .filter_map(|x| items_status.get(x))
+        .flat_map(|x| x.get_self_and_parents_flattened(Filter::All))
+        .map(|x| x.get_surreal_record_id())
+        .collect()
+}
+
+/// Total time invested in each item from the time spent that stopped before `until`
+pub(crate) fn time_invested<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_spent_log: &[TimeSpent<'_>],
+    until: &DateTime<Utc>,
+) -> HashMap<&'s RecordId, TimeDelta> {
+    let mut result: HashMap<&RecordId, TimeDelta> = HashMap::default();
+    for time_spent in time_spent_log
+        .iter()
+        .filter(|x| x.get_stopped_at() <= until)
+    {
+        for record_id in credited_items(items_status, time_spent) {
+            *result.entry(record_id).or_default() += time_spent.get_time_delta();
+        }
+    }
+    result
+}
+
+/// Active motivations at the top of a tree that have had time invested in them, most invested first
+pub(crate) fn time_invested_by_top_motivation<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_invested: &HashMap<&'s RecordId, TimeDelta>,
+) -> Vec<(&'s Item<'s>, TimeDelta)> {
+    let mut result = items_status
+        .values()
+        .filter(|x| x.is_active() && x.is_type_motivation() && !x.has_parents(Filter::Active))
+        .filter_map(|x| {
+            let invested = time_invested.get(x.get_surreal_record_id())?;
+            Some((x.get_item(), *invested))
+        })
+        .collect::<Vec<_>>();
+    result.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| a.0.get_summary().cmp(b.0.get_summary()))
+    });
+    result
+}
+
+/// The local days on which time was spent towards any item that `counts` accepts
+pub(crate) fn days_worked_towards<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_spent_log: &[TimeSpent<'_>],
+    counts: impl Fn(&Item<'_>) -> bool,
+) -> HashSet<NaiveDate> {
+    time_spent_log
+        .iter()
+        .filter(|time_spent| {
+            credited_items(items_status, time_spent)
+                .into_iter()
+                .filter_map(|x| items_status.get(x))
+                .any(|x| counts(x.get_item()))
+        })
+        .map(|x| x.get_started_at().with_timezone(&Local).date_naive())
+        .collect()
+}
+
+/// Consecutive days up to and including today. Today is not over yet so a streak that ran through
+/// yesterday is still going.
+pub(crate) fn current_streak(days: &HashSet<NaiveDate>, today: NaiveDate) -> u32 {
+    let mut day = if days.contains(&today) {
+        Some(today)
+    } else {
+        today.pred_opt()
+    };
+    let mut streak = 0;
+    while let Some(counted) = day.filter(|x| days.contains(x)) {
+        streak += 1;
+        day = counted.pred_opt();
+    }
+    streak
+}
+
+pub(crate) fn longest_streak(days: &HashSet<NaiveDate>) -> u32 {
+    days.iter()
+        .filter(|x| x.pred_opt().is_none_or(|x| !days.contains(&x)))
+        .map(|first| {
+            let mut streak = 0;
+            let mut day = Some(*first);
+            while let Some(counted) = day.filter(|x| days.contains(x)) {
+                streak += 1;
+                day = counted.succ_opt();
+            }
+            streak
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+pub(crate) struct HabitStreak<'s> {
+    habit: &'s Item<'s>,
+    current: u32,
+    longest: u32,
+}
+
+impl<'s> HabitStreak<'s> {
+    pub(crate) fn get_habit(&self) -> &'s Item<'s> {
+        self.habit
+    }
+
+    pub(crate) fn get_current(&self) -> u32 {
+        self.current
+    }
+
+    pub(crate) fn get_longest(&self) -> u32 {
+        self.longest
+    }
+}
+
+/// One streak for each active item tagged as a habit, the longest running streaks first
+pub(crate) fn habit_streaks<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_spent_log: &[TimeSpent<'_>],
+    today: NaiveDate,
+) -> Vec<HabitStreak<'s>> {
+    let mut result = items_status
+        .values()
+        .filter(|x| x.is_active() && x.get_item().has_tag(HABIT_TAG))
+        .map(|x| {
+            let habit = x.get_item();
+            let days = days_worked_towards(items_status, time_spent_log, |item| {
+                item.get_surreal_record_id() == habit.get_surreal_record_id()
+            });
+            HabitStreak {
+                habit,
+                current: current_streak(&days, today),
+                longest: longest_streak(&days),
+            }
+        })
+        .collect::<Vec<_>>();
+    result.sort_by(|a, b| {
+        b.current
+            .cmp(&a.current)
+            .then_with(|| a.habit.get_summary().cmp(b.habit.get_summary()))
+    });
+    result
+}
+
+/// A finished item and how big of an accomplishment it was
+pub(crate) struct RecapItem<'s> {
+    item: &'s Item<'s>,
+    subtree_size: usize,
+    time_invested: TimeDelta,
+}
+
+impl<'s> RecapItem<'s> {
+    pub(crate) fn get_item(&self) -> &'s Item<'s> {
+        self.item
+    }
+
+    /// The item itself plus everything under it
+    pub(crate) fn get_subtree_size(&self) -> usize {
+        self.subtree_size
+    }
+
+    pub(crate) fn get_time_invested(&self) -> TimeDelta {
+        self.time_invested
+    }
+
+    /// The subtree size scaled up by the hours invested, so a goal that took many steps and a lot of
+    /// time outweighs a quick action
+    pub(crate) fn get_weight(&self) -> f64 {
+        let hours = self.time_invested.num_minutes() as f64 / 60.0;
+        self.subtree_size as f64 * (1.0 + hours.max(0.0))
+    }
+}
+
+fn subtree_size<'s>(
+    item: &'s Item<'s>,
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    counted: &mut HashSet<&'s RecordId>,
+) -> usize {
+    if !counted.insert(item.get_surreal_record_id()) {
+        return 0;
+    }
+    1 + item
+        .get_children()
+        .filter_map(|x| items_status.get(x))
+        .map(|x| subtree_size(x.get_item(), items_status, counted))
+        .sum::<usize>()
+}
+
+/// Items finished since `since`, the biggest accomplishments first
+pub(crate) fn finished_recap<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_invested: &HashMap<&'s RecordId, TimeDelta>,
+    since: &DateTime<Utc>,
+) -> Vec<RecapItem<'s>> {
+    let mut result = items_status
+        .values()
+        .map(|x| x.get_item())
+        .filter(|x| !x.is_person_or_group())
+        .filter(|x| x.get_finished_at().is_some_and(|x| x >= *since))
+        .map(|item| RecapItem {
+            item,
+            subtree_size: subtree_size(item, items_status, &mut HashSet::default()),
+            time_invested: time_invested
+                .get(item.get_surreal_record_id())
+                .copied()
+                .unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    result.sort_by(|a, b| {
+        b.get_weight()
+            .total_cmp(&a.get_weight())
+            .then_with(|| a.item.get_summary().cmp(b.item.get_summary()))
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::HashSet;
+    use chrono::{DateTime, Local, NaiveDate, TimeDelta, Utc};
+    use surrealdb::RecordId;
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_in_the_moment_priority::SurrealAction,
+            surreal_item::{
+                SurrealItemBuilder, SurrealItemType, SurrealMotivationKind, SurrealOrderedSubItem,
+            },
+            surreal_tables::SurrealTablesBuilder,
+            surreal_time_spent::SurrealTimeSpent,
+        },
+    };
+
+    use super::{
+        GOALS_FINISHED_MILESTONES, current_streak, days_worked_towards, finished_recap,
+        habit_streaks, longest_streak, milestones, time_invested, time_invested_by_top_motivation,
+    };
+
+    #[test]
+    fn milestones_are_the_biggest_reached_and_the_next_to_aim_for() {
+        assert_eq!(milestones(0, &GOALS_FINISHED_MILESTONES), (None, Some(1)));
+        assert_eq!(
+            milestones(10, &GOALS_FINISHED_MILESTONES),
+            (Some(10), Some(25))
+        );
+        assert_eq!(
+            milestones(2000, &GOALS_FINISHED_MILESTONES),
+            (Some(1000), None)
+        );
+    }
+
+    #[test]
+    fn a_streak_is_still_going_when_today_has_nothing_logged_yet() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+        let days = [1, 2, 3, 5, 6].into_iter().map(day).collect::<HashSet<_>>();
+
+        assert_eq!(current_streak(&days, day(6)), 2);
+        assert_eq!(current_streak(&days, day(7)), 2);
+        assert_eq!(current_streak(&days, day(8)), 0);
+        assert_eq!(longest_streak(&days), 3);
+        assert_eq!(longest_streak(&HashSet::default()), 0);
+    }
+
+    fn time_spent(item: &str, when_started: DateTime<Utc>, minutes: i64) -> SurrealTimeSpent {
+        SurrealTimeSpent {
+            id: None,
+            version: 1,
+            working_on: vec![SurrealAction::MakeProgress(("item", item).into())],
+            why_in_scope: vec![],
+            urgency: None,
+            when_started: when_started.into(),
+            when_stopped: (when_started + TimeDelta::minutes(minutes)).into(),
+            dedication: None,
+        }
+    }
+
+    #[test]
+    fn time_counts_towards_parents_and_bigger_finished_items_come_first_in_the_recap() {
+        let now = Utc::now();
+        let long_ago = now - TimeDelta::days(60);
+        let yesterday_noon = (Local::now().date_naive() - TimeDelta::days(1))
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc);
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "motivation").into()))
+                    .summary("Grow the business")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::CoreWork))
+                    .created(long_ago)
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "goal").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "quick").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "goal").into()))
+                    .summary("Launch the website")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .created(long_ago)
+                    .finished(Some((now - TimeDelta::days(2)).into()))
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "design").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "write").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "design").into()))
+                    .summary("Design the pages")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .finished(Some((now - TimeDelta::days(20)).into()))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "write").into()))
+                    .summary("Write the copy")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .finished(Some((now - TimeDelta::days(3)).into()))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "quick").into()))
+                    .summary("Reply to the accountant")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .finished(Some((now - TimeDelta::days(1)).into()))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "stretch").into()))
+                    .summary("Stretch")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .tags(Some(vec!["Habit".to_string()]))
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_time_spent_log(vec![
+                time_spent("design", now - TimeDelta::days(21), 120),
+                time_spent("write", now - TimeDelta::days(4), 60),
+                time_spent("quick", now - TimeDelta::days(1), 10),
+                time_spent("stretch", yesterday_noon, 15),
+                time_spent("stretch", yesterday_noon - TimeDelta::days(1), 15),
+                time_spent("stretch", yesterday_noon - TimeDelta::days(3), 15),
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+        let items_status = calculated_data.get_items_status();
+        let time_spent_log = calculated_data.get_base_data().get_time_spent_log();
+
+        let invested = time_invested(items_status, time_spent_log, &now);
+        let by_motivation = time_invested_by_top_motivation(items_status, &invested);
+        assert_eq!(by_motivation.len(), 1);
+        assert_eq!(by_motivation[0].0.get_summary(), "Grow the business");
+        assert_eq!(by_motivation[0].1, TimeDelta::minutes(190));
+
+        let before_this_week =
+            time_invested(items_status, time_spent_log, &(now - TimeDelta::days(7)));
+        let goal: RecordId = ("item", "goal").into();
+        assert_eq!(
+            before_this_week.get(&goal).copied(),
+            Some(TimeDelta::minutes(120))
+        );
+
+        let recap = finished_recap(items_status, &invested, &(now - TimeDelta::days(7)));
+        let summaries = recap
+            .iter()
+            .map(|x| x.get_item().get_summary())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summaries,
+            vec![
+                "Launch the website",
+                "Write the copy",
+                "Reply to the accountant"
+            ]
+        );
+        assert_eq!(recap[0].get_subtree_size(), 3);
+        assert_eq!(recap[0].get_time_invested(), TimeDelta::minutes(180));
+
+        let core_days = days_worked_towards(items_status, time_spent_log, |x| {
+            x.is_type_motivation_kind_core()
+        });
+        assert_eq!(core_days.len(), 3);
+
+        let habits = habit_streaks(items_status, time_spent_log, Local::now().date_naive());
+        assert_eq!(habits.len(), 1);
+        assert_eq!(habits[0].get_habit().get_summary(), "Stretch");
+        assert_eq!(habits[0].get_current(), 2);
+        assert_eq!(habits[0].get_longest(), 2);
+    }
+}
@@ -0,0 +1,139 @@
+use ahash::HashMap;
+use surrealdb::RecordId;
+
+use crate::{
+    data_storage::surrealdb_layer::surreal_item::SurrealItemType,
+    node::{Filter, item_status::ItemStatus},
+};
+
+/// An item still needs processing when it has not been given a type, or when it is an action or a
+/// goal that is not under anything yet so there is no reason recorded for doing it.
+fn is_in_inbox(item_status: &ItemStatus<'_>) -> bool {
+    if !item_status.is_active() {
+        return false;
+    }
+    match item_status.get_item().get_item_type() {
+        SurrealItemType::Undeclared => true,
+        SurrealItemType::Action | SurrealItemType::Goal(_) => {
+            !item_status.has_parents(Filter::Active)
+        }
+        SurrealItemType::IdeaOrThought
+        | SurrealItemType::Motivation(_)
+        | SurrealItemType::PersonOrGroup => false,
+    }
+}
+
+/// The items to process, oldest capture first so the inbox is worked through in the order things
+/// were captured.
+pub(crate) fn find_inbox_items<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+) -> Vec<&'s ItemStatus<'s>> {
+    let mut items = items_status
+        .values()
+        .filter(|x| is_in_inbox(x))
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| {
+        a.get_created()
+            .cmp(b.get_created())
+            .then_with(|| a.get_summary().cmp(b.get_summary()))
+    });
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{
+                SurrealItemBuilder, SurrealItemType, SurrealMotivationKind, SurrealOrderedSubItem,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::find_inbox_items;
+
+    #[test]
+    fn inbox_has_undeclared_and_parentless_items_oldest_first() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "motivation").into()))
+                    .summary("Keep the house running")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::NotSet))
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "under").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "undeclared_under").into(),
+                        },
+                    ])
+                    .created((now - TimeDelta::days(30)).into())
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "under").into()))
+                    .summary("Fix the leak")
+                    .item_type(SurrealItemType::Action)
+                    .created((now - TimeDelta::days(20)).into())
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "undeclared_under").into()))
+                    .summary("Something about the gutters")
+                    .created((now - TimeDelta::days(1)).into())
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "undeclared").into()))
+                    .summary("Random thought")
+                    .created((now - TimeDelta::days(2)).into())
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "parentless").into()))
+                    .summary("Call the plumber")
+                    .item_type(SurrealItemType::Action)
+                    .created((now - TimeDelta::days(10)).into())
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "idea").into()))
+                    .summary("Learn to juggle")
+                    .item_type(SurrealItemType::IdeaOrThought)
+                    .created((now - TimeDelta::days(5)).into())
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "finished").into()))
+                    .summary("Buy milk")
+                    .finished(Some(now.into()))
+                    .created((now - TimeDelta::days(15)).into())
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let summaries = find_inbox_items(calculated_data.get_items_status())
+            .iter()
+            .map(|x| x.get_summary())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summaries,
+            vec![
+                "Call the plumber",
+                "Random thought",
+                "Something about the gutters"
+            ]
+        );
+    }
+}
@@ -0,0 +1,689 @@
+//! A small filter language for items, used by the search menu, smart lists, and the `--list` and
+//! `--export` command line options. A few examples:
+//! * `type:action ready` ready actions
+//! * `urgency>=definitely` anything at least definitely urgent
+//! * `under:"Health" created<30d` items under a "Health" item created within the last 30 days
+//! * `finished:last-week` items finished last week
+//! * `text:~"invoice" or #errand` summary or notes mention invoice, or the item is tagged errand
+//!
+//! Terms next to each other must all match, `or` matches either side, `not` or a leading `-` negates
+//! the next term, and parentheses group terms. A word that is not a field is searched for in the
+//! summary.
+
+mod parser;
+
+use ahash::HashMap;
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeDelta, TimeZone, Utc};
+use surrealdb::RecordId;
+
+use crate::{
+    data_storage::surrealdb_layer::surreal_smart_list::{
+        SurrealSmartListItemType, SurrealSmartListUrgency,
+    },
+    node::{Filter, item_status::ItemStatus},
+};
+
+pub(crate) use parser::{parse_item_query, parse_item_type, parse_urgency};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ItemQuery {
+    /// The empty query, matches everything
+    All,
+    Predicate(ItemPredicate),
+    Not(Box<ItemQuery>),
+    And(Vec<ItemQuery>),
+    Or(Vec<ItemQuery>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ItemPredicate {
+    Type(SurrealSmartListItemType),
+    /// Active and not waiting on anything
+    Ready,
+    Active,
+    Finished,
+    Urgency(Comparison, SurrealSmartListUrgency),
+    /// One of the items this item is under has a summary containing this text
+    Under(String),
+    Tag(String),
+    Created(TimeFilter),
+    FinishedAt(TimeFilter),
+    /// The summary or the notes contain this text
+    TextContains(String),
+    SummaryContains(String),
+    SummaryIs(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Comparison {
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    fn compare<T: Ord>(&self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Equal => left == right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TimeFilter {
+    /// How long ago it happened, so `created<30d` is anything created within the last 30 days. With
+    /// `:` or `=` it means within that long ago.
+    Age(Comparison, TimeDelta),
+    /// Compared against the local calendar date, so `created>2025-01-31` starts in February
+    Date(Comparison, NaiveDate),
+    Range(NamedRange),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NamedRange {
+    Today,
+    Yesterday,
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+    LastMonth,
+}
+
+impl NamedRange {
+    /// The start (inclusive) and end (exclusive) of the range in the given time zone, weeks start on
+    /// Monday.
+    fn get_start_and_end<Tz: TimeZone>(
+        &self,
+        now: &DateTime<Utc>,
+        time_zone: &Tz,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let today = now.with_timezone(time_zone).date_naive();
+        let start_of_week = today - TimeDelta::days(today.weekday().num_days_from_monday().into());
+        let start_of_month = today.with_day(1).expect("Every month has a first day");
+        let (start, end) = match self {
+            NamedRange::Today => (today, today + TimeDelta::days(1)),
+            NamedRange::Yesterday => (today - TimeDelta::days(1), today),
+            NamedRange::ThisWeek => (start_of_week, start_of_week + TimeDelta::weeks(1)),
+            NamedRange::LastWeek => (start_of_week - TimeDelta::weeks(1), start_of_week),
+            NamedRange::ThisMonth => (
+                start_of_month,
+                start_of_month
+                    .checked_add_months(chrono::Months::new(1))
+                    .expect("Not near the end of time"),
+            ),
+            NamedRange::LastMonth => (
+                start_of_month
+                    .checked_sub_months(chrono::Months::new(1))
+                    .expect("Not near the start of time"),
+                start_of_month,
+            ),
+        };
+        (start_of_day(start, time_zone), start_of_day(end, time_zone))
+    }
+}
+
+fn start_of_day<Tz: TimeZone>(date: NaiveDate, time_zone: &Tz) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).expect("Midnight is a valid time");
+    time_zone
+        .from_local_datetime(&midnight)
+        .earliest()
+        //A daylight saving change at midnight skips midnight, an hour later is close enough
+        .unwrap_or_else(|| {
+            time_zone
+                .from_local_datetime(&(midnight + TimeDelta::hours(1)))
+                .earliest()
+                .expect("An hour after a skipped midnight exists")
+        })
+        .with_timezone(&Utc)
+}
+
+impl TimeFilter {
+    fn matches<Tz: TimeZone>(
+        &self,
+        when: &DateTime<Utc>,
+        now: &DateTime<Utc>,
+        time_zone: &Tz,
+    ) -> bool {
+        match self {
+            TimeFilter::Age(Comparison::Equal, age) => *now - *when <= *age,
+            TimeFilter::Age(comparison, age) => comparison.compare(*now - *when, *age),
+            TimeFilter::Date(comparison, date) => {
+                comparison.compare(when.with_timezone(time_zone).date_naive(), *date)
+            }
+            TimeFilter::Range(range) => {
+                let (start, end) = range.get_start_and_end(now, time_zone);
+                start <= *when && *when < end
+            }
+        }
+    }
+}
+
+/// The name used for the type in a query, for example `type:action`
+pub(crate) fn item_type_query_name(item_type: &SurrealSmartListItemType) -> &'static str {
+    match item_type {
+        SurrealSmartListItemType::Undeclared => "undeclared",
+        SurrealSmartListItemType::Action => "action",
+        SurrealSmartListItemType::Goal => "goal",
+        SurrealSmartListItemType::IdeaOrThought => "idea",
+        SurrealSmartListItemType::Motivation => "motivation",
+        SurrealSmartListItemType::PersonOrGroup => "person",
+    }
+}
+
+/// The name used for the urgency in a query, for example `urgency:definitely`
+pub(crate) fn urgency_query_name(urgency: &SurrealSmartListUrgency) -> &'static str {
+    match urgency {
+        SurrealSmartListUrgency::InTheModeByImportance => "importance",
+        SurrealSmartListUrgency::InTheModeMaybeUrgent => "maybe",
+        SurrealSmartListUrgency::InTheModeDefinitelyUrgent => "definitely",
+        SurrealSmartListUrgency::InTheModeScheduled => "scheduled",
+        SurrealSmartListUrgency::MoreUrgentThanMode => "more-than-mode",
+        SurrealSmartListUrgency::ScheduledAnyMode => "scheduled-any-mode",
+        SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled => "top",
+    }
+}
+
+/// Higher is more urgent, matching the order things show up in the Do Now list
+fn urgency_rank(urgency: &SurrealSmartListUrgency) -> u8 {
+    match urgency {
+        SurrealSmartListUrgency::InTheModeByImportance => 0,
+        SurrealSmartListUrgency::InTheModeMaybeUrgent => 1,
+        SurrealSmartListUrgency::InTheModeDefinitelyUrgent => 2,
+        SurrealSmartListUrgency::InTheModeScheduled => 3,
+        SurrealSmartListUrgency::MoreUrgentThanMode => 4,
+        SurrealSmartListUrgency::ScheduledAnyMode => 5,
+        SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled => 6,
+    }
+}
+
+fn contains_ignore_case(text: &str, search_for: &str) -> bool {
+    text.to_lowercase().contains(&search_for.to_lowercase())
+}
+
+impl ItemPredicate {
+    fn matches<'s, Tz: TimeZone>(&self, item_status: &'s ItemStatus<'s>, time_zone: &Tz) -> bool {
+        let item = item_status.get_item();
+        match self {
+            ItemPredicate::Type(item_type) => {
+                SurrealSmartListItemType::from(item.get_item_type()) == *item_type
+            }
+            ItemPredicate::Ready => {
+                item_status.is_active() && !item_status.has_dependencies(Filter::Active)
+            }
+            ItemPredicate::Active => item_status.is_active(),
+            ItemPredicate::Finished => item_status.is_finished(),
+            ItemPredicate::Urgency(comparison, urgency) => {
+                let urgency_now = match item_status.get_urgency_now() {
+                    Some(urgency_now) => SurrealSmartListUrgency::from(urgency_now),
+                    //Items without an urgency plan are worked on by importance in the Do Now list
+                    None => SurrealSmartListUrgency::InTheModeByImportance,
+                };
+                comparison.compare(urgency_rank(&urgency_now), urgency_rank(urgency))
+            }
+            ItemPredicate::Under(text) => item_status
+                .get_self_and_parents_flattened(Filter::All)
+                .iter()
+                .any(|x| *x != item && contains_ignore_case(x.get_summary(), text)),
+            ItemPredicate::Tag(tag) => item.has_tag(tag),
+            ItemPredicate::Created(time_filter) => {
+                time_filter.matches(item.get_created(), item_status.get_now(), time_zone)
+            }
+            ItemPredicate::FinishedAt(time_filter) => match item_status.get_finished_at() {
+                Some(finished_at) => {
+                    time_filter.matches(finished_at, item_status.get_now(), time_zone)
+                }
+                None => false,
+            },
+            ItemPredicate::TextContains(text) => {
+                contains_ignore_case(item.get_summary(), text) || item.notes_contain(text)
+            }
+            ItemPredicate::SummaryContains(text) => contains_ignore_case(item.get_summary(), text),
+            ItemPredicate::SummaryIs(text) => item.get_summary().trim().eq_ignore_ascii_case(text),
+        }
+    }
+
+    fn is_about_finished_items(&self) -> bool {
+        matches!(self, ItemPredicate::Finished | ItemPredicate::FinishedAt(_))
+    }
+}
+
+impl ItemQuery {
+    /// Named ranges like `today` or `last-week` use the local time zone.
+    pub(crate) fn matches<'s>(&self, item_status: &'s ItemStatus<'s>) -> bool {
+        self.matches_in_time_zone(item_status, &Local)
+    }
+
+    fn matches_in_time_zone<'s, Tz: TimeZone>(
+        &self,
+        item_status: &'s ItemStatus<'s>,
+        time_zone: &Tz,
+    ) -> bool {
+        match self {
+            ItemQuery::All => true,
+            ItemQuery::Predicate(predicate) => predicate.matches(item_status, time_zone),
+            ItemQuery::Not(query) => !query.matches_in_time_zone(item_status, time_zone),
+            ItemQuery::And(queries) => queries
+                .iter()
+                .all(|x| x.matches_in_time_zone(item_status, time_zone)),
+            ItemQuery::Or(queries) => queries
+                .iter()
+                .any(|x| x.matches_in_time_zone(item_status, time_zone)),
+        }
+    }
+
+    /// Finished items are only searched when the query asks about them, otherwise every query
+    /// would need to say `active`.
+    pub(crate) fn is_about_finished_items(&self) -> bool {
+        match self {
+            ItemQuery::All => false,
+            ItemQuery::Predicate(predicate) => predicate.is_about_finished_items(),
+            ItemQuery::Not(query) => query.is_about_finished_items(),
+            ItemQuery::And(queries) | ItemQuery::Or(queries) => {
+                queries.iter().any(|x| x.is_about_finished_items())
+            }
+        }
+    }
+}
+
+/// The items matching the query sorted by summary. Unless the query asks about finished items only
+/// active items are included.
+pub(crate) fn find_items_matching_query<'s>(
+    query: &ItemQuery,
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+) -> Vec<&'s ItemStatus<'s>> {
+    find_items_matching_query_in_time_zone(query, items_status, &Local)
+}
+
+fn find_items_matching_query_in_time_zone<'s, Tz: TimeZone>(
+    query: &ItemQuery,
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_zone: &Tz,
+) -> Vec<&'s ItemStatus<'s>> {
+    let include_finished = query.is_about_finished_items();
+    let mut items = items_status
+        .values()
+        .filter(|x| {
+            (include_finished || x.is_active()) && query.matches_in_time_zone(*x, time_zone)
+        })
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| a.get_summary().cmp(b.get_summary()));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{
+                NotesLocation, SurrealDependency, SurrealItemBuilder, SurrealItemType,
+                SurrealMotivationKind, SurrealOrderedSubItem, SurrealUrgency, SurrealUrgencyPlan,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{NamedRange, find_items_matching_query_in_time_zone, parse_item_query};
+
+    /// A Wednesday, so this week started on Monday 2025-06-09
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-11T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn calculated_data() -> CalculatedData {
+        let now = now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "health").into()))
+                    .summary("Health")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::NotSet))
+                    .created((now - TimeDelta::days(400)).into())
+                    .smaller_items_in_priority_order(vec![SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: ("item", "run").into(),
+                    }])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "run").into()))
+                    .summary("Train for the 10k")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .created((now - TimeDelta::days(40)).into())
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "shoes").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "stretch").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "shoes").into()))
+                    .summary("Buy running shoes")
+                    .item_type(SurrealItemType::Action)
+                    .created((now - TimeDelta::days(3)).into())
+                    .urgency_plan(Some(SurrealUrgencyPlan::StaysTheSame(
+                        SurrealUrgency::InTheModeDefinitelyUrgent,
+                    )))
+                    .tags(Some(vec!["errand".to_string()]))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "stretch").into()))
+                    .summary("Stretch")
+                    .item_type(SurrealItemType::Action)
+                    .created((now - TimeDelta::days(3)).into())
+                    .dependencies(vec![SurrealDependency::AfterDateTime(
+                        (now + TimeDelta::days(1)).into(),
+                    )])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "bill").into()))
+                    .summary("Send the March bill")
+                    .item_type(SurrealItemType::Action)
+                    .created((now - TimeDelta::days(60)).into())
+                    .urgency_plan(Some(SurrealUrgencyPlan::StaysTheSame(
+                        SurrealUrgency::MoreUrgentThanMode,
+                    )))
+                    .notes_location(NotesLocation::Markdown(
+                        "Attach the invoice PDF".to_string(),
+                    ))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "banjo").into()))
+                    .summary("Learn the banjo")
+                    .item_type(SurrealItemType::IdeaOrThought)
+                    .created((now - TimeDelta::days(10)).into())
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "taxes").into()))
+                    .summary("File taxes")
+                    .item_type(SurrealItemType::Action)
+                    .created((now - TimeDelta::days(100)).into())
+                    .finished(Some((now - TimeDelta::days(7)).into()))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "plants").into()))
+                    .summary("Water the plants")
+                    .item_type(SurrealItemType::Action)
+                    .created((now - TimeDelta::days(5)).into())
+                    .finished(Some((now - TimeDelta::hours(2)).into()))
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        CalculatedData::new_from_base_data(BaseData::new_from_surreal_tables(surreal_tables, now))
+    }
+
+    /// Evaluated in UTC so named ranges like `today` do not depend on where the tests run
+    fn summaries(calculated_data: &CalculatedData, query: &str) -> Vec<String> {
+        let query = parse_item_query(query).unwrap();
+        find_items_matching_query_in_time_zone(&query, calculated_data.get_items_status(), &Utc)
+            .iter()
+            .map(|x| x.get_summary().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn type_predicate() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "type:action"),
+            vec!["Buy running shoes", "Send the March bill", "Stretch"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:goal"),
+            vec!["Train for the 10k"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:project"),
+            vec!["Train for the 10k"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:idea"),
+            vec!["Learn the banjo"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:motivation"),
+            vec!["Health"]
+        );
+    }
+
+    #[test]
+    fn ready_predicate() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "type:action ready"),
+            vec!["Buy running shoes", "Send the March bill"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:action not ready"),
+            vec!["Stretch"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:action -ready"),
+            vec!["Stretch"]
+        );
+    }
+
+    #[test]
+    fn urgency_predicate() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "urgency>=definitely"),
+            vec!["Buy running shoes", "Send the March bill"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "urgency>definitely"),
+            vec!["Send the March bill"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "urgency:definitely"),
+            vec!["Buy running shoes"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "urgency=more-than-mode"),
+            vec!["Send the March bill"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:action urgency<=maybe"),
+            vec!["Stretch"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "type:action urgency:importance"),
+            vec!["Stretch"]
+        );
+    }
+
+    #[test]
+    fn under_predicate_matches_any_parent_but_not_the_item_itself() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "under:\"health\""),
+            vec!["Buy running shoes", "Stretch", "Train for the 10k"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "under:\"Train for\""),
+            vec!["Buy running shoes", "Stretch"]
+        );
+        assert!(summaries(&calculated_data, "under:banjo").is_empty());
+    }
+
+    #[test]
+    fn tag_predicate() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "tag:errand"),
+            vec!["Buy running shoes"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "tag:#Errand"),
+            vec!["Buy running shoes"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "#errand"),
+            vec!["Buy running shoes"]
+        );
+        assert!(summaries(&calculated_data, "tag:phone").is_empty());
+    }
+
+    #[test]
+    fn created_predicate_by_age_and_by_date() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "created<30d"),
+            vec!["Buy running shoes", "Learn the banjo", "Stretch"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "created:1w"),
+            vec!["Buy running shoes", "Stretch"]
+        );
+        assert_eq!(summaries(&calculated_data, "created>100d"), vec!["Health"]);
+        assert_eq!(
+            summaries(&calculated_data, "created>=2025-06-01"),
+            vec!["Buy running shoes", "Learn the banjo", "Stretch"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "created:2025-06-08"),
+            vec!["Buy running shoes", "Stretch"]
+        );
+    }
+
+    #[test]
+    fn finished_predicates_include_finished_items() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "finished"),
+            vec!["File taxes", "Water the plants"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "finished:last-week"),
+            vec!["File taxes"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "finished:today"),
+            vec!["Water the plants"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "finished:this-month"),
+            vec!["File taxes", "Water the plants"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "finished<1d"),
+            vec!["Water the plants"]
+        );
+        assert!(summaries(&calculated_data, "finished:yesterday").is_empty());
+    }
+
+    #[test]
+    fn finished_items_are_left_out_unless_asked_for() {
+        let calculated_data = calculated_data();
+        assert!(!summaries(&calculated_data, "type:action").contains(&"File taxes".to_string()));
+        assert_eq!(
+            summaries(&calculated_data, "type:action created<6d"),
+            vec!["Buy running shoes", "Stretch"]
+        );
+        assert_eq!(
+            summaries(
+                &calculated_data,
+                "type:action created<6d (active or finished)"
+            ),
+            vec!["Buy running shoes", "Stretch", "Water the plants"]
+        );
+    }
+
+    #[test]
+    fn text_predicates() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "text:~\"invoice\""),
+            vec!["Send the March bill"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "text:~march"),
+            vec!["Send the March bill"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "text:\"stretch\""),
+            vec!["Stretch"]
+        );
+        assert!(summaries(&calculated_data, "text:stret").is_empty());
+        assert_eq!(
+            summaries(&calculated_data, "RUNNING"),
+            vec!["Buy running shoes"]
+        );
+        assert!(summaries(&calculated_data, "invoice").is_empty());
+    }
+
+    #[test]
+    fn and_or_not_and_parentheses() {
+        let calculated_data = calculated_data();
+        assert_eq!(
+            summaries(&calculated_data, "type:goal or tag:errand"),
+            vec!["Buy running shoes", "Train for the 10k"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "(type:goal or tag:errand) created<30d"),
+            vec!["Buy running shoes"]
+        );
+        assert_eq!(
+            summaries(&calculated_data, "under:health not (type:goal or #errand)"),
+            vec!["Stretch"]
+        );
+        assert_eq!(summaries(&calculated_data, "").len(), 6);
+    }
+
+    #[test]
+    fn named_ranges_start_on_monday_and_on_the_first_of_the_month() {
+        let date = |y, m, d| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let now = now();
+        assert_eq!(
+            NamedRange::Today.get_start_and_end(&now, &Utc),
+            (date(2025, 6, 11), date(2025, 6, 12))
+        );
+        assert_eq!(
+            NamedRange::Yesterday.get_start_and_end(&now, &Utc),
+            (date(2025, 6, 10), date(2025, 6, 11))
+        );
+        assert_eq!(
+            NamedRange::ThisWeek.get_start_and_end(&now, &Utc),
+            (date(2025, 6, 9), date(2025, 6, 16))
+        );
+        assert_eq!(
+            NamedRange::LastWeek.get_start_and_end(&now, &Utc),
+            (date(2025, 6, 2), date(2025, 6, 9))
+        );
+        assert_eq!(
+            NamedRange::ThisMonth.get_start_and_end(&now, &Utc),
+            (date(2025, 6, 1), date(2025, 7, 1))
+        );
+        assert_eq!(
+            NamedRange::LastMonth.get_start_and_end(&now, &Utc),
+            (date(2025, 5, 1), date(2025, 6, 1))
+        );
+    }
+}
@@ -0,0 +1,533 @@
+use chrono::{NaiveDate, TimeDelta};
+
+use crate::data_storage::surrealdb_layer::surreal_smart_list::{
+    SurrealSmartListItemType, SurrealSmartListUrgency,
+};
+
+use super::{Comparison, ItemPredicate, ItemQuery, NamedRange, TimeFilter};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Operator {
+    Comparison(Comparison),
+    /// `:~` contains
+    Contains,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    OpenParen,
+    CloseParen,
+    Or,
+    Not,
+    /// A word on its own like `ready` or `invoice`, quoted words are always text
+    Word {
+        text: String,
+        quoted: bool,
+    },
+    Field {
+        name: String,
+        operator: Operator,
+        value: String,
+    },
+}
+
+fn read_quoted(chars: &[char], i: &mut usize) -> Result<String, String> {
+    debug_assert_eq!(chars[*i], '"');
+    *i += 1;
+    let start = *i;
+    while *i < chars.len() && chars[*i] != '"' {
+        *i += 1;
+    }
+    if *i == chars.len() {
+        return Err("Missing closing quote".to_string());
+    }
+    let text = chars[start..*i].iter().collect();
+    *i += 1;
+    Ok(text)
+}
+
+fn is_end_of_word(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')'
+}
+
+fn read_operator(chars: &[char], i: &mut usize) -> Option<Operator> {
+    let next = chars.get(*i + 1).copied();
+    let (operator, length) = match (chars.get(*i).copied()?, next) {
+        (':', Some('~')) => (Operator::Contains, 2),
+        ('>', Some('=')) => (Operator::Comparison(Comparison::GreaterOrEqual), 2),
+        ('<', Some('=')) => (Operator::Comparison(Comparison::LessOrEqual), 2),
+        (':' | '=', _) => (Operator::Comparison(Comparison::Equal), 1),
+        ('>', _) => (Operator::Comparison(Comparison::Greater), 1),
+        ('<', _) => (Operator::Comparison(Comparison::Less), 1),
+        _ => return None,
+    };
+    *i += length;
+    Some(operator)
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::default();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::OpenParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::CloseParen);
+            i += 1;
+        } else if c == '-' && chars.get(i + 1).is_some_and(|x| !is_end_of_word(*x)) {
+            tokens.push(Token::Not);
+            i += 1;
+        } else if c == '"' {
+            let text = read_quoted(&chars, &mut i)?;
+            tokens.push(Token::Word { text, quoted: true });
+        } else {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '-') {
+                i += 1;
+            }
+            let name = chars[start..i].iter().collect::<String>();
+            let operator = if name.is_empty() {
+                None
+            } else {
+                read_operator(&chars, &mut i)
+            };
+            match operator {
+                Some(operator) => {
+                    let value = if chars.get(i) == Some(&'"') {
+                        read_quoted(&chars, &mut i)?
+                    } else {
+                        let value_start = i;
+                        while i < chars.len() && !is_end_of_word(chars[i]) {
+                            i += 1;
+                        }
+                        chars[value_start..i].iter().collect()
+                    };
+                    if value.is_empty() {
+                        return Err(format!("Missing a value after '{}'", name));
+                    }
+                    tokens.push(Token::Field {
+                        name: name.to_lowercase(),
+                        operator,
+                        value,
+                    });
+                }
+                None => {
+                    while i < chars.len() && !is_end_of_word(chars[i]) {
+                        i += 1;
+                    }
+                    let text = chars[start..i].iter().collect::<String>();
+                    if text.eq_ignore_ascii_case("or") {
+                        tokens.push(Token::Or);
+                    } else if text.eq_ignore_ascii_case("not") {
+                        tokens.push(Token::Not);
+                    } else if !text.eq_ignore_ascii_case("and") {
+                        tokens.push(Token::Word {
+                            text,
+                            quoted: false,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+pub(crate) fn parse_item_type(value: &str) -> Result<SurrealSmartListItemType, String> {
+    match value.to_lowercase().as_str() {
+        "action" | "step" => Ok(SurrealSmartListItemType::Action),
+        "goal" | "project" => Ok(SurrealSmartListItemType::Goal),
+        "idea" | "thought" => Ok(SurrealSmartListItemType::IdeaOrThought),
+        "motivation" | "purpose" => Ok(SurrealSmartListItemType::Motivation),
+        "person" | "group" => Ok(SurrealSmartListItemType::PersonOrGroup),
+        "undeclared" => Ok(SurrealSmartListItemType::Undeclared),
+        _ => Err(format!(
+            "Unknown type '{}', expected action, goal, idea, motivation, person, or undeclared",
+            value
+        )),
+    }
+}
+
+pub(crate) fn parse_urgency(value: &str) -> Result<SurrealSmartListUrgency, String> {
+    match value.to_lowercase().as_str() {
+        "importance" | "none" => Ok(SurrealSmartListUrgency::InTheModeByImportance),
+        "maybe" => Ok(SurrealSmartListUrgency::InTheModeMaybeUrgent),
+        "definitely" => Ok(SurrealSmartListUrgency::InTheModeDefinitelyUrgent),
+        "scheduled" => Ok(SurrealSmartListUrgency::InTheModeScheduled),
+        "more-than-mode" => Ok(SurrealSmartListUrgency::MoreUrgentThanMode),
+        "scheduled-any-mode" => Ok(SurrealSmartListUrgency::ScheduledAnyMode),
+        "top" => Ok(SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled),
+        _ => Err(format!(
+            "Unknown urgency '{}', expected importance, maybe, definitely, scheduled, more-than-mode, scheduled-any-mode, or top",
+            value
+        )),
+    }
+}
+
+/// A duration like `30d`, `2w`, `12h`, `90m`, or `1y`
+fn parse_age(value: &str) -> Option<TimeDelta> {
+    let unit_start = value.find(|c: char| !c.is_ascii_digit())?;
+    let amount: i64 = value[..unit_start].parse().ok()?;
+    match &value[unit_start..] {
+        "m" | "min" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        "mo" => TimeDelta::try_days(amount.checked_mul(30)?),
+        "y" => TimeDelta::try_days(amount.checked_mul(365)?),
+        _ => None,
+    }
+}
+
+fn parse_time_filter(
+    field: &str,
+    comparison: Comparison,
+    value: &str,
+) -> Result<TimeFilter, String> {
+    let named_range = match value.to_lowercase().as_str() {
+        "today" => Some(NamedRange::Today),
+        "yesterday" => Some(NamedRange::Yesterday),
+        "this-week" => Some(NamedRange::ThisWeek),
+        "last-week" => Some(NamedRange::LastWeek),
+        "this-month" => Some(NamedRange::ThisMonth),
+        "last-month" => Some(NamedRange::LastMonth),
+        _ => None,
+    };
+    if let Some(named_range) = named_range {
+        return if comparison == Comparison::Equal {
+            Ok(TimeFilter::Range(named_range))
+        } else {
+            Err(format!("Use {}:{} rather than comparing", field, value))
+        };
+    }
+    if let Some(age) = parse_age(value) {
+        return Ok(TimeFilter::Age(comparison, age));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(TimeFilter::Date(comparison, date));
+    }
+    Err(format!(
+        "Unknown {} time '{}', expected something like 30d, 2025-01-31, today, or last-week",
+        field, value
+    ))
+}
+
+fn parse_field(name: &str, operator: &Operator, value: &str) -> Result<ItemPredicate, String> {
+    let only_equal = |predicate: ItemPredicate| match operator {
+        Operator::Comparison(Comparison::Equal) => Ok(predicate),
+        _ => Err(format!("Use {}:{} rather than comparing", name, value)),
+    };
+    match (name, operator) {
+        ("type", _) => only_equal(ItemPredicate::Type(parse_item_type(value)?)),
+        ("urgency", Operator::Comparison(comparison)) => {
+            Ok(ItemPredicate::Urgency(*comparison, parse_urgency(value)?))
+        }
+        ("under", _) => only_equal(ItemPredicate::Under(value.to_string())),
+        ("tag", _) => only_equal(ItemPredicate::Tag(
+            value.trim_start_matches('#').to_string(),
+        )),
+        ("created", Operator::Comparison(comparison)) => Ok(ItemPredicate::Created(
+            parse_time_filter(name, *comparison, value)?,
+        )),
+        ("finished", Operator::Comparison(comparison)) => Ok(ItemPredicate::FinishedAt(
+            parse_time_filter(name, *comparison, value)?,
+        )),
+        ("text", Operator::Contains) => Ok(ItemPredicate::TextContains(value.to_string())),
+        ("text", _) => only_equal(ItemPredicate::SummaryIs(value.trim().to_string())),
+        ("urgency" | "created" | "finished", Operator::Contains) => {
+            Err(format!("{}:~ is not supported, use {}:", name, name))
+        }
+        _ => Err(format!(
+            "Unknown field '{}', expected type, urgency, under, tag, created, finished, or text",
+            name
+        )),
+    }
+}
+
+fn parse_word(text: String, quoted: bool) -> ItemPredicate {
+    if quoted {
+        return ItemPredicate::SummaryContains(text);
+    }
+    match text.to_lowercase().as_str() {
+        "ready" => ItemPredicate::Ready,
+        "active" => ItemPredicate::Active,
+        "finished" => ItemPredicate::Finished,
+        _ => match text.strip_prefix('#') {
+            Some(tag) if tag.starts_with(char::is_alphabetic) => {
+                ItemPredicate::Tag(tag.to_string())
+            }
+            _ => ItemPredicate::SummaryContains(text),
+        },
+    }
+}
+
+struct Parser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    /// or_query := and_query ("or" and_query)*
+    fn parse_or(&mut self) -> Result<ItemQuery, String> {
+        let mut queries = vec![self.parse_and()?];
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            queries.push(self.parse_and()?);
+        }
+        Ok(if queries.len() == 1 {
+            queries.pop().expect("Just checked there is one")
+        } else {
+            ItemQuery::Or(queries)
+        })
+    }
+
+    /// and_query := unary unary*
+    fn parse_and(&mut self) -> Result<ItemQuery, String> {
+        let mut queries = Vec::default();
+        while let Some(token) = self.tokens.peek() {
+            if matches!(token, Token::Or | Token::CloseParen) {
+                break;
+            }
+            queries.push(self.parse_unary()?);
+        }
+        match queries.len() {
+            0 => Err(match self.tokens.peek() {
+                Some(Token::CloseParen) => "Empty parentheses or an extra ')'".to_string(),
+                _ => "'or' needs something on both sides".to_string(),
+            }),
+            1 => Ok(queries.pop().expect("Just checked there is one")),
+            _ => Ok(ItemQuery::And(queries)),
+        }
+    }
+
+    /// unary := "not" unary | "(" or_query ")" | term
+    fn parse_unary(&mut self) -> Result<ItemQuery, String> {
+        match self.tokens.next() {
+            Some(Token::Not) => match self.tokens.peek() {
+                None | Some(Token::Or | Token::CloseParen) => {
+                    Err("'not' needs something after it".to_string())
+                }
+                Some(_) => Ok(ItemQuery::Not(Box::new(self.parse_unary()?))),
+            },
+            Some(Token::OpenParen) => {
+                let query = self.parse_or()?;
+                match self.tokens.next() {
+                    Some(Token::CloseParen) => Ok(query),
+                    _ => Err("Missing closing ')'".to_string()),
+                }
+            }
+            Some(Token::Word { text, quoted }) => {
+                Ok(ItemQuery::Predicate(parse_word(text, quoted)))
+            }
+            Some(Token::Field {
+                name,
+                operator,
+                value,
+            }) => Ok(ItemQuery::Predicate(parse_field(&name, &operator, &value)?)),
+            Some(Token::Or | Token::CloseParen) | None => {
+                unreachable!("parse_and stops before these")
+            }
+        }
+    }
+}
+
+/// Parses a query like `type:action ready urgency>=definitely`. An empty query matches everything.
+pub(crate) fn parse_item_query(input: &str) -> Result<ItemQuery, String> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Ok(ItemQuery::All);
+    }
+    let mut parser = Parser {
+        tokens: tokens.into_iter().peekable(),
+    };
+    let query = parser.parse_or()?;
+    match parser.tokens.next() {
+        None => Ok(query),
+        Some(_) => Err("Extra ')' without a matching '('".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeDelta};
+
+    use crate::{
+        data_storage::surrealdb_layer::surreal_smart_list::{
+            SurrealSmartListItemType, SurrealSmartListUrgency,
+        },
+        systems::item_query::{Comparison, ItemPredicate, ItemQuery, NamedRange, TimeFilter},
+    };
+
+    use super::parse_item_query;
+    use crate::systems::item_query::{item_type_query_name, urgency_query_name};
+
+    fn predicate(predicate: ItemPredicate) -> ItemQuery {
+        ItemQuery::Predicate(predicate)
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(parse_item_query("   "), Ok(ItemQuery::All));
+    }
+
+    #[test]
+    fn fields_are_parsed() {
+        assert_eq!(
+            parse_item_query("type:Action"),
+            Ok(predicate(ItemPredicate::Type(
+                SurrealSmartListItemType::Action
+            )))
+        );
+        assert_eq!(
+            parse_item_query("urgency>=definitely"),
+            Ok(predicate(ItemPredicate::Urgency(
+                Comparison::GreaterOrEqual,
+                SurrealSmartListUrgency::InTheModeDefinitelyUrgent
+            )))
+        );
+        assert_eq!(
+            parse_item_query("under:\"Health and fitness\""),
+            Ok(predicate(ItemPredicate::Under(
+                "Health and fitness".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_item_query("created<30d"),
+            Ok(predicate(ItemPredicate::Created(TimeFilter::Age(
+                Comparison::Less,
+                TimeDelta::days(30)
+            ))))
+        );
+        assert_eq!(
+            parse_item_query("created>=2025-01-31"),
+            Ok(predicate(ItemPredicate::Created(TimeFilter::Date(
+                Comparison::GreaterOrEqual,
+                NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()
+            ))))
+        );
+        assert_eq!(
+            parse_item_query("finished:last-week"),
+            Ok(predicate(ItemPredicate::FinishedAt(TimeFilter::Range(
+                NamedRange::LastWeek
+            ))))
+        );
+        assert_eq!(
+            parse_item_query("text:~\"invoice\""),
+            Ok(predicate(ItemPredicate::TextContains(
+                "invoice".to_string()
+            )))
+        );
+        assert_eq!(
+            parse_item_query("tag:#phone"),
+            Ok(predicate(ItemPredicate::Tag("phone".to_string())))
+        );
+    }
+
+    #[test]
+    fn query_names_parse_back_to_the_same_value() {
+        for item_type in [
+            SurrealSmartListItemType::Undeclared,
+            SurrealSmartListItemType::Action,
+            SurrealSmartListItemType::Goal,
+            SurrealSmartListItemType::IdeaOrThought,
+            SurrealSmartListItemType::Motivation,
+            SurrealSmartListItemType::PersonOrGroup,
+        ] {
+            assert_eq!(
+                parse_item_query(&format!("type:{}", item_type_query_name(&item_type))),
+                Ok(predicate(ItemPredicate::Type(item_type)))
+            );
+        }
+        for urgency in [
+            SurrealSmartListUrgency::InTheModeByImportance,
+            SurrealSmartListUrgency::InTheModeMaybeUrgent,
+            SurrealSmartListUrgency::InTheModeDefinitelyUrgent,
+            SurrealSmartListUrgency::InTheModeScheduled,
+            SurrealSmartListUrgency::MoreUrgentThanMode,
+            SurrealSmartListUrgency::ScheduledAnyMode,
+            SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled,
+        ] {
+            assert_eq!(
+                parse_item_query(&format!("urgency:{}", urgency_query_name(&urgency))),
+                Ok(predicate(ItemPredicate::Urgency(
+                    Comparison::Equal,
+                    urgency
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn bare_words_are_keywords_tags_or_text() {
+        assert_eq!(
+            parse_item_query("ready #errand \"the plumber\" invoice"),
+            Ok(ItemQuery::And(vec![
+                predicate(ItemPredicate::Ready),
+                predicate(ItemPredicate::Tag("errand".to_string())),
+                predicate(ItemPredicate::SummaryContains("the plumber".to_string())),
+                predicate(ItemPredicate::SummaryContains("invoice".to_string())),
+            ]))
+        );
+        assert_eq!(
+            parse_item_query("\"ready\" C# follow-up"),
+            Ok(ItemQuery::And(vec![
+                predicate(ItemPredicate::SummaryContains("ready".to_string())),
+                predicate(ItemPredicate::SummaryContains("C#".to_string())),
+                predicate(ItemPredicate::SummaryContains("follow-up".to_string())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn or_binds_looser_than_and() {
+        assert_eq!(
+            parse_item_query("ready type:goal or finished and #phone"),
+            Ok(ItemQuery::Or(vec![
+                ItemQuery::And(vec![
+                    predicate(ItemPredicate::Ready),
+                    predicate(ItemPredicate::Type(SurrealSmartListItemType::Goal)),
+                ]),
+                ItemQuery::And(vec![
+                    predicate(ItemPredicate::Finished),
+                    predicate(ItemPredicate::Tag("phone".to_string())),
+                ]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn not_and_parentheses() {
+        assert_eq!(
+            parse_item_query("-ready not (type:goal OR active)"),
+            Ok(ItemQuery::And(vec![
+                ItemQuery::Not(Box::new(predicate(ItemPredicate::Ready))),
+                ItemQuery::Not(Box::new(ItemQuery::Or(vec![
+                    predicate(ItemPredicate::Type(SurrealSmartListItemType::Goal)),
+                    predicate(ItemPredicate::Active),
+                ]))),
+            ]))
+        );
+    }
+
+    #[test]
+    fn mistakes_give_a_helpful_error() {
+        let error = |query| parse_item_query(query).expect_err(query);
+        assert!(error("colour:red").contains("Unknown field 'colour'"));
+        assert!(error("type:chore").contains("Unknown type 'chore'"));
+        assert!(error("type>action").contains("rather than comparing"));
+        assert!(error("urgency>=very").contains("Unknown urgency 'very'"));
+        assert!(error("created<soon").contains("Unknown created time 'soon'"));
+        assert!(error("finished>today").contains("rather than comparing"));
+        assert!(error("under:\"Health").contains("Missing closing quote"));
+        assert!(error("(ready").contains("Missing closing ')'"));
+        assert!(error("ready)").contains("Extra ')'"));
+        assert!(error("()").contains("Empty parentheses"));
+        assert!(error("or ready").contains("'or' needs something on both sides"));
+        assert!(error("ready or").contains("'or' needs something on both sides"));
+        assert!(error("ready not").contains("'not' needs something after it"));
+        assert!(error("tag:").contains("Missing a value after 'tag'"));
+    }
+}
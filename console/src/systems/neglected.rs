@@ -0,0 +1,238 @@
+use ahash::HashMap;
+use chrono::{DateTime, TimeDelta, Utc};
+use surrealdb::RecordId;
+
+use crate::{
+    base_data::{item::Item, time_spent::TimeSpent},
+    data_storage::surrealdb_layer::surreal_item::SurrealItemType,
+    node::{Filter, item_status::ItemStatus},
+};
+
+/// An active goal or action that has not been worked on, reviewed, or created recently. Reactive items
+/// are waiting to be interrupted so they are never considered neglected.
+pub(crate) struct NeglectedItem<'s> {
+    item_status: &'s ItemStatus<'s>,
+    last_touched: DateTime<Utc>,
+    top_motivation: Option<&'s Item<'s>>,
+}
+
+impl<'s> NeglectedItem<'s> {
+    pub(crate) fn get_item_status(&self) -> &'s ItemStatus<'s> {
+        self.item_status
+    }
+
+    pub(crate) fn get_last_touched(&self) -> &DateTime<Utc> {
+        &self.last_touched
+    }
+
+    pub(crate) fn get_days_since_last_touched(&self, now: &DateTime<Utc>) -> i64 {
+        (*now - self.last_touched).num_days()
+    }
+
+    /// The motivation at the top of the tree this item is under or None if the item is not under a motivation.
+    pub(crate) fn get_top_motivation(&self) -> Option<&'s Item<'s>> {
+        self.top_motivation
+    }
+}
+
+/// The motivation at the top of the tree the item is under, None if it is not under a motivation
+pub(crate) fn find_top_motivation<'s>(
+    item_status: &'s ItemStatus<'s>,
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+) -> Option<&'s Item<'s>> {
+    item_status
+        .get_self_and_parents_flattened(Filter::Active)
+        .into_iter()
+        .find(|x| {
+            x.is_type_motivation()
+                && items_status
+                    .get(x.get_surreal_record_id())
+                    .is_some_and(|x| !x.has_parents(Filter::Active))
+        })
+}
+
+/// Working on an item counts as touching that item and everything above it, otherwise a goal would
+/// show up as neglected even though its children are being worked on regularly.
+///
+/// The result is grouped by top motivation and within each group the item that was touched longest
+/// ago is first.
+pub(crate) fn find_neglected_items<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    time_spent_log: &[TimeSpent<'_>],
+    now: &DateTime<Utc>,
+    untouched_for: TimeDelta,
+) -> Vec<NeglectedItem<'s>> {
+    let mut last_worked_on: HashMap<&RecordId, DateTime<Utc>> = HashMap::default();
+    for time_spent in time_spent_log.iter() {
+        for worked_towards in time_spent.worked_towards().iter() {
+            let Some(item_status) = items_status.get(worked_towards) else {
+                continue;
+            };
+            for item in item_status.get_self_and_parents_flattened(Filter::All) {
+                let last = last_worked_on
+                    .entry(item.get_surreal_record_id())
+                    .or_insert(*time_spent.get_stopped_at());
+                if *last < *time_spent.get_stopped_at() {
+                    *last = *time_spent.get_stopped_at();
+                }
+            }
+        }
+    }
+
+    let mut result = items_status
+        .values()
+        .filter(|x| x.is_active() && !x.get_item().is_responsibility_reactive())
+        .filter(|x| {
+            matches!(
+                x.get_item().get_item_type(),
+                SurrealItemType::Action | SurrealItemType::Goal(..)
+            )
+        })
+        .filter_map(|item_status| {
+            let item = item_status.get_item();
+            let last_touched = [
+                Some(*item.get_created()),
+                item.get_last_reviewed().copied(),
+                last_worked_on.get(item.get_surreal_record_id()).copied(),
+            ]
+            .into_iter()
+            .flatten()
+            .max()
+            .expect("created is always set");
+            if *now - last_touched < untouched_for {
+                return None;
+            }
+            let top_motivation = find_top_motivation(item_status, items_status);
+            Some(NeglectedItem {
+                item_status,
+                last_touched,
+                top_motivation,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    result.sort_by(|a, b| {
+        let a_motivation = a.top_motivation.map(|x| x.get_summary());
+        let b_motivation = b.top_motivation.map(|x| x.get_summary());
+        //Items without a motivation go last
+        b_motivation
+            .is_some()
+            .cmp(&a_motivation.is_some())
+            .then_with(|| a_motivation.cmp(&b_motivation))
+            .then_with(|| a.last_touched.cmp(&b.last_touched))
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_in_the_moment_priority::SurrealAction,
+            surreal_item::{
+                SurrealItemBuilder, SurrealItemType, SurrealMotivationKind, SurrealOrderedSubItem,
+            },
+            surreal_tables::SurrealTablesBuilder,
+            surreal_time_spent::SurrealTimeSpent,
+        },
+    };
+
+    use super::find_neglected_items;
+
+    #[test]
+    fn items_not_touched_recently_are_neglected_and_grouped_under_their_motivation() {
+        let now = Utc::now();
+        let long_ago = now - TimeDelta::days(120);
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "motivation").into()))
+                    .summary("Stay healthy")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::NotSet))
+                    .created(long_ago)
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "goal").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "worked_on").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "goal").into()))
+                    .summary("Run a marathon")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .created(long_ago)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "worked_on").into()))
+                    .summary("Go for a walk")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "reviewed").into()))
+                    .summary("Clean the garage")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .last_reviewed(Some((now - TimeDelta::days(2)).into()))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "new").into()))
+                    .summary("Just captured")
+                    .item_type(SurrealItemType::Action)
+                    .created(now)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "no_motivation").into()))
+                    .summary("Fix the fence")
+                    .item_type(SurrealItemType::Action)
+                    .created(long_ago)
+                    .build()
+                    .unwrap(),
+            ])
+            .surreal_time_spent_log(vec![SurrealTimeSpent {
+                id: None,
+                version: 1,
+                working_on: vec![SurrealAction::MakeProgress(("item", "worked_on").into())],
+                why_in_scope: vec![],
+                urgency: None,
+                when_started: (now - TimeDelta::days(1)).into(),
+                when_stopped: (now - TimeDelta::days(1) + TimeDelta::minutes(30)).into(),
+                dedication: None,
+            }])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let neglected = find_neglected_items(
+            calculated_data.get_items_status(),
+            calculated_data.get_base_data().get_time_spent_log(),
+            &now,
+            TimeDelta::days(30),
+        );
+
+        let summaries = neglected
+            .iter()
+            .map(|x| x.get_item_status().get_summary())
+            .collect::<Vec<_>>();
+        assert_eq!(summaries, vec!["Run a marathon", "Fix the fence"]);
+        assert_eq!(
+            neglected[0].get_top_motivation().map(|x| x.get_summary()),
+            Some("Stay healthy")
+        );
+        assert!(neglected[1].get_top_motivation().is_none());
+        assert_eq!(neglected[0].get_days_since_last_touched(&now), 120);
+    }
+}
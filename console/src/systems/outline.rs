@@ -0,0 +1,403 @@
+//! Item trees as markdown outlines, so a project breakdown drafted as a nested bullet list can be
+//! imported and any subtree can be written back out. For example:
+//!
+//! ```markdown
+//! - Launch the website type:goal
+//!   - Pick a domain name urgency:definitely
+//!   - [x] Write the about page #writing
+//!   - Ask Sam for feedback type:action
+//! ```
+//!
+//! Nesting follows the indentation and sibling order is priority order, the first being the most
+//! important. A `[ ]` or `[x]` checkbox after the bullet marks the item as not finished or finished.
+//! Words at the end of a line can set the type, the urgency, and tags using the same names as an
+//! item query, `type:<type>`, `urgency:<urgency>`, and `#tag`. Scheduled urgencies need a schedule so
+//! they can only be set from the menus.
+
+use ahash::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use surrealdb::RecordId;
+
+use crate::{
+    base_data::item::Item,
+    data_storage::surrealdb_layer::{
+        surreal_item::{SurrealItemType, SurrealUrgency, SurrealUrgencyPlan},
+        surreal_smart_list::{SurrealSmartListItemType, SurrealSmartListUrgency},
+    },
+    new_item::{NewItem, NewItemTree},
+    node::item_status::ItemStatus,
+    systems::item_query::{
+        item_type_query_name, parse_item_type, parse_urgency, urgency_query_name,
+    },
+};
+
+/// How many spaces a tab counts as when working out how deeply a bullet is nested
+const TAB_WIDTH: usize = 4;
+
+/// How many spaces each level of an exported outline is indented by
+const EXPORT_INDENT: usize = 2;
+
+fn item_type_from_marker(value: &str) -> Result<SurrealItemType, String> {
+    Ok(match parse_item_type(value)? {
+        SurrealSmartListItemType::Undeclared => SurrealItemType::Undeclared,
+        SurrealSmartListItemType::Action => SurrealItemType::Action,
+        SurrealSmartListItemType::Goal => SurrealItemType::Goal(Default::default()),
+        SurrealSmartListItemType::IdeaOrThought => SurrealItemType::IdeaOrThought,
+        SurrealSmartListItemType::Motivation => SurrealItemType::Motivation(Default::default()),
+        SurrealSmartListItemType::PersonOrGroup => SurrealItemType::PersonOrGroup,
+    })
+}
+
+fn urgency_from_marker(value: &str) -> Result<SurrealUrgency, String> {
+    match parse_urgency(value)? {
+        SurrealSmartListUrgency::InTheModeByImportance => Ok(SurrealUrgency::InTheModeByImportance),
+        SurrealSmartListUrgency::InTheModeMaybeUrgent => Ok(SurrealUrgency::InTheModeMaybeUrgent),
+        SurrealSmartListUrgency::InTheModeDefinitelyUrgent => {
+            Ok(SurrealUrgency::InTheModeDefinitelyUrgent)
+        }
+        SurrealSmartListUrgency::MoreUrgentThanMode => Ok(SurrealUrgency::MoreUrgentThanMode),
+        SurrealSmartListUrgency::MoreUrgentThanAnythingIncludingScheduled => {
+            Ok(SurrealUrgency::MoreUrgentThanAnythingIncludingScheduled)
+        }
+        SurrealSmartListUrgency::InTheModeScheduled | SurrealSmartListUrgency::ScheduledAnyMode => {
+            Err(format!(
+                "urgency:{} needs a schedule, set it from the menu after importing",
+                value
+            ))
+        }
+    }
+}
+
+/// Applies the marker to the new item, or returns false when the word is not a marker and so is
+/// part of the summary.
+fn apply_marker(word: &str, new_item: &mut NewItem) -> Result<bool, String> {
+    if let Some(value) = word.strip_prefix("type:") {
+        new_item.item_type = item_type_from_marker(value)?;
+    } else if let Some(value) = word.strip_prefix("urgency:") {
+        new_item.urgency_plan = Some(SurrealUrgencyPlan::StaysTheSame(urgency_from_marker(
+            value,
+        )?));
+    } else if let Some(tag) = word.strip_prefix('#')
+        && !tag.is_empty()
+        && !tag.starts_with('#')
+    {
+        new_item.tags.insert(0, tag.to_string());
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// The text after a `-`, `*`, `+`, `1.`, or `1)` bullet
+fn strip_bullet(line: &str) -> Option<&str> {
+    if let Some(rest) = line.strip_prefix(['-', '*', '+']) {
+        return (rest.is_empty() || rest.starts_with([' ', '\t'])).then_some(rest);
+    }
+    let rest = line.trim_start_matches(|c: char| c.is_ascii_digit());
+    if rest.len() == line.len() {
+        return None;
+    }
+    rest.strip_prefix(['.', ')'])
+        .filter(|x| x.is_empty() || x.starts_with([' ', '\t']))
+}
+
+/// The indentation and the new item for one bullet, or None for a blank line or a heading
+fn parse_line(
+    line: &str,
+    line_number: usize,
+    now: DateTime<Utc>,
+) -> Result<Option<(usize, NewItem)>, String> {
+    let text = line.trim_start();
+    if text.is_empty() || text.starts_with("# ") || text.starts_with("##") {
+        return Ok(None);
+    }
+    let indent = line[..line.len() - text.len()]
+        .chars()
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum();
+    let Some(text) = strip_bullet(text) else {
+        return Err(format!(
+            "Line {} is not a bullet, start it with \"- \"",
+            line_number
+        ));
+    };
+    let text = text.trim();
+    let (finished, text) = if let Some(rest) = text.strip_prefix("[ ]") {
+        (false, rest)
+    } else if let Some(rest) = text
+        .strip_prefix("[x]")
+        .or_else(|| text.strip_prefix("[X]"))
+    {
+        (true, rest)
+    } else {
+        (false, text)
+    };
+
+    let mut new_item = NewItem::new(String::default(), now);
+    if finished {
+        new_item.finished = Some(now.into());
+    }
+    let mut words = text.split_whitespace().collect::<Vec<_>>();
+    while let Some(word) = words.last() {
+        if !apply_marker(word, &mut new_item)
+            .map_err(|err| format!("Line {}: {}", line_number, err))?
+        {
+            break;
+        }
+        words.pop();
+    }
+    if words.is_empty() {
+        return Err(format!("Line {} has no summary", line_number));
+    }
+    new_item.summary = words.join(" ");
+    Ok(Some((indent, new_item)))
+}
+
+/// Puts the last open bullet under the one before it, or at the top level when it is the only one
+fn close_last(open: &mut Vec<(usize, NewItemTree)>, top_level: &mut Vec<NewItemTree>) {
+    let (_, closed) = open.pop().expect("Only called when something is open");
+    match open.last_mut() {
+        Some((_, parent)) => parent.smaller_items.push(closed),
+        None => top_level.push(closed),
+    }
+}
+
+/// Turns a markdown bullet list into trees of new items, one for each top level bullet. A bullet
+/// indented more than the one above it goes under it. Blank lines and headings are skipped, any
+/// other line is an error.
+pub(crate) fn parse_outline(text: &str, now: DateTime<Utc>) -> Result<Vec<NewItemTree>, String> {
+    let mut top_level = Vec::default();
+    //The bullets that later bullets could still go under, with their indentation
+    let mut open: Vec<(usize, NewItemTree)> = Vec::default();
+    for (i, line) in text.lines().enumerate() {
+        let Some((indent, item)) = parse_line(line, i + 1, now)? else {
+            continue;
+        };
+        while open.last().is_some_and(|(x, _)| *x >= indent) {
+            close_last(&mut open, &mut top_level);
+        }
+        open.push((
+            indent,
+            NewItemTree {
+                item,
+                smaller_items: Vec::default(),
+            },
+        ));
+    }
+    while !open.is_empty() {
+        close_last(&mut open, &mut top_level);
+    }
+    if top_level.is_empty() {
+        return Err("The outline has no bullets".to_string());
+    }
+    Ok(top_level)
+}
+
+/// The markers that bring the type, urgency, and tags back when the outline is imported. The kind of
+/// goal or motivation and any urgency that escalates or is scheduled are left out.
+fn markers(item: &Item) -> String {
+    let mut markers = Vec::default();
+    if item.get_item_type() != &SurrealItemType::Undeclared {
+        markers.push(format!(
+            "type:{}",
+            item_type_query_name(&SurrealSmartListItemType::from(item.get_item_type()))
+        ));
+    }
+    if let Some(SurrealUrgencyPlan::StaysTheSame(
+        urgency @ (SurrealUrgency::InTheModeMaybeUrgent
+        | SurrealUrgency::InTheModeDefinitelyUrgent
+        | SurrealUrgency::MoreUrgentThanMode
+        | SurrealUrgency::MoreUrgentThanAnythingIncludingScheduled),
+    )) = item.get_surreal_urgency_plan()
+    {
+        markers.push(format!(
+            "urgency:{}",
+            urgency_query_name(&SurrealSmartListUrgency::from(urgency))
+        ));
+    }
+    markers.extend(
+        item.get_tags()
+            .iter()
+            .filter(|x| !x.is_empty() && !x.contains(char::is_whitespace))
+            .map(|x| format!("#{}", x)),
+    );
+    markers.iter().map(|x| format!(" {}", x)).collect()
+}
+
+fn write_outline<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    item_status: &'s ItemStatus<'s>,
+    depth: usize,
+    seen: &mut HashSet<&'s RecordId>,
+    outline: &mut String,
+) {
+    if !seen.insert(item_status.get_surreal_record_id()) {
+        return;
+    }
+    let item = item_status.get_item();
+    outline.push_str(&format!(
+        "{}- [{}] {}{}\n",
+        " ".repeat(depth * EXPORT_INDENT),
+        if item_status.is_finished() { 'x' } else { ' ' },
+        item.get_summary()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        markers(item)
+    ));
+    for child in item.get_children().filter_map(|x| items_status.get(x)) {
+        write_outline(items_status, child, depth + 1, seen, outline);
+    }
+}
+
+/// The item and everything under it, finished or not, as a markdown outline that `parse_outline`
+/// reads back
+pub(crate) fn item_outline<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+    root: &'s ItemStatus<'s>,
+) -> String {
+    let mut outline = String::default();
+    write_outline(items_status, root, 0, &mut HashSet::default(), &mut outline);
+    outline
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use surrealdb::RecordId;
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{
+                SurrealItemBuilder, SurrealItemType, SurrealOrderedSubItem, SurrealUrgency,
+                SurrealUrgencyPlan,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{item_outline, parse_outline};
+
+    #[test]
+    fn nesting_follows_indentation_and_markers_set_type_urgency_and_tags() {
+        let now = Utc::now();
+        let outline = "# Website\n\
+            - Launch the website type:goal\n  \
+              * Pick a domain name urgency:definitely\n  \
+              * [x] Write the about page #writing\n    \
+                1. Fix typo #2 on the home page\n\
+            \n\
+            - [ ] Ask Sam for feedback type:action";
+
+        let trees = parse_outline(outline, now).unwrap();
+
+        assert_eq!(trees.len(), 2);
+        let launch = &trees[0];
+        assert_eq!(launch.item.summary, "Launch the website");
+        assert!(matches!(launch.item.item_type, SurrealItemType::Goal(_)));
+        assert_eq!(launch.smaller_items.len(), 2);
+        let domain = &launch.smaller_items[0];
+        assert_eq!(domain.item.summary, "Pick a domain name");
+        assert_eq!(
+            domain.item.urgency_plan,
+            Some(SurrealUrgencyPlan::StaysTheSame(
+                SurrealUrgency::InTheModeDefinitelyUrgent
+            ))
+        );
+        let about = &launch.smaller_items[1];
+        assert_eq!(about.item.summary, "Write the about page");
+        assert!(about.item.finished.is_some());
+        assert_eq!(about.item.tags, vec!["writing".to_string()]);
+        assert_eq!(
+            about.smaller_items[0].item.summary,
+            "Fix typo #2 on the home page"
+        );
+        assert!(about.smaller_items[0].item.tags.is_empty());
+        assert_eq!(trees[1].item.summary, "Ask Sam for feedback");
+        assert_eq!(trees[1].item.item_type, SurrealItemType::Action);
+        assert!(trees[1].item.finished.is_none());
+    }
+
+    #[test]
+    fn mistakes_give_the_line_number() {
+        let now = Utc::now();
+
+        let err = parse_outline("- Plan\nsome notes", now).unwrap_err();
+        assert!(err.contains("Line 2 is not a bullet"), "{}", err);
+
+        let err = parse_outline("- Plan\n  - Call type:robot", now).unwrap_err();
+        assert!(err.contains("Line 2: Unknown type 'robot'"), "{}", err);
+
+        let err = parse_outline("- Plan urgency:scheduled", now).unwrap_err();
+        assert!(err.contains("needs a schedule"), "{}", err);
+
+        let err = parse_outline("- [x] type:action", now).unwrap_err();
+        assert!(err.contains("Line 1 has no summary"), "{}", err);
+
+        assert!(parse_outline("\n# Nothing here\n", now).is_err());
+    }
+
+    #[test]
+    fn exported_outline_has_checkboxes_and_reads_back_the_same() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "launch").into()))
+                    .summary("Launch the website")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "domain").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "about").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "domain").into()))
+                    .summary("Pick a domain name")
+                    .item_type(SurrealItemType::Action)
+                    .urgency_plan(Some(SurrealUrgencyPlan::StaysTheSame(
+                        SurrealUrgency::InTheModeDefinitelyUrgent,
+                    )))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "about").into()))
+                    .summary("Write the about page")
+                    .tags(Some(vec!["writing".to_string()]))
+                    .finished(Some(now.into()))
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let calculated_data = CalculatedData::new_from_base_data(
+            BaseData::new_from_surreal_tables(surreal_tables, now),
+        );
+        let items_status = calculated_data.get_items_status();
+        let launch: RecordId = ("item", "launch").into();
+
+        let outline = item_outline(items_status, items_status.get(&launch).unwrap());
+
+        assert_eq!(
+            outline,
+            "- [ ] Launch the website type:goal\n  \
+               - [ ] Pick a domain name type:action urgency:definitely\n  \
+               - [x] Write the about page #writing\n"
+        );
+        let trees = parse_outline(&outline, now).unwrap();
+        assert_eq!(trees.len(), 1);
+        assert_eq!(trees[0].smaller_items.len(), 2);
+        assert_eq!(
+            trees[0].smaller_items[1].item.tags,
+            vec!["writing".to_string()]
+        );
+        assert!(trees[0].smaller_items[1].item.finished.is_some());
+    }
+}
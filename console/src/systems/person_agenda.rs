@@ -0,0 +1,187 @@
+use ahash::HashMap;
+use surrealdb::RecordId;
+
+use crate::{
+    data_storage::surrealdb_layer::surreal_item::SurrealDependency,
+    node::{Filter, item_status::ItemStatus},
+};
+
+/// Why an item is on the agenda for meeting with someone, in the order they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum AgendaReason {
+    /// Handed off to them and waiting on them to do it
+    DelegatedToThem,
+    /// Stated as a smaller item of the person so it is something to bring up with them
+    ToDiscuss,
+    /// Has the person as a smaller item or waits on them so it cannot move forward without them
+    NeedsThem,
+}
+
+pub(crate) struct AgendaItem<'s> {
+    item_status: &'s ItemStatus<'s>,
+    reason: AgendaReason,
+}
+
+impl<'s> AgendaItem<'s> {
+    pub(crate) fn get_item_status(&self) -> &'s ItemStatus<'s> {
+        self.item_status
+    }
+
+    pub(crate) fn get_reason(&self) -> AgendaReason {
+        self.reason
+    }
+}
+
+fn agenda_reason(item_status: &ItemStatus<'_>, person: &RecordId) -> Option<AgendaReason> {
+    let item = item_status.get_item();
+    if item.get_waiting_for().is_some_and(|x| &x.person == person) {
+        Some(AgendaReason::DelegatedToThem)
+    } else if item_status
+        .get_parents(Filter::Active)
+        .any(|x| x.get_surreal_record_id() == person)
+    {
+        Some(AgendaReason::ToDiscuss)
+    } else if item_status
+        .get_children(Filter::Active)
+        .any(|x| x.get_surreal_record_id() == person)
+        || item
+            .get_surreal_dependencies()
+            .iter()
+            .any(|x| matches!(x, SurrealDependency::AfterItem(after) if after == person))
+    {
+        Some(AgendaReason::NeedsThem)
+    } else {
+        None
+    }
+}
+
+/// Every active item that should come up when meeting with the person, grouped by why it is on the
+/// agenda and then sorted by summary.
+pub(crate) fn find_person_agenda<'s>(
+    person: &RecordId,
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+) -> Vec<AgendaItem<'s>> {
+    let mut agenda = items_status
+        .values()
+        .filter(|x| x.is_active() && x.get_surreal_record_id() != person)
+        .filter_map(|item_status| {
+            agenda_reason(item_status, person).map(|reason| AgendaItem {
+                item_status,
+                reason,
+            })
+        })
+        .collect::<Vec<_>>();
+    agenda.sort_by(|a, b| {
+        a.reason
+            .cmp(&b.reason)
+            .then_with(|| a.item_status.get_summary().cmp(b.item_status.get_summary()))
+    });
+    agenda
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{
+                Responsibility, SurrealDependency, SurrealItemBuilder, SurrealItemType,
+                SurrealOrderedSubItem, SurrealWaitingFor,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::{AgendaReason, find_person_agenda};
+
+    #[test]
+    fn agenda_has_items_to_discuss_waiting_on_and_delegated_to_the_person() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "bob").into()))
+                    .summary("Bob")
+                    .item_type(SurrealItemType::PersonOrGroup)
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "budget").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "discussed").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "budget").into()))
+                    .summary("Ask about the budget")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "discussed").into()))
+                    .summary("Already talked about")
+                    .item_type(SurrealItemType::Action)
+                    .finished(Some(now.into()))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "sign_off").into()))
+                    .summary("Get the design signed off")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .smaller_items_in_priority_order(vec![SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: ("item", "bob").into(),
+                    }])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "after_bob").into()))
+                    .summary("Book the venue")
+                    .item_type(SurrealItemType::Action)
+                    .dependencies(vec![SurrealDependency::AfterItem(("item", "bob").into())])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "report").into()))
+                    .summary("Quarterly report")
+                    .item_type(SurrealItemType::Action)
+                    .responsibility(Responsibility::WaitingFor)
+                    .waiting_for(Some(SurrealWaitingFor::new(
+                        ("item", "bob").into(),
+                        (now - TimeDelta::days(3)).into(),
+                        (now + TimeDelta::days(3)).into(),
+                    )))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "unrelated").into()))
+                    .summary("Water the plants")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let agenda =
+            find_person_agenda(&("item", "bob").into(), calculated_data.get_items_status())
+                .iter()
+                .map(|x| (x.get_reason(), x.get_item_status().get_summary()))
+                .collect::<Vec<_>>();
+        assert_eq!(
+            agenda,
+            vec![
+                (AgendaReason::DelegatedToThem, "Quarterly report"),
+                (AgendaReason::ToDiscuss, "Ask about the budget"),
+                (AgendaReason::NeedsThem, "Book the venue"),
+                (AgendaReason::NeedsThem, "Get the design signed off"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use ahash::HashMap;
+use surrealdb::RecordId;
+
+use crate::{
+    base_data::item::Item,
+    data_storage::surrealdb_layer::surreal_priority_comparison::SurrealPriorityComparison,
+};
+
+const STARTING_RATING: f64 = 1500.0;
+const K_FACTOR: f64 = 32.0;
+
+/// Only suggest a change to the order of children once both items have been compared at least this
+/// many times, otherwise a single answer would be enough to suggest a change.
+const MINIMUM_COMPARISONS_FOR_SUGGESTION: usize = 3;
+
+/// A rating difference of 200 means the higher rated item is expected to be chosen about 76% of
+/// the time, anything less than that is not a strong enough disagreement to bother the user with.
+const STRONG_DISAGREEMENT: f64 = 200.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rating {
+    value: f64,
+    comparisons: usize,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            value: STARTING_RATING,
+            comparisons: 0,
+        }
+    }
+}
+
+/// Elo ratings learned from the history of priority comparisons. Comparisons that were made for
+/// all modes always count, comparisons that were made for a specific mode only count when that
+/// mode is the one the ratings are being calculated for.
+pub(crate) struct PriorityRatings<'s> {
+    ratings: HashMap<&'s RecordId, Rating>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ReorderSuggestion<'s> {
+    pub(crate) parent: &'s RecordId,
+    pub(crate) move_this: &'s RecordId,
+    pub(crate) above_this: &'s RecordId,
+    pub(crate) rating_difference: f64,
+}
+
+impl<'s> PriorityRatings<'s> {
+    pub(crate) fn new(
+        comparisons: &'s [SurrealPriorityComparison],
+        for_mode: Option<&RecordId>,
+    ) -> Self {
+        let mut in_scope = comparisons
+            .iter()
+            .filter(|x| match &x.for_mode {
+                None => true,
+                Some(comparison_mode) => Some(comparison_mode) == for_mode,
+            })
+            .collect::<Vec<_>>();
+        //Elo ratings depend on the order that results are applied so replay them in the order they were made
+        in_scope.sort_by(|a, b| a.created.cmp(&b.created));
+
+        let mut ratings: HashMap<&'s RecordId, Rating> = HashMap::default();
+        for comparison in in_scope {
+            if comparison.winner == comparison.loser {
+                continue;
+            }
+            let winner = ratings.get(&comparison.winner).copied().unwrap_or_default();
+            let loser = ratings.get(&comparison.loser).copied().unwrap_or_default();
+
+            let expected_winner_score = expected_score(winner.value, loser.value);
+            let change = K_FACTOR * (1.0 - expected_winner_score);
+
+            ratings.insert(
+                &comparison.winner,
+                Rating {
+                    value: winner.value + change,
+                    comparisons: winner.comparisons + 1,
+                },
+            );
+            ratings.insert(
+                &comparison.loser,
+                Rating {
+                    value: loser.value - change,
+                    comparisons: loser.comparisons + 1,
+                },
+            );
+        }
+
+        PriorityRatings { ratings }
+    }
+
+    /// Items that have never been compared are given the starting rating.
+    pub(crate) fn get_rating(&self, item: &RecordId) -> f64 {
+        self.ratings
+            .get(item)
+            .map(|x| x.value)
+            .unwrap_or(STARTING_RATING)
+    }
+
+    pub(crate) fn get_comparison_count(&self, item: &RecordId) -> usize {
+        self.ratings.get(item).map(|x| x.comparisons).unwrap_or(0)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+
+    /// Sorts highest rated first. The sort is stable so items with the same rating keep their order.
+    pub(crate) fn sort_by_rating<T>(
+        &self,
+        items: &mut [T],
+        get_record_id: impl Fn(&T) -> &RecordId,
+    ) {
+        items.sort_by(|a, b| {
+            self.get_rating(get_record_id(b))
+                .total_cmp(&self.get_rating(get_record_id(a)))
+        });
+    }
+
+    /// Finds children that are listed as lower importance than a sibling while the learned ratings
+    /// strongly say they should be higher importance. At most one suggestion is made per child, moving
+    /// it above the highest sibling that it strongly outranks.
+    pub(crate) fn find_reorder_suggestions<'a>(
+        &self,
+        items: &'a HashMap<&'a RecordId, Item<'a>>,
+    ) -> Vec<ReorderSuggestion<'a>> {
+        let mut suggestions = Vec::default();
+        for parent in items.values().filter(|x| x.is_active()) {
+            let children = parent
+                .get_children()
+                .filter(|x| items.get(x).is_some_and(|x| x.is_active()))
+                .filter(|x| self.get_comparison_count(x) >= MINIMUM_COMPARISONS_FOR_SUGGESTION)
+                .collect::<Vec<_>>();
+            for (index, lower) in children.iter().enumerate() {
+                let strongly_outranks = children[..index]
+                    .iter()
+                    .map(|higher| (higher, self.get_rating(lower) - self.get_rating(higher)))
+                    .find(|(_, difference)| *difference >= STRONG_DISAGREEMENT);
+                if let Some((higher, rating_difference)) = strongly_outranks {
+                    suggestions.push(ReorderSuggestion {
+                        parent: parent.get_surreal_record_id(),
+                        move_this: *lower,
+                        above_this: *higher,
+                        rating_difference,
+                    });
+                }
+            }
+        }
+        suggestions.sort_by(|a, b| b.rating_difference.total_cmp(&a.rating_difference));
+        suggestions
+    }
+}
+
+fn expected_score(rating: f64, opponent_rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_rating - rating) / 400.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use surrealdb::RecordId;
+
+    use crate::data_storage::surrealdb_layer::{
+        surreal_item::{SurrealItemBuilder, SurrealItemType, SurrealOrderedSubItem},
+        surreal_priority_comparison::SurrealPriorityComparison,
+        surreal_tables::SurrealTablesBuilder,
+    };
+
+    use super::PriorityRatings;
+
+    fn make_comparisons(
+        winners_and_losers: &[(&str, &str)],
+        for_mode: Option<RecordId>,
+    ) -> Vec<SurrealPriorityComparison> {
+        let start = Utc::now();
+        winners_and_losers
+            .iter()
+            .enumerate()
+            .map(|(index, (winner, loser))| {
+                SurrealPriorityComparison::new(
+                    ("item", *winner).into(),
+                    ("item", *loser).into(),
+                    for_mode.clone(),
+                    (start + Duration::seconds(index as i64)).into(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn winner_is_rated_higher_than_loser() {
+        let comparisons = make_comparisons(&[("1", "2")], None);
+        let ratings = PriorityRatings::new(&comparisons, None);
+
+        let one: RecordId = ("item", "1").into();
+        let two: RecordId = ("item", "2").into();
+        let never_compared: RecordId = ("item", "3").into();
+        assert!(ratings.get_rating(&one) > ratings.get_rating(&never_compared));
+        assert!(ratings.get_rating(&two) < ratings.get_rating(&never_compared));
+        assert_eq!(ratings.get_comparison_count(&one), 1);
+        assert_eq!(ratings.get_comparison_count(&never_compared), 0);
+    }
+
+    #[test]
+    fn comparisons_for_another_mode_are_ignored() {
+        let mode: RecordId = ("modes", "work").into();
+        let other_mode: RecordId = ("modes", "home").into();
+        let mut comparisons = make_comparisons(&[("1", "2")], Some(mode.clone()));
+        comparisons.extend(make_comparisons(
+            &[("2", "1"), ("2", "1")],
+            Some(other_mode),
+        ));
+
+        let ratings = PriorityRatings::new(&comparisons, Some(&mode));
+        let one: RecordId = ("item", "1").into();
+        let two: RecordId = ("item", "2").into();
+        assert!(ratings.get_rating(&one) > ratings.get_rating(&two));
+        assert_eq!(ratings.get_comparison_count(&one), 1);
+
+        let ratings = PriorityRatings::new(&comparisons, None);
+        assert!(ratings.is_empty());
+    }
+
+    #[test]
+    fn sort_by_rating_puts_highest_rated_first_and_keeps_ties_in_order() {
+        let comparisons = make_comparisons(&[("3", "1"), ("3", "1")], None);
+        let ratings = PriorityRatings::new(&comparisons, None);
+
+        let mut items: Vec<RecordId> = vec![
+            ("item", "1").into(),
+            ("item", "2").into(),
+            ("item", "4").into(),
+            ("item", "3").into(),
+        ];
+        ratings.sort_by_rating(&mut items, |x| x);
+        let expected: Vec<RecordId> = vec![
+            ("item", "3").into(),
+            ("item", "2").into(),
+            ("item", "4").into(),
+            ("item", "1").into(),
+        ];
+        assert_eq!(items, expected);
+    }
+
+    #[test]
+    fn suggests_moving_a_child_that_keeps_winning_above_its_sibling() {
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "parent").into()))
+                    .summary("Parent")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "1").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "2").into(),
+                        },
+                    ])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "1").into()))
+                    .summary("Listed first")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "2").into()))
+                    .summary("Listed second")
+                    .item_type(SurrealItemType::Action)
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let now = Utc::now();
+        let items = surreal_tables.make_items(&now);
+
+        let comparisons = make_comparisons(&[("2", "1"); 3], None);
+        let ratings = PriorityRatings::new(&comparisons, None);
+        assert!(ratings.find_reorder_suggestions(&items).is_empty());
+
+        let comparisons = make_comparisons(&[("2", "1"); 10], None);
+        let ratings = PriorityRatings::new(&comparisons, None);
+        let suggestions = ratings.find_reorder_suggestions(&items);
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = suggestions.first().expect("len() == 1");
+        assert_eq!(suggestion.parent, &RecordId::from(("item", "parent")));
+        assert_eq!(suggestion.move_this, &RecordId::from(("item", "2")));
+        assert_eq!(suggestion.above_this, &RecordId::from(("item", "1")));
+    }
+}
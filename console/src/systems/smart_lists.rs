@@ -0,0 +1,264 @@
+use ahash::HashMap;
+use surrealdb::RecordId;
+
+use crate::{
+    data_storage::surrealdb_layer::surreal_smart_list::{
+        SurrealSmartListFilter, SurrealSmartListItemType, SurrealSmartListReadiness,
+        SurrealSmartListUrgency,
+    },
+    node::{Filter, item_status::ItemStatus},
+    systems::item_query::parse_item_query,
+};
+
+pub(crate) fn is_in_smart_list<'s>(
+    filter: &SurrealSmartListFilter,
+    item_status: &'s ItemStatus<'s>,
+) -> bool {
+    let item = item_status.get_item();
+    if !filter.tags.iter().all(|tag| item.has_tag(tag)) {
+        return false;
+    }
+
+    if !filter.item_types.is_empty()
+        && !filter
+            .item_types
+            .contains(&SurrealSmartListItemType::from(item.get_item_type()))
+    {
+        return false;
+    }
+
+    if let Some(urgency) = &filter.urgency {
+        let urgency_now = match item_status.get_urgency_now() {
+            Some(urgency_now) => SurrealSmartListUrgency::from(urgency_now),
+            //Items without an urgency plan are worked on by importance in the Do Now list
+            None => SurrealSmartListUrgency::InTheModeByImportance,
+        };
+        if urgency_now != *urgency {
+            return false;
+        }
+    }
+
+    match filter.readiness {
+        Some(SurrealSmartListReadiness::Ready) if item_status.has_dependencies(Filter::Active) => {
+            return false;
+        }
+        Some(SurrealSmartListReadiness::NotReady)
+            if !item_status.has_dependencies(Filter::Active) =>
+        {
+            return false;
+        }
+        Some(SurrealSmartListReadiness::Ready | SurrealSmartListReadiness::NotReady) | None => {}
+    }
+
+    if let Some(under_motivation) = &filter.under_motivation {
+        let is_under = item_status
+            .get_self_and_parents_flattened(Filter::Active)
+            .iter()
+            .any(|x| x.get_surreal_record_id() == under_motivation && *x != item);
+        if !is_under {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// The active items in the smart list sorted by summary. Finished items are only included when the
+/// query asks about them, and a saved query that no longer parses matches nothing.
+pub(crate) fn find_smart_list_items<'s>(
+    filter: &SurrealSmartListFilter,
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+) -> Vec<&'s ItemStatus<'s>> {
+    let query = match filter.query.as_deref().map(parse_item_query) {
+        Some(Ok(query)) => Some(query),
+        Some(Err(_)) => return Vec::default(),
+        None => None,
+    };
+    let include_finished = query.as_ref().is_some_and(|x| x.is_about_finished_items());
+    let mut items = items_status
+        .values()
+        .filter(|x| {
+            (include_finished || x.is_active())
+                && is_in_smart_list(filter, *x)
+                && query.as_ref().is_none_or(|query| query.matches(*x))
+        })
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| a.get_summary().cmp(b.get_summary()));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{
+                SurrealDependency, SurrealItemBuilder, SurrealItemType, SurrealMotivationKind,
+                SurrealOrderedSubItem, SurrealUrgency, SurrealUrgencyPlan,
+            },
+            surreal_smart_list::{
+                SurrealSmartListFilter, SurrealSmartListItemType, SurrealSmartListReadiness,
+                SurrealSmartListUrgency,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::find_smart_list_items;
+
+    #[test]
+    fn smart_list_combines_tags_type_urgency_readiness_and_motivation() {
+        let now = Utc::now();
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "motivation").into()))
+                    .summary("Keep the house running")
+                    .item_type(SurrealItemType::Motivation(SurrealMotivationKind::NotSet))
+                    .smaller_items_in_priority_order(vec![
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "goal").into(),
+                        },
+                        SurrealOrderedSubItem::SubItem {
+                            surreal_item_id: ("item", "waiting").into(),
+                        },
+                    ])
+                    .tags(Some(vec!["phone".to_string()]))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "goal").into()))
+                    .summary("Fix the leak")
+                    .item_type(SurrealItemType::Goal(Default::default()))
+                    .smaller_items_in_priority_order(vec![SurrealOrderedSubItem::SubItem {
+                        surreal_item_id: ("item", "call").into(),
+                    }])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "call").into()))
+                    .summary("Call the plumber")
+                    .item_type(SurrealItemType::Action)
+                    .urgency_plan(Some(SurrealUrgencyPlan::StaysTheSame(
+                        SurrealUrgency::InTheModeDefinitelyUrgent,
+                    )))
+                    .tags(Some(vec!["phone".to_string(), "errand".to_string()]))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "waiting").into()))
+                    .summary("Call the landlord")
+                    .item_type(SurrealItemType::Action)
+                    .dependencies(vec![SurrealDependency::AfterDateTime(
+                        (now + TimeDelta::days(3)).into(),
+                    )])
+                    .tags(Some(vec!["phone".to_string()]))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "unrelated").into()))
+                    .summary("Call mom")
+                    .item_type(SurrealItemType::Action)
+                    .tags(Some(vec!["Phone".to_string()]))
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+        let items_status = calculated_data.get_items_status();
+        let summaries = |filter: &SurrealSmartListFilter| {
+            find_smart_list_items(filter, items_status)
+                .iter()
+                .map(|x| x.get_summary())
+                .collect::<Vec<_>>()
+        };
+
+        let phone = SurrealSmartListFilter {
+            tags: vec!["phone".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            summaries(&phone),
+            vec![
+                "Call mom",
+                "Call the landlord",
+                "Call the plumber",
+                "Keep the house running"
+            ]
+        );
+
+        let phone_actions = SurrealSmartListFilter {
+            item_types: vec![SurrealSmartListItemType::Action],
+            ..phone.clone()
+        };
+        assert_eq!(
+            summaries(&phone_actions),
+            vec!["Call mom", "Call the landlord", "Call the plumber"]
+        );
+
+        let ready_phone_actions = SurrealSmartListFilter {
+            readiness: Some(SurrealSmartListReadiness::Ready),
+            ..phone_actions.clone()
+        };
+        assert_eq!(
+            summaries(&ready_phone_actions),
+            vec!["Call mom", "Call the plumber"]
+        );
+
+        let not_ready_phone_actions = SurrealSmartListFilter {
+            readiness: Some(SurrealSmartListReadiness::NotReady),
+            ..phone_actions.clone()
+        };
+        assert_eq!(
+            summaries(&not_ready_phone_actions),
+            vec!["Call the landlord"]
+        );
+
+        let urgent_phone_actions = SurrealSmartListFilter {
+            urgency: Some(SurrealSmartListUrgency::InTheModeDefinitelyUrgent),
+            ..phone_actions.clone()
+        };
+        assert_eq!(summaries(&urgent_phone_actions), vec!["Call the plumber"]);
+
+        let by_importance_phone_actions = SurrealSmartListFilter {
+            urgency: Some(SurrealSmartListUrgency::InTheModeByImportance),
+            ..phone_actions.clone()
+        };
+        assert_eq!(
+            summaries(&by_importance_phone_actions),
+            vec!["Call mom", "Call the landlord"]
+        );
+
+        let under_motivation = SurrealSmartListFilter {
+            under_motivation: Some(("item", "motivation").into()),
+            ..phone.clone()
+        };
+        assert_eq!(
+            summaries(&under_motivation),
+            vec!["Call the landlord", "Call the plumber"]
+        );
+
+        let errands_and_phone = SurrealSmartListFilter {
+            tags: vec!["phone".to_string(), "errand".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(summaries(&errands_and_phone), vec!["Call the plumber"]);
+
+        let urgent_by_query = SurrealSmartListFilter {
+            query: Some("type:action urgency>=definitely or under:\"leak\"".to_string()),
+            ..phone.clone()
+        };
+        assert_eq!(summaries(&urgent_by_query), vec!["Call the plumber"]);
+
+        let broken_query = SurrealSmartListFilter {
+            query: Some("urgency>=very".to_string()),
+            ..Default::default()
+        };
+        assert!(summaries(&broken_query).is_empty());
+    }
+}
@@ -87,6 +87,14 @@ impl<'s> ScheduledItem<'s> {
         &self.start
     }
 
+    pub(crate) fn get_scheduled_end(&self) -> &DateTime<Utc> {
+        &self.end
+    }
+
+    pub(crate) fn get_item_status(&self) -> &'s ItemStatus<'s> {
+        self.item
+    }
+
     pub(crate) fn get_summary(&self) -> &str {
         self.item.get_summary()
     }
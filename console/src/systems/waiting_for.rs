@@ -0,0 +1,185 @@
+use ahash::HashMap;
+use surrealdb::RecordId;
+
+use crate::{base_data::item::Item, node::item_status::ItemStatus};
+
+/// Everything that is waiting on one person or group, soonest follow up first.
+pub(crate) struct WaitingForPerson<'s> {
+    person: Option<&'s Item<'s>>,
+    waiting: Vec<&'s ItemStatus<'s>>,
+}
+
+impl<'s> WaitingForPerson<'s> {
+    /// None when the person this was waiting on no longer exists
+    pub(crate) fn get_person(&self) -> Option<&'s Item<'s>> {
+        self.person
+    }
+
+    pub(crate) fn get_waiting(&self) -> &[&'s ItemStatus<'s>] {
+        &self.waiting
+    }
+}
+
+/// The active items that are waiting on someone grouped by who they are waiting on. People are
+/// sorted by name with anyone who no longer exists last.
+pub(crate) fn find_waiting_for_by_person<'s>(
+    items_status: &'s HashMap<&'s RecordId, ItemStatus<'s>>,
+) -> Vec<WaitingForPerson<'s>> {
+    let mut by_person: HashMap<Option<&RecordId>, WaitingForPerson<'s>> = HashMap::default();
+    for item_status in items_status.values().filter(|x| x.is_active()) {
+        if item_status.get_item().get_waiting_for().is_none() {
+            continue;
+        }
+        let person = item_status.get_waiting_on();
+        by_person
+            .entry(person.map(|x| x.get_surreal_record_id()))
+            .or_insert_with(|| WaitingForPerson {
+                person,
+                waiting: Vec::default(),
+            })
+            .waiting
+            .push(item_status);
+    }
+
+    let mut result = by_person.into_values().collect::<Vec<_>>();
+    for waiting_for_person in result.iter_mut() {
+        waiting_for_person.waiting.sort_by(|a, b| {
+            let a_follow_up = &a
+                .get_item()
+                .get_waiting_for()
+                .expect("Filtered above")
+                .follow_up;
+            let b_follow_up = &b
+                .get_item()
+                .get_waiting_for()
+                .expect("Filtered above")
+                .follow_up;
+            a_follow_up
+                .cmp(b_follow_up)
+                .then_with(|| a.get_summary().cmp(b.get_summary()))
+        });
+    }
+    result.sort_by(|a, b| match (a.person, b.person) {
+        (Some(a), Some(b)) => a.get_summary().cmp(b.get_summary()),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+
+    use crate::{
+        base_data::BaseData,
+        calculated_data::CalculatedData,
+        data_storage::surrealdb_layer::{
+            surreal_item::{
+                Responsibility, SurrealDependency, SurrealItemBuilder, SurrealItemType,
+                SurrealWaitingFor,
+            },
+            surreal_tables::SurrealTablesBuilder,
+        },
+    };
+
+    use super::find_waiting_for_by_person;
+
+    #[test]
+    fn waiting_for_is_grouped_by_person_soonest_follow_up_first() {
+        let now = Utc::now();
+        let waiting_on = |person: &str, follow_up_in_days: i64| {
+            SurrealWaitingFor::new(
+                ("item", person).into(),
+                (now - TimeDelta::days(7)).into(),
+                (now + TimeDelta::days(follow_up_in_days)).into(),
+            )
+        };
+        let surreal_tables = SurrealTablesBuilder::default()
+            .surreal_items(vec![
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "zoe").into()))
+                    .summary("Zoe")
+                    .item_type(SurrealItemType::PersonOrGroup)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "adam").into()))
+                    .summary("Adam")
+                    .item_type(SurrealItemType::PersonOrGroup)
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "quote").into()))
+                    .summary("Kitchen quote")
+                    .item_type(SurrealItemType::Action)
+                    .responsibility(Responsibility::WaitingFor)
+                    .waiting_for(Some(waiting_on("zoe", 5)))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "invoice").into()))
+                    .summary("Signed invoice")
+                    .item_type(SurrealItemType::Action)
+                    .responsibility(Responsibility::WaitingFor)
+                    .waiting_for(Some(waiting_on("zoe", -1)))
+                    .dependencies(vec![SurrealDependency::AfterDateTime(
+                        (now - TimeDelta::days(1)).into(),
+                    )])
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "keys").into()))
+                    .summary("Spare keys")
+                    .item_type(SurrealItemType::Action)
+                    .responsibility(Responsibility::WaitingFor)
+                    .waiting_for(Some(waiting_on("adam", 2)))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "taken_back").into()))
+                    .summary("No longer waiting")
+                    .item_type(SurrealItemType::Action)
+                    .waiting_for(Some(waiting_on("adam", 2)))
+                    .build()
+                    .unwrap(),
+                SurrealItemBuilder::default()
+                    .id(Some(("item", "received").into()))
+                    .summary("Already received")
+                    .item_type(SurrealItemType::Action)
+                    .responsibility(Responsibility::WaitingFor)
+                    .waiting_for(Some(waiting_on("adam", 1)))
+                    .finished(Some(now.into()))
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let base_data = BaseData::new_from_surreal_tables(surreal_tables, now);
+        let calculated_data = CalculatedData::new_from_base_data(base_data);
+
+        let by_person = find_waiting_for_by_person(calculated_data.get_items_status())
+            .iter()
+            .map(|x| {
+                (
+                    x.get_person().map(|x| x.get_summary()),
+                    x.get_waiting()
+                        .iter()
+                        .map(|x| (x.get_summary(), x.get_item().is_follow_up_due()))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            by_person,
+            vec![
+                (Some("Adam"), vec![("Spare keys", false)]),
+                (
+                    Some("Zoe"),
+                    vec![("Signed invoice", true), ("Kitchen quote", false)]
+                ),
+            ]
+        );
+    }
+}